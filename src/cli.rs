@@ -0,0 +1,346 @@
+//! Command line arguments
+
+use std::path::PathBuf;
+
+use clap::Args;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+
+use crate::data::database::CipherData;
+use crate::data::export::ExportFormat;
+use crate::data::health;
+use crate::data::import::ImportFormat;
+use crate::data::template::EntryTemplate;
+
+/// PassK -- A simple password manager
+#[derive(Parser)]
+#[command(
+	name = "passk",
+	disable_version_flag = true,
+	subcommand_negates_reqs = true
+)]
+pub struct Cli {
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
+	/// Paths to the database files, each opened in a tab, defaults to `database` of the
+	/// configuration, or to one picked from the recently opened databases
+	#[arg(value_name = "DATABASE")]
+	pub paths: Vec<PathBuf>,
+
+	/// Display program version
+	#[arg(short, long)]
+	pub version: bool,
+	/// Open the database without acquiring its lock, changes cannot be saved
+	#[arg(long, conflicts_with = "create")]
+	pub readonly: bool,
+	/// Fail if the database already exists, a missing database is always created
+	#[arg(long)]
+	pub create: bool,
+	/// Directory holding the configuration, defaults to `$XDG_CONFIG_HOME/passk`
+	#[arg(long, value_name = "DIR", global = true)]
+	pub config: Option<PathBuf>,
+
+	/// Cipher of the new database: xchacha20poly1305 (default) or aes256gcm
+	#[arg(long, requires = "create", value_parser = |cipher: &str| CipherData::try_from(cipher))]
+	pub cipher: Option<CipherData>,
+	/// Require the HMAC-SHA1 response of the YubiKey in SLOT (1 or 2) to open the new database
+	#[arg(
+		long,
+		value_name = "SLOT",
+		requires = "create",
+		value_parser = clap::value_parser!(u8).range(1..=2)
+	)]
+	pub yubikey: Option<u8>,
+	/// Cache the database key in the OS keyring for MINUTES after unlocking
+	#[arg(long, value_name = "MINUTES")]
+	pub keyring: Option<u32>,
+	/// Remove the database key from the OS keyring, stop its agent and exit
+	#[arg(long, conflicts_with = "create")]
+	pub lock: bool,
+	/// Print the database header without unlocking it and exit
+	#[arg(long, conflicts_with = "create")]
+	pub info: bool,
+	/// Seconds after which copied values are cleared from the clipboard, 0 to keep them, defaults
+	/// to `clear-clipboard` of the configuration or 45
+	#[arg(long, value_name = "SECONDS")]
+	pub clear_clipboard: Option<u64>,
+	/// Lock the session after SECONDS without input, 0 to never lock it, defaults to `lock-after`
+	/// of the configuration or 0
+	#[arg(long, value_name = "SECONDS")]
+	pub lock_after: Option<u64>,
+	/// Serve the local HTTP API on 127.0.0.1:PORT, for the first database
+	#[arg(long, value_name = "PORT")]
+	pub http: Option<u16>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+	/// Print a field of an entry, or all its fields
+	Get(GetArgs),
+	/// Create an entry
+	Add(AddArgs),
+	/// Delete an entry
+	Rm(RmArgs),
+	/// Print the names of the entries
+	List(ListArgs),
+	/// Print the current one-time password of an entry
+	Totp(TotpArgs),
+	/// Pick an entry with dmenu, rofi or fzf and copy or type its password
+	Menu(MenuArgs),
+	/// Serve the SSH keys stored in the database to SSH clients
+	SshAgent(SshAgentArgs),
+	/// Answer the requests of the browser extension, as a native messaging host
+	Host(HostArgs),
+	/// Edit an entry as a TOML document in `$VISUAL` or `$EDITOR`
+	Edit(EditArgs),
+	/// Report weak, reused, old and expired passwords, exits with an error if any is found
+	Audit(AuditArgs),
+	/// Write the entries to a file for another password manager
+	Export(ExportArgs),
+	/// Add the entries of a file exported by another password manager
+	Import(ImportArgs),
+	/// Merge two copies of a database, the most recently modified version of each entry is kept
+	Merge(MergeArgs),
+	/// Unlock the database once and hold its key for the other subcommands, until idle for too long
+	Agent(AgentArgs),
+}
+
+/// Database opened by a subcommand
+#[derive(Args)]
+pub struct DatabaseArgs {
+	/// Path to the database file
+	#[arg(value_name = "DATABASE")]
+	pub path: PathBuf,
+	/// Key file required to open the database
+	#[arg(long, value_name = "FILE")]
+	pub key_file: Option<PathBuf>,
+	/// Read the password from the first line of stdin
+	#[arg(long)]
+	pub password_stdin: bool,
+	/// Read the password from the first line of file descriptor FD
+	#[arg(long, value_name = "FD", env = "PASSK_PASSWORD_FD")]
+	pub password_fd: Option<i32>,
+	/// Use the first line printed by COMMAND as the password, e.g. `pass show passk`
+	#[arg(long, value_name = "COMMAND", env = "PASSK_PASSWORD_COMMAND")]
+	pub password_command: Option<String>,
+	/// Use the key cached in the OS keyring by a session started with `--keyring`
+	#[arg(long)]
+	pub keyring: bool,
+}
+
+#[derive(Args)]
+pub struct GetArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Name of the entry
+	pub entry: String,
+	/// Name of the field, all fields are printed when omitted
+	pub field: Option<String>,
+}
+
+#[derive(Args)]
+pub struct EditArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Name of the entry
+	pub entry: String,
+}
+
+/// Parse a `NAME=VALUE` field
+fn parse_field(field: &str) -> Result<(String, String), String> {
+	field
+		.split_once('=')
+		.map(|(name, value)| (name.trim().to_string(), value.to_string()))
+		.ok_or_else(|| format!("Expected NAME=VALUE, got '{field}'"))
+}
+
+#[derive(Args)]
+pub struct AddArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Name of the new entry
+	pub entry: String,
+	/// Template of the entry: empty, login, credit-card, ssh-key or wifi
+	#[arg(long, value_parser = |name: &str| EntryTemplate::try_from(name))]
+	pub template: Option<EntryTemplate>,
+	/// Set field NAME to VALUE, template fields keep their kind
+	#[arg(short, long = "field", value_name = "NAME=VALUE", value_parser = parse_field)]
+	pub fields: Vec<(String, String)>,
+	/// Read more fields from stdin, as `NAME: VALUE` lines
+	#[arg(long)]
+	pub stdin: bool,
+	/// Hide field NAME from previews
+	#[arg(long, value_name = "NAME")]
+	pub hidden: Vec<String>,
+	/// Add tag NAME to the entry
+	#[arg(short, long = "tag", value_name = "NAME")]
+	pub tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct RmArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Name of the entry to delete
+	pub entry: String,
+}
+
+/// Output of `passk list`
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum ListFormat {
+	/// One name per line
+	#[default]
+	Plain,
+	/// Names grouped by their `/`-separated components
+	Tree,
+	/// Names terminated by NUL characters, for `xargs -0`
+	Null,
+}
+
+#[derive(Args)]
+pub struct ListArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Only list entries whose name matches PATTERN, `*` matches anything and `?` any character
+	pub pattern: Option<String>,
+	/// Only list entries with tag NAME
+	#[arg(short, long = "tag", value_name = "NAME")]
+	pub tags: Vec<String>,
+	/// Output format
+	#[arg(long, value_enum, default_value_t)]
+	pub format: ListFormat,
+}
+
+#[derive(Args)]
+pub struct TotpArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Name of the entry
+	pub entry: String,
+	/// Name of the OTP field, defaults to the first TOTP or HOTP field of the entry
+	#[arg(short, long)]
+	pub field: Option<String>,
+	/// Wait for the current TOTP code to expire and print the next one
+	#[arg(long)]
+	pub next: bool,
+}
+
+#[derive(Args)]
+pub struct MenuArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Command reading entry names on stdin and printing the chosen one, e.g. `rofi -dmenu`
+	#[arg(
+		long,
+		value_name = "COMMAND",
+		env = "PASSK_PICKER",
+		default_value = "dmenu"
+	)]
+	pub picker: String,
+	/// Field to use, defaults to the first hidden field of the entry
+	#[arg(short, long)]
+	pub field: Option<String>,
+	/// Type the value with xdotool, or wtype on Wayland, instead of copying it
+	#[arg(long = "type")]
+	pub type_value: bool,
+	/// Seconds after which the copied value is cleared from the clipboard
+	#[arg(long, value_name = "SECONDS", default_value_t = 45)]
+	pub clear_after: u64,
+}
+
+#[derive(Args)]
+pub struct SshAgentArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Socket to listen on, defaults to `$XDG_RUNTIME_DIR/passk-agent.sock`
+	#[arg(long, value_name = "PATH")]
+	pub socket: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct HostArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Arguments added by the browser: the origin or the ID of the extension
+	#[arg(trailing_var_arg = true, allow_hyphen_values = true, hide = true)]
+	pub browser_args: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct AuditArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Passwords with less entropy are weak
+	#[arg(long, value_name = "BITS", default_value_t = health::DEFAULT_MIN_ENTROPY)]
+	pub min_entropy: u32,
+	/// Passwords not changed for longer are old
+	#[arg(long, value_name = "DAYS", default_value_t = health::DEFAULT_MAX_AGE)]
+	pub max_age: u32,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// File to write, only KeePass databases are encrypted
+	#[arg(value_name = "FILE")]
+	pub output: PathBuf,
+	/// Format of the file: csv, json, bitwarden, kdbx or the format of a plugin
+	#[arg(short, long, value_parser = |name: &str| ExportFormat::try_from(name))]
+	pub format: ExportFormat,
+	/// Read the password of the KeePass database from the first line of file descriptor FD
+	#[arg(long, value_name = "FD")]
+	pub file_password_fd: Option<i32>,
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// File to import
+	#[arg(value_name = "FILE")]
+	pub input: PathBuf,
+	/// Format of the file: proton, json, csv, lastpass, chrome, firefox, aegis, andotp, bitwarden,
+	/// kdbx or the format of a plugin
+	#[arg(short, long, value_parser = |name: &str| ImportFormat::try_from(name))]
+	pub format: ImportFormat,
+	/// Field mapping of the json format
+	#[arg(long, value_name = "FILE")]
+	pub mapping: Option<PathBuf>,
+	/// Read the password of the KeePass database or Aegis backup from the first line of file
+	/// descriptor FD
+	#[arg(long, value_name = "FD")]
+	pub file_password_fd: Option<i32>,
+	/// Print the entries that would be imported without saving them
+	#[arg(long)]
+	pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct MergeArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Other copy of the database
+	#[arg(value_name = "OTHER")]
+	pub other: PathBuf,
+	/// Key file required to open the other copy
+	#[arg(long, value_name = "FILE")]
+	pub other_key_file: Option<PathBuf>,
+	/// Read the password of the other copy from the first line of file descriptor FD
+	#[arg(long, value_name = "FD")]
+	pub other_password_fd: Option<i32>,
+	/// File to write the merged database to, either DATABASE or a new file
+	#[arg(short, long, value_name = "FILE")]
+	pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct AgentArgs {
+	#[command(flatten)]
+	pub database: DatabaseArgs,
+	/// Forget the key and exit after MINUTES without requests, 0 to never exit
+	#[arg(long, value_name = "MINUTES", default_value_t = 15)]
+	pub timeout: u32,
+}
@@ -0,0 +1,75 @@
+//! Clipboard shared by the interface
+//!
+//! Copied values are cleared after a delay, checked by the event loop with [`clear_expired`].
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use clipboard_rs::Clipboard;
+
+use crate::CLIPBOARD_CTX;
+
+/// Delay after which copied values are cleared, never cleared when `None`
+static CLEAR_AFTER: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// Last copied value and the time it expires at
+static PENDING: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// Clear copied values after `delay`, or keep them when `None`
+pub fn set_clear_after(delay: Option<Duration>) {
+	let _ = CLEAR_AFTER.set(delay);
+}
+
+/// Copy `text` to the clipboard, it is cleared once the delay elapses
+pub fn copy(text: String) -> Result<(), String> {
+	CLIPBOARD_CTX
+		.set_text(text.clone())
+		.map_err(|err| format!("Failed to copy to the clipboard: {err}"))?;
+	if let Some(delay) = CLEAR_AFTER.get().copied().flatten() {
+		*PENDING.lock().unwrap() = Some((text, Instant::now() + delay));
+	}
+	Ok(())
+}
+
+/// Whether copied values are cleared after a delay
+pub fn clears() -> bool {
+	CLEAR_AFTER.get().copied().flatten().is_some()
+}
+
+/// Time left before the copied value is cleared
+pub fn remaining() -> Option<Duration> {
+	PENDING
+		.lock()
+		.unwrap()
+		.as_ref()
+		.map(|(_, expires)| expires.saturating_duration_since(Instant::now()))
+}
+
+/// Clear the copied value, unless something else was copied since
+fn clear(text: &str) {
+	if CLIPBOARD_CTX
+		.get_text()
+		.is_ok_and(|content| content == text)
+	{
+		let _ = CLIPBOARD_CTX.clear();
+	}
+}
+
+/// Clear the copied value if its delay elapsed
+pub fn clear_expired() {
+	let expired = PENDING
+		.lock()
+		.unwrap()
+		.take_if(|(_, expires)| *expires <= Instant::now());
+	if let Some((text, _)) = expired {
+		clear(&text);
+	}
+}
+
+/// Clear the copied value now, when exiting
+pub fn clear_pending() {
+	if let Some((text, _)) = PENDING.lock().unwrap().take() {
+		clear(&text);
+	}
+}
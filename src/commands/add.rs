@@ -0,0 +1,84 @@
+use std::io::Read;
+
+use crate::cli::AddArgs;
+use crate::commands::Vault;
+use crate::data::audit::AuditAction;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+
+/// Parse `NAME: VALUE` lines, as printed by `passk get`
+fn parse_lines(input: &str) -> Result<Vec<(String, String)>, String> {
+	input
+		.lines()
+		.filter(|line| !line.trim().is_empty())
+		.map(|line| {
+			line.split_once(": ")
+				.map(|(name, value)| (name.trim().to_string(), value.to_string()))
+				.ok_or_else(|| format!("Expected 'NAME: VALUE', got '{line}'"))
+		})
+		.collect()
+}
+
+/// Set the value of `field`, keeping its kind for text-like values
+fn set_value(field: &mut Field, value: String) {
+	match &mut field.value {
+		FieldValue::Text(text)
+		| FieldValue::Url(text)
+		| FieldValue::Phone(text)
+		| FieldValue::Email(text)
+		| FieldValue::Note(text) => *text = value,
+		_ => field.value = FieldValue::Text(value),
+	}
+}
+
+/// Create an entry from the template, fields and tags given on the command line
+pub fn run(args: AddArgs) -> Result<(), String> {
+	if args.stdin && args.database.password_stdin {
+		return Err("--stdin and --password-stdin can't be used together".into());
+	}
+	let mut fields = args.fields;
+	if args.stdin {
+		let mut input = String::new();
+		std::io::stdin()
+			.read_to_string(&mut input)
+			.map_err(|err| format!("Failed to read stdin: {err}"))?;
+		fields.extend(parse_lines(&input)?);
+	}
+
+	let mut vault = Vault::open_mut(&args.database)?;
+	if vault.entry(&args.entry).is_ok() {
+		return Err(format!("Entry '{}' already exists", args.entry));
+	}
+
+	let mut entry = args.template.unwrap_or_default().entry(args.entry);
+	for (name, value) in fields {
+		match entry.fields.iter_mut().find(|field| field.name == name) {
+			Some(field) => set_value(field, value),
+			None => entry.fields.push(Field {
+				name,
+				value: FieldValue::Text(value),
+				..Default::default()
+			}),
+		}
+	}
+	for name in &args.hidden {
+		let field = entry
+			.fields
+			.iter_mut()
+			.find(|field| &field.name == name)
+			.ok_or_else(|| format!("No field named '{name}' to hide"))?;
+		field.hidden = true;
+	}
+	entry.tags = args
+		.tags
+		.iter()
+		.map(|tag| vault.data.tags.resolve(tag))
+		.collect();
+
+	vault
+		.data
+		.log
+		.record(Some(&entry.name), AuditAction::EntryCreated);
+	vault.data.entries.push(entry);
+	vault.save()
+}
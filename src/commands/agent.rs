@@ -0,0 +1,120 @@
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::cli::AgentArgs;
+use crate::commands::bind;
+use crate::commands::block_termination_signals;
+use crate::commands::Vault;
+use crate::data::agent;
+use crate::data::secret::SecretBuffer;
+
+/// Key held by the agent
+struct State {
+	/// Forgotten when the agent stops
+	key: Option<SecretBuffer>,
+	last_request: Instant,
+}
+
+/// Wake the main thread so the agent exits
+fn terminate() {
+	// SAFETY: sends a signal to the current process, which waits for it
+	unsafe { libc::kill(libc::getpid(), libc::SIGTERM) };
+}
+
+/// Answer the request of a client
+fn serve(mut stream: UnixStream, state: &Mutex<State>) -> Result<(), String> {
+	let mut request = [0u8; 1];
+	// Clients checking whether an agent is running send nothing
+	if stream.read_exact(&mut request).is_err() {
+		return Ok(());
+	}
+	match request[0] {
+		agent::REQUEST_KEY => {
+			let key = {
+				let mut state = state.lock().unwrap();
+				state.last_request = Instant::now();
+				state.key.clone()
+			};
+			let Some(key) = key else {
+				return Ok(());
+			};
+			stream
+				.write_all(&(key.len() as u32).to_be_bytes())
+				.and_then(|_| stream.write_all(&key))
+				.map_err(|err| format!("Failed to write response: {err}"))
+		}
+		agent::REQUEST_STOP => {
+			state.lock().unwrap().key = None;
+			terminate();
+			Ok(())
+		}
+		request => Err(format!("Unsupported request {request}")),
+	}
+}
+
+/// Stop the agent once no request was received for `timeout`
+fn watch_idle(state: &Mutex<State>, timeout: Duration) {
+	loop {
+		let idle = state.lock().unwrap().last_request.elapsed();
+		if idle >= timeout {
+			eprintln!("No request for {} minute(s)", timeout.as_secs() / 60);
+			terminate();
+			return;
+		}
+		std::thread::sleep(timeout - idle);
+	}
+}
+
+/// Unlock the database and hand its key to the other subcommands, until interrupted or idle for
+/// `args.timeout` minutes
+pub fn run(args: AgentArgs) -> Result<(), String> {
+	let socket = agent::socket_path(&args.database.path)
+		.ok_or("XDG_RUNTIME_DIR is not set, the agent socket cannot be created")?;
+	// Fail before asking for the password
+	if UnixStream::connect(&socket).is_ok() {
+		return Err(format!(
+			"An agent is already running for '{}'",
+			args.database.path.display()
+		));
+	}
+
+	// Only the key is kept, the entries are dropped right away
+	let Vault { key, .. } = Vault::open(&args.database)?;
+	let state = Arc::new(Mutex::new(State {
+		key: Some(key),
+		last_request: Instant::now(),
+	}));
+
+	let signals = block_termination_signals();
+	let listener = bind(&socket)?;
+	eprintln!("Holding the key of '{}'", args.database.path.display());
+
+	let clients = state.clone();
+	std::thread::spawn(move || {
+		for stream in listener.incoming().flatten() {
+			let state = clients.clone();
+			std::thread::spawn(move || {
+				if let Err(err) = serve(stream, &state) {
+					eprintln!("{err}");
+				}
+			});
+		}
+	});
+	if args.timeout != 0 {
+		let state = state.clone();
+		let timeout = Duration::from_secs(u64::from(args.timeout) * 60);
+		std::thread::spawn(move || watch_idle(&state, timeout));
+	}
+
+	let mut signal = 0;
+	// SAFETY: the set was initialized by block_termination_signals
+	unsafe { libc::sigwait(&signals, &mut signal) };
+	state.lock().unwrap().key = None;
+	std::fs::remove_file(&socket)
+		.map_err(|err| format!("Failed to remove '{}': {err}", socket.display()))
+}
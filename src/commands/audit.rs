@@ -0,0 +1,31 @@
+use chrono::TimeDelta;
+use chrono::Utc;
+
+use crate::cli::AuditArgs;
+use crate::commands::Vault;
+use crate::data::health;
+
+/// Print the problems found with the passwords of the database
+///
+/// Nothing is printed when all passwords are fine, so it can be run from cron.
+pub fn run(args: AuditArgs) -> Result<(), String> {
+	let vault = Vault::open(&args.database)?;
+	let reports = health::check(
+		&vault.data.entries,
+		args.min_entropy,
+		TimeDelta::days(args.max_age.into()),
+		Utc::now(),
+	);
+	for report in &reports {
+		let issues = report
+			.issues
+			.iter()
+			.map(|issue| issue.description())
+			.collect::<Vec<_>>();
+		println!("{}/{}: {}", report.entry, report.field, issues.join(", "));
+	}
+	if !reports.is_empty() {
+		return Err(format!("Found problems with {} password(s)", reports.len()));
+	}
+	Ok(())
+}
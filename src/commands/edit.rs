@@ -0,0 +1,124 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::cli::EditArgs;
+use crate::commands::Vault;
+use crate::data::audit::AuditAction;
+use crate::data::document::apply_document;
+use crate::data::document::to_document;
+use crate::data::entry::Entry;
+use crate::data::export::create_private;
+use crate::data::secret::wipe;
+use crate::data::tag::TagRegistry;
+
+/// Temporary file holding the document, in the runtime directory when possible as it is not
+/// written to disk
+fn document_path() -> PathBuf {
+	std::env::var_os("XDG_RUNTIME_DIR")
+		.map(PathBuf::from)
+		.unwrap_or_else(std::env::temp_dir)
+		.join(format!("passk-edit-{}.toml", std::process::id()))
+}
+
+/// Open `text` in `$VISUAL` or `$EDITOR` and return the edited text
+fn run_editor(text: &str) -> Result<String, String> {
+	let editor = std::env::var("VISUAL")
+		.or_else(|_| std::env::var("EDITOR"))
+		.unwrap_or_else(|_| "vi".into());
+	let path = document_path();
+	create_private(&path)?
+		.write_all(text.as_bytes())
+		.map_err(|err| format!("Failed to write '{}': {err}", path.display()))?;
+
+	// The editor may be given with arguments, e.g. `code --wait`
+	let status = std::process::Command::new("sh")
+		.arg("-c")
+		.arg(format!("{editor} \"$1\""))
+		.arg("sh")
+		.arg(&path)
+		.status();
+	let edited = std::fs::read(&path);
+	// Overwrite the decrypted values before removing the file
+	if let Ok(len) = std::fs::metadata(&path).map(|metadata| metadata.len()) {
+		let _ = std::fs::write(&path, vec![0u8; len as usize]);
+	}
+	let _ = std::fs::remove_file(&path);
+
+	let status = status.map_err(|err| format!("Failed to run '{editor}': {err}"))?;
+	if !status.success() {
+		return Err(format!("'{editor}' exited with {status}"));
+	}
+	let edited = edited.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	String::from_utf8(edited).map_err(|err| {
+		let mut bytes = err.into_bytes();
+		wipe(&mut bytes);
+		"The document is not valid UTF-8".to_string()
+	})
+}
+
+/// Ask a yes/no question on the terminal, defaulting to yes
+fn ask(question: &str) -> bool {
+	eprint!("{question} [Y/n] ");
+	let mut answer = String::new();
+	if std::io::stdin().read_line(&mut answer).is_err() {
+		return false;
+	}
+	!answer.trim().eq_ignore_ascii_case("n")
+}
+
+/// Edit `entry` as a TOML document in the external editor
+///
+/// Invalid documents are reopened after asking the user on the terminal. Returns `None` when the
+/// document was left unchanged or the user gave up.
+pub fn edit_entry(entry: &Entry, tags: &TagRegistry) -> Result<Option<Entry>, String> {
+	let original = to_document(entry)?;
+	let mut text = original.clone();
+	loop {
+		text = run_editor(&text)?;
+		if text == original {
+			return Ok(None);
+		}
+		match apply_document(entry, &text, tags) {
+			Ok(edited) => return Ok(Some(edited)),
+			Err(err) => {
+				eprintln!("{err}");
+				if !ask("Edit again?") {
+					return Ok(None);
+				}
+			}
+		}
+	}
+}
+
+/// Edit an entry in the external editor and save the changes
+pub fn run(args: EditArgs) -> Result<(), String> {
+	let mut vault = Vault::open_mut(&args.database)?;
+	let index = vault
+		.data
+		.entries
+		.iter()
+		.position(|entry| entry.name == args.entry)
+		.ok_or_else(|| format!("No entry named '{}'", args.entry))?;
+	let entry = vault.data.entries[index].clone();
+	let Some(mut edited) = edit_entry(&entry, &vault.data.tags)? else {
+		eprintln!("Entry '{}' unchanged", args.entry);
+		return Ok(());
+	};
+	if edited.name != entry.name && vault.entry(&edited.name).is_ok() {
+		return Err(format!("Entry '{}' already exists", edited.name));
+	}
+
+	edited.commit(entry.revision());
+	if edited.name != entry.name {
+		vault.data.log.record(
+			Some(&edited.name),
+			AuditAction::EntryRenamed { from: entry.name },
+		);
+	}
+	vault
+		.data
+		.log
+		.record(Some(&edited.name), AuditAction::EntryEdited);
+	vault.data.entries[index] = edited;
+	vault.save()
+}
@@ -0,0 +1,36 @@
+use crate::cli::ExportArgs;
+use crate::commands::read_file_password;
+use crate::commands::Vault;
+use crate::data::audit::AuditAction;
+use crate::data::export::export;
+use crate::data::secret::wipe;
+
+/// Export the entries, the export is recorded in the audit log of the database
+pub fn run(args: ExportArgs) -> Result<(), String> {
+	let mut vault = Vault::open_mut(&args.database)?;
+	let password = match args.format.needs_password() {
+		true => Some(read_file_password(
+			&args.output,
+			args.file_password_fd,
+			true,
+		)?),
+		false => None,
+	};
+	let result = export(
+		args.format,
+		&args.output,
+		&vault.data.entries,
+		password.as_deref(),
+	);
+	if let Some(password) = password {
+		wipe(&mut password.into_bytes());
+	}
+	result?;
+	vault.data.log.record(
+		None,
+		AuditAction::Exported {
+			format: args.format.name().into(),
+		},
+	);
+	vault.save()
+}
@@ -0,0 +1,34 @@
+use std::io::Write;
+
+use crate::cli::GetArgs;
+use crate::commands::Vault;
+use crate::data::attachment;
+use crate::data::export::exported_value;
+use crate::data::field::FieldValue;
+
+/// Print a field of an entry, or all its fields when no field is given
+///
+/// Attachments are written as raw bytes, so they can be redirected to a file.
+pub fn run(args: GetArgs) -> Result<(), String> {
+	let vault = Vault::open(&args.database)?;
+	let entry = vault.entry(&args.entry)?;
+	let mut stdout = std::io::stdout().lock();
+
+	let Some(name) = &args.field else {
+		for field in &entry.fields {
+			writeln!(stdout, "{}: {}", field.name, exported_value(&field.value))
+				.map_err(|err| format!("Failed to write to stdout: {err}"))?;
+		}
+		return Ok(());
+	};
+	let field = entry
+		.fields
+		.iter()
+		.find(|field| &field.name == name)
+		.ok_or_else(|| format!("No field named '{name}' in entry '{}'", entry.name))?;
+	match &field.value {
+		FieldValue::Binary { .. } => stdout.write_all(&attachment::decode(&field.value)?),
+		value => writeln!(stdout, "{}", exported_value(value)),
+	}
+	.map_err(|err| format!("Failed to write to stdout: {err}"))
+}
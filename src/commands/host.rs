@@ -0,0 +1,275 @@
+//! Browser native messaging host
+//!
+//! Browsers start the host and exchange JSON messages on its stdin and stdout, each prefixed by
+//! its length as a native-endian `u32`.
+//!
+//! Requests:
+//! - `{"action": "ping"}`: answered with `{"version": "<version>"}`
+//! - `{"action": "get-logins", "url": "<url>"}`: logins of the entries with a URL field on the
+//!   same site, as `{"logins": [{"entry": ..., "username": ..., "password": ...}]}`
+//!
+//! The `id` of a request is copied to its response, failures are answered with `{"error": ...}`.
+//!
+//! Sites must be approved before their logins are sent. Approved sites are listed, one per line,
+//! in the `host-approvals` file of the configuration directory. Requests from other sites are
+//! written to a `host-pending` file, for a running passk instance to ask the user, or for the
+//! user to add the site to the approvals file.
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::cli::HostArgs;
+use crate::commands::Vault;
+use crate::config::config_dir;
+use crate::data::entry::Entry;
+use crate::data::field::FieldValue;
+use crate::data::file::PasskVersion;
+
+/// Largest message accepted from the browser, in bytes
+const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// Time given to the user to approve a site
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(60);
+/// Interval between checks of the approval files
+const APPROVAL_POLL: Duration = Duration::from_millis(500);
+
+/// File listing the approved sites
+fn approvals_path() -> Result<PathBuf, String> {
+	config_dir()
+		.map(|dir| dir.join("host-approvals"))
+		.ok_or("Failed to find the configuration directory".into())
+}
+
+/// File holding the site waiting for approval
+fn pending_path() -> Result<PathBuf, String> {
+	std::env::var_os("XDG_RUNTIME_DIR")
+		.map(|dir| PathBuf::from(dir).join("passk"))
+		.or_else(config_dir)
+		.map(|dir| dir.join("host-pending"))
+		.ok_or("Failed to find a directory for pending approvals".into())
+}
+
+/// Whether `site` is listed in the approvals file
+fn is_approved(site: &str) -> bool {
+	let Some(approvals) = approvals_path()
+		.ok()
+		.and_then(|path| std::fs::read_to_string(path).ok())
+	else {
+		return false;
+	};
+	approvals
+		.lines()
+		.map(str::trim)
+		.any(|line| !line.starts_with('#') && line.eq_ignore_ascii_case(site))
+}
+
+/// Site waiting for approval, if any
+pub fn pending_request() -> Option<String> {
+	let site = std::fs::read_to_string(pending_path().ok()?).ok()?;
+	let site = site.trim();
+	(!site.is_empty()).then(|| site.to_string())
+}
+
+/// Add `site` to the approvals file and answer the pending request
+pub fn approve(site: &str) -> Result<(), String> {
+	let path = approvals_path()?;
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)
+			.map_err(|err| format!("Failed to create '{}': {err}", parent.display()))?;
+	}
+	std::fs::OpenOptions::new()
+		.append(true)
+		.create(true)
+		.mode(0o600)
+		.open(&path)
+		.and_then(|mut file| writeln!(file, "{site}"))
+		.map_err(|err| format!("Failed to write '{}': {err}", path.display()))?;
+	deny()
+}
+
+/// Reject the pending request
+pub fn deny() -> Result<(), String> {
+	let path = pending_path()?;
+	match std::fs::remove_file(&path) {
+		Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+			Err(format!("Failed to remove '{}': {err}", path.display()))
+		}
+		_ => Ok(()),
+	}
+}
+
+/// Ask for `site` to be approved and wait for the answer
+fn request_approval(site: &str) -> Result<bool, String> {
+	if is_approved(site) {
+		return Ok(true);
+	}
+	let path = pending_path()?;
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)
+			.map_err(|err| format!("Failed to create '{}': {err}", parent.display()))?;
+	}
+	std::fs::OpenOptions::new()
+		.write(true)
+		.create(true)
+		.truncate(true)
+		.mode(0o600)
+		.open(&path)
+		.and_then(|mut file| file.write_all(site.as_bytes()))
+		.map_err(|err| format!("Failed to write '{}': {err}", path.display()))?;
+
+	let start = Instant::now();
+	loop {
+		std::thread::sleep(APPROVAL_POLL);
+		if is_approved(site) {
+			return Ok(true);
+		}
+		// The request is removed when it is denied
+		let Some(pending) = pending_request() else {
+			return Ok(false);
+		};
+		if start.elapsed() >= APPROVAL_TIMEOUT {
+			if pending == site {
+				deny()?;
+			}
+			return Ok(false);
+		}
+	}
+}
+
+/// Host of `url`, without credentials and port
+fn host(url: &str) -> &str {
+	let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+	let end = url.find(['/', '?', '#']).unwrap_or(url.len());
+	let host = &url[..end];
+	let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+	host.rsplit_once(':').map_or(host, |(host, _)| host)
+}
+
+/// Whether `entry` has a URL on the site `site`, or one of its parent domains
+fn matches_site(entry: &Entry, site: &str) -> bool {
+	entry.fields.iter().any(|field| {
+		let FieldValue::Url(url) = &field.value else {
+			return false;
+		};
+		let host = host(url).to_lowercase();
+		!host.is_empty()
+			&& (site == host
+				|| site
+					.strip_suffix(&host)
+					.is_some_and(|sub| sub.ends_with('.')))
+	})
+}
+
+/// Username and password of `entry`
+///
+/// The username is the `Username` field or the first e-mail, the password is the `Password`
+/// field or the first hidden field.
+fn login(entry: &Entry) -> Value {
+	let username = entry
+		.fields
+		.iter()
+		.find(|field| field.name == "Username")
+		.or_else(|| {
+			entry
+				.fields
+				.iter()
+				.find(|field| matches!(field.value, FieldValue::Email(_)))
+		})
+		.and_then(|field| field.value.text());
+	let password = entry
+		.fields
+		.iter()
+		.find(|field| field.name == "Password")
+		.or_else(|| entry.fields.iter().find(|field| field.hidden))
+		.and_then(|field| field.value.text());
+	json!({
+		"entry": entry.name,
+		"username": username,
+		"password": password,
+	})
+}
+
+/// Answer a request
+fn handle(vault: &Result<Vault, String>, request: &Value) -> Result<Value, String> {
+	match request["action"].as_str() {
+		Some("ping") => Ok(json!({ "version": <&'static str>::from(PasskVersion::default()) })),
+		Some("get-logins") => {
+			let url = request["url"].as_str().ok_or("Missing 'url'")?;
+			let site = host(url).to_lowercase();
+			if site.is_empty() {
+				return Err(format!("Invalid URL '{url}'"));
+			}
+			let vault = vault.as_ref().map_err(String::clone)?;
+			if !request_approval(&site)? {
+				return Err(format!(
+					"Access to {site} was denied, approve it in passk or add it to '{}'",
+					approvals_path()?.display()
+				));
+			}
+			let logins = vault
+				.data
+				.entries
+				.iter()
+				.filter(|entry| matches_site(entry, &site))
+				.map(login)
+				.collect::<Vec<_>>();
+			Ok(json!({ "logins": logins }))
+		}
+		Some(action) => Err(format!("Unknown action '{action}'")),
+		None => Err("Missing 'action'".into()),
+	}
+}
+
+/// Read a message, `None` when the browser closed the connection
+fn read_message(input: &mut impl Read) -> Result<Option<Value>, String> {
+	let mut len = [0u8; 4];
+	if input.read_exact(&mut len).is_err() {
+		return Ok(None);
+	}
+	let len = u32::from_ne_bytes(len) as usize;
+	if len > MAX_MESSAGE_SIZE {
+		return Err(format!("Message of {len} bytes is too large"));
+	}
+	let mut message = vec![0u8; len];
+	input
+		.read_exact(&mut message)
+		.map_err(|err| format!("Failed to read message: {err}"))?;
+	serde_json::from_slice(&message)
+		.map(Some)
+		.map_err(|err| format!("Invalid message: {err}"))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<(), String> {
+	let message = message.to_string();
+	output
+		.write_all(&(message.len() as u32).to_ne_bytes())
+		.and_then(|_| output.write_all(message.as_bytes()))
+		.and_then(|_| output.flush())
+		.map_err(|err| format!("Failed to write message: {err}"))
+}
+
+/// Answer the requests of the browser until it closes the connection
+pub fn run(args: HostArgs) -> Result<(), String> {
+	if args.database.password_stdin {
+		return Err("--password-stdin cannot be used, stdin is used by the browser".into());
+	}
+	// Failures are reported to the extension rather than closing the connection
+	let vault = Vault::open(&args.database);
+
+	let mut stdin = std::io::stdin().lock();
+	let mut stdout = std::io::stdout().lock();
+	while let Some(request) = read_message(&mut stdin)? {
+		let mut response = handle(&vault, &request).unwrap_or_else(|err| json!({ "error": err }));
+		if !request["id"].is_null() {
+			response["id"] = request["id"].clone();
+		}
+		write_message(&mut stdout, &response)?;
+	}
+	Ok(())
+}
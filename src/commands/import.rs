@@ -0,0 +1,80 @@
+use crate::cli::ImportArgs;
+use crate::commands::read_file_password;
+use crate::commands::Vault;
+use crate::data::audit::AuditAction;
+use crate::data::entry::Entry;
+use crate::data::import::import;
+use crate::data::import::otp;
+use crate::data::secret::wipe;
+
+/// Print the changes between `before` and `after`, as `+ name (fields)` for new entries and
+/// `~ name (fields)` for the fields added to existing entries
+fn print_changes(before: &[Entry], after: &[Entry]) {
+	let names = |entry: &Entry, skip: usize| {
+		entry
+			.fields
+			.iter()
+			.skip(skip)
+			.map(|field| field.name.as_str())
+			.collect::<Vec<_>>()
+			.join(", ")
+	};
+	for (old, new) in before.iter().zip(after) {
+		if new.fields.len() > old.fields.len() {
+			println!("~ {} ({})", new.name, names(new, old.fields.len()));
+		}
+	}
+	for new in &after[before.len()..] {
+		let exists = before.iter().any(|old| old.name == new.name);
+		println!(
+			"+ {} ({}){}",
+			new.name,
+			names(new, 0),
+			if exists { " [duplicate name]" } else { "" }
+		);
+	}
+}
+
+/// Import entries from a file, or print them with `--dry-run`
+pub fn run(args: ImportArgs) -> Result<(), String> {
+	let mut vault = match args.dry_run {
+		true => Vault::open(&args.database)?,
+		false => Vault::open_mut(&args.database)?,
+	};
+	let password = match args.format.needs_password() {
+		true => Some(read_file_password(
+			&args.input,
+			args.file_password_fd,
+			false,
+		)?)
+		.filter(|password| !password.is_empty()),
+		false => None,
+	};
+	let imported = import(
+		args.format,
+		&args.input,
+		args.mapping.as_deref(),
+		password.as_deref(),
+	);
+	if let Some(password) = password {
+		wipe(&mut password.into_bytes());
+	}
+	let imported = imported?;
+	let count = imported.len();
+
+	let data = &mut vault.data;
+	let before = args.dry_run.then(|| data.entries.clone());
+	if args.format.attaches_otp() {
+		otp::attach(&mut data.entries, imported);
+	} else {
+		data.entries.extend(imported);
+	}
+	if let Some(before) = before {
+		print_changes(&before, &data.entries);
+		return Ok(());
+	}
+	data.tags.sync(&data.entries);
+	data.tags.apply(&mut data.entries);
+	data.log.record(None, AuditAction::Imported { count });
+	vault.save()
+}
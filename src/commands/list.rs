@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::cli::ListArgs;
+use crate::cli::ListFormat;
+use crate::commands::Vault;
+use crate::data::entry::Entry;
+
+/// Match `name` against a shell-style pattern, where `*` matches any sequence and `?` any character
+fn glob_match(pattern: &str, name: &str) -> bool {
+	let pattern = pattern.chars().collect::<Vec<_>>();
+	let name = name.chars().collect::<Vec<_>>();
+	let (mut p, mut n) = (0, 0);
+	// Position of the last `*` and the name position it was tried at
+	let mut backtrack = None;
+	while n < name.len() {
+		match pattern.get(p) {
+			Some('*') => {
+				backtrack = Some((p, n));
+				p += 1;
+			}
+			Some('?') => {
+				p += 1;
+				n += 1;
+			}
+			Some(c) if *c == name[n] => {
+				p += 1;
+				n += 1;
+			}
+			_ => match backtrack {
+				Some((star, position)) => {
+					p = star + 1;
+					n = position + 1;
+					backtrack = Some((star, position + 1));
+				}
+				None => return false,
+			},
+		}
+	}
+	pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Entries grouped by the `/`-separated components of their names
+#[derive(Default)]
+struct Tree {
+	children: BTreeMap<String, Tree>,
+}
+
+impl Tree {
+	fn insert(&mut self, name: &str) {
+		let mut node = self;
+		for part in name.split('/').filter(|part| !part.is_empty()) {
+			node = node.children.entry(part.to_string()).or_default();
+		}
+	}
+
+	fn write(&self, out: &mut impl Write, prefix: &str) -> std::io::Result<()> {
+		for (index, (name, child)) in self.children.iter().enumerate() {
+			let last = index + 1 == self.children.len();
+			writeln!(out, "{prefix}{}{name}", if last { "└── " } else { "├── " })?;
+			child.write(
+				out,
+				&format!("{prefix}{}", if last { "    " } else { "│   " }),
+			)?;
+		}
+		Ok(())
+	}
+}
+
+fn matches(entry: &Entry, args: &ListArgs) -> bool {
+	let tagged = args.tags.iter().all(|tag| {
+		entry
+			.tags
+			.iter()
+			.any(|entry_tag| entry_tag.name.eq_ignore_ascii_case(tag.trim()))
+	});
+	tagged
+		&& args
+			.pattern
+			.as_ref()
+			.is_none_or(|pattern| glob_match(pattern, &entry.name))
+}
+
+/// Print the names of the entries matching the pattern and tags
+pub fn run(args: ListArgs) -> Result<(), String> {
+	let vault = Vault::open(&args.database)?;
+	let mut names = vault
+		.data
+		.entries
+		.iter()
+		.filter(|entry| matches(entry, &args))
+		.map(|entry| entry.name.as_str());
+
+	let mut stdout = std::io::stdout().lock();
+	match args.format {
+		ListFormat::Plain => names.try_for_each(|name| writeln!(stdout, "{name}")),
+		ListFormat::Null => names.try_for_each(|name| write!(stdout, "{name}\0")),
+		ListFormat::Tree => {
+			let mut tree = Tree::default();
+			names.for_each(|name| tree.insert(name));
+			writeln!(stdout, "{}", args.database.path.display())
+				.and_then(|_| tree.write(&mut stdout, ""))
+		}
+	}
+	.map_err(|err| format!("Failed to write to stdout: {err}"))
+}
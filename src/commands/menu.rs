@@ -0,0 +1,96 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+
+use clipboard_rs::Clipboard;
+
+use crate::cli::MenuArgs;
+use crate::commands::Vault;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::CLIPBOARD_CTX;
+
+/// Run `command` through the shell, feeding it `input`, and return its output
+fn pipe(command: &str, input: &str) -> Result<String, String> {
+	let mut child = Command::new("sh")
+		.arg("-c")
+		.arg(command)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.map_err(|err| format!("Failed to run '{command}': {err}"))?;
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(input.as_bytes())
+		.map_err(|err| format!("Failed to write to '{command}': {err}"))?;
+	let output = child
+		.wait_with_output()
+		.map_err(|err| format!("Failed to run '{command}': {err}"))?;
+	Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Type `text` in the focused window
+fn type_text(text: &str) -> Result<(), String> {
+	let command = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+		"wtype -"
+	} else {
+		"xdotool type --clearmodifiers --file -"
+	};
+	pipe(command, text).map(|_| ())
+}
+
+/// Field used when none is given: the first hidden field, or the first field
+fn default_field(fields: &[Field]) -> Option<&Field> {
+	fields
+		.iter()
+		.find(|field| field.hidden)
+		.or_else(|| fields.first())
+}
+
+/// Pick an entry with an external menu, then copy or type the value of one of its fields
+pub fn run(args: MenuArgs) -> Result<(), String> {
+	let vault = Vault::open(&args.database)?;
+	let names = vault
+		.data
+		.entries
+		.iter()
+		.map(|entry| entry.name.as_str())
+		.collect::<Vec<_>>()
+		.join("\n");
+	let choice = pipe(&args.picker, &names)?;
+	let choice = choice.trim_end_matches(['\n', '\r']);
+	// The picker was cancelled
+	if choice.is_empty() {
+		return Ok(());
+	}
+
+	let entry = vault.entry(choice)?;
+	let field = match &args.field {
+		Some(name) => entry.fields.iter().find(|field| &field.name == name),
+		None => default_field(&entry.fields),
+	}
+	.ok_or_else(|| format!("No field to use in entry '{}'", entry.name))?;
+	if matches!(field.value, FieldValue::HOTP { .. }) {
+		return Err("HOTP counters can't be advanced from the menu, use `passk totp`".into());
+	}
+	let value = field
+		.value
+		.clipboard_text()
+		.ok_or_else(|| format!("Field '{}' has no value to use", field.name))?;
+
+	if args.type_value {
+		return type_text(&value);
+	}
+	CLIPBOARD_CTX
+		.set_text(value.clone())
+		.map_err(|err| format!("Failed to copy to the clipboard: {err}"))?;
+	// The clipboard is owned by this process, keep it alive until the value is cleared
+	std::thread::sleep(Duration::from_secs(args.clear_after));
+	if CLIPBOARD_CTX.get_text().is_ok_and(|text| text == value) {
+		let _ = CLIPBOARD_CTX.clear();
+	}
+	Ok(())
+}
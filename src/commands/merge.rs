@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use crate::cli::DatabaseArgs;
+use crate::cli::MergeArgs;
+use crate::commands::Vault;
+use crate::data::database::merge;
+
+/// Whether `a` and `b` are the same file
+fn same_file(a: &Path, b: &Path) -> bool {
+	match (a.canonicalize(), b.canonicalize()) {
+		(Ok(a), Ok(b)) => a == b,
+		_ => false,
+	}
+}
+
+/// Merge the other copy into the database and write the result
+///
+/// The result is encrypted with the key of the first database. The changes are printed, a
+/// conflict is an entry modified in both copies: the most recent version is kept.
+pub fn run(args: MergeArgs) -> Result<(), String> {
+	let in_place = same_file(&args.output, &args.database.path);
+	let mut vault = match in_place {
+		true => Vault::open_mut(&args.database)?,
+		false => Vault::open(&args.database)?,
+	};
+	let other = Vault::open(&DatabaseArgs {
+		path: args.other.clone(),
+		key_file: args.other_key_file.clone(),
+		password_stdin: false,
+		password_fd: args.other_password_fd,
+		password_command: None,
+		keyring: args.database.keyring,
+	})?;
+
+	let (data, report) = merge(&vault.data, &other.data);
+	for name in &report.added {
+		println!("added: {name}");
+	}
+	for name in &report.updated {
+		println!("updated: {name}");
+	}
+	for conflict in &report.conflicts {
+		let from_other = other.data.entries.iter().any(|entry| {
+			entry.name == conflict.kept.name && entry.modified_at == conflict.kept.modified_at
+		});
+		let source = match from_other {
+			true => &args.other,
+			false => &args.database.path,
+		};
+		println!(
+			"conflict: {}, kept the version of '{}' modified on {}, discarded the version modified on {}",
+			conflict.kept.name,
+			source.display(),
+			conflict.kept.modified_at.format("%Y-%m-%d %H:%M:%S"),
+			conflict.discarded.modified_at.format("%Y-%m-%d %H:%M:%S")
+		);
+	}
+	eprintln!(
+		"{} added, {} updated, {} conflict(s)",
+		report.added.len(),
+		report.updated.len(),
+		report.conflicts.len()
+	);
+
+	vault.data = data;
+	match in_place {
+		true => vault.save(),
+		false => vault.save_as(&args.output),
+	}
+}
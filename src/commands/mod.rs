@@ -0,0 +1,351 @@
+//! Non-interactive subcommands, for use from scripts
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::os::fd::FromRawFd;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixListener;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use chrono::Utc;
+
+use crate::cli::Command;
+use crate::cli::DatabaseArgs;
+use crate::data::database::decrypt_database_with_key;
+use crate::data::database::derive_key;
+use crate::data::database::encrypt_database_with_key;
+use crate::data::database::Data;
+use crate::data::database::Database;
+use crate::data::entry::Entry;
+use crate::data::file::load_database;
+use crate::data::file::lock_database;
+use crate::data::file::save_database;
+use crate::data::file::DatabaseLock;
+use crate::data::file::LockState;
+use crate::data::journal::journal_path;
+use crate::data::keyring;
+use crate::data::secret::wipe;
+use crate::data::secret::SecretBuffer;
+use crate::hooks::run_hooks;
+use crate::hooks::HookContext;
+use crate::hooks::HookEvent;
+
+pub mod add;
+pub mod agent;
+pub mod audit;
+pub mod edit;
+pub mod export;
+pub mod get;
+pub mod host;
+pub mod import;
+pub mod list;
+pub mod menu;
+pub mod merge;
+pub mod rm;
+pub mod ssh_agent;
+pub mod totp;
+
+fn check_exists(path: &Path) -> Result<(), String> {
+	if !path.exists() {
+		return Err(format!("Database '{}' does not exist", path.display()));
+	}
+	Ok(())
+}
+
+/// Read the first line of `reader`, without the line break
+fn read_line(reader: impl Read) -> Result<String, String> {
+	let mut line = String::new();
+	BufReader::new(reader)
+		.read_line(&mut line)
+		.map_err(|err| format!("Failed to read password: {err}"))?;
+	let len = line.trim_end_matches(['\n', '\r']).len();
+	line.truncate(len);
+	Ok(line)
+}
+
+/// Read the password from the source given on the command line, or prompt for it on the terminal
+///
+/// Sources are tried in order: stdin, file descriptor, then command.
+fn read_password(args: &DatabaseArgs) -> Result<String, String> {
+	if args.password_stdin {
+		return read_line(std::io::stdin());
+	}
+	if let Some(fd) = args.password_fd {
+		// SAFETY: the descriptor is handed to us by the caller and only used here
+		let file = unsafe { std::fs::File::from_raw_fd(fd) };
+		return read_line(file);
+	}
+	if let Some(command) = &args.password_command {
+		let output = std::process::Command::new("sh")
+			.arg("-c")
+			.arg(command)
+			.stdin(Stdio::null())
+			.stderr(Stdio::piped())
+			.output()
+			.map_err(|err| format!("Failed to run '{command}': {err}"))?;
+		if !output.status.success() {
+			return Err(format!(
+				"Password command '{command}' failed: {}",
+				String::from_utf8_lossy(&output.stderr).trim()
+			));
+		}
+		let mut stdout = output.stdout;
+		let password = read_line(stdout.as_slice());
+		wipe(&mut stdout);
+		return password;
+	}
+
+	let name = args
+		.path
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.unwrap_or_else(|| args.path.display().to_string());
+	rpassword::prompt_password(format!("Password for {name}: "))
+		.map_err(|err| format!("Failed to read password: {err}"))
+}
+
+/// Read the password of a file to import or export from file descriptor `fd`, or prompt for it
+/// on the terminal
+///
+/// New passwords are asked twice when prompted.
+fn read_file_password(path: &Path, fd: Option<i32>, new: bool) -> Result<String, String> {
+	if let Some(fd) = fd {
+		// SAFETY: the descriptor is handed to us by the caller and only used here
+		let file = unsafe { std::fs::File::from_raw_fd(fd) };
+		return read_line(file);
+	}
+	let name = path
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.unwrap_or_else(|| path.display().to_string());
+	let password = rpassword::prompt_password(format!("Password for {name}: "))
+		.map_err(|err| format!("Failed to read password: {err}"))?;
+	if new {
+		let repeated = rpassword::prompt_password("Repeat password: ")
+			.map_err(|err| format!("Failed to read password: {err}"))?;
+		let matches = repeated == password;
+		wipe(&mut repeated.into_bytes());
+		if !matches {
+			wipe(&mut password.into_bytes());
+			return Err("Passwords do not match".into());
+		}
+	}
+	Ok(password)
+}
+
+/// Lock the database at `path` for modification
+fn acquire_lock(path: &Path) -> Result<DatabaseLock, String> {
+	let lock = match lock_database(path)? {
+		LockState::Acquired(lock) => lock,
+		LockState::Held { pid } => {
+			return Err(format!(
+				"'{}' is already opened by another instance (pid {pid})",
+				path.display()
+			))
+		}
+	};
+	// Saving would be undone by recovering the journal
+	if journal_path(path).exists() {
+		return Err(format!(
+			"'{}' has unsaved changes from a previous session, open it to recover them first",
+			path.display()
+		));
+	}
+	Ok(lock)
+}
+
+/// Bind the agent socket, readable only by the current user
+fn bind(path: &Path) -> Result<UnixListener, String> {
+	if path.exists() {
+		// Replace sockets left behind by agents that did not exit cleanly
+		if UnixStream::connect(path).is_ok() {
+			return Err(format!(
+				"An agent is already listening on '{}'",
+				path.display()
+			));
+		}
+		std::fs::remove_file(path)
+			.map_err(|err| format!("Failed to remove '{}': {err}", path.display()))?;
+	}
+	let listener = UnixListener::bind(path)
+		.map_err(|err| format!("Failed to bind '{}': {err}", path.display()))?;
+	std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+		.map_err(|err| format!("Failed to set permissions of '{}': {err}", path.display()))?;
+	Ok(listener)
+}
+
+/// Block SIGINT and SIGTERM in the current thread and the threads it spawns
+fn block_termination_signals() -> libc::sigset_t {
+	// SAFETY: the set is initialized by sigemptyset before use
+	unsafe {
+		let mut set = std::mem::zeroed::<libc::sigset_t>();
+		libc::sigemptyset(&mut set);
+		libc::sigaddset(&mut set, libc::SIGINT);
+		libc::sigaddset(&mut set, libc::SIGTERM);
+		libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut());
+		set
+	}
+}
+
+/// Unlocked database
+pub struct Vault {
+	pub path: PathBuf,
+	pub db: Database,
+	pub key: SecretBuffer,
+	pub data: Data,
+	/// Lock held while the database is opened for writing
+	lock: Option<DatabaseLock>,
+}
+
+impl Vault {
+	/// Load the database and unlock it with the password read from the terminal, or the source
+	/// given on the command line
+	///
+	/// The key held by a running `passk agent`, or cached in the keyring by an interactive session
+	/// when `--keyring` is given, is used when available.
+	pub fn open(args: &DatabaseArgs) -> Result<Self, String> {
+		let path = args.path.as_path();
+		let key_file = args.key_file.as_deref();
+		check_exists(path)?;
+		let db = load_database(path)?;
+		if let Ok(Some(key)) = crate::data::agent::lookup(path) {
+			if let Ok(data) = decrypt_database_with_key(&db, &key) {
+				return Ok(Self {
+					path: path.to_path_buf(),
+					db,
+					key,
+					data,
+					lock: None,
+				});
+			}
+		}
+		let cached = match args.keyring {
+			true => keyring::lookup(path).ok().flatten(),
+			false => None,
+		};
+		if let Some(cached) = cached {
+			if let Ok(data) = decrypt_database_with_key(&db, &cached.key) {
+				return Ok(Self {
+					path: path.to_path_buf(),
+					db,
+					key: cached.key,
+					data,
+					lock: None,
+				});
+			}
+		}
+		if db.kdf.requires_key_file() && key_file.is_none() {
+			return Err("This database requires a key file, use --key-file".into());
+		}
+
+		let password = read_password(args)?;
+		let key = derive_key(&db.kdf, &password, key_file);
+		wipe(&mut password.into_bytes());
+		let key = key.map_err(|err| format!("Failed to derive key: {err}"))?;
+		let data = decrypt_database_with_key(&db, &key)
+			.map_err(|err| format!("Failed to decrypt database: {err}"))?;
+
+		Ok(Self {
+			path: path.to_path_buf(),
+			db,
+			key,
+			data,
+			lock: None,
+		})
+	}
+
+	/// Open the database for modification, holding its lock until the vault is dropped
+	pub fn open_mut(args: &DatabaseArgs) -> Result<Self, String> {
+		check_exists(&args.path)?;
+		let lock = acquire_lock(&args.path)?;
+		let mut vault = Self::open(args)?;
+		vault.lock = Some(lock);
+		Ok(vault)
+	}
+
+	/// Lock a database opened with [`Vault::open`], to save it
+	///
+	/// Fails if the file was modified since it was opened.
+	pub fn lock(&mut self) -> Result<(), String> {
+		if self.lock.is_some() {
+			return Ok(());
+		}
+		let lock = acquire_lock(&self.path)?;
+		if load_database(&self.path)?.blob != self.db.blob {
+			return Err(format!(
+				"'{}' was modified since it was opened",
+				self.path.display()
+			));
+		}
+		self.lock = Some(lock);
+		Ok(())
+	}
+
+	/// Encrypt and write the database
+	pub fn save(&mut self) -> Result<(), String> {
+		if self.lock.is_none() {
+			return Err("Database is opened read-only".into());
+		}
+		let now = Utc::now();
+		self.data.iteration += 1;
+		self.data.modified_at = now;
+		if let Some(metadata) = &mut self.db.metadata {
+			metadata.modified_at = now;
+		}
+		encrypt_database_with_key(&self.data, &mut self.db, &self.key)
+			.map_err(|err| format!("Failed to encrypt database: {err}"))?;
+		save_database(&self.db, &self.path)
+			.map_err(|err| format!("Failed to save database: {err}"))?;
+		run_hooks(
+			HookEvent::PostSave,
+			&HookContext {
+				database: Some(&self.path),
+				..Default::default()
+			},
+		);
+		Ok(())
+	}
+
+	/// Encrypt and write the database to the new file `path`, which is then the saved file
+	pub fn save_as(&mut self, path: &Path) -> Result<(), String> {
+		if path.exists() {
+			return Err(format!("'{}' already exists", path.display()));
+		}
+		self.lock = Some(acquire_lock(path)?);
+		self.path = path.to_path_buf();
+		self.save()
+	}
+
+	/// Find the entry named `name`
+	pub fn entry(&self, name: &str) -> Result<&Entry, String> {
+		self.data
+			.entries
+			.iter()
+			.find(|entry| entry.name == name)
+			.ok_or_else(|| format!("No entry named '{name}'"))
+	}
+}
+
+/// Run a subcommand
+pub fn run(command: Command) -> Result<(), String> {
+	match command {
+		Command::Get(args) => get::run(args),
+		Command::Add(args) => add::run(args),
+		Command::Rm(args) => rm::run(args),
+		Command::List(args) => list::run(args),
+		Command::Totp(args) => totp::run(args),
+		Command::Menu(args) => menu::run(args),
+		Command::SshAgent(args) => ssh_agent::run(args),
+		Command::Host(args) => host::run(args),
+		Command::Edit(args) => edit::run(args),
+		Command::Audit(args) => audit::run(args),
+		Command::Export(args) => export::run(args),
+		Command::Import(args) => import::run(args),
+		Command::Merge(args) => merge::run(args),
+		Command::Agent(args) => agent::run(args),
+	}
+}
@@ -0,0 +1,20 @@
+use crate::cli::RmArgs;
+use crate::commands::Vault;
+use crate::data::audit::AuditAction;
+
+/// Delete an entry
+pub fn run(args: RmArgs) -> Result<(), String> {
+	let mut vault = Vault::open_mut(&args.database)?;
+	let index = vault
+		.data
+		.entries
+		.iter()
+		.position(|entry| entry.name == args.entry)
+		.ok_or_else(|| format!("No entry named '{}'", args.entry))?;
+	let entry = vault.data.entries.remove(index);
+	vault
+		.data
+		.log
+		.record(Some(&entry.name), AuditAction::EntryDeleted);
+	vault.save()
+}
@@ -0,0 +1,165 @@
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ssh_key::HashAlg;
+
+use crate::cli::SshAgentArgs;
+use crate::commands::bind;
+use crate::commands::block_termination_signals;
+use crate::commands::Vault;
+use crate::data::ssh;
+use crate::data::ssh::SshKey;
+
+/// Replies and requests of the SSH agent protocol, see draft-miller-ssh-agent
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+/// Sign request flags selecting the RSA signature algorithm
+const SSH_AGENT_RSA_SHA2_256: u32 = 2;
+const SSH_AGENT_RSA_SHA2_512: u32 = 4;
+
+/// Largest message accepted from clients, in bytes
+const MAX_MESSAGE_SIZE: usize = 256 * 1024;
+
+/// Socket used when none is given on the command line
+fn default_socket() -> Result<PathBuf, String> {
+	std::env::var_os("XDG_RUNTIME_DIR")
+		.map(|dir| PathBuf::from(dir).join("passk-agent.sock"))
+		.ok_or_else(|| "XDG_RUNTIME_DIR is not set, use --socket".into())
+}
+
+/// Read a `string` of the SSH wire format from the start of `data`
+fn read_string<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+	let len = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as usize;
+	let string = data.get(4..4 + len)?;
+	*data = &data[4 + len..];
+	Some(string)
+}
+
+/// Append a `string` of the SSH wire format to `out`
+fn write_string(out: &mut Vec<u8>, string: &[u8]) {
+	out.extend((string.len() as u32).to_be_bytes());
+	out.extend(string);
+}
+
+/// Answer an `SSH_AGENTC_REQUEST_IDENTITIES` request
+fn identities(keys: &[SshKey]) -> Result<Vec<u8>, String> {
+	let mut response = vec![SSH_AGENT_IDENTITIES_ANSWER];
+	response.extend((keys.len() as u32).to_be_bytes());
+	for key in keys {
+		write_string(&mut response, &key.public_blob()?);
+		write_string(&mut response, key.comment.as_bytes());
+	}
+	Ok(response)
+}
+
+/// Answer an `SSH_AGENTC_SIGN_REQUEST` request
+fn sign(keys: &[SshKey], mut request: &[u8]) -> Result<Vec<u8>, String> {
+	let blob = read_string(&mut request).ok_or("Malformed sign request")?;
+	let data = read_string(&mut request).ok_or("Malformed sign request")?;
+	let flags = request
+		.get(..4)
+		.map(|flags| u32::from_be_bytes(flags.try_into().unwrap()))
+		.unwrap_or(0);
+	let hash = if flags & SSH_AGENT_RSA_SHA2_512 != 0 {
+		Some(HashAlg::Sha512)
+	} else if flags & SSH_AGENT_RSA_SHA2_256 != 0 {
+		Some(HashAlg::Sha256)
+	} else {
+		None
+	};
+
+	let key = keys
+		.iter()
+		.find(|key| key.public_blob().is_ok_and(|public| public == blob))
+		.ok_or("Unknown key")?;
+	let signature = key.sign(data, hash)?;
+	let mut encoded = vec![];
+	write_string(&mut encoded, signature.algorithm().as_str().as_bytes());
+	write_string(&mut encoded, signature.as_bytes());
+
+	let mut response = vec![SSH_AGENT_SIGN_RESPONSE];
+	write_string(&mut response, &encoded);
+	Ok(response)
+}
+
+/// Serve the requests of a client until it disconnects
+fn serve(mut stream: UnixStream, keys: &[SshKey]) -> Result<(), String> {
+	loop {
+		let mut len = [0u8; 4];
+		if stream.read_exact(&mut len).is_err() {
+			return Ok(());
+		}
+		let len = u32::from_be_bytes(len) as usize;
+		if len == 0 || len > MAX_MESSAGE_SIZE {
+			return Err(format!("Invalid message length {len}"));
+		}
+		let mut message = vec![0u8; len];
+		stream
+			.read_exact(&mut message)
+			.map_err(|err| format!("Failed to read request: {err}"))?;
+
+		let response = match message[0] {
+			SSH_AGENTC_REQUEST_IDENTITIES => identities(keys),
+			SSH_AGENTC_SIGN_REQUEST => sign(keys, &message[1..]),
+			_ => Err(format!("Unsupported request {}", message[0])),
+		}
+		.unwrap_or_else(|err| {
+			eprintln!("{err}");
+			vec![SSH_AGENT_FAILURE]
+		});
+		stream
+			.write_all(&(response.len() as u32).to_be_bytes())
+			.and_then(|_| stream.write_all(&response))
+			.map_err(|err| format!("Failed to write response: {err}"))?;
+	}
+}
+
+/// Unlock the database and serve its SSH keys to SSH clients, until interrupted
+pub fn run(args: SshAgentArgs) -> Result<(), String> {
+	let vault = Vault::open(&args.database)?;
+	let mut keys = vec![];
+	for key in ssh::load_keys(&vault.data) {
+		match key {
+			Ok(key) => keys.push(key),
+			Err(err) => eprintln!("{err}"),
+		}
+	}
+	drop(vault);
+	if keys.is_empty() {
+		return Err("The database contains no usable SSH key".into());
+	}
+	let keys = Arc::new(keys);
+
+	let socket = match args.socket {
+		Some(socket) => socket,
+		None => default_socket()?,
+	};
+	let signals = block_termination_signals();
+	let listener = bind(&socket)?;
+	println!("SSH_AUTH_SOCK={}; export SSH_AUTH_SOCK;", socket.display());
+	eprintln!("Serving {} key(s)", keys.len());
+
+	std::thread::spawn(move || {
+		for stream in listener.incoming().flatten() {
+			let keys = keys.clone();
+			std::thread::spawn(move || {
+				if let Err(err) = serve(stream, &keys) {
+					eprintln!("{err}");
+				}
+			});
+		}
+	});
+
+	let mut signal = 0;
+	// SAFETY: the set was initialized by block_termination_signals
+	unsafe { libc::sigwait(&signals, &mut signal) };
+	std::fs::remove_file(&socket)
+		.map_err(|err| format!("Failed to remove '{}': {err}", socket.display()))
+}
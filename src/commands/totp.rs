@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::cli::TotpArgs;
+use crate::commands::Vault;
+use crate::data::field::FieldValue;
+use crate::data::otp;
+use crate::data::otp::Totp;
+
+fn is_otp(value: &FieldValue) -> bool {
+	matches!(
+		value,
+		FieldValue::TOTPRFC6238(_) | FieldValue::TOTPSteam(_) | FieldValue::HOTP { .. }
+	)
+}
+
+/// Print the current code of an OTP field
+///
+/// HOTP counters are advanced and saved, as each code can only be used once.
+pub fn run(args: TotpArgs) -> Result<(), String> {
+	let mut vault = Vault::open(&args.database)?;
+	let entry = vault.entry(&args.entry)?;
+	let index = match &args.field {
+		Some(name) => entry
+			.fields
+			.iter()
+			.position(|field| &field.name == name)
+			.ok_or_else(|| format!("No field named '{name}' in entry '{}'", entry.name))?,
+		None => entry
+			.fields
+			.iter()
+			.position(|field| is_otp(&field.value))
+			.ok_or_else(|| format!("Entry '{}' has no OTP field", entry.name))?,
+	};
+	let field = &entry.fields[index];
+
+	let totp = match &field.value {
+		FieldValue::TOTPRFC6238(secret) => Totp::parse(secret)?,
+		FieldValue::TOTPSteam(secret) => Totp::parse_steam(secret)?,
+		FieldValue::HOTP { secret, counter } => {
+			if args.next {
+				return Err("--next only applies to TOTP fields".into());
+			}
+			let code = otp::generate_hotp(secret, *counter)?;
+			let entry = args.entry.clone();
+			vault.lock()?;
+			let field = &mut vault
+				.data
+				.entries
+				.iter_mut()
+				.find(|ent| ent.name == entry)
+				.unwrap()
+				.fields[index];
+			if let FieldValue::HOTP { counter, .. } = &mut field.value {
+				*counter += 1;
+			}
+			vault.save()?;
+			println!("{code}");
+			return Ok(());
+		}
+		_ => return Err(format!("Field '{}' is not an OTP field", field.name)),
+	};
+
+	if args.next {
+		let remaining = totp.remaining(Utc::now());
+		std::thread::sleep(Duration::from_secs(remaining));
+	}
+	println!("{}", totp.code(Utc::now()));
+	Ok(())
+}
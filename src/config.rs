@@ -0,0 +1,220 @@
+//! User configuration
+//!
+//! Settings are read from `$XDG_CONFIG_HOME/passk/config.toml`, every setting is optional:
+//! ```toml
+//! database = "~/passwords.passk"
+//! clear-clipboard = 30
+//! lock-after = 300
+//! lock-on-focus-lost = true
+//! mouse = false
+//! autosave = true
+//! theme = "dark"
+//! icons = "auto"
+//! password-max-age = 180
+//! columns = ["health", "name", "modified", "tags"]
+//!
+//! [kdf]
+//! memory = 131072
+//! iterations = 3
+//! parallelism = 4
+//!
+//! [[generator]]
+//! name = "PIN"
+//! charset = "0123456789"
+//! length = 6
+//! ```
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::data::database::Argon2Params;
+use crate::data::health;
+
+/// Configuration directory given on the command line
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir` instead of the default configuration directory
+pub fn set_config_dir(dir: PathBuf) {
+	let _ = CONFIG_DIR.set(dir);
+}
+
+/// Directory holding passk's configuration: `$XDG_CONFIG_HOME/passk`, unless overridden with
+/// `--config`
+pub fn config_dir() -> Option<PathBuf> {
+	if let Some(dir) = CONFIG_DIR.get() {
+		return Some(dir.clone());
+	}
+	let config = std::env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+	Some(config.join("passk"))
+}
+
+/// Palette of the interface
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+	#[default]
+	Dark,
+	Light,
+	HighContrast,
+}
+
+/// Icons of the interface
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+	/// ASCII icons on terminals unlikely to display the nerd font ones
+	#[default]
+	Auto,
+	Nerd,
+	Ascii,
+}
+
+/// Column of the entries in the explorer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntryColumn {
+	/// Health of the passwords
+	Health,
+	/// Icon and name, with the favorite, expiration and password age indicators
+	Name,
+	Tags,
+	/// Number of fields
+	Fields,
+	/// Date of the last modification
+	Modified,
+}
+
+/// Password generator preset, offered along the predefined charsets
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneratorPreset {
+	pub name: String,
+	pub charset: String,
+	pub length: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+	/// Database opened when none is given on the command line
+	pub database: Option<PathBuf>,
+	/// Seconds after which copied values are cleared from the clipboard, 0 to keep them
+	pub clear_clipboard: u64,
+	/// Seconds without input after which the session is locked, 0 to never lock it
+	pub lock_after: u64,
+	/// Lock the session when the terminal loses focus, if it reports focus changes
+	pub lock_on_focus_lost: bool,
+	/// Capture the mouse, which prevents selecting text in the terminal
+	pub mouse: bool,
+	/// Save the database after every change, instead of keeping changes in the journal until
+	/// saved
+	pub autosave: bool,
+	/// Palette of the interface: dark, light or high-contrast
+	pub theme: ThemeName,
+	/// Icons of the interface: auto, nerd (requires a nerd font) or ascii
+	pub icons: IconSet,
+	/// Days after which unchanged passwords are shown as old in the explorer, 0 to never show them
+	pub password_max_age: u32,
+	/// Columns of the entries in the explorer, in order, the name is required
+	pub columns: Vec<EntryColumn>,
+	/// Key derivation of new databases
+	pub kdf: Argon2Params,
+	#[serde(rename = "generator")]
+	pub generators: Vec<GeneratorPreset>,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self {
+			database: None,
+			clear_clipboard: 45,
+			lock_after: 0,
+			lock_on_focus_lost: false,
+			mouse: true,
+			autosave: false,
+			theme: ThemeName::default(),
+			icons: IconSet::default(),
+			password_max_age: health::DEFAULT_MAX_AGE,
+			columns: vec![
+				EntryColumn::Health,
+				EntryColumn::Name,
+				EntryColumn::Fields,
+				EntryColumn::Tags,
+			],
+			kdf: Argon2Params::default(),
+			generators: vec![],
+		}
+	}
+}
+
+impl Config {
+	fn validate(&self) -> Result<(), String> {
+		self.kdf.validate().map_err(|err| format!("kdf: {err}"))?;
+		if !self.columns.contains(&EntryColumn::Name) {
+			return Err("columns: The name column is required".into());
+		}
+		for (pos, column) in self.columns.iter().enumerate() {
+			if self.columns[..pos].contains(column) {
+				let column = format!("{column:?}").to_lowercase();
+				return Err(format!("columns: Column '{column}' is repeated"));
+			}
+		}
+		for preset in &self.generators {
+			if preset.name.is_empty() {
+				return Err("generator: Preset name is empty".into());
+			}
+			if preset.charset.is_empty() || preset.length == 0 {
+				return Err(format!(
+					"generator: Preset '{}' has an empty charset or length",
+					preset.name
+				));
+			}
+		}
+		Ok(())
+	}
+}
+
+fn load() -> Result<Config, String> {
+	let Some(path) = config_dir().map(|dir| dir.join("config.toml")) else {
+		return Ok(Config::default());
+	};
+	if !path.exists() {
+		return Ok(Config::default());
+	}
+	let content = std::fs::read_to_string(&path)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	let mut config: Config = toml::from_str(&content)
+		.map_err(|err| format!("Failed to parse '{}': {err}", path.display()))?;
+	config
+		.validate()
+		.map_err(|err| format!("Invalid '{}': {err}", path.display()))?;
+
+	// Paths relative to the home directory
+	let home = std::env::var_os("HOME").map(PathBuf::from);
+	if let (Some(database), Some(home)) = (&mut config.database, home) {
+		if let Ok(relative) = database.strip_prefix("~") {
+			*database = home.join(relative);
+		}
+	}
+	Ok(config)
+}
+
+/// Configuration, with the defaults when it failed to load
+static CONFIG: LazyLock<(Config, Option<String>)> = LazyLock::new(|| match load() {
+	Ok(config) => (config, None),
+	Err(err) => (Config::default(), Some(err)),
+});
+
+/// Loaded configuration, the defaults are used when the file is absent or invalid
+pub fn config() -> &'static Config {
+	&CONFIG.0
+}
+
+/// Error encountered when loading the configuration
+pub fn load_error() -> Option<&'static str> {
+	CONFIG.1.as_deref()
+}
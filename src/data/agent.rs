@@ -0,0 +1,72 @@
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::data::secret::SecretBuffer;
+
+/// Requests understood by the agent, a single byte sent by the client
+pub const REQUEST_KEY: u8 = 1;
+pub const REQUEST_STOP: u8 = 2;
+
+/// Largest key accepted from the agent, in bytes
+const MAX_KEY_SIZE: usize = 1024;
+
+/// Socket of the agent holding the key of the database at `path`
+///
+/// Each database has its own agent, the socket is named after the canonical path of the database.
+pub fn socket_path(path: &Path) -> Option<PathBuf> {
+	let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+	let digest = Sha256::digest(path.as_os_str().as_encoded_bytes());
+	let name = digest[..8]
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect::<String>();
+	std::env::var_os("XDG_RUNTIME_DIR")
+		.map(|dir| PathBuf::from(dir).join(format!("passk-unlock-{name}.sock")))
+}
+
+/// Connect to the agent of the database at `path`, if one is running
+fn connect(path: &Path) -> Option<UnixStream> {
+	UnixStream::connect(socket_path(path)?).ok()
+}
+
+/// Get the key held by the agent of the database at `path`
+pub fn lookup(path: &Path) -> Result<Option<SecretBuffer>, String> {
+	let Some(mut stream) = connect(path) else {
+		return Ok(None);
+	};
+	stream
+		.write_all(&[REQUEST_KEY])
+		.map_err(|err| format!("Failed to write to agent: {err}"))?;
+	let mut len = [0u8; 4];
+	stream
+		.read_exact(&mut len)
+		.map_err(|err| format!("Failed to read from agent: {err}"))?;
+	let len = u32::from_be_bytes(len) as usize;
+	if len == 0 || len > MAX_KEY_SIZE {
+		return Err(format!("Invalid key length {len} from agent"));
+	}
+	let mut key = SecretBuffer::new(len);
+	stream
+		.read_exact(&mut key)
+		.map_err(|err| format!("Failed to read from agent: {err}"))?;
+	Ok(Some(key))
+}
+
+/// Stop the agent of the database at `path`, returns whether one was running
+pub fn stop(path: &Path) -> Result<bool, String> {
+	let Some(mut stream) = connect(path) else {
+		return Ok(false);
+	};
+	stream
+		.write_all(&[REQUEST_STOP])
+		.map_err(|err| format!("Failed to write to agent: {err}"))?;
+	// The agent closes the connection once it has forgotten the key
+	let _ = stream.read(&mut [0u8; 1]);
+	Ok(true)
+}
@@ -0,0 +1,103 @@
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Action recorded in the [`AuditLog`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditAction {
+	/// Database was unlocked
+	Unlocked,
+	/// Master password was changed
+	PasswordChanged,
+	EntryCreated,
+	EntryEdited,
+	EntryRenamed {
+		from: String,
+	},
+	EntryDeleted,
+	/// Entry was moved to the trash
+	EntryTrashed,
+	/// Entry was restored from the trash
+	EntryRestored,
+	/// Field value was copied to the clipboard
+	FieldCopied {
+		field: String,
+	},
+	/// Entries were imported
+	Imported {
+		count: usize,
+	},
+	/// Entries were exported unencrypted
+	Exported {
+		format: String,
+	},
+}
+
+impl AuditAction {
+	pub fn description(&self) -> String {
+		match self {
+			AuditAction::Unlocked => "Database unlocked".into(),
+			AuditAction::PasswordChanged => "Master password changed".into(),
+			AuditAction::EntryCreated => "Entry created".into(),
+			AuditAction::EntryEdited => "Entry edited".into(),
+			AuditAction::EntryRenamed { from } => format!("Entry renamed from '{from}'"),
+			AuditAction::EntryDeleted => "Entry deleted".into(),
+			AuditAction::EntryTrashed => "Entry moved to the trash".into(),
+			AuditAction::EntryRestored => "Entry restored from the trash".into(),
+			AuditAction::FieldCopied { field } => format!("Field '{field}' copied"),
+			AuditAction::Imported { count } => format!("{count} entries imported"),
+			AuditAction::Exported { format } => format!("Entries exported as {format}"),
+		}
+	}
+}
+
+/// Entry of the [`AuditLog`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEvent {
+	pub at: DateTime<Utc>,
+	/// Name of the affected entry
+	pub entry: Option<String>,
+	pub action: AuditAction,
+}
+
+/// Append-only log of the operations performed on the database
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+	/// Events, oldest first
+	events: Vec<AuditEvent>,
+}
+
+impl From<Vec<AuditEvent>> for AuditLog {
+	fn from(events: Vec<AuditEvent>) -> Self {
+		AuditLog { events }
+	}
+}
+
+impl AuditLog {
+	/// Append an event at the current time
+	pub fn record(&mut self, entry: Option<&str>, action: AuditAction) {
+		self.events.push(AuditEvent {
+			at: Utc::now(),
+			entry: entry.map(str::to_string),
+			action,
+		});
+	}
+
+	/// Recorded events, oldest first
+	pub fn events(&self) -> &[AuditEvent] {
+		&self.events
+	}
+
+	/// Combine the events of two logs in chronological order
+	pub fn merge(&self, other: &AuditLog) -> AuditLog {
+		let mut events = self.events.clone();
+		for event in &other.events {
+			if !events.contains(event) {
+				events.push(event.clone());
+			}
+		}
+		events.sort_by_key(|event| event.at);
+		AuditLog { events }
+	}
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use argon2::Argon2;
 use chacha20poly1305::KeyInit;
 use chrono::DateTime;
@@ -6,27 +8,89 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_with::base64::Base64;
 use serde_with::serde_as;
+use sha2::Digest;
+use sha2::Sha256;
 
+use crate::data::audit::AuditLog;
 use crate::data::entry::Entry;
+use crate::data::entry::EntrySort;
+use crate::data::entry::TrashedEntry;
+use crate::data::migration;
+use crate::data::secret::SecretBuffer;
+use crate::data::tag::TagRegistry;
+use crate::data::yubikey;
 
 /// Database format version
+///
+/// The version is bumped when the layout of the [`Data`] changes, data saved in the layout of a
+/// previous version is migrated on load, see [`migration`].
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Version {
 	#[default]
 	V1,
+	/// Navigation state of the explorer, [`Data::session`]
+	V2,
+	/// Revisions of the entries, [`Entry::history`]
+	V3,
+	/// Icons of the entries, [`Entry::icon`]
+	V4,
+	/// Tag definitions, [`Data::tags`]
+	V5,
+	/// Expiration of the fields, [`Field::expires_at`](crate::data::field::Field::expires_at)
+	V6,
+	/// Operations log, [`Data::log`]
+	V7,
+	/// Order of the explorer, [`SessionState::sort`]
+	V8,
+	/// Favorite entries, [`Entry::favorite`]
+	V9,
+	/// Tag sidebar of the explorer, [`SessionState::tag_selected`]
+	V10,
+	/// Deleted entries, [`Data::trash`], and actions on them in the log
+	V11,
+	/// Previous values of the fields, [`Field::history`](crate::data::field::Field::history)
+	V12,
+}
+
+impl Version {
+	/// Version of the databases written by this build
+	pub const CURRENT: Version = Version::V12;
 }
 
 /// Database cipher
 #[derive(Clone, Serialize, Deserialize)]
 pub enum CipherData {
 	XChaCha20Poly1305V1 {},
+	Aes256GcmV1 {},
 }
 
 impl CipherData {
 	pub fn key_len(&self) -> usize {
 		match self {
 			CipherData::XChaCha20Poly1305V1 {} => 32,
+			CipherData::Aes256GcmV1 {} => 32,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			CipherData::XChaCha20Poly1305V1 {} => "xchacha20poly1305",
+			CipherData::Aes256GcmV1 {} => "aes256gcm",
+		}
+	}
+}
+
+impl TryFrom<&str> for CipherData {
+	type Error = String;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value {
+			"xchacha20poly1305" => Ok(CipherData::XChaCha20Poly1305V1 {}),
+			"aes256gcm" => Ok(CipherData::Aes256GcmV1 {}),
+			_ => Err(format!(
+				"Unknown cipher '{value}', expected 'xchacha20poly1305' or 'aes256gcm'"
+			)),
 		}
 	}
 }
@@ -39,6 +103,14 @@ pub struct XChaCha20Poly1305BlobV1 {
 	ciphertext: Vec<u8>,
 }
 
+/// Cipher-specific data
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Aes256GcmBlobV1 {
+	nonce: [u8; 12],
+	// ciphertext || tag
+	ciphertext: Vec<u8>,
+}
+
 /// HKDF data
 #[derive(Clone, Serialize, Deserialize)]
 pub enum KdfData {
@@ -49,6 +121,109 @@ pub enum KdfData {
 		key_len: u16,
 		parallelism: u32,
 	},
+	/// Mixes the SHA-256 digest of a key file with the password before running `kdf`
+	KeyFile { kdf: Box<KdfData> },
+	/// Mixes the output of `kdf` with the HMAC-SHA1 response of a YubiKey to `challenge`
+	YubiKey {
+		slot: u8,
+		challenge: [u8; 32],
+		kdf: Box<KdfData>,
+	},
+}
+
+impl KdfData {
+	/// Whether a key file is required to derive the key
+	pub fn requires_key_file(&self) -> bool {
+		match self {
+			KdfData::Argon2Id { .. } => false,
+			KdfData::KeyFile { .. } => true,
+			KdfData::YubiKey { kdf, .. } => kdf.requires_key_file(),
+		}
+	}
+
+	/// Human readable description of the KDF chain
+	pub fn describe(&self) -> String {
+		match self {
+			KdfData::Argon2Id {
+				memory,
+				iterations,
+				parallelism,
+				..
+			} => format!(
+				"argon2id ({} MiB, {iterations} iterations, {parallelism} lanes)",
+				memory / 1024
+			),
+			KdfData::KeyFile { kdf } => format!("{} + key file", kdf.describe()),
+			KdfData::YubiKey { slot, kdf, .. } => {
+				format!("{} + YubiKey (slot {slot})", kdf.describe())
+			}
+		}
+	}
+
+	/// YubiKey slot required to derive the key
+	pub fn yubikey_slot(&self) -> Option<u8> {
+		match self {
+			KdfData::Argon2Id { .. } => None,
+			KdfData::KeyFile { kdf } => kdf.yubikey_slot(),
+			KdfData::YubiKey { slot, .. } => Some(*slot),
+		}
+	}
+
+	/// Copy of the innermost KDF parameters with a fresh salt
+	fn resalted(&self) -> KdfData {
+		match self {
+			KdfData::Argon2Id {
+				salt: _,
+				memory,
+				iterations,
+				key_len,
+				parallelism,
+			} => {
+				let mut salt = [0u8; 16];
+				rand::fill(&mut salt);
+				KdfData::Argon2Id {
+					salt,
+					memory: *memory,
+					iterations: *iterations,
+					key_len: *key_len,
+					parallelism: *parallelism,
+				}
+			}
+			KdfData::KeyFile { kdf } => kdf.resalted(),
+			KdfData::YubiKey { kdf, .. } => kdf.resalted(),
+		}
+	}
+
+	/// Wrap `self` so that the key also requires the YubiKey in `slot`, with a fresh challenge
+	pub fn with_yubikey(self, slot: u8) -> KdfData {
+		let mut challenge = [0u8; 32];
+		rand::fill(&mut challenge);
+		KdfData::YubiKey {
+			slot,
+			challenge,
+			kdf: Box::new(self),
+		}
+	}
+}
+
+/// Human metadata stored in the database header, readable without the password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseMetadata {
+	pub name: String,
+	pub description: String,
+	pub created_at: DateTime<Utc>,
+	pub modified_at: DateTime<Utc>,
+}
+
+impl Default for DatabaseMetadata {
+	fn default() -> Self {
+		Self {
+			name: Default::default(),
+			description: Default::default(),
+			created_at: Utc::now(),
+			modified_at: Utc::now(),
+		}
+	}
 }
 
 /// Database
@@ -58,17 +233,98 @@ pub struct Database {
 	pub version: Version,
 	pub cipher: CipherData,
 	pub kdf: KdfData,
+	/// Missing from databases created before metadata was introduced, skipped when absent so their
+	/// header AAD is unchanged
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub metadata: Option<DatabaseMetadata>,
 
 	// Cipher specific data
 	#[serde_as(as = "Base64")]
 	pub blob: Vec<u8>,
 }
 
+/// Argon2id parameters of new databases
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Argon2Params {
+	/// Memory cost, in KiB
+	pub memory: u32,
+	pub iterations: u32,
+	pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+	fn default() -> Self {
+		Self {
+			memory: 65536,
+			iterations: 2,
+			parallelism: 2,
+		}
+	}
+}
+
+impl Argon2Params {
+	/// Check that the parameters are accepted by Argon2
+	pub fn validate(&self) -> Result<(), String> {
+		argon2::Params::new(self.memory, self.iterations, self.parallelism, None)
+			.map(|_| ())
+			.map_err(|err| format!("Invalid argon2 params: {err}"))
+	}
+}
+
+impl Database {
+	/// Header for a new database, with a fresh salt and an empty blob
+	pub fn new(cipher: CipherData, params: Argon2Params) -> Self {
+		let mut salt = [0u8; 16];
+		rand::fill(&mut salt);
+		Self {
+			version: Version::CURRENT,
+			kdf: KdfData::Argon2Id {
+				salt,
+				memory: params.memory,
+				iterations: params.iterations,
+				key_len: cipher.key_len() as u16,
+				parallelism: params.parallelism,
+			},
+			cipher,
+			metadata: Some(DatabaseMetadata::default()),
+			blob: vec![],
+		}
+	}
+}
+
+/// UI state restored on unlock
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SessionState {
+	/// Name of the selected entry
+	pub selected: Option<String>,
+	/// Explorer filter
+	pub filter: String,
+	/// First visible row of the entries
+	pub offset: usize,
+	/// Order of the entries, the one remembered between runs is kept when unset
+	pub sort: Option<EntrySort>,
+	/// Selected tag of the sidebar
+	pub tag_selected: usize,
+	/// First visible row of the tag sidebar
+	pub tag_offset: usize,
+}
+
 /// Database content
+///
+/// Fields are serialized by position, changing the layout of the data or of any type it holds
+/// requires a new [`Version`] and a migration from the previous layout.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Data {
 	pub iteration: u64,
 	pub entries: Vec<Entry>,
+	/// Deleted entries, most recently deleted last
+	pub trash: Vec<TrashedEntry>,
+	/// Tag definitions
+	pub tags: TagRegistry,
+	/// Operations performed on the database
+	pub log: AuditLog,
+	pub session: SessionState,
 
 	pub created_at: DateTime<Utc>,
 	pub modified_at: DateTime<Utc>,
@@ -79,13 +335,30 @@ impl Default for Data {
 		Self {
 			iteration: Default::default(),
 			entries: Default::default(),
+			trash: Default::default(),
+			tags: Default::default(),
+			log: Default::default(),
+			session: Default::default(),
 			created_at: Utc::now(),
 			modified_at: Utc::now(),
 		}
 	}
 }
 
-fn derive_key(kdf: &KdfData, password: &str) -> Result<Vec<u8>, String> {
+/// Derive the database key from the password and optional key file
+pub fn derive_key(
+	kdf: &KdfData,
+	password: &str,
+	key_file: Option<&Path>,
+) -> Result<SecretBuffer, String> {
+	derive_key_from_secret(kdf, password.as_bytes(), key_file)
+}
+
+fn derive_key_from_secret(
+	kdf: &KdfData,
+	secret: &[u8],
+	key_file: Option<&Path>,
+) -> Result<SecretBuffer, String> {
 	match kdf {
 		KdfData::Argon2Id {
 			salt,
@@ -99,67 +372,63 @@ fn derive_key(kdf: &KdfData, password: &str) -> Result<Vec<u8>, String> {
 					.map_err(|err| format!("Failed to build argon2 params: {err}"))?;
 
 			let argon = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, config);
-			let mut key = vec![0u8; *key_len as usize];
+			let mut key = SecretBuffer::new(*key_len as usize);
 			argon
-				.hash_password_into(password.as_bytes(), salt, &mut key)
+				.hash_password_into(secret, salt, &mut key)
 				.map_err(|err| format!("Failed to hash password: {err}"))?;
 			Ok(key)
 		}
-	}
-}
-
-pub fn decrypt_database(db: &Database, password: &str) -> Result<Data, String> {
-	let key = derive_key(&db.kdf, password)?;
-
-	match &db.cipher {
-		CipherData::XChaCha20Poly1305V1 {} => {
-			let blob: XChaCha20Poly1305BlobV1 = bincode2::deserialize(&db.blob)
-				.map_err(|err| format!("Failed to deserialize blob into cipher blob: {err}"))?;
-			let cipher = chacha20poly1305::XChaCha20Poly1305::new_from_slice(&key)
-				.map_err(|err| format!("Failed to initialize chacha20-poly1305 cipher: {err}"))?;
-			let mut header = db.clone();
-			header.blob = vec![]; // Use an empty blob for AAD
-			let plaintext = chacha20poly1305::aead::Aead::decrypt(
-				&cipher,
-				&blob.nonce.into(),
-				chacha20poly1305::aead::Payload {
-					msg: &blob.ciphertext,
-					aad: bincode2::serialize(&header)
-						.map_err(|err| format!("Failed to serialize database: {err}"))?
-						.as_slice(),
-				},
-			)
-			.map_err(|err| format!("Failed to decrypt chacha20-poly1305 ciphertext: {err}"))?;
-			let data: Data = bincode2::deserialize(&plaintext)
-				.map_err(|err| format!("Failed to deserialize database: {err}"))?;
-			Ok(data)
+		KdfData::KeyFile { kdf } => {
+			let path = key_file.ok_or("This database requires a key file".to_string())?;
+			let content = std::fs::read(path)
+				.map_err(|err| format!("Failed to read key file '{}': {err}", path.display()))?;
+			let digest = Sha256::digest(&content);
+			let mut combined = SecretBuffer::new(secret.len() + 1 + digest.len());
+			combined[..secret.len()].copy_from_slice(secret);
+			combined[secret.len() + 1..].copy_from_slice(&digest);
+			derive_key_from_secret(kdf, &combined, key_file)
+		}
+		KdfData::YubiKey {
+			slot,
+			challenge,
+			kdf,
+		} => {
+			let key = derive_key_from_secret(kdf, secret, key_file)?;
+			let response = yubikey::challenge_response(*slot, challenge)?;
+			Ok(yubikey::mix(&key, &response))
 		}
 	}
 }
 
-pub fn encrypt_database(data: &Data, db: &Database, password: &str) -> Result<Vec<u8>, String> {
-	let key = derive_key(&db.kdf, password)?;
+/// Additional data authenticated alongside every ciphertext: the database header with an empty
+/// blob
+pub fn header_aad(db: &Database) -> Result<Vec<u8>, String> {
+	let mut header = db.clone();
+	header.blob = vec![]; // Use an empty blob for AAD
+	bincode2::serialize(&header).map_err(|err| format!("Failed to serialize database: {err}"))
+}
 
-	match &db.cipher {
+/// Encrypt `plaintext` using `cipher`, returns the serialized cipher blob
+pub fn seal(
+	cipher: &CipherData,
+	key: &[u8],
+	plaintext: &[u8],
+	aad: &[u8],
+) -> Result<Vec<u8>, String> {
+	match cipher {
 		CipherData::XChaCha20Poly1305V1 {} => {
-			let plaintext = bincode2::serialize(data)
-				.map_err(|err| format!("Failed to serialize data: {err}"))?;
-			let cipher = chacha20poly1305::XChaCha20Poly1305::new_from_slice(&key)
+			let cipher = chacha20poly1305::XChaCha20Poly1305::new_from_slice(key)
 				.map_err(|err| format!("Failed to initialize chacha20-poly1305 cipher: {err}"))?;
 			let nonce =
 				<chacha20poly1305::XChaCha20Poly1305 as chacha20poly1305::AeadCore>::generate_nonce(
 					&mut chacha20poly1305::aead::OsRng,
 				);
-			let mut header = db.clone();
-			header.blob = vec![]; // Use an empty blob for AAD
 			let ciphertext = chacha20poly1305::aead::Aead::encrypt(
 				&cipher,
 				&nonce,
 				chacha20poly1305::aead::Payload {
-					msg: &plaintext,
-					aad: bincode2::serialize(&header)
-						.map_err(|err| format!("Failed to serialize database: {err}"))?
-						.as_slice(),
+					msg: plaintext,
+					aad,
 				},
 			)
 			.map_err(|err| format!("Failed to encrypt using chacha20-poly1305: {err}"))?;
@@ -169,5 +438,274 @@ pub fn encrypt_database(data: &Data, db: &Database, password: &str) -> Result<Ve
 			};
 			bincode2::serialize(&blob).map_err(|err| format!("Failed to serialize data: {err}"))
 		}
+		CipherData::Aes256GcmV1 {} => {
+			let cipher = aes_gcm::Aes256Gcm::new_from_slice(key)
+				.map_err(|err| format!("Failed to initialize aes-256-gcm cipher: {err}"))?;
+			// 96-bit random nonces, a fresh one is drawn on every save
+			let nonce = <aes_gcm::Aes256Gcm as aes_gcm::AeadCore>::generate_nonce(
+				&mut aes_gcm::aead::OsRng,
+			);
+			let ciphertext = aes_gcm::aead::Aead::encrypt(
+				&cipher,
+				&nonce,
+				aes_gcm::aead::Payload {
+					msg: plaintext,
+					aad,
+				},
+			)
+			.map_err(|err| format!("Failed to encrypt using aes-256-gcm: {err}"))?;
+			let blob = Aes256GcmBlobV1 {
+				nonce: nonce.into(),
+				ciphertext,
+			};
+			bincode2::serialize(&blob).map_err(|err| format!("Failed to serialize data: {err}"))
+		}
+	}
+}
+
+/// Decrypt a cipher blob produced by [`seal`]
+pub fn open(cipher: &CipherData, key: &[u8], blob: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+	match cipher {
+		CipherData::XChaCha20Poly1305V1 {} => {
+			let blob: XChaCha20Poly1305BlobV1 = bincode2::deserialize(blob)
+				.map_err(|err| format!("Failed to deserialize blob into cipher blob: {err}"))?;
+			let cipher = chacha20poly1305::XChaCha20Poly1305::new_from_slice(key)
+				.map_err(|err| format!("Failed to initialize chacha20-poly1305 cipher: {err}"))?;
+			chacha20poly1305::aead::Aead::decrypt(
+				&cipher,
+				&blob.nonce.into(),
+				chacha20poly1305::aead::Payload {
+					msg: &blob.ciphertext,
+					aad,
+				},
+			)
+			.map_err(|err| format!("Failed to decrypt chacha20-poly1305 ciphertext: {err}"))
+		}
+		CipherData::Aes256GcmV1 {} => {
+			let blob: Aes256GcmBlobV1 = bincode2::deserialize(blob)
+				.map_err(|err| format!("Failed to deserialize blob into cipher blob: {err}"))?;
+			let cipher = aes_gcm::Aes256Gcm::new_from_slice(key)
+				.map_err(|err| format!("Failed to initialize aes-256-gcm cipher: {err}"))?;
+			aes_gcm::aead::Aead::decrypt(
+				&cipher,
+				&blob.nonce.into(),
+				aes_gcm::aead::Payload {
+					msg: &blob.ciphertext,
+					aad,
+				},
+			)
+			.map_err(|err| format!("Failed to decrypt aes-256-gcm ciphertext: {err}"))
+		}
+	}
+}
+
+/// Decrypt the blob of `db`, migrating data saved by a previous version
+pub fn decrypt_database_with_key(db: &Database, key: &[u8]) -> Result<Data, String> {
+	let plaintext = open(&db.cipher, key, &db.blob, &header_aad(db)?)?;
+	migration::decode(db.version, &plaintext)
+}
+
+/// Encrypt `data` into the blob of `db`, which is upgraded to the current version
+///
+/// `db` is left untouched on failure.
+pub fn encrypt_database_with_key(data: &Data, db: &mut Database, key: &[u8]) -> Result<(), String> {
+	let plaintext =
+		bincode2::serialize(data).map_err(|err| format!("Failed to serialize data: {err}"))?;
+	let mut header = db.clone();
+	header.version = Version::CURRENT;
+	header.blob = seal(&header.cipher, key, &plaintext, &header_aad(&header)?)?;
+	*db = header;
+	Ok(())
+}
+
+/// Entry modified on both sides of a merge
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+	/// Version kept in the merged data
+	pub kept: Entry,
+	/// Version that was discarded
+	pub discarded: Entry,
+}
+
+/// Summary of a merge
+#[derive(Debug, Default, Clone)]
+pub struct MergeReport {
+	/// Entries only present in the other database
+	pub added: Vec<String>,
+	/// Entries replaced by a newer version from the other database
+	pub updated: Vec<String>,
+	/// Entries that were modified on both sides
+	pub conflicts: Vec<MergeConflict>,
+}
+
+/// Merge `other` into `local`
+///
+/// Entries are matched by name. When both sides differ, the most recently modified entry wins.
+/// If the winning side also has the higher iteration counter, the other side is assumed to be an
+/// older copy and the entry is reported as updated, otherwise it is reported as a conflict.
+/// Entries present on a single side are always kept.
+pub fn merge(local: &Data, other: &Data) -> (Data, MergeReport) {
+	let mut report = MergeReport::default();
+	let mut entries = local.entries.clone();
+
+	for theirs in &other.entries {
+		let Some(ours) = entries.iter_mut().find(|entry| entry.name == theirs.name) else {
+			report.added.push(theirs.name.clone());
+			entries.push(theirs.clone());
+			continue;
+		};
+		if ours.modified_at == theirs.modified_at {
+			continue;
+		}
+
+		let theirs_newer = theirs.modified_at > ours.modified_at;
+		let (winner_iteration, loser_iteration) = if theirs_newer {
+			(other.iteration, local.iteration)
+		} else {
+			(local.iteration, other.iteration)
+		};
+		let (kept, discarded) = if theirs_newer {
+			(theirs.clone(), std::mem::replace(ours, theirs.clone()))
+		} else {
+			(ours.clone(), theirs.clone())
+		};
+
+		if winner_iteration > loser_iteration {
+			if theirs_newer {
+				report.updated.push(kept.name.clone());
+			}
+		} else {
+			report.conflicts.push(MergeConflict { kept, discarded });
+		}
+	}
+
+	// Local tag definitions take precedence
+	let mut tags = local.tags.clone();
+	for tag in other.tags.sorted() {
+		if tags.get(&tag.name).is_none() {
+			tags.register(tag.clone());
+		}
+	}
+	tags.sync(&entries);
+	tags.apply(&mut entries);
+
+	// Entries deleted on either side stay in the trash
+	let mut trash = local.trash.clone();
+	for theirs in &other.trash {
+		if !trash.iter().any(|ours| {
+			ours.entry.name == theirs.entry.name && ours.deleted_at == theirs.deleted_at
+		}) {
+			trash.push(theirs.clone());
+		}
+	}
+	trash.sort_by_key(|trashed| trashed.deleted_at);
+
+	let data = Data {
+		iteration: local.iteration.max(other.iteration) + 1,
+		entries,
+		trash,
+		tags,
+		log: local.log.merge(&other.log),
+		session: local.session.clone(),
+		created_at: local.created_at.min(other.created_at),
+		modified_at: Utc::now(),
+	};
+	(data, report)
+}
+
+/// Build a header for a new password, with a fresh salt, and derive its key
+///
+/// When `yubikey` is set, the key also requires the response of the YubiKey in that slot. The blob
+/// of the returned header is empty, the data must be re-encrypted with the returned key.
+pub fn rekey(
+	db: &Database,
+	password: &str,
+	key_file: Option<&Path>,
+	yubikey: Option<u8>,
+) -> Result<(Database, SecretBuffer), String> {
+	let mut kdf = db.kdf.resalted();
+	if let Some(slot) = yubikey {
+		kdf = kdf.with_yubikey(slot);
+	}
+	if key_file.is_some() {
+		kdf = KdfData::KeyFile { kdf: Box::new(kdf) };
+	}
+	let header = Database {
+		version: db.version,
+		cipher: db.cipher.clone(),
+		kdf,
+		metadata: db.metadata.clone(),
+		blob: vec![],
+	};
+	let key = derive_key(&header.kdf, password, key_file)?;
+	Ok((header, key))
+}
+
+pub fn decrypt_database(
+	db: &Database,
+	password: &str,
+	key_file: Option<&Path>,
+) -> Result<Data, String> {
+	let key = derive_key(&db.kdf, password, key_file)?;
+	decrypt_database_with_key(db, &key)
+}
+
+pub fn encrypt_database(
+	data: &Data,
+	db: &mut Database,
+	password: &str,
+	key_file: Option<&Path>,
+) -> Result<(), String> {
+	let key = derive_key(&db.kdf, password, key_file)?;
+	encrypt_database_with_key(data, db, &key)
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::TimeZone;
+
+	use super::*;
+	use crate::data::import::imported_entry;
+
+	/// Entry named `name`, last modified on `day` of January
+	fn entry(name: &str, day: u32) -> Entry {
+		let date = Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap();
+		imported_entry(name.into(), vec![], vec![], Some(date), Some(date))
+	}
+
+	fn data(iteration: u64, entries: Vec<Entry>) -> Data {
+		Data {
+			iteration,
+			entries,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn merge_newer_copy() {
+		let local = data(1, vec![entry("mail", 1), entry("bank", 1)]);
+		let other = data(2, vec![entry("mail", 2), entry("bank", 1), entry("vpn", 1)]);
+		let (merged, report) = merge(&local, &other);
+		assert_eq!(merged.iteration, 3);
+		assert_eq!(report.added, ["vpn"]);
+		assert_eq!(report.updated, ["mail"]);
+		assert!(report.conflicts.is_empty());
+		let mail = merged.entries.iter().find(|entry| entry.name == "mail");
+		assert_eq!(mail.unwrap().modified_at, entry("mail", 2).modified_at);
+		assert_eq!(merged.entries.len(), 3);
+	}
+
+	#[test]
+	fn merge_conflict() {
+		// Modified on both sides since they diverged
+		let local = data(3, vec![entry("mail", 2)]);
+		let other = data(2, vec![entry("mail", 3)]);
+		let (merged, report) = merge(&local, &other);
+		assert!(report.updated.is_empty());
+		assert_eq!(report.conflicts.len(), 1);
+		let conflict = &report.conflicts[0];
+		assert_eq!(conflict.kept.modified_at, entry("mail", 3).modified_at);
+		assert_eq!(conflict.discarded.modified_at, entry("mail", 2).modified_at);
+		assert_eq!(merged.entries[0].modified_at, entry("mail", 3).modified_at);
 	}
 }
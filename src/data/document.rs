@@ -0,0 +1,211 @@
+//! Entries as TOML documents, for editing in an external editor
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::field::TwoFACode;
+use crate::data::otp;
+use crate::data::otp::Totp;
+use crate::data::tag::TagRegistry;
+
+/// Comment at the top of documents
+const HEADER: &str = "\
+# Save and quit to apply the changes, lines starting with '#' are ignored.
+# Kinds: text, url, phone, email, note, totp, steam, hotp, recovery-codes, binary
+# Recovery codes are separated by spaces, binary fields keep their content.
+";
+
+/// Kind of a field in a document
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum FieldKind {
+	#[default]
+	Text,
+	Url,
+	Phone,
+	Email,
+	Note,
+	Totp,
+	Steam,
+	Hotp,
+	RecoveryCodes,
+	Binary,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldDocument {
+	name: String,
+	#[serde(default)]
+	kind: FieldKind,
+	#[serde(default)]
+	value: String,
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	hidden: bool,
+	/// Counter of HOTP fields
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	counter: Option<u64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryDocument {
+	name: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	icon: Option<String>,
+	#[serde(default)]
+	tags: Vec<String>,
+	#[serde(default, rename = "field")]
+	fields: Vec<FieldDocument>,
+}
+
+impl From<&Field> for FieldDocument {
+	fn from(field: &Field) -> Self {
+		let (kind, value, counter) = match &field.value {
+			FieldValue::Text(text) => (FieldKind::Text, text.clone(), None),
+			FieldValue::Url(text) => (FieldKind::Url, text.clone(), None),
+			FieldValue::Phone(text) => (FieldKind::Phone, text.clone(), None),
+			FieldValue::Email(text) => (FieldKind::Email, text.clone(), None),
+			FieldValue::Note(text) => (FieldKind::Note, text.clone(), None),
+			FieldValue::TOTPRFC6238(secret) => (FieldKind::Totp, secret.clone(), None),
+			FieldValue::TOTPSteam(secret) => (FieldKind::Steam, secret.clone(), None),
+			FieldValue::HOTP { secret, counter } => {
+				(FieldKind::Hotp, secret.clone(), Some(*counter))
+			}
+			FieldValue::TwoFactorRecovery(codes) => (
+				FieldKind::RecoveryCodes,
+				codes
+					.iter()
+					.map(|code| code.value.as_str())
+					.collect::<Vec<_>>()
+					.join(" "),
+				None,
+			),
+			FieldValue::Binary { .. } => (FieldKind::Binary, String::default(), None),
+		};
+		Self {
+			name: field.name.clone(),
+			kind,
+			value,
+			hidden: field.hidden,
+			counter,
+			expires_at: field.expires_at,
+		}
+	}
+}
+
+/// Serialize `entry` to a TOML document
+pub fn to_document(entry: &Entry) -> Result<String, String> {
+	let document = EntryDocument {
+		name: entry.name.clone(),
+		icon: entry.icon.clone(),
+		tags: entry.tags.iter().map(|tag| tag.name.clone()).collect(),
+		fields: entry.fields.iter().map(FieldDocument::from).collect(),
+	};
+	let document = toml::to_string_pretty(&document)
+		.map_err(|err| format!("Failed to serialize entry: {err}"))?;
+	Ok(format!("{HEADER}\n{document}"))
+}
+
+/// Value of a field read from a document, `previous` is the field of the same name
+fn parse_value(document: &FieldDocument, previous: Option<&Field>) -> Result<FieldValue, String> {
+	let value = document.value.trim().to_string();
+	let value = match document.kind {
+		FieldKind::Text => FieldValue::Text(document.value.clone()),
+		FieldKind::Url => FieldValue::Url(value),
+		FieldKind::Phone => FieldValue::Phone(value),
+		FieldKind::Email => FieldValue::Email(value),
+		FieldKind::Note => FieldValue::Note(document.value.clone()),
+		FieldKind::Totp => {
+			Totp::parse(&value)?;
+			FieldValue::TOTPRFC6238(value)
+		}
+		FieldKind::Steam => {
+			Totp::parse_steam(&value)?;
+			FieldValue::TOTPSteam(value)
+		}
+		FieldKind::Hotp => {
+			otp::base32_decode(&value)?;
+			FieldValue::HOTP {
+				secret: value,
+				counter: document.counter.unwrap_or(0),
+			}
+		}
+		FieldKind::RecoveryCodes => {
+			let previous = match previous.map(|field| &field.value) {
+				Some(FieldValue::TwoFactorRecovery(codes)) => codes.as_slice(),
+				_ => &[],
+			};
+			let codes = TwoFACode::parse_list(&value, previous);
+			if codes.is_empty() {
+				return Err("No recovery codes".into());
+			}
+			FieldValue::TwoFactorRecovery(codes)
+		}
+		FieldKind::Binary => match previous.map(|field| &field.value) {
+			Some(value @ FieldValue::Binary { .. }) => value.clone(),
+			_ => return Err("Binary fields can't be created from a document".into()),
+		},
+	};
+	Ok(value)
+}
+
+/// Apply the document `text` to `entry`
+///
+/// Fields keep their dates when their value is unchanged and their previous value otherwise, new tags are resolved with `tags`.
+pub fn apply_document(entry: &Entry, text: &str, tags: &TagRegistry) -> Result<Entry, String> {
+	let document: EntryDocument =
+		toml::from_str(text).map_err(|err| format!("Invalid document: {err}"))?;
+	let name = document.name.trim();
+	if name.is_empty() {
+		return Err("The entry name can't be empty".into());
+	}
+
+	let mut fields = vec![];
+	for field in &document.fields {
+		let previous = entry.fields.iter().find(|other| other.name == field.name);
+		let value =
+			parse_value(field, previous).map_err(|err| format!("Field '{}': {err}", field.name))?;
+		let mut new = match previous {
+			Some(previous) => {
+				let mut new = previous.clone();
+				new.set_value(value);
+				new
+			}
+			None => Field {
+				value,
+				..Default::default()
+			},
+		};
+		new.name = field.name.clone();
+		new.hidden = field.hidden;
+		new.expires_at = field.expires_at;
+		fields.push(new);
+	}
+
+	let mut edited = entry.clone();
+	edited.name = name.to_string();
+	edited.icon = document.icon.filter(|icon| !icon.is_empty());
+	edited.tags = document
+		.tags
+		.iter()
+		.filter(|tag| !tag.trim().is_empty())
+		.map(|tag| {
+			entry
+				.tags
+				.iter()
+				.find(|other| other.name == *tag)
+				.cloned()
+				.unwrap_or_else(|| tags.resolve(tag))
+		})
+		.collect();
+	edited.fields = fields;
+	Ok(edited)
+}
@@ -1,9 +1,15 @@
+use std::cmp::Ordering;
+use std::fmt;
+
 use chrono::DateTime;
+use chrono::TimeDelta;
 use chrono::Utc;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::data::field::Expiry;
 use crate::data::field::Field;
+use crate::data::field::FieldValue;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntryTag {
@@ -12,13 +18,224 @@ pub struct EntryTag {
 	pub color: Option<u32>,
 }
 
+/// Built-in entry icons, by name
+pub const ENTRY_ICONS: &[(&str, &str)] = &[
+	("key", "󰌋"),
+	("lock", "󰌾"),
+	("web", "󰖟"),
+	("mail", "󰇮"),
+	("bank", "󰁰"),
+	("card", "󰆛"),
+	("wallet", "󰖄"),
+	("server", "󰒋"),
+	("terminal", "󰆍"),
+	("wifi", "󰖩"),
+	("phone", "󰏲"),
+	("cloud", "󰅟"),
+	("code", "󰅩"),
+	("git", "󰊢"),
+	("game", "󰊗"),
+	("music", "󰝚"),
+	("shop", "󰓜"),
+	("chat", "󰭹"),
+	("work", "󰃖"),
+	("home", "󰋜"),
+	("id", "󰯀"),
+	("note", "󰠮"),
+	("shield", "󰒃"),
+	("star", "󰓎"),
+];
+
+/// Maximum number of revisions kept for an entry
+pub const MAX_HISTORY: usize = 16;
+
+/// Previous state of an entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryRevision {
+	pub fields: Vec<Field>,
+	pub tags: Vec<EntryTag>,
+	pub modified_at: DateTime<Utc>,
+}
+
+/// Deleted entry, kept in the trash until it is restored or permanently deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedEntry {
+	pub entry: Entry,
+	pub deleted_at: DateTime<Utc>,
+}
+
+/// Kind of record held by an entry, see [`Entry::kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+	Login,
+	CreditCard,
+	SshKey,
+	WiFi,
+	/// Only one-time passwords and recovery codes
+	Otp,
+	Note,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Entry {
 	pub name: String,
 	pub fields: Vec<Field>,
 	pub tags: Vec<EntryTag>,
+	/// Glyph displayed before the name
+	pub icon: Option<String>,
+	/// Listed in the favorites of the explorer
+	pub favorite: bool,
 
 	pub created_at: DateTime<Utc>,
 	pub modified_at: DateTime<Utc>,
 	pub accessed_at: DateTime<Utc>,
+
+	/// Previous revisions, oldest first
+	pub history: Vec<EntryRevision>,
+}
+
+impl Entry {
+	/// Number of fields in each expiration state at `now`, as (expired, expiring soon)
+	pub fn expiry_counts(&self, now: DateTime<Utc>) -> (usize, usize) {
+		self.fields
+			.iter()
+			.fold((0, 0), |(expired, soon), field| match field.expiry(now) {
+				Some(Expiry::Expired) => (expired + 1, soon),
+				Some(Expiry::Soon) => (expired, soon + 1),
+				None => (expired, soon),
+			})
+	}
+
+	/// Time at `now` since the least recently changed password, the non-empty hidden fields, was
+	/// changed
+	pub fn password_age(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+		self.fields
+			.iter()
+			.filter(|field| field.hidden)
+			.filter(|field| field.value.text().is_some_and(|text| !text.is_empty()))
+			.map(|field| now - field.date_modified)
+			.max()
+	}
+
+	/// Kind of the entry, from the fields named as in the templates and the types of the values
+	pub fn kind(&self) -> EntryKind {
+		let has = |name: &str| {
+			self.fields
+				.iter()
+				.any(|field| field.name.eq_ignore_ascii_case(name))
+		};
+		let otp = |value: &FieldValue| {
+			matches!(
+				value,
+				FieldValue::TOTPRFC6238(_)
+					| FieldValue::TOTPSteam(_)
+					| FieldValue::HOTP { .. }
+					| FieldValue::TwoFactorRecovery(_)
+			)
+		};
+		if has("Card Number") {
+			EntryKind::CreditCard
+		} else if has("Private Key") {
+			EntryKind::SshKey
+		} else if has("SSID") {
+			EntryKind::WiFi
+		} else if !self.fields.is_empty() && self.fields.iter().all(|field| otp(&field.value)) {
+			EntryKind::Otp
+		} else if has("Password") || has("Username") {
+			EntryKind::Login
+		} else {
+			EntryKind::Note
+		}
+	}
+
+	/// Record that the entry was opened or one of its fields copied
+	///
+	/// Accesses alone don't modify the data, they are saved along with the next change.
+	pub fn touch(&mut self) {
+		self.accessed_at = Utc::now();
+	}
+
+	/// Snapshot of the current state
+	pub fn revision(&self) -> EntryRevision {
+		EntryRevision {
+			fields: self.fields.clone(),
+			tags: self.tags.clone(),
+			modified_at: self.modified_at,
+		}
+	}
+
+	/// Record `previous` in the history after the entry was modified
+	pub fn commit(&mut self, previous: EntryRevision) {
+		self.history.push(previous);
+		if self.history.len() > MAX_HISTORY {
+			self.history.drain(..self.history.len() - MAX_HISTORY);
+		}
+		self.modified_at = Utc::now();
+	}
+}
+
+/// Property the entries are sorted by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKey {
+	#[default]
+	Name,
+	Created,
+	Modified,
+	Accessed,
+}
+
+/// Order of the entries in the explorer
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntrySort {
+	pub key: SortKey,
+	pub descending: bool,
+}
+
+impl EntrySort {
+	pub fn compare(&self, a: &Entry, b: &Entry) -> Ordering {
+		let ordering = match self.key {
+			SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+			SortKey::Created => a.created_at.cmp(&b.created_at),
+			SortKey::Modified => a.modified_at.cmp(&b.modified_at),
+			SortKey::Accessed => a.accessed_at.cmp(&b.accessed_at),
+		};
+		if self.descending {
+			ordering.reverse()
+		} else {
+			ordering
+		}
+	}
+
+	/// Next sort: each key ascending then descending
+	pub fn next(self) -> Self {
+		if !self.descending {
+			return Self {
+				descending: true,
+				..self
+			};
+		}
+		let key = match self.key {
+			SortKey::Name => SortKey::Created,
+			SortKey::Created => SortKey::Modified,
+			SortKey::Modified => SortKey::Accessed,
+			SortKey::Accessed => SortKey::Name,
+		};
+		Self {
+			key,
+			descending: false,
+		}
+	}
+}
+
+impl fmt::Display for SortKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			SortKey::Name => "name",
+			SortKey::Created => "created",
+			SortKey::Modified => "modified",
+			SortKey::Accessed => "accessed",
+		})
+	}
 }
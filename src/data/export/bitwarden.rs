@@ -0,0 +1,169 @@
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::data::entry::Entry;
+use crate::data::export::exported_value;
+use crate::data::field::FieldValue;
+
+#[derive(Serialize)]
+struct BitwardenExport {
+	encrypted: bool,
+	folders: Vec<BitwardenFolder>,
+	items: Vec<BitwardenItem>,
+}
+
+#[derive(Serialize)]
+struct BitwardenFolder {
+	id: String,
+	name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenItem {
+	id: String,
+	folder_id: Option<String>,
+	/// Always a login
+	#[serde(rename = "type")]
+	kind: u8,
+	name: String,
+	notes: Option<String>,
+	favorite: bool,
+	fields: Vec<BitwardenField>,
+	login: BitwardenLogin,
+	creation_date: DateTime<Utc>,
+	revision_date: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct BitwardenField {
+	name: String,
+	value: String,
+	/// 0: text, 1: hidden
+	#[serde(rename = "type")]
+	kind: u8,
+}
+
+#[derive(Serialize)]
+struct BitwardenLogin {
+	uris: Vec<BitwardenUri>,
+	username: Option<String>,
+	password: Option<String>,
+	totp: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BitwardenUri {
+	#[serde(rename = "match")]
+	kind: Option<u8>,
+	uri: String,
+}
+
+/// Random version 4 UUID, Bitwarden identifies items and folders by UUIDs
+fn uuid() -> String {
+	let mut bytes = [0u8; 16];
+	rand::fill(&mut bytes);
+	bytes[6] = (bytes[6] & 0x0f) | 0x40;
+	bytes[8] = (bytes[8] & 0x3f) | 0x80;
+	let hex = bytes
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect::<String>();
+	format!(
+		"{}-{}-{}-{}-{}",
+		&hex[..8],
+		&hex[8..12],
+		&hex[12..16],
+		&hex[16..20],
+		&hex[20..]
+	)
+}
+
+fn item(entry: &Entry, folder_id: Option<String>, name: &str) -> BitwardenItem {
+	let mut login = BitwardenLogin {
+		uris: vec![],
+		username: None,
+		password: None,
+		totp: None,
+	};
+	let mut notes = None;
+	let mut fields = vec![];
+	for field in &entry.fields {
+		match &field.value {
+			FieldValue::Text(text) if field.name == "Username" && login.username.is_none() => {
+				login.username = Some(text.clone());
+			}
+			FieldValue::Text(text) if field.name == "Password" && login.password.is_none() => {
+				login.password = Some(text.clone());
+			}
+			FieldValue::Note(text) | FieldValue::Text(text)
+				if field.name == "Note" && notes.is_none() =>
+			{
+				notes = Some(text.clone());
+			}
+			FieldValue::Url(url) => login.uris.push(BitwardenUri {
+				kind: None,
+				uri: url.clone(),
+			}),
+			FieldValue::TOTPRFC6238(secret) if login.totp.is_none() => {
+				login.totp = Some(secret.clone());
+			}
+			FieldValue::TOTPSteam(secret) if login.totp.is_none() => {
+				login.totp = Some(format!("steam://{secret}"));
+			}
+			value => fields.push(BitwardenField {
+				name: field.name.clone(),
+				value: exported_value(value),
+				kind: field.hidden as u8,
+			}),
+		}
+	}
+	BitwardenItem {
+		id: uuid(),
+		folder_id,
+		kind: 1,
+		name: name.into(),
+		notes,
+		favorite: false,
+		fields,
+		login,
+		creation_date: entry.created_at,
+		revision_date: entry.modified_at,
+	}
+}
+
+/// Export entries as an unencrypted Bitwarden JSON export
+///
+/// The part of the entry names before the last `/` becomes the folder of the items. Fields with no
+/// Bitwarden equivalent are exported as custom fields.
+pub fn export(entries: &[Entry]) -> Result<String, String> {
+	let mut folders: Vec<BitwardenFolder> = vec![];
+	let mut items = vec![];
+	for entry in entries {
+		let (folder_id, name) = match entry.name.rsplit_once('/') {
+			Some((folder, name)) if !folder.is_empty() && !name.is_empty() => {
+				let id = match folders.iter().find(|other| other.name == folder) {
+					Some(other) => other.id.clone(),
+					None => {
+						let id = uuid();
+						folders.push(BitwardenFolder {
+							id: id.clone(),
+							name: folder.into(),
+						});
+						id
+					}
+				};
+				(Some(id), name)
+			}
+			_ => (None, entry.name.as_str()),
+		};
+		items.push(item(entry, folder_id, name));
+	}
+	serde_json::to_string_pretty(&BitwardenExport {
+		encrypted: false,
+		folders,
+		items,
+	})
+	.map_err(|err| format!("Failed to serialize entries: {err}"))
+}
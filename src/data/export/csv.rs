@@ -0,0 +1,73 @@
+use crate::data::entry::Entry;
+use crate::data::export::exported_value;
+
+/// Quote `value` if needed
+fn escape(value: &str) -> String {
+	if value.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_string()
+	}
+}
+
+fn write_row(out: &mut String, row: &[String]) {
+	let row = row
+		.iter()
+		.map(|value| escape(value))
+		.collect::<Vec<_>>()
+		.join(",");
+	out.push_str(&row);
+	out.push_str("\r\n");
+}
+
+/// Export entries as CSV
+///
+/// Columns are the entry name, its tags and one column per field name, in order of first
+/// appearance. Repeated field names within an entry get their own numbered columns.
+pub fn export(entries: &[Entry]) -> String {
+	let column_name = |name: &str, occurrence: usize| match occurrence {
+		0 => name.to_string(),
+		n => format!("{name} ({})", n + 1),
+	};
+
+	let mut columns: Vec<String> = vec![];
+	for entry in entries {
+		for (id, field) in entry.fields.iter().enumerate() {
+			let occurrence = entry.fields[..id]
+				.iter()
+				.filter(|other| other.name == field.name)
+				.count();
+			let column = column_name(&field.name, occurrence);
+			if !columns.contains(&column) {
+				columns.push(column);
+			}
+		}
+	}
+
+	let mut out = String::new();
+	let mut header = vec!["name".to_string(), "tags".to_string()];
+	header.extend(columns.iter().cloned());
+	write_row(&mut out, &header);
+
+	for entry in entries {
+		let mut row = vec![String::default(); columns.len() + 2];
+		row[0] = entry.name.clone();
+		row[1] = entry
+			.tags
+			.iter()
+			.map(|tag| tag.name.as_str())
+			.collect::<Vec<_>>()
+			.join(",");
+		for (id, field) in entry.fields.iter().enumerate() {
+			let occurrence = entry.fields[..id]
+				.iter()
+				.filter(|other| other.name == field.name)
+				.count();
+			let column = column_name(&field.name, occurrence);
+			let index = columns.iter().position(|name| *name == column).unwrap();
+			row[index + 2] = exported_value(&field.value);
+		}
+		write_row(&mut out, &row);
+	}
+	out
+}
@@ -0,0 +1,31 @@
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+
+#[derive(Serialize)]
+struct ExportedEntry<'a> {
+	name: &'a str,
+	tags: Vec<&'a str>,
+	fields: &'a [Field],
+	created_at: DateTime<Utc>,
+	modified_at: DateTime<Utc>,
+}
+
+/// Export entries as JSON, without their history
+pub fn export(entries: &[Entry]) -> Result<String, String> {
+	let entries = entries
+		.iter()
+		.map(|entry| ExportedEntry {
+			name: &entry.name,
+			tags: entry.tags.iter().map(|tag| tag.name.as_str()).collect(),
+			fields: &entry.fields,
+			created_at: entry.created_at,
+			modified_at: entry.modified_at,
+		})
+		.collect::<Vec<_>>();
+	serde_json::to_string_pretty(&entries)
+		.map_err(|err| format!("Failed to serialize entries: {err}"))
+}
@@ -0,0 +1,262 @@
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use quick_xml::escape::escape;
+
+use crate::data::attachment;
+use crate::data::entry::Entry;
+use crate::data::export::exported_value;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::kdbx;
+use crate::data::kdbx::InnerStream;
+use crate::data::kdbx::Kdbx;
+
+/// Entry strings with a meaning for KeePass
+const STANDARD_KEYS: [&str; 6] = ["Title", "UserName", "Password", "URL", "Notes", "otp"];
+
+/// Group of entries, from the `/`-separated components of their names
+#[derive(Default)]
+struct Group<'a> {
+	name: &'a str,
+	groups: Vec<Group<'a>>,
+	/// Entries and their title
+	entries: Vec<(&'a Entry, &'a str)>,
+}
+
+impl<'a> Group<'a> {
+	fn insert(&mut self, entry: &'a Entry) {
+		let mut group = self;
+		let mut components = entry.name.split('/').filter(|name| !name.is_empty());
+		let mut title = components.next_back().unwrap_or(&entry.name);
+		for name in components {
+			let index = match group.groups.iter().position(|other| other.name == name) {
+				Some(index) => index,
+				None => {
+					group.groups.push(Group {
+						name,
+						..Default::default()
+					});
+					group.groups.len() - 1
+				}
+			};
+			group = &mut group.groups[index];
+		}
+		if title.is_empty() {
+			title = &entry.name;
+		}
+		group.entries.push((entry, title));
+	}
+}
+
+/// Random UUID of a group or entry
+fn uuid() -> String {
+	let mut bytes = [0u8; 16];
+	rand::fill(&mut bytes);
+	BASE64_STANDARD.encode(bytes)
+}
+
+/// `otpauth://` URI of a TOTP field, as stored by KeePassXC
+fn otp_uri(entry: &Entry, value: &FieldValue) -> Option<String> {
+	let (secret, steam) = match value {
+		FieldValue::TOTPRFC6238(secret) => (secret, false),
+		FieldValue::TOTPSteam(secret) => (secret, true),
+		_ => return None,
+	};
+	if secret.starts_with("otpauth://") {
+		return Some(secret.clone());
+	}
+	let label = entry
+		.name
+		.bytes()
+		.map(|byte| match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+				(byte as char).to_string()
+			}
+			_ => format!("%{byte:02X}"),
+		})
+		.collect::<String>();
+	let secret = secret.replace(' ', "");
+	Some(match steam {
+		true => format!("otpauth://totp/{label}?secret={secret}&encoder=steam"),
+		false => format!("otpauth://totp/{label}?secret={secret}"),
+	})
+}
+
+/// Writer of the XML document
+struct Document {
+	xml: String,
+	stream: InnerStream,
+	binaries: Vec<Vec<u8>>,
+}
+
+impl Document {
+	fn element(&mut self, name: &str, text: &str) {
+		self.xml
+			.push_str(&format!("<{name}>{}</{name}>", escape(text)));
+	}
+
+	fn string(&mut self, key: &str, value: &str, protected: bool) {
+		self.xml.push_str("<String>");
+		self.element("Key", key);
+		if protected {
+			let value = self.stream.protect(value);
+			self.xml
+				.push_str(&format!("<Value Protected=\"True\">{value}</Value>"));
+		} else {
+			self.element("Value", value);
+		}
+		self.xml.push_str("</String>");
+	}
+
+	fn entry(&mut self, entry: &Entry, title: &str) -> Result<(), String> {
+		self.xml.push_str("<Entry>");
+		self.element("UUID", &uuid());
+		let tags = entry
+			.tags
+			.iter()
+			.map(|tag| tag.name.as_str())
+			.collect::<Vec<_>>();
+		self.element("Tags", &tags.join(";"));
+		self.xml.push_str("<Times>");
+		self.element("CreationTime", &kdbx::format_time(entry.created_at));
+		self.element(
+			"LastModificationTime",
+			&kdbx::format_time(entry.modified_at),
+		);
+		self.element("LastAccessTime", &kdbx::format_time(entry.accessed_at));
+		self.element("Expires", "False");
+		self.xml.push_str("</Times>");
+
+		// Fields stored as the standard strings
+		let fields = &entry.fields;
+		let is_text = |field: &Field| field.value.text().is_some();
+		let username = fields
+			.iter()
+			.position(|field| field.name == "Username" && is_text(field))
+			.or_else(|| {
+				fields
+					.iter()
+					.position(|field| matches!(field.value, FieldValue::Email(_)))
+			});
+		let password = fields
+			.iter()
+			.position(|field| field.name == "Password" && is_text(field))
+			.or_else(|| {
+				fields
+					.iter()
+					.position(|field| field.hidden && is_text(field))
+			});
+		let url = fields
+			.iter()
+			.position(|field| matches!(field.value, FieldValue::Url(_)));
+		let notes = fields
+			.iter()
+			.position(|field| matches!(field.value, FieldValue::Note(_)))
+			.or_else(|| {
+				fields
+					.iter()
+					.position(|field| field.name == "Note" && is_text(field))
+			});
+		let otp = fields
+			.iter()
+			.position(|field| otp_uri(entry, &field.value).is_some());
+
+		let text = |index: Option<usize>| {
+			index
+				.and_then(|index| fields[index].value.text())
+				.unwrap_or_default()
+				.to_string()
+		};
+		self.string("Title", title, false);
+		self.string("UserName", &text(username), false);
+		self.string("Password", &text(password), true);
+		self.string("URL", &text(url), false);
+		self.string("Notes", &text(notes), false);
+		if let Some(index) = otp {
+			self.string("otp", &otp_uri(entry, &fields[index].value).unwrap(), true);
+		}
+
+		let mut keys: Vec<String> = STANDARD_KEYS.iter().map(|key| key.to_string()).collect();
+		let mut attachments = vec![];
+		for (index, field) in fields.iter().enumerate() {
+			if [username, password, url, notes, otp].contains(&Some(index)) {
+				continue;
+			}
+			if let FieldValue::Binary { .. } = field.value {
+				attachments.push(field);
+				continue;
+			}
+			// Keys must be unique within an entry
+			let mut key = field.name.clone();
+			let mut occurrence = 1;
+			while keys.contains(&key) {
+				occurrence += 1;
+				key = format!("{} ({occurrence})", field.name);
+			}
+			self.string(&key, &exported_value(&field.value), field.hidden);
+			keys.push(key);
+		}
+
+		let mut keys: Vec<&str> = vec![];
+		for field in attachments {
+			if keys.contains(&field.name.as_str()) {
+				continue;
+			}
+			keys.push(&field.name);
+			self.xml.push_str("<Binary>");
+			self.element("Key", &field.name);
+			self.xml
+				.push_str(&format!("<Value Ref=\"{}\"/>", self.binaries.len()));
+			self.xml.push_str("</Binary>");
+			self.binaries.push(attachment::decode(&field.value)?);
+		}
+		self.xml.push_str("</Entry>");
+		Ok(())
+	}
+
+	fn group(&mut self, group: &Group) -> Result<(), String> {
+		self.xml.push_str("<Group>");
+		self.element("UUID", &uuid());
+		self.element("Name", group.name);
+		for (entry, title) in &group.entries {
+			self.entry(entry, title)?;
+		}
+		for child in &group.groups {
+			self.group(child)?;
+		}
+		self.xml.push_str("</Group>");
+		Ok(())
+	}
+}
+
+/// Export entries to a KeePass database encrypted with `password`, without their history
+///
+/// Entries are placed in groups named after the `/`-separated components of their names.
+pub fn export(entries: &[Entry], password: &str) -> Result<Vec<u8>, String> {
+	let mut root = Group {
+		name: "passk",
+		..Default::default()
+	};
+	for entry in entries {
+		root.insert(entry);
+	}
+
+	let mut kdbx = Kdbx::default();
+	let mut document = Document {
+		xml: String::new(),
+		stream: kdbx.stream(),
+		binaries: vec![],
+	};
+	document
+		.xml
+		.push_str("<?xml version=\"1.0\" encoding=\"utf-8\" standalone=\"yes\"?>");
+	document.xml.push_str("<KeePassFile><Meta>");
+	document.element("Generator", "passk");
+	document.element("DatabaseName", "passk");
+	document.xml.push_str("</Meta><Root>");
+	document.group(&root)?;
+	document.xml.push_str("</Root></KeePassFile>");
+	kdbx.xml = std::mem::take(&mut document.xml);
+	kdbx.binaries = std::mem::take(&mut document.binaries);
+	kdbx::write(&kdbx, password)
+}
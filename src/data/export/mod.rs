@@ -0,0 +1,178 @@
+use std::io::Write;
+use std::path::Path;
+
+use crate::data::database::derive_key;
+use crate::data::database::encrypt_database_with_key;
+use crate::data::database::Argon2Params;
+use crate::data::database::CipherData;
+use crate::data::database::Data;
+use crate::data::database::Database;
+use crate::data::entry::Entry;
+use crate::data::file::save_database;
+use crate::data::field::FieldValue;
+use crate::data::tag::TagRegistry;
+use crate::plugin::PLUGINS;
+use crate::plugin::PLUGIN_EXPORTERS;
+
+pub mod bitwarden;
+pub mod csv;
+pub mod json;
+pub mod kdbx;
+
+/// Supported export formats
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ExportFormat {
+	#[default]
+	Csv,
+	Json,
+	/// Unencrypted Bitwarden JSON export
+	Bitwarden,
+	/// KeePass KDBX 4 database, encrypted with a password
+	Kdbx,
+	/// Format provided by a plugin, index in [`PLUGIN_EXPORTERS`]
+	Plugin(usize),
+}
+
+impl TryFrom<usize> for ExportFormat {
+	type Error = &'static str;
+
+	fn try_from(value: usize) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(ExportFormat::Csv),
+			1 => Ok(ExportFormat::Json),
+			2 => Ok(ExportFormat::Bitwarden),
+			3 => Ok(ExportFormat::Kdbx),
+			n if n - 4 < PLUGIN_EXPORTERS.len() => Ok(ExportFormat::Plugin(n - 4)),
+			_ => Err("Invalid value"),
+		}
+	}
+}
+
+impl TryFrom<&str> for ExportFormat {
+	type Error = String;
+
+	/// Parse the name of a format on the command line
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		(0usize..)
+			.map_while(|id| ExportFormat::try_from(id).ok())
+			.find(|format| format.id().eq_ignore_ascii_case(value))
+			.ok_or_else(|| format!("Unknown export format '{value}'"))
+	}
+}
+
+impl ExportFormat {
+	pub fn name(&self) -> &'static str {
+		match self {
+			ExportFormat::Csv => "CSV",
+			ExportFormat::Json => "JSON",
+			ExportFormat::Bitwarden => "Bitwarden",
+			ExportFormat::Kdbx => "KeePass",
+			ExportFormat::Plugin(id) => PLUGIN_EXPORTERS[*id].1,
+		}
+	}
+
+	/// Name of the format on the command line
+	pub fn id(&self) -> &'static str {
+		match self {
+			ExportFormat::Csv => "csv",
+			ExportFormat::Json => "json",
+			ExportFormat::Bitwarden => "bitwarden",
+			ExportFormat::Kdbx => "kdbx",
+			ExportFormat::Plugin(id) => PLUGIN_EXPORTERS[*id].1,
+		}
+	}
+
+	/// Whether the output is encrypted with a password
+	pub fn needs_password(&self) -> bool {
+		*self == ExportFormat::Kdbx
+	}
+}
+
+/// Plain text representation of a field value
+pub fn exported_value(value: &FieldValue) -> String {
+	match value {
+		FieldValue::Text(text)
+		| FieldValue::Url(text)
+		| FieldValue::Phone(text)
+		| FieldValue::Email(text)
+		| FieldValue::Note(text)
+		| FieldValue::TOTPRFC6238(text)
+		| FieldValue::TOTPSteam(text) => text.clone(),
+		FieldValue::TwoFactorRecovery(codes) => codes
+			.iter()
+			.map(|code| code.value.as_str())
+			.collect::<Vec<_>>()
+			.join(" "),
+		FieldValue::Binary { mimetype, base64 } => format!("data:{mimetype};base64,{base64}"),
+		FieldValue::HOTP { secret, counter } => {
+			format!("otpauth://hotp/?secret={secret}&counter={counter}")
+		}
+	}
+}
+
+/// Create `path` readable only by the current user
+pub(crate) fn create_private(path: &Path) -> Result<std::fs::File, String> {
+	let mut options = std::fs::OpenOptions::new();
+	options.write(true).create(true).truncate(true);
+	#[cfg(unix)]
+	std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+	options
+		.open(path)
+		.map_err(|err| format!("Failed to create '{}': {err}", path.display()))
+}
+
+/// Export decrypted `entries` to file `path`
+///
+/// Only KeePass databases are encrypted, with `password`, other outputs are NOT encrypted.
+pub fn export(
+	format: ExportFormat,
+	path: &Path,
+	entries: &[Entry],
+	password: Option<&str>,
+) -> Result<(), String> {
+	let content = match format {
+		ExportFormat::Csv => csv::export(entries).into_bytes(),
+		ExportFormat::Json => json::export(entries)?.into_bytes(),
+		ExportFormat::Bitwarden => bitwarden::export(entries)?.into_bytes(),
+		ExportFormat::Kdbx => {
+			let password = password.ok_or("A password is required for KeePass databases")?;
+			kdbx::export(entries, password)?
+		}
+		ExportFormat::Plugin(id) => {
+			let (plugin, format) = PLUGIN_EXPORTERS[id];
+			return PLUGINS[plugin].export(format, path, entries);
+		}
+	};
+
+	let mut file = create_private(path)?;
+	file.write_all(&content)
+		.map_err(|err| format!("Failed to write '{}': {err}", path.display()))?;
+	file.sync_all()
+		.map_err(|err| format!("Failed to sync '{}': {err}", path.display()))
+}
+
+/// Write `entries` to a standalone database at `path`, encrypted with `passphrase`
+///
+/// Entry history is left out so only the current values are shared.
+pub fn export_encrypted(entries: &[Entry], path: &Path, passphrase: &str) -> Result<(), String> {
+	let mut db = Database::new(CipherData::XChaCha20Poly1305V1 {}, Argon2Params::default());
+	let entries = entries
+		.iter()
+		.cloned()
+		.map(|mut entry| {
+			entry.history.clear();
+			entry
+		})
+		.collect::<Vec<_>>();
+	let mut tags = TagRegistry::default();
+	tags.sync(&entries);
+	let data = Data {
+		entries,
+		tags,
+		..Default::default()
+	};
+	let key = derive_key(&db.kdf, passphrase, None)?;
+	encrypt_database_with_key(&data, &mut db, &key)?;
+	save_database(&db, path)
+}
@@ -1,11 +1,14 @@
 
 use chrono::DateTime;
+use chrono::TimeDelta;
 use chrono::Utc;
-use clipboard_rs::Clipboard;
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::CLIPBOARD_CTX;
+use crate::clipboard;
+use crate::data::attachment;
+use crate::data::otp;
+use crate::data::otp::Totp;
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TwoFACode {
@@ -13,6 +16,36 @@ pub struct TwoFACode {
 	pub expired: Option<DateTime<Utc>>,
 }
 
+impl TwoFACode {
+	pub fn is_used(&self) -> bool {
+		self.expired.is_some()
+	}
+
+	/// Parse a pasted block of recovery codes, separated by spaces, commas or new lines
+	///
+	/// Codes already in `previous` keep their used state.
+	pub fn parse_list(input: &str, previous: &[TwoFACode]) -> Vec<TwoFACode> {
+		let mut codes: Vec<TwoFACode> = vec![];
+		for value in input
+			.split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+			.filter(|value| !value.is_empty())
+		{
+			if codes.iter().any(|code| code.value == value) {
+				continue;
+			}
+			let expired = previous
+				.iter()
+				.find(|code| code.value == value)
+				.and_then(|code| code.expired);
+			codes.push(TwoFACode {
+				value: value.to_string(),
+				expired,
+			});
+		}
+		codes
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum FieldValue {
 	Text(String),
@@ -30,6 +63,14 @@ pub enum FieldValue {
 		mimetype: String,
 		base64: String,
 	},
+	/// HOTP RFC4226, `counter` is the counter of the next code
+	#[allow(clippy::upper_case_acronyms)]
+	HOTP {
+		secret: String,
+		counter: u64,
+	},
+	/// Multiline text
+	Note(String),
 }
 
 impl Default for FieldValue {
@@ -39,19 +80,64 @@ impl Default for FieldValue {
 }
 
 impl FieldValue {
-	pub fn copy_to_clipboard(&self) {
+	/// Text copied to the clipboard: the current code of OTP fields, the first unused recovery
+	/// code, or the value itself
+	pub fn clipboard_text(&self) -> Option<String> {
 		let content = match self {
 			FieldValue::Text(text)
 			| FieldValue::Url(text)
 			| FieldValue::Phone(text)
-			| FieldValue::Email(text) => text.clone(),
-			FieldValue::TOTPRFC6238(_) => todo!(),
-			FieldValue::TOTPSteam(_) => todo!(),
-			FieldValue::TwoFactorRecovery(_two_facodes) => todo!(),
-			FieldValue::Binary { mimetype: _, base64: _ } => todo!(),
+			| FieldValue::Email(text)
+			| FieldValue::Note(text) => text.clone(),
+			FieldValue::TOTPRFC6238(secret) => Totp::parse(secret).ok()?.code(Utc::now()),
+			FieldValue::HOTP { secret, counter } => otp::generate_hotp(secret, *counter).ok()?,
+			FieldValue::TOTPSteam(secret) => Totp::parse_steam(secret).ok()?.code(Utc::now()),
+			FieldValue::TwoFactorRecovery(codes) => {
+				codes.iter().find(|code| !code.is_used())?.value.clone()
+			}
+			FieldValue::Binary { mimetype, base64 } => {
+				// Copy text attachments as-is, other data as a data URI
+				match attachment::decode(self).map(String::from_utf8) {
+					Ok(Ok(text)) if mimetype.starts_with("text/") => text,
+					_ => format!("data:{mimetype};base64,{base64}"),
+				}
+			}
 		};
-		CLIPBOARD_CTX.set_text(content)
-			.unwrap();
+		Some(content)
+	}
+
+	pub fn copy_to_clipboard(&self) {
+		if let Some(content) = self.clipboard_text() {
+			clipboard::copy(content).unwrap();
+		}
+	}
+
+	/// Textual content of the value, for text-like kinds
+	pub fn text(&self) -> Option<&str> {
+		match self {
+			FieldValue::Text(text)
+			| FieldValue::Url(text)
+			| FieldValue::Phone(text)
+			| FieldValue::Email(text)
+			| FieldValue::Note(text) => Some(text.as_str()),
+			_ => None,
+		}
+	}
+
+	/// Display name of the value kind
+	pub fn kind_name(&self) -> &'static str {
+		match self {
+			FieldValue::Text(_) => "Text",
+			FieldValue::Url(_) => "URL",
+			FieldValue::Phone(_) => "Phone Number",
+			FieldValue::Email(_) => "E-Mail",
+			FieldValue::TOTPRFC6238(_) => "TOTP/RFC 6238",
+			FieldValue::TOTPSteam(_) => "TOTP/Steam",
+			FieldValue::TwoFactorRecovery(_) => "2FA Recovery",
+			FieldValue::Binary { .. } => "Binary",
+			FieldValue::HOTP { .. } => "HOTP",
+			FieldValue::Note(_) => "Note",
+		}
 	}
 
 	pub fn get_id(&self) -> usize {
@@ -63,11 +149,35 @@ impl FieldValue {
 			FieldValue::TOTPRFC6238(_) => 4,
 			FieldValue::TOTPSteam(_) => 5,
 			FieldValue::TwoFactorRecovery(_) => 6,
-			FieldValue::Binary { mimetype: _, base64: _ } => todo!(),
+			FieldValue::Binary { .. } => 7,
+			FieldValue::HOTP { .. } => 8,
+			FieldValue::Note(_) => 9,
 		}
 	}
 }
 
+/// Number of days before expiration during which a field is reported as expiring
+pub const EXPIRY_WARNING_DAYS: i64 = 14;
+
+/// Maximum number of previous values kept for a field
+pub const MAX_FIELD_HISTORY: usize = 8;
+
+/// Previous value of a field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldRevision {
+	pub value: FieldValue,
+	/// Date the value was replaced
+	pub replaced_at: DateTime<Utc>,
+}
+
+/// Expiration state of a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Expiry {
+	/// Expires within [`EXPIRY_WARNING_DAYS`]
+	Soon,
+	Expired,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
 	/// Field name
@@ -80,6 +190,48 @@ pub struct Field {
 	pub date_added: DateTime<Utc>,
 	pub date_modified: DateTime<Utc>,
 	pub date_accessed: DateTime<Utc>,
+	/// Date after which the value should be rotated
+	pub expires_at: Option<DateTime<Utc>>,
+	/// Previous values, oldest first
+	pub history: Vec<FieldRevision>,
+}
+
+impl Field {
+	/// Expiration state at `now`, `None` if the field is not expiring
+	pub fn expiry(&self, now: DateTime<Utc>) -> Option<Expiry> {
+		let expires_at = self.expires_at?;
+		if expires_at <= now {
+			Some(Expiry::Expired)
+		} else if expires_at - now <= TimeDelta::days(EXPIRY_WARNING_DAYS) {
+			Some(Expiry::Soon)
+		} else {
+			None
+		}
+	}
+
+	/// Replace the value, the previous one is kept in the history when it differs
+	pub fn set_value(&mut self, value: FieldValue) {
+		if value == self.value {
+			return;
+		}
+		let now = Utc::now();
+		let previous = std::mem::replace(&mut self.value, value);
+		self.history.push(FieldRevision {
+			value: previous,
+			replaced_at: now,
+		});
+		if self.history.len() > MAX_FIELD_HISTORY {
+			self.history.drain(..self.history.len() - MAX_FIELD_HISTORY);
+		}
+		self.date_modified = now;
+	}
+
+	/// Take the history of `previous`, the field replaced by this one, with its value if it changed
+	pub fn inherit_history(&mut self, previous: Field) {
+		let value = std::mem::replace(&mut self.value, previous.value);
+		self.history = previous.history;
+		self.set_value(value);
+	}
 }
 
 impl Default for Field {
@@ -92,6 +244,8 @@ impl Default for Field {
 			date_added: now,
 			date_modified: now,
 			date_accessed: now,
+			expires_at: None,
+			history: vec![],
 		}
 	}
 }
@@ -1,20 +1,44 @@
+use std::fs::File;
+use std::fs::TryLockError;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
 use std::io::Write;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::fs::OpenOptionsExt;
 use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::base64::Base64;
+use serde_with::serde_as;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::data::database::CipherData;
 use crate::data::database::Database;
+use crate::data::database::DatabaseMetadata;
+use crate::data::database::KdfData;
+use crate::data::database::Version;
 
 pub static MAGIC: &[u8] = b"\xFF\x00\x91PASSK";
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PasskVersion {
-	#[default]
+	/// JSON database with an inline base64 blob
 	V0_1,
+	/// JSON header line followed by the raw blob
+	#[default]
+	V0_2,
 }
 
 impl From<PasskVersion> for &'static str {
 	fn from(val: PasskVersion) -> Self {
 		match val {
 			PasskVersion::V0_1 => "0.1",
+			PasskVersion::V0_2 => "0.2",
 		}
 	}
 }
@@ -25,45 +49,335 @@ impl TryFrom<&[u8]> for PasskVersion {
 	fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
 		match value {
 			b"0.1" => Ok(PasskVersion::V0_1),
+			b"0.2" => Ok(PasskVersion::V0_2),
 			_ => Err("Unknown version"),
 		}
 	}
 }
 
-pub fn load_database(path: &Path) -> Result<Database, String> {
-	let bytes =
-		std::fs::read(path).map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
-	if !bytes.starts_with(MAGIC) {
+/// Header of a database file, readable without the encrypted blob
+///
+/// The blob is still bound to the header by the AAD of its ciphertext, the length and digest only
+/// let sync tools detect changes without reading it.
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DatabaseHeader {
+	pub version: Version,
+	pub cipher: CipherData,
+	pub kdf: KdfData,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub metadata: Option<DatabaseMetadata>,
+
+	/// Length of the blob following the header
+	pub blob_len: u64,
+	/// SHA-256 digest of the blob
+	#[serde_as(as = "Base64")]
+	pub blob_digest: Vec<u8>,
+}
+
+impl DatabaseHeader {
+	pub fn new(database: &Database) -> Self {
+		Self {
+			version: database.version,
+			cipher: database.cipher.clone(),
+			kdf: database.kdf.clone(),
+			metadata: database.metadata.clone(),
+			blob_len: database.blob.len() as u64,
+			blob_digest: Sha256::digest(&database.blob).to_vec(),
+		}
+	}
+
+	/// Attach `blob` to the header, after checking it against the header
+	fn with_blob(self, blob: Vec<u8>) -> Result<Database, String> {
+		if blob.len() as u64 != self.blob_len {
+			return Err(format!(
+				"Expected a blob of {} bytes, found {}",
+				self.blob_len,
+				blob.len()
+			));
+		}
+		if Sha256::digest(&blob).as_slice() != self.blob_digest {
+			return Err("Blob digest mismatch".into());
+		}
+		Ok(Database {
+			version: self.version,
+			cipher: self.cipher,
+			kdf: self.kdf,
+			metadata: self.metadata,
+			blob,
+		})
+	}
+}
+
+/// Read the MAGIC number and file version of the database in `reader`
+fn read_version(reader: &mut impl BufRead, path: &Path) -> Result<PasskVersion, String> {
+	let mut line = vec![];
+	reader
+		.read_until(b'\n', &mut line)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	if !line.starts_with(MAGIC) {
 		return Err(format!(
 			"Failed to verify MAGIC number in '{}'",
 			path.display()
 		));
 	}
-	let nl = bytes
-		.iter()
-		.position(|c| *c == b'\n')
-		.ok_or(format!("Invalid header in '{}'", path.display()))?;
-	let _ = PasskVersion::try_from(&bytes[MAGIC.len()..nl])
-		.map_err(|err| format!("Invaid header in '{}': {err}", path.display()))?;
-	let payload = &bytes[nl + 1..];
-	// TODO: Migrate DB structure
-	let db: Database = serde_json::from_slice(payload)
-		.map_err(|err| format!("Failed to deserialize '{}': {err}", path.display()))?;
-	Ok(db)
+	if line.pop() != Some(b'\n') {
+		return Err(format!("Invalid header in '{}'", path.display()));
+	}
+	PasskVersion::try_from(&line[MAGIC.len()..])
+		.map_err(|err| format!("Invaid header in '{}': {err}", path.display()))
 }
 
-pub fn save_database(database: &Database, path: &Path) -> Result<(), String> {
-	let mut file = std::fs::File::create(path)
-		.map_err(|err| format!("Failed to create '{}': {err}", path.display()))?;
-	let version: &str = PasskVersion::default().into();
-
-	file.write(MAGIC)
-		.map_err(|err| format!("Failed to write MAGIC to '{}': {err}", path.display()))?;
-	file.write(version.as_bytes())
-		.map_err(|err| format!("Failed to write version to '{}': {err}", path.display()))?;
-	file.write(b"\n")
-		.map_err(|err| format!("Failed to write version to '{}': {err}", path.display()))?;
-	file.write(serde_json::to_string(database).unwrap().as_bytes())
-		.map_err(|err| format!("Failed to write content to '{}': {err}", path.display()))?;
+/// Read the header of the database at `path`, without reading the blob
+pub fn load_header(path: &Path) -> Result<DatabaseHeader, String> {
+	let file = std::fs::File::open(path)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	let mut reader = BufReader::new(file);
+	match read_version(&mut reader, path)? {
+		PasskVersion::V0_1 => load_database(path).map(|db| DatabaseHeader::new(&db)),
+		PasskVersion::V0_2 => {
+			let mut line = vec![];
+			reader
+				.read_until(b'\n', &mut line)
+				.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+			serde_json::from_slice(&line)
+				.map_err(|err| format!("Failed to deserialize '{}': {err}", path.display()))
+		}
+	}
+}
+
+pub fn load_database(path: &Path) -> Result<Database, String> {
+	let file = std::fs::File::open(path)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	let mut reader = BufReader::new(file);
+	match read_version(&mut reader, path)? {
+		PasskVersion::V0_1 => {
+			let mut payload = vec![];
+			reader
+				.read_to_end(&mut payload)
+				.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+			serde_json::from_slice(&payload)
+				.map_err(|err| format!("Failed to deserialize '{}': {err}", path.display()))
+		}
+		PasskVersion::V0_2 => {
+			let mut line = vec![];
+			reader
+				.read_until(b'\n', &mut line)
+				.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+			let header: DatabaseHeader = serde_json::from_slice(&line)
+				.map_err(|err| format!("Failed to deserialize '{}': {err}", path.display()))?;
+			let mut blob = vec![];
+			reader
+				.read_to_end(&mut blob)
+				.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+			header
+				.with_blob(blob)
+				.map_err(|err| format!("Invalid blob in '{}': {err}", path.display()))
+		}
+	}
+}
+
+/// Path of the temporary file used while saving `path`
+fn temporary_path(path: &Path) -> PathBuf {
+	let mut name = std::ffi::OsString::from(".");
+	name.push(path.file_name().unwrap_or_default());
+	name.push(".tmp");
+	path.with_file_name(name)
+}
+
+/// Write `path` with `write`, through a temporary file
+///
+/// The temporary file is atomically renamed over `path`, so an interrupted write never leaves a
+/// truncated file behind. It is only readable by the current user, and keeps the permissions of
+/// the file it replaces.
+pub fn write_atomic(
+	path: &Path,
+	write: impl FnOnce(&mut File, &Path) -> Result<(), String>,
+) -> Result<(), String> {
+	let tmp = temporary_path(path);
+	let write = || -> Result<(), String> {
+		// Left over by an interrupted write
+		let _ = std::fs::remove_file(&tmp);
+		let mut file = std::fs::OpenOptions::new()
+			.write(true)
+			.create_new(true)
+			.mode(0o600)
+			.open(&tmp)
+			.map_err(|err| format!("Failed to create '{}': {err}", tmp.display()))?;
+		if let Ok(metadata) = std::fs::metadata(path) {
+			file.set_permissions(metadata.permissions())
+				.map_err(|err| {
+					format!("Failed to set permissions of '{}': {err}", tmp.display())
+				})?;
+		}
+		write(&mut file, &tmp)?;
+		file.sync_all()
+			.map_err(|err| format!("Failed to sync '{}': {err}", tmp.display()))
+	};
+	if let Err(err) = write() {
+		let _ = std::fs::remove_file(&tmp);
+		return Err(err);
+	}
+
+	std::fs::rename(&tmp, path).map_err(|err| {
+		let _ = std::fs::remove_file(&tmp);
+		format!(
+			"Failed to move '{}' to '{}': {err}",
+			tmp.display(),
+			path.display()
+		)
+	})?;
+	// Persist the rename
+	if let Some(parent) = path
+		.parent()
+		.filter(|parent| !parent.as_os_str().is_empty())
+	{
+		if let Ok(dir) = std::fs::File::open(parent) {
+			let _ = dir.sync_all();
+		}
+	}
 	Ok(())
 }
+
+/// Save the database to `path`, see [`write_atomic`]
+pub fn save_database(database: &Database, path: &Path) -> Result<(), String> {
+	write_atomic(path, |file, tmp| {
+		let version: &str = PasskVersion::default().into();
+		let header = serde_json::to_string(&DatabaseHeader::new(database))
+			.map_err(|err| format!("Failed to serialize database: {err}"))?;
+
+		file.write_all(MAGIC)
+			.map_err(|err| format!("Failed to write MAGIC to '{}': {err}", tmp.display()))?;
+		file.write_all(version.as_bytes())
+			.map_err(|err| format!("Failed to write version to '{}': {err}", tmp.display()))?;
+		file.write_all(b"\n")
+			.map_err(|err| format!("Failed to write version to '{}': {err}", tmp.display()))?;
+		file.write_all(header.as_bytes())
+			.map_err(|err| format!("Failed to write header to '{}': {err}", tmp.display()))?;
+		file.write_all(b"\n")
+			.map_err(|err| format!("Failed to write header to '{}': {err}", tmp.display()))?;
+		file.write_all(&database.blob)
+			.map_err(|err| format!("Failed to write content to '{}': {err}", tmp.display()))
+	})
+}
+
+/// Path of the lock file for the database at `path`
+pub fn lock_path(path: &Path) -> PathBuf {
+	let mut name = path.file_name().unwrap_or_default().to_os_string();
+	name.push(".lock");
+	path.with_file_name(name)
+}
+
+/// Advisory lock on a database, released when dropped
+pub struct DatabaseLock {
+	path: PathBuf,
+	/// Holds the `flock`, released when closed
+	_file: File,
+}
+
+impl Drop for DatabaseLock {
+	fn drop(&mut self) {
+		// Removed while still locked, see [`lock_database`]
+		let _ = std::fs::remove_file(&self.path);
+	}
+}
+
+/// Result of trying to lock a database
+pub enum LockState {
+	/// The lock was acquired by this process
+	Acquired(DatabaseLock),
+	/// The database is opened by another running process
+	Held { pid: u32 },
+}
+
+/// Attempts at locking a database, while the lock file is being replaced or written by another
+/// process
+const LOCK_ATTEMPTS: usize = 50;
+
+/// Lock the database at `path` against concurrent opens
+///
+/// The lock file holds an exclusive `flock` of the process that opened the database, which the
+/// system releases when that process exits, so locks left by crashed processes are taken over
+/// rather than removed. The file also holds the pid of its owner, for the messages.
+pub fn lock_database(path: &Path) -> Result<LockState, String> {
+	let lock = lock_path(path);
+	for _ in 0..LOCK_ATTEMPTS {
+		let mut file = std::fs::OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create(true)
+			.truncate(false)
+			.mode(0o600)
+			.open(&lock)
+			.map_err(|err| format!("Failed to open '{}': {err}", lock.display()))?;
+		match file.try_lock() {
+			Ok(()) => {}
+			Err(TryLockError::WouldBlock) => {
+				let mut content = String::new();
+				file.read_to_string(&mut content)
+					.map_err(|err| format!("Failed to read '{}': {err}", lock.display()))?;
+				// Locked, but the pid is not written yet
+				if content.is_empty() {
+					std::thread::sleep(Duration::from_millis(10));
+					continue;
+				}
+				let pid = content.trim().parse::<u32>().map_err(|_| {
+					format!(
+						"Invalid lock '{}', remove it if no other instance is using the database",
+						lock.display()
+					)
+				})?;
+				return Ok(LockState::Held { pid });
+			}
+			Err(TryLockError::Error(err)) => {
+				return Err(format!("Failed to lock '{}': {err}", lock.display()));
+			}
+		}
+		// The previous owner removes the file before releasing the lock, the file locked here
+		// may no longer be the lock file
+		let locked = file
+			.metadata()
+			.map_err(|err| format!("Failed to read '{}': {err}", lock.display()))?;
+		match std::fs::metadata(&lock) {
+			Ok(current) if current.dev() == locked.dev() && current.ino() == locked.ino() => {}
+			_ => continue,
+		}
+		file.set_len(0)
+			.and_then(|()| file.write_all(std::process::id().to_string().as_bytes()))
+			.map_err(|err| format!("Failed to write '{}': {err}", lock.display()))?;
+		return Ok(LockState::Acquired(DatabaseLock {
+			path: lock,
+			_file: file,
+		}));
+	}
+	Err(format!("Failed to lock '{}'", lock.display()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn lock_is_exclusive() {
+		let dir = std::env::temp_dir().join(format!("passk-lock-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("test.passk");
+
+		let LockState::Acquired(lock) = lock_database(&path).unwrap() else {
+			panic!("the database is not locked yet");
+		};
+		// Held through another open file, even by the same process
+		let LockState::Held { pid } = lock_database(&path).unwrap() else {
+			panic!("the database is already locked");
+		};
+		assert_eq!(pid, std::process::id());
+
+		drop(lock);
+		assert!(!lock_path(&path).exists());
+		assert!(matches!(
+			lock_database(&path).unwrap(),
+			LockState::Acquired(_)
+		));
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
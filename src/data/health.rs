@@ -0,0 +1,187 @@
+//! Weak, reused and old passwords
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+
+use crate::data::entry::Entry;
+use crate::data::field::Expiry;
+
+/// Passwords with less entropy are weak, in bits
+pub const DEFAULT_MIN_ENTROPY: u32 = 64;
+/// Passwords not modified for longer are old, in days
+pub const DEFAULT_MAX_AGE: u32 = 365;
+
+/// Estimate the entropy of `password`, in bits, from its length and character classes
+pub fn entropy(password: &str) -> f64 {
+	let has = |class: fn(&char) -> bool| password.chars().any(|c| class(&c));
+	let pool = [
+		(has(char::is_ascii_lowercase), 26),
+		(has(char::is_ascii_uppercase), 26),
+		(has(char::is_ascii_digit), 10),
+		(has(char::is_ascii_punctuation), 33),
+		(has(|c| c.is_ascii_whitespace()), 1),
+		(has(|c| !c.is_ascii()), 100),
+	]
+	.iter()
+	.filter(|(present, _)| *present)
+	.map(|(_, size)| size)
+	.sum::<u32>();
+	if pool == 0 {
+		return 0.0;
+	}
+	password.chars().count() as f64 * (pool as f64).log2()
+}
+
+/// Problem found with a password
+#[derive(Debug, Clone)]
+pub enum Issue {
+	Weak {
+		bits: u32,
+	},
+	/// Also used by other fields, as `entry/field`
+	Reused {
+		by: Vec<String>,
+	},
+	Old {
+		days: i64,
+	},
+	Expired {
+		at: DateTime<Utc>,
+	},
+}
+
+impl Issue {
+	pub fn description(&self) -> String {
+		match self {
+			Issue::Weak { bits } => format!("weak ({bits} bits)"),
+			Issue::Reused { by } => format!("reused by {}", by.join(", ")),
+			Issue::Old { days } => format!("not changed for {days} days"),
+			Issue::Expired { at } => format!("expired on {}", at.format("%Y-%m-%d")),
+		}
+	}
+
+	/// Points of health lost by a password with the issue, out of 100
+	fn penalty(&self, min_entropy: u32) -> u8 {
+		match self {
+			Issue::Weak { bits } => (60 - 60 * bits / min_entropy.max(1)) as u8,
+			Issue::Reused { .. } => 25,
+			Issue::Old { .. } => 15,
+			// Expired passwords are already shown on their own
+			Issue::Expired { .. } => 0,
+		}
+	}
+}
+
+/// Problems found with a password
+#[derive(Debug, Clone)]
+pub struct Report {
+	pub entry: String,
+	pub field: String,
+	pub issues: Vec<Issue>,
+}
+
+/// Check the passwords, the hidden text fields, of `entries`
+///
+/// Only passwords with problems are reported.
+pub fn check(
+	entries: &[Entry],
+	min_entropy: u32,
+	max_age: TimeDelta,
+	now: DateTime<Utc>,
+) -> Vec<Report> {
+	let passwords = entries
+		.iter()
+		.flat_map(|entry| {
+			entry
+				.fields
+				.iter()
+				.filter(|field| field.hidden)
+				.filter_map(move |field| Some((entry, field, field.value.text()?)))
+		})
+		.filter(|(_, _, password)| !password.is_empty())
+		.collect::<Vec<_>>();
+
+	let mut users: HashMap<&str, Vec<String>> = HashMap::new();
+	for (entry, field, password) in &passwords {
+		users
+			.entry(*password)
+			.or_default()
+			.push(format!("{}/{}", entry.name, field.name));
+	}
+
+	passwords
+		.iter()
+		.filter_map(|(entry, field, password)| {
+			let mut issues = vec![];
+			let bits = entropy(password) as u32;
+			if bits < min_entropy {
+				issues.push(Issue::Weak { bits });
+			}
+			let name = format!("{}/{}", entry.name, field.name);
+			let by = users[*password]
+				.iter()
+				.filter(|other| **other != name)
+				.cloned()
+				.collect::<Vec<_>>();
+			if !by.is_empty() {
+				issues.push(Issue::Reused { by });
+			}
+			let age = now - field.date_modified;
+			if age > max_age {
+				issues.push(Issue::Old {
+					days: age.num_days(),
+				});
+			}
+			if let (Some(Expiry::Expired), Some(at)) = (field.expiry(now), field.expires_at) {
+				issues.push(Issue::Expired { at });
+			}
+			(!issues.is_empty()).then(|| Report {
+				entry: entry.name.clone(),
+				field: field.name.clone(),
+				issues,
+			})
+		})
+		.collect()
+}
+
+/// Health of the passwords of each entry of `entries`, from 0 to 100, `None` for the entries
+/// without passwords
+///
+/// Passwords lose up to 60 points when weak, 25 when reused and 15 when old, and entries score as
+/// their worst password.
+pub fn scores(
+	entries: &[Entry],
+	min_entropy: u32,
+	max_age: TimeDelta,
+	now: DateTime<Utc>,
+) -> Vec<Option<u8>> {
+	let penalties = check(entries, min_entropy, max_age, now)
+		.into_iter()
+		.map(|report| {
+			let penalty = report
+				.issues
+				.iter()
+				.map(|issue| issue.penalty(min_entropy))
+				.sum::<u8>();
+			((report.entry, report.field), penalty)
+		})
+		.collect::<HashMap<_, _>>();
+	entries
+		.iter()
+		.map(|entry| {
+			entry
+				.fields
+				.iter()
+				.filter(|field| field.hidden)
+				.filter(|field| field.value.text().is_some_and(|text| !text.is_empty()))
+				.map(|field| {
+					let key = (entry.name.clone(), field.name.clone());
+					100u8.saturating_sub(penalties.get(&key).copied().unwrap_or(0))
+				})
+				.min()
+		})
+		.collect()
+}
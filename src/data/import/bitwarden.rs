@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+#[derive(Deserialize)]
+struct BitwardenExport {
+	#[serde(default)]
+	encrypted: bool,
+	#[serde(default)]
+	folders: Vec<BitwardenFolder>,
+	#[serde(default)]
+	items: Vec<BitwardenItem>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenFolder {
+	id: String,
+	name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenItem {
+	#[serde(default)]
+	folder_id: Option<String>,
+	/// 1: login, 2: secure note, 3: card, 4: identity, 5: SSH key
+	#[serde(rename = "type")]
+	kind: u8,
+	name: String,
+	#[serde(default)]
+	notes: Option<String>,
+	#[serde(default)]
+	fields: Vec<BitwardenField>,
+	#[serde(default)]
+	login: Option<BitwardenLogin>,
+	#[serde(default)]
+	card: Option<HashMap<String, serde_json::Value>>,
+	#[serde(default)]
+	identity: Option<HashMap<String, serde_json::Value>>,
+	#[serde(default)]
+	ssh_key: Option<BitwardenSshKey>,
+	#[serde(default)]
+	creation_date: Option<DateTime<Utc>>,
+	#[serde(default)]
+	revision_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenField {
+	#[serde(default)]
+	name: Option<String>,
+	#[serde(default)]
+	value: Option<String>,
+	/// 0: text, 1: hidden, 2: boolean, 3: linked
+	#[serde(rename = "type")]
+	kind: u8,
+}
+
+#[derive(Deserialize)]
+struct BitwardenLogin {
+	#[serde(default)]
+	uris: Option<Vec<BitwardenUri>>,
+	#[serde(default)]
+	username: Option<String>,
+	#[serde(default)]
+	password: Option<String>,
+	#[serde(default)]
+	totp: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenUri {
+	#[serde(default)]
+	uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenSshKey {
+	#[serde(default)]
+	private_key: Option<String>,
+	#[serde(default)]
+	public_key: Option<String>,
+}
+
+/// Card and identity properties, in display order
+const CARD_KEYS: [(&str, &str, bool); 6] = [
+	("cardholderName", "Cardholder", false),
+	("brand", "Brand", false),
+	("number", "Number", true),
+	("expMonth", "Expiration Month", false),
+	("expYear", "Expiration Year", false),
+	("code", "CVV", true),
+];
+const IDENTITY_KEYS: [(&str, &str, bool); 18] = [
+	("title", "Title", false),
+	("firstName", "First Name", false),
+	("middleName", "Middle Name", false),
+	("lastName", "Last Name", false),
+	("username", "Username", false),
+	("company", "Company", false),
+	("email", "E-Mail", false),
+	("phone", "Phone", false),
+	("address1", "Address", false),
+	("address2", "Address 2", false),
+	("address3", "Address 3", false),
+	("city", "City", false),
+	("state", "State", false),
+	("postalCode", "Postal Code", false),
+	("country", "Country", false),
+	("ssn", "SSN", true),
+	("passportNumber", "Passport Number", true),
+	("licenseNumber", "License Number", true),
+];
+
+/// Non-empty string `value`
+fn non_empty(value: &Option<String>) -> Option<&str> {
+	value.as_deref().filter(|value| !value.is_empty())
+}
+
+/// Fields of the card or identity properties `keys`
+fn object_fields(
+	object: &HashMap<String, serde_json::Value>,
+	keys: &[(&str, &str, bool)],
+	fields: &mut Vec<Field>,
+) {
+	for (key, name, hidden) in keys {
+		let Some(value) = object
+			.get(*key)
+			.and_then(|value| value.as_str())
+			.filter(|value| !value.is_empty())
+		else {
+			continue;
+		};
+		let value = match *key {
+			"email" => FieldValue::Email(value.into()),
+			"phone" => FieldValue::Phone(value.into()),
+			_ => FieldValue::Text(value.into()),
+		};
+		fields.push(imported_field(name, value, *hidden));
+	}
+}
+
+fn item_fields(item: &BitwardenItem) -> Vec<Field> {
+	let mut fields = vec![];
+	if let Some(login) = &item.login {
+		if let Some(username) = non_empty(&login.username) {
+			fields.push(imported_field(
+				"Username",
+				FieldValue::Text(username.into()),
+				false,
+			));
+		}
+		if let Some(password) = non_empty(&login.password) {
+			fields.push(imported_field(
+				"Password",
+				FieldValue::Text(password.into()),
+				true,
+			));
+		}
+		for uri in login.uris.iter().flatten() {
+			if let Some(uri) = non_empty(&uri.uri) {
+				fields.push(imported_field("URL", FieldValue::Url(uri.into()), false));
+			}
+		}
+		if let Some(totp) = non_empty(&login.totp) {
+			let value = match totp.strip_prefix("steam://") {
+				Some(secret) => FieldValue::TOTPSteam(secret.into()),
+				None => FieldValue::TOTPRFC6238(totp.into()),
+			};
+			fields.push(imported_field("TOTP", value, true));
+		}
+	}
+	if let Some(card) = &item.card {
+		object_fields(card, &CARD_KEYS, &mut fields);
+	}
+	if let Some(identity) = &item.identity {
+		object_fields(identity, &IDENTITY_KEYS, &mut fields);
+	}
+	if let Some(key) = &item.ssh_key {
+		if let Some(private_key) = non_empty(&key.private_key) {
+			fields.push(imported_field(
+				"Private Key",
+				FieldValue::Text(private_key.into()),
+				true,
+			));
+		}
+		if let Some(public_key) = non_empty(&key.public_key) {
+			fields.push(imported_field(
+				"Public Key",
+				FieldValue::Text(public_key.into()),
+				false,
+			));
+		}
+	}
+	if let Some(notes) = non_empty(&item.notes) {
+		fields.push(imported_field(
+			"Note",
+			FieldValue::Note(notes.into()),
+			false,
+		));
+	}
+	for field in &item.fields {
+		// Linked fields only point to another property of the item
+		if field.kind == 3 {
+			continue;
+		}
+		let Some(name) = non_empty(&field.name) else {
+			continue;
+		};
+		let value = field.value.clone().unwrap_or_default();
+		fields.push(imported_field(
+			name,
+			FieldValue::Text(value),
+			field.kind == 1,
+		));
+	}
+	fields
+}
+
+/// Import an unencrypted Bitwarden JSON export
+///
+/// Entries are named after their folder and tagged with their kind.
+pub fn import(bytes: &[u8]) -> Result<Vec<Entry>, String> {
+	let export: BitwardenExport = serde_json::from_slice(bytes)
+		.map_err(|err| format!("Failed to parse Bitwarden export: {err}"))?;
+	if export.encrypted {
+		return Err(
+			"Encrypted Bitwarden exports are not supported, export as unencrypted JSON".into(),
+		);
+	}
+
+	let folders = export
+		.folders
+		.iter()
+		.map(|folder| (folder.id.as_str(), folder.name.as_str()))
+		.collect::<HashMap<_, _>>();
+	let entries = export
+		.items
+		.iter()
+		.map(|item| {
+			let name = match item.folder_id.as_deref().and_then(|id| folders.get(id)) {
+				Some(folder) => format!("{folder}/{}", item.name),
+				None => item.name.clone(),
+			};
+			let kind = match item.kind {
+				1 => "login",
+				2 => "note",
+				3 => "card",
+				4 => "identity",
+				5 => "ssh-key",
+				_ => "",
+			};
+			imported_entry(
+				name,
+				item_fields(item),
+				vec![kind.into()],
+				item.creation_date,
+				item.revision_date,
+			)
+		})
+		.collect();
+	Ok(entries)
+}
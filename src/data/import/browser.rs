@@ -0,0 +1,143 @@
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::data::entry::Entry;
+use crate::data::field::FieldValue;
+use crate::data::import::csv;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+/// Tag added to every imported entry
+pub static BROWSER_TAG: &str = "browser-import";
+
+/// Browser whose password export is imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Browser {
+	/// Chrome and Chromium based browsers: `name,url,username,password,note`
+	Chrome,
+	/// Firefox: `url,username,password,httpRealm,formActionOrigin,guid,timeCreated,...`
+	Firefox,
+}
+
+/// Host part of `url`
+fn host(url: &str) -> &str {
+	let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+	let end = url.find(['/', '?', '#']).unwrap_or(url.len());
+	let host = &url[..end];
+	host.rsplit_once('@').map_or(host, |(_, host)| host)
+}
+
+/// Login read from a row
+struct Login {
+	name: String,
+	url: String,
+	username: String,
+	password: String,
+	note: String,
+	created_at: Option<DateTime<Utc>>,
+	modified_at: Option<DateTime<Utc>>,
+}
+
+/// Import a browser password CSV export
+///
+/// Logins with the same URL and username are merged, the last modified one is kept.
+pub fn import(text: &str, browser: Browser) -> Result<Vec<Entry>, String> {
+	let rows = csv::parse(text)?;
+	let Some((header, rows)) = rows.split_first() else {
+		return Err("CSV file is empty".into());
+	};
+	let column = |name: &str| header.iter().position(|column| column.trim() == name);
+	let required = |name: &str| {
+		column(name).ok_or(format!(
+			"Missing column '{name}', is this a {browser:?} export?"
+		))
+	};
+	let url = required("url")?;
+	let username = required("username")?;
+	let password = required("password")?;
+	let (name, note, created, modified) = match browser {
+		Browser::Chrome => (Some(required("name")?), column("note"), None, None),
+		Browser::Firefox => (
+			None,
+			None,
+			column("timeCreated"),
+			column("timePasswordChanged"),
+		),
+	};
+
+	let mut logins: Vec<Login> = vec![];
+	for row in rows {
+		let get = |column: Option<usize>| {
+			column
+				.and_then(|column| row.get(column))
+				.map(|value| value.trim().to_string())
+				.unwrap_or_default()
+		};
+		// Firefox stores timestamps in milliseconds
+		let time = |column: Option<usize>| {
+			get(column)
+				.parse::<i64>()
+				.ok()
+				.and_then(DateTime::from_timestamp_millis)
+		};
+
+		let url = get(Some(url));
+		let login = Login {
+			name: Some(get(name))
+				.filter(|name| !name.is_empty())
+				.unwrap_or_else(|| host(&url).to_string()),
+			username: get(Some(username)),
+			password: get(Some(password)),
+			note: get(note),
+			created_at: time(created),
+			modified_at: time(modified),
+			url,
+		};
+
+		match logins
+			.iter_mut()
+			.find(|other| other.url == login.url && other.username == login.username)
+		{
+			Some(other) => {
+				if login.modified_at >= other.modified_at {
+					*other = login;
+				}
+			}
+			None => logins.push(login),
+		}
+	}
+
+	Ok(logins
+		.into_iter()
+		.map(|login| {
+			let mut fields = vec![];
+			if !login.username.is_empty() {
+				fields.push(imported_field(
+					"Username",
+					FieldValue::Text(login.username),
+					false,
+				));
+			}
+			if !login.password.is_empty() {
+				fields.push(imported_field(
+					"Password",
+					FieldValue::Text(login.password),
+					true,
+				));
+			}
+			if !login.url.is_empty() {
+				fields.push(imported_field("URL", FieldValue::Url(login.url), false));
+			}
+			if !login.note.is_empty() {
+				fields.push(imported_field("Note", FieldValue::Note(login.note), false));
+			}
+			imported_entry(
+				login.name,
+				fields,
+				vec![BROWSER_TAG.into()],
+				login.created_at,
+				login.modified_at,
+			)
+		})
+		.collect())
+}
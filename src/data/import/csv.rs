@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use crate::data::entry::Entry;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+/// Parse CSV text (RFC 4180) into rows of values
+pub fn parse(text: &str) -> Result<Vec<Vec<String>>, String> {
+	let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+	let mut rows = vec![];
+	let mut row = vec![];
+	let mut value = String::new();
+	let mut quoted = false;
+	let mut chars = text.chars().peekable();
+	let mut line = 1;
+
+	while let Some(c) = chars.next() {
+		match c {
+			'"' if quoted => {
+				if chars.peek() == Some(&'"') {
+					chars.next();
+					value.push('"');
+				} else {
+					quoted = false;
+				}
+			}
+			'"' if value.is_empty() => quoted = true,
+			',' if !quoted => row.push(std::mem::take(&mut value)),
+			'\r' if !quoted && chars.peek() == Some(&'\n') => {}
+			'\n' if !quoted => {
+				row.push(std::mem::take(&mut value));
+				if row.len() > 1 || !row[0].is_empty() {
+					rows.push(std::mem::take(&mut row));
+				} else {
+					row.clear();
+				}
+				line += 1;
+			}
+			_ => {
+				if c == '\n' {
+					line += 1;
+				}
+				value.push(c)
+			}
+		}
+	}
+	if quoted {
+		return Err(format!("Unterminated quoted value on line {line}"));
+	}
+	if !row.is_empty() || !value.is_empty() {
+		row.push(value);
+		rows.push(row);
+	}
+	Ok(rows)
+}
+
+/// Read and parse the CSV file at `path`
+pub fn load(path: &Path) -> Result<Vec<Vec<String>>, String> {
+	let text = std::fs::read_to_string(path)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	parse(&text).map_err(|err| format!("Failed to parse '{}': {err}", path.display()))
+}
+
+/// Assignment of CSV columns to entry data
+#[derive(Debug, Default, Clone)]
+pub struct CsvMapping {
+	pub name: Option<usize>,
+	pub username: Option<usize>,
+	pub password: Option<usize>,
+	pub url: Option<usize>,
+	/// Comma or semicolon separated tags
+	pub tags: Option<usize>,
+	/// Columns imported as text fields named after their header
+	pub extra: Vec<usize>,
+}
+
+impl CsvMapping {
+	/// Guess the mapping from the column names of `header`
+	pub fn guess(header: &[String]) -> Self {
+		let find = |names: &[&str]| {
+			header
+				.iter()
+				.position(|column| names.contains(&column.trim().to_lowercase().as_str()))
+		};
+		let mut mapping = Self {
+			name: find(&["name", "title", "entry"]),
+			username: find(&["username", "user", "login", "email", "login_username"]),
+			password: find(&["password", "pass", "login_password"]),
+			url: find(&["url", "uri", "website", "login_uri"]),
+			tags: find(&["tags", "tag", "group", "folder", "grouping"]),
+			extra: vec![],
+		};
+		mapping.extra = (0..header.len())
+			.filter(|column| !mapping.mapped().contains(&Some(*column)))
+			.collect();
+		mapping
+	}
+
+	fn mapped(&self) -> [Option<usize>; 5] {
+		[self.name, self.username, self.password, self.url, self.tags]
+	}
+}
+
+/// Get the non-empty value of `column` in `row`
+fn value(row: &[String], column: Option<usize>) -> Option<&str> {
+	column
+		.and_then(|column| row.get(column))
+		.map(|value| value.trim())
+		.filter(|value| !value.is_empty())
+}
+
+/// Import CSV rows, the first row is the header
+pub fn import(rows: &[Vec<String>], mapping: &CsvMapping) -> Result<Vec<Entry>, String> {
+	let Some((header, rows)) = rows.split_first() else {
+		return Err("CSV file is empty".into());
+	};
+
+	let mut entries = vec![];
+	for (id, row) in rows.iter().enumerate() {
+		let mut fields = vec![];
+		if let Some(username) = value(row, mapping.username) {
+			fields.push(imported_field(
+				"Username",
+				FieldValue::Text(username.into()),
+				false,
+			));
+		}
+		if let Some(password) = value(row, mapping.password) {
+			fields.push(imported_field(
+				"Password",
+				FieldValue::Text(password.into()),
+				true,
+			));
+		}
+		if let Some(url) = value(row, mapping.url) {
+			fields.push(imported_field("URL", FieldValue::Url(url.into()), false));
+		}
+		for column in &mapping.extra {
+			if let Some(extra) = value(row, Some(*column)) {
+				fields.push(imported_field(
+					&header[*column],
+					FieldValue::Text(extra.into()),
+					false,
+				));
+			}
+		}
+		let tags = value(row, mapping.tags)
+			.map(|tags| {
+				tags.split([',', ';'])
+					.map(|tag| tag.trim().to_string())
+					.collect()
+			})
+			.unwrap_or_default();
+		let name = value(row, mapping.name)
+			.or(value(row, mapping.url))
+			.map(str::to_string)
+			.unwrap_or_else(|| format!("Imported entry {}", id + 1));
+		entries.push(imported_entry(name, fields, tags, None, None));
+	}
+	Ok(entries)
+}
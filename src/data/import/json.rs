@@ -0,0 +1,134 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::data::entry::Entry;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+/// Kind of a mapped field
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MappedKind {
+	#[default]
+	Text,
+	Url,
+	Phone,
+	Email,
+	Totp,
+}
+
+/// Mapping of a JSON value to a field
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldMapping {
+	/// Field name
+	pub name: String,
+	/// Path to the value, relative to the entry
+	pub path: String,
+	#[serde(default)]
+	pub kind: MappedKind,
+	#[serde(default)]
+	pub hidden: bool,
+}
+
+/// Describes how to extract entries from an arbitrary JSON document
+///
+/// Paths are dot-separated keys, numeric components index into arrays, e.g: `login.uris.0.uri`
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonMapping {
+	/// Path to the array of entries, empty for the document root
+	#[serde(default)]
+	pub entries: String,
+	/// Path to the entry name
+	pub name: String,
+	/// Path to a tag or array of tags
+	pub tags: Option<String>,
+	/// Path to a folder, prepended to the entry name
+	pub folder: Option<String>,
+	#[serde(default)]
+	pub fields: Vec<FieldMapping>,
+}
+
+impl JsonMapping {
+	pub fn load(path: &Path) -> Result<Self, String> {
+		let bytes = std::fs::read(path)
+			.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+		serde_json::from_slice(&bytes)
+			.map_err(|err| format!("Failed to parse mapping '{}': {err}", path.display()))
+	}
+}
+
+/// Resolve `path` inside `value`
+fn resolve<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+	path.split('.')
+		.filter(|key| !key.is_empty())
+		.try_fold(value, |value, key| match value {
+			Value::Array(array) => array.get(key.parse::<usize>().ok()?),
+			Value::Object(object) => object.get(key),
+			_ => None,
+		})
+}
+
+/// Convert a scalar to a string
+fn scalar(value: &Value) -> Option<String> {
+	match value {
+		Value::String(s) if !s.is_empty() => Some(s.clone()),
+		Value::Number(n) => Some(n.to_string()),
+		Value::Bool(b) => Some(b.to_string()),
+		_ => None,
+	}
+}
+
+/// Import entries from `bytes` using `mapping`
+pub fn import(bytes: &[u8], mapping: &JsonMapping) -> Result<Vec<Entry>, String> {
+	let document: Value =
+		serde_json::from_slice(bytes).map_err(|err| format!("Failed to parse JSON: {err}"))?;
+	let items = resolve(&document, &mapping.entries)
+		.and_then(|items| items.as_array())
+		.ok_or(format!(
+			"Path '{}' does not point to an array of entries",
+			mapping.entries
+		))?;
+
+	let mut entries = vec![];
+	for (id, item) in items.iter().enumerate() {
+		let mut name = resolve(item, &mapping.name)
+			.and_then(scalar)
+			.unwrap_or_else(|| format!("Imported #{id}"));
+		if let Some(folder) = mapping
+			.folder
+			.as_ref()
+			.and_then(|path| resolve(item, path))
+			.and_then(scalar)
+		{
+			name = format!("{folder}/{name}");
+		}
+
+		let tags = match mapping.tags.as_ref().and_then(|path| resolve(item, path)) {
+			Some(Value::Array(tags)) => tags.iter().filter_map(scalar).collect(),
+			Some(tag) => scalar(tag).into_iter().collect(),
+			None => vec![],
+		};
+
+		let fields = mapping
+			.fields
+			.iter()
+			.filter_map(|field| {
+				let value = resolve(item, &field.path).and_then(scalar)?;
+				let value = match field.kind {
+					MappedKind::Text => FieldValue::Text(value),
+					MappedKind::Url => FieldValue::Url(value),
+					MappedKind::Phone => FieldValue::Phone(value),
+					MappedKind::Email => FieldValue::Email(value),
+					MappedKind::Totp => FieldValue::TOTPRFC6238(value),
+				};
+				Some(imported_field(&field.name, value, field.hidden))
+			})
+			.collect();
+
+		entries.push(imported_entry(name, fields, tags, None, None));
+	}
+	Ok(entries)
+}
@@ -0,0 +1,283 @@
+use std::path::Path;
+
+use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
+use quick_xml::events::BytesStart;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::data::attachment::detect_mimetype;
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+use crate::data::kdbx;
+use crate::data::kdbx::Kdbx;
+
+/// Group being read
+#[derive(Default)]
+struct Group {
+	name: String,
+	uuid: String,
+}
+
+/// Entry being read
+#[derive(Default)]
+struct KdbxEntry {
+	/// Key, value and whether it is protected
+	strings: Vec<(String, String, bool)>,
+	/// Key and index of the attachment
+	binaries: Vec<(String, usize)>,
+	tags: Vec<String>,
+	created_at: Option<DateTime<Utc>>,
+	modified_at: Option<DateTime<Utc>>,
+}
+
+impl KdbxEntry {
+	fn string(&self, key: &str) -> Option<&str> {
+		self.strings
+			.iter()
+			.find(|(other, value, _)| other == key && !value.is_empty())
+			.map(|(_, value, _)| value.as_str())
+	}
+
+	/// Name of the entry, prefixed by its groups
+	fn name(&self, groups: &[Group]) -> String {
+		let title = self.string("Title").unwrap_or("Untitled");
+		// The root group is named after the database
+		groups
+			.iter()
+			.skip(1)
+			.map(|group| group.name.as_str())
+			.chain([title])
+			.collect::<Vec<_>>()
+			.join("/")
+	}
+
+	fn fields(&self, kdbx: &Kdbx) -> Vec<Field> {
+		let mut fields = vec![];
+		if let Some(username) = self.string("UserName") {
+			fields.push(imported_field(
+				"Username",
+				FieldValue::Text(username.into()),
+				false,
+			));
+		}
+		if let Some(password) = self.string("Password") {
+			fields.push(imported_field(
+				"Password",
+				FieldValue::Text(password.into()),
+				true,
+			));
+		}
+		if let Some(url) = self.string("URL") {
+			fields.push(imported_field("URL", FieldValue::Url(url.into()), false));
+		}
+		// KeePassXC stores an `otpauth://` URI, KeePass 2 the secret
+		if let Some(otp) = self.string("otp") {
+			let value = match otp.contains("encoder=steam") {
+				true => FieldValue::TOTPSteam(otp.into()),
+				false => FieldValue::TOTPRFC6238(otp.into()),
+			};
+			fields.push(imported_field("TOTP", value, true));
+		} else if let Some(secret) = self.string("TimeOtp-Secret-Base32") {
+			fields.push(imported_field(
+				"TOTP",
+				FieldValue::TOTPRFC6238(secret.into()),
+				true,
+			));
+		}
+		const STANDARD: [&str; 7] = [
+			"Title",
+			"UserName",
+			"Password",
+			"URL",
+			"Notes",
+			"otp",
+			"TimeOtp-Secret-Base32",
+		];
+		for (key, value, protected) in &self.strings {
+			if !STANDARD.contains(&key.as_str()) {
+				fields.push(imported_field(
+					key,
+					FieldValue::Text(value.clone()),
+					*protected,
+				));
+			}
+		}
+		if let Some(notes) = self.string("Notes") {
+			fields.push(imported_field(
+				"Note",
+				FieldValue::Note(notes.into()),
+				false,
+			));
+		}
+		for (key, index) in &self.binaries {
+			let Some(data) = kdbx.binaries.get(*index) else {
+				continue;
+			};
+			fields.push(imported_field(
+				key,
+				FieldValue::Binary {
+					mimetype: detect_mimetype(Path::new(key), data),
+					base64: base64::engine::general_purpose::STANDARD.encode(data),
+				},
+				false,
+			));
+		}
+		fields
+	}
+}
+
+/// Value of attribute `name` of `element`
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+	element
+		.try_get_attribute(name)
+		.ok()
+		.flatten()
+		.and_then(|attribute| attribute.unescape_value().ok())
+		.map(|value| value.into_owned())
+}
+
+/// Import the entries of a KeePass database, entries in the recycle bin and history are left out
+pub fn import(bytes: &[u8], password: Option<&str>) -> Result<Vec<Entry>, String> {
+	let password = password.ok_or("A password is required for KeePass databases".to_string())?;
+	let kdbx = kdbx::read(bytes, password)?;
+	let mut stream = kdbx.stream();
+
+	let mut reader = Reader::from_str(&kdbx.xml);
+	let mut path: Vec<Vec<u8>> = vec![];
+	let mut groups: Vec<Group> = vec![];
+	let mut recycle_bin = None;
+	let mut entry: Option<KdbxEntry> = None;
+	let mut entries = vec![];
+
+	let mut text = String::new();
+	let mut key = String::new();
+	let mut value = String::new();
+	let mut protected = false;
+	let mut reference = None;
+	loop {
+		let event = reader
+			.read_event()
+			.map_err(|err| format!("Invalid database document: {err}"))?;
+		let (start, end) = match &event {
+			Event::Start(element) => (Some(element), None),
+			Event::Empty(element) => (Some(element), Some(element.name())),
+			Event::End(element) => (None, Some(element.name())),
+			Event::Text(content) => {
+				let content = content
+					.unescape()
+					.map_err(|err| format!("Invalid database document: {err}"))?;
+				text.push_str(&content);
+				continue;
+			}
+			Event::CData(content) => {
+				text.push_str(&String::from_utf8_lossy(content));
+				continue;
+			}
+			Event::Eof => break,
+			_ => continue,
+		};
+
+		if let Some(element) = start {
+			let name = element.name().as_ref().to_vec();
+			text.clear();
+			match name.as_slice() {
+				b"Group" => groups.push(Group::default()),
+				b"Entry" if !path.iter().any(|parent| parent == b"History") => {
+					entry = Some(KdbxEntry::default())
+				}
+				b"Value" => {
+					protected = attribute(element, "Protected").as_deref() == Some("True");
+					reference = attribute(element, "Ref").and_then(|index| index.parse().ok());
+				}
+				_ => {}
+			}
+			path.push(name);
+		}
+		let Some(name) = end else {
+			continue;
+		};
+		path.pop();
+		let in_history = path.iter().any(|parent| parent == b"History");
+		if name.as_ref() == b"Entry" && !in_history {
+			let Some(entry) = entry.take() else {
+				continue;
+			};
+			let recycled = recycle_bin
+				.as_ref()
+				.is_some_and(|uuid| groups.iter().any(|group| group.uuid == *uuid));
+			if !recycled {
+				entries.push(imported_entry(
+					entry.name(&groups),
+					entry.fields(&kdbx),
+					entry.tags,
+					entry.created_at,
+					entry.modified_at,
+				));
+			}
+			continue;
+		}
+
+		// Values of the history are left out
+		let current = entry.as_mut().filter(|_| !in_history);
+		let parent = path.last().map(Vec::as_slice);
+		match (name.as_ref(), parent) {
+			(b"Key", _) => key = std::mem::take(&mut text),
+			// Protected values must all be decrypted in order, even those left out
+			(b"Value", _) if protected => value = stream.unprotect(&text)?,
+			(b"Value", _) => value = std::mem::take(&mut text),
+			(b"RecycleBinUUID", Some(b"Meta")) => recycle_bin = Some(text.trim().to_string()),
+			(b"Name", Some(b"Group")) => {
+				if let Some(group) = groups.last_mut() {
+					group.name = text.trim().to_string();
+				}
+			}
+			(b"UUID", Some(b"Group")) => {
+				if let Some(group) = groups.last_mut() {
+					group.uuid = text.trim().to_string();
+				}
+			}
+			(b"Group", _) => {
+				groups.pop();
+			}
+			(b"String", Some(b"Entry")) => {
+				if let Some(entry) = current {
+					entry.strings.push((
+						std::mem::take(&mut key),
+						std::mem::take(&mut value),
+						protected,
+					));
+				}
+			}
+			(b"Binary", Some(b"Entry")) => {
+				if let (Some(entry), Some(reference)) = (current, reference) {
+					entry.binaries.push((std::mem::take(&mut key), reference));
+				}
+			}
+			(b"Tags", Some(b"Entry")) => {
+				if let Some(entry) = current {
+					entry.tags = text
+						.split([';', ','])
+						.map(|tag| tag.trim().to_string())
+						.collect();
+				}
+			}
+			(b"CreationTime", Some(b"Times")) => {
+				if let Some(entry) = current {
+					entry.created_at = kdbx::parse_time(&text);
+				}
+			}
+			(b"LastModificationTime", Some(b"Times")) => {
+				if let Some(entry) = current {
+					entry.modified_at = kdbx::parse_time(&text);
+				}
+			}
+			_ => {}
+		}
+	}
+	Ok(entries)
+}
@@ -0,0 +1,151 @@
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::import::csv;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+/// URL used by LastPass for secure notes
+const SECURE_NOTE_URL: &str = "http://sn";
+
+/// LastPass escapes a few HTML entities in names and notes
+fn unescape(value: &str) -> String {
+	value
+		.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&quot;", "\"")
+		.replace("&#39;", "'")
+		.replace("&amp;", "&")
+}
+
+/// Fields of a secure note
+///
+/// Typed notes (credit cards, addresses, ...) start with `NoteType:` followed by `Key:Value`
+/// lines, other notes are kept as a single text field.
+fn note_fields(note: &str) -> Vec<Field> {
+	if !note.starts_with("NoteType:") {
+		return vec![imported_field("Note", FieldValue::Note(note.into()), false)];
+	}
+
+	let mut fields: Vec<Field> = vec![];
+	// The free-form `Notes` value is last and may contain colons
+	let mut in_notes = false;
+	for line in note.lines() {
+		match line.split_once(':') {
+			Some(("NoteType", _)) => {}
+			Some((key, value)) if !in_notes => {
+				in_notes = key == "Notes";
+				if !value.trim().is_empty() || in_notes {
+					let hidden = ["Password", "Number", "Security Code", "PIN"]
+						.iter()
+						.any(|secret| key.contains(secret));
+					let value = value.trim().to_string();
+					let value = if in_notes {
+						FieldValue::Note(value)
+					} else {
+						FieldValue::Text(value)
+					};
+					fields.push(imported_field(key, value, hidden));
+				}
+			}
+			// Continuation of a multiline value
+			_ => {
+				if let Some(FieldValue::Text(text) | FieldValue::Note(text)) =
+					fields.last_mut().map(|field| &mut field.value)
+				{
+					if !text.is_empty() {
+						text.push('\n');
+					}
+					text.push_str(line);
+				}
+			}
+		}
+	}
+	fields.retain(|field| field.value.text().is_some_and(|text| !text.is_empty()));
+	fields
+}
+
+/// Import a LastPass CSV export
+///
+/// Columns: `url,username,password,totp,extra,name,grouping,fav`. The grouping (folder) becomes
+/// a tag and secure notes are stored in the `extra` column.
+pub fn import(text: &str) -> Result<Vec<Entry>, String> {
+	let rows = csv::parse(text)?;
+	let Some((header, rows)) = rows.split_first() else {
+		return Err("CSV file is empty".into());
+	};
+	let column = |name: &str| {
+		header
+			.iter()
+			.position(|column| column.trim() == name)
+			.ok_or(format!(
+				"Missing column '{name}', is this a LastPass export?"
+			))
+	};
+	let url = column("url")?;
+	let username = column("username")?;
+	let password = column("password")?;
+	let totp = column("totp").ok();
+	let extra = column("extra")?;
+	let name = column("name")?;
+	let grouping = column("grouping")?;
+
+	let mut entries = vec![];
+	for row in rows {
+		let get = |column: usize| {
+			row.get(column)
+				.map(|value| value.trim())
+				.filter(|value| !value.is_empty())
+		};
+
+		let mut fields = vec![];
+		let url = get(url).filter(|url| *url != "http://");
+		if url == Some(SECURE_NOTE_URL) {
+			if let Some(note) = get(extra) {
+				fields.extend(note_fields(&unescape(note)));
+			}
+		} else {
+			if let Some(username) = get(username) {
+				fields.push(imported_field(
+					"Username",
+					FieldValue::Text(username.into()),
+					false,
+				));
+			}
+			if let Some(password) = get(password) {
+				fields.push(imported_field(
+					"Password",
+					FieldValue::Text(password.into()),
+					true,
+				));
+			}
+			if let Some(url) = url {
+				fields.push(imported_field("URL", FieldValue::Url(url.into()), false));
+			}
+			if let Some(totp) = totp.and_then(get) {
+				fields.push(imported_field(
+					"TOTP",
+					FieldValue::TOTPRFC6238(totp.into()),
+					true,
+				));
+			}
+			if let Some(note) = get(extra) {
+				fields.push(imported_field(
+					"Note",
+					FieldValue::Note(unescape(note)),
+					false,
+				));
+			}
+		}
+
+		let name = get(name)
+			.map(unescape)
+			.or(url.map(str::to_string))
+			.unwrap_or_else(|| "Untitled".into());
+		let tags = get(grouping)
+			.map(|group| vec![unescape(group)])
+			.unwrap_or_default();
+		entries.push(imported_entry(name, fields, tags, None, None));
+	}
+	Ok(entries)
+}
@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::data::entry::Entry;
+use crate::data::entry::EntryTag;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::plugin::PLUGINS;
+use crate::plugin::PLUGIN_IMPORTERS;
+
+pub mod bitwarden;
+pub mod browser;
+pub mod csv;
+pub mod json;
+pub mod kdbx;
+pub mod lastpass;
+pub mod otp;
+pub mod proton;
+
+/// Supported import formats
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ImportFormat {
+	#[default]
+	ProtonPass,
+	/// Arbitrary JSON, using a field-path mapping
+	JsonMapping,
+	/// CSV with a column mapping
+	Csv,
+	LastPass,
+	/// Chrome password export
+	Chrome,
+	/// Firefox password export
+	Firefox,
+	/// Aegis backup, TOTP seeds only
+	Aegis,
+	/// andOTP backup, TOTP seeds only
+	AndOtp,
+	/// Unencrypted Bitwarden JSON export
+	Bitwarden,
+	/// KeePass KDBX 4 database
+	Kdbx,
+	/// Format provided by a plugin, index in [`PLUGIN_IMPORTERS`]
+	Plugin(usize),
+}
+
+impl TryFrom<usize> for ImportFormat {
+	type Error = &'static str;
+
+	fn try_from(value: usize) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(ImportFormat::ProtonPass),
+			1 => Ok(ImportFormat::JsonMapping),
+			2 => Ok(ImportFormat::Csv),
+			3 => Ok(ImportFormat::LastPass),
+			4 => Ok(ImportFormat::Chrome),
+			5 => Ok(ImportFormat::Firefox),
+			6 => Ok(ImportFormat::Aegis),
+			7 => Ok(ImportFormat::AndOtp),
+			8 => Ok(ImportFormat::Bitwarden),
+			9 => Ok(ImportFormat::Kdbx),
+			n if n - 10 < PLUGIN_IMPORTERS.len() => Ok(ImportFormat::Plugin(n - 10)),
+			_ => Err("Invalid value"),
+		}
+	}
+}
+
+impl TryFrom<&str> for ImportFormat {
+	type Error = String;
+
+	/// Parse the name of a format on the command line
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		(0usize..)
+			.map_while(|id| ImportFormat::try_from(id).ok())
+			.find(|format| format.id().eq_ignore_ascii_case(value))
+			.ok_or_else(|| format!("Unknown import format '{value}'"))
+	}
+}
+
+impl ImportFormat {
+	pub fn name(&self) -> &'static str {
+		match self {
+			ImportFormat::ProtonPass => "Proton Pass",
+			ImportFormat::JsonMapping => "JSON (mapping)",
+			ImportFormat::Csv => "CSV",
+			ImportFormat::LastPass => "LastPass",
+			ImportFormat::Chrome => "Chrome",
+			ImportFormat::Firefox => "Firefox",
+			ImportFormat::Aegis => "Aegis",
+			ImportFormat::AndOtp => "andOTP",
+			ImportFormat::Bitwarden => "Bitwarden",
+			ImportFormat::Kdbx => "KeePass",
+			ImportFormat::Plugin(id) => PLUGIN_IMPORTERS[*id].1,
+		}
+	}
+
+	/// Name of the format on the command line
+	pub fn id(&self) -> &'static str {
+		match self {
+			ImportFormat::ProtonPass => "proton",
+			ImportFormat::JsonMapping => "json",
+			ImportFormat::Csv => "csv",
+			ImportFormat::LastPass => "lastpass",
+			ImportFormat::Chrome => "chrome",
+			ImportFormat::Firefox => "firefox",
+			ImportFormat::Aegis => "aegis",
+			ImportFormat::AndOtp => "andotp",
+			ImportFormat::Bitwarden => "bitwarden",
+			ImportFormat::Kdbx => "kdbx",
+			ImportFormat::Plugin(id) => PLUGIN_IMPORTERS[*id].1,
+		}
+	}
+
+	/// Whether this format requires a mapping file
+	pub fn needs_mapping(&self) -> bool {
+		*self == ImportFormat::JsonMapping
+	}
+
+	/// Whether this format may be encrypted with a password
+	pub fn needs_password(&self) -> bool {
+		matches!(self, ImportFormat::Aegis | ImportFormat::Kdbx)
+	}
+
+	/// Whether imported entries are OTP seeds to attach to existing entries
+	pub fn attaches_otp(&self) -> bool {
+		matches!(self, ImportFormat::Aegis | ImportFormat::AndOtp)
+	}
+}
+
+/// Import entries from file `path`
+pub fn import(
+	format: ImportFormat,
+	path: &Path,
+	mapping: Option<&Path>,
+	password: Option<&str>,
+) -> Result<Vec<Entry>, String> {
+	if let ImportFormat::Plugin(id) = format {
+		let (plugin, format) = PLUGIN_IMPORTERS[id];
+		return PLUGINS[plugin].import(format, path);
+	}
+
+	let bytes =
+		std::fs::read(path).map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	match format {
+		ImportFormat::ProtonPass => proton::import(&bytes),
+		ImportFormat::JsonMapping => {
+			let mapping = mapping.ok_or("A mapping file is required".to_string())?;
+			let mapping = json::JsonMapping::load(mapping)?;
+			json::import(&bytes, &mapping)
+		}
+		ImportFormat::Csv => {
+			let text = std::str::from_utf8(&bytes)
+				.map_err(|err| format!("Invalid UTF-8 in '{}': {err}", path.display()))?;
+			let rows = csv::parse(text)
+				.map_err(|err| format!("Failed to parse '{}': {err}", path.display()))?;
+			let mapping = csv::CsvMapping::guess(rows.first().map_or(&[], |header| header));
+			csv::import(&rows, &mapping)
+		}
+		ImportFormat::LastPass => {
+			let text = std::str::from_utf8(&bytes)
+				.map_err(|err| format!("Invalid UTF-8 in '{}': {err}", path.display()))?;
+			lastpass::import(text)
+				.map_err(|err| format!("Failed to parse '{}': {err}", path.display()))
+		}
+		ImportFormat::Chrome | ImportFormat::Firefox => {
+			let text = std::str::from_utf8(&bytes)
+				.map_err(|err| format!("Invalid UTF-8 in '{}': {err}", path.display()))?;
+			let browser = match format {
+				ImportFormat::Chrome => browser::Browser::Chrome,
+				_ => browser::Browser::Firefox,
+			};
+			browser::import(text, browser)
+				.map_err(|err| format!("Failed to parse '{}': {err}", path.display()))
+		}
+		ImportFormat::Aegis => otp::import_aegis(&bytes, password),
+		ImportFormat::AndOtp => otp::import_andotp(&bytes),
+		ImportFormat::Bitwarden => bitwarden::import(&bytes),
+		ImportFormat::Kdbx => kdbx::import(&bytes, password),
+		ImportFormat::Plugin(_) => unreachable!(),
+	}
+}
+
+/// Build a field for an imported value
+pub fn imported_field(name: &str, value: FieldValue, hidden: bool) -> Field {
+	Field {
+		name: name.into(),
+		value,
+		hidden,
+		..Default::default()
+	}
+}
+
+/// Build an entry for imported fields and tags
+pub fn imported_entry(
+	name: String,
+	fields: Vec<Field>,
+	tags: Vec<String>,
+	created_at: Option<DateTime<Utc>>,
+	modified_at: Option<DateTime<Utc>>,
+) -> Entry {
+	let now = Utc::now();
+	Entry {
+		name,
+		fields,
+		tags: tags
+			.into_iter()
+			.filter(|tag| !tag.trim().is_empty())
+			.map(|name| EntryTag {
+				name,
+				icon: None,
+				color: None,
+			})
+			.collect(),
+		icon: None,
+		favorite: false,
+		created_at: created_at.unwrap_or(now),
+		modified_at: modified_at.unwrap_or(now),
+		accessed_at: now,
+		history: vec![],
+	}
+}
@@ -0,0 +1,383 @@
+use aes_gcm::aead::Aead;
+use aes_gcm::aead::Payload;
+use aes_gcm::KeyInit;
+use base64::Engine;
+use serde::Deserialize;
+
+use crate::data::entry::Entry;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+/// Percent-encode `value` for an `otpauth://` URI
+fn encode(value: &str) -> String {
+	value
+		.bytes()
+		.map(|c| match c {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+				(c as char).to_string()
+			}
+			_ => format!("%{c:02X}"),
+		})
+		.collect()
+}
+
+/// OTP account read from a backup
+struct OtpAccount {
+	kind: String,
+	issuer: String,
+	account: String,
+	secret: String,
+	algorithm: String,
+	digits: u32,
+	period: u32,
+	note: String,
+	tags: Vec<String>,
+}
+
+impl OtpAccount {
+	/// Field holding the OTP, `None` for unsupported kinds
+	fn field_value(&self) -> Option<FieldValue> {
+		match self.kind.to_lowercase().as_str() {
+			"totp" => {
+				let label = if self.issuer.is_empty() {
+					encode(&self.account)
+				} else {
+					format!("{}:{}", encode(&self.issuer), encode(&self.account))
+				};
+				Some(FieldValue::TOTPRFC6238(format!(
+					"otpauth://totp/{label}?secret={}&issuer={}&algorithm={}&digits={}&period={}",
+					self.secret,
+					encode(&self.issuer),
+					self.algorithm.to_uppercase(),
+					self.digits,
+					self.period
+				)))
+			}
+			"steam" => Some(FieldValue::TOTPSteam(self.secret.clone())),
+			_ => None,
+		}
+	}
+
+	fn into_entry(self) -> Option<Entry> {
+		let value = self.field_value()?;
+		let mut fields = vec![];
+		if !self.account.is_empty() {
+			fields.push(imported_field(
+				"Username",
+				FieldValue::Text(self.account.clone()),
+				false,
+			));
+		}
+		fields.push(imported_field("TOTP", value, true));
+		if !self.note.is_empty() {
+			fields.push(imported_field("Note", FieldValue::Note(self.note), false));
+		}
+		let name = if self.issuer.is_empty() {
+			self.account
+		} else {
+			self.issuer
+		};
+		Some(imported_entry(name, fields, self.tags, None, None))
+	}
+}
+
+#[derive(Deserialize)]
+struct AegisInfo {
+	secret: String,
+	#[serde(default = "default_algorithm")]
+	algo: String,
+	#[serde(default = "default_digits")]
+	digits: u32,
+	#[serde(default = "default_period")]
+	period: u32,
+}
+
+fn default_algorithm() -> String {
+	"SHA1".into()
+}
+
+fn default_digits() -> u32 {
+	6
+}
+
+fn default_period() -> u32 {
+	30
+}
+
+#[derive(Deserialize)]
+struct AegisEntry {
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default)]
+	name: String,
+	#[serde(default)]
+	issuer: String,
+	#[serde(default)]
+	note: String,
+	/// Group name, in older backups
+	#[serde(default)]
+	group: Option<String>,
+	/// Group identifiers
+	#[serde(default)]
+	groups: Vec<String>,
+	info: AegisInfo,
+}
+
+#[derive(Deserialize)]
+struct AegisGroup {
+	uuid: String,
+	name: String,
+}
+
+#[derive(Deserialize)]
+struct AegisDb {
+	entries: Vec<AegisEntry>,
+	#[serde(default)]
+	groups: Vec<AegisGroup>,
+}
+
+#[derive(Deserialize)]
+struct AegisKeyParams {
+	nonce: String,
+	tag: String,
+}
+
+#[derive(Deserialize)]
+struct AegisSlot {
+	#[serde(rename = "type")]
+	kind: u32,
+	key: String,
+	key_params: AegisKeyParams,
+	n: Option<u64>,
+	r: Option<u32>,
+	p: Option<u32>,
+	salt: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AegisHeader {
+	slots: Option<Vec<AegisSlot>>,
+	params: Option<AegisKeyParams>,
+}
+
+#[derive(Deserialize)]
+struct AegisBackup {
+	header: AegisHeader,
+	db: serde_json::Value,
+}
+
+fn hex(value: &str) -> Result<Vec<u8>, String> {
+	if !value.len().is_multiple_of(2) {
+		return Err(format!("Invalid hex value '{value}'"));
+	}
+	(0..value.len())
+		.step_by(2)
+		.map(|i| {
+			u8::from_str_radix(&value[i..i + 2], 16)
+				.map_err(|err| format!("Invalid hex value '{value}': {err}"))
+		})
+		.collect()
+}
+
+/// AES-256-GCM decryption with a detached tag, as used by Aegis
+fn decrypt(key: &[u8], params: &AegisKeyParams, ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+	let cipher = aes_gcm::Aes256Gcm::new_from_slice(key)
+		.map_err(|err| format!("Failed to build cipher: {err}"))?;
+	let nonce = hex(&params.nonce)?;
+	if nonce.len() != 12 {
+		return Err("Invalid nonce length".into());
+	}
+	let mut msg = ciphertext.to_vec();
+	msg.extend(hex(&params.tag)?);
+	cipher
+		.decrypt(
+			aes_gcm::Nonce::from_slice(&nonce),
+			Payload {
+				msg: &msg,
+				aad: &[],
+			},
+		)
+		.map_err(|err| format!("Failed to decrypt: {err}"))
+}
+
+/// Find the master key of an encrypted Aegis vault using its password slots
+fn aegis_master_key(slots: &[AegisSlot], password: &str) -> Result<Vec<u8>, String> {
+	for slot in slots.iter().filter(|slot| slot.kind == 1) {
+		let (Some(n), Some(r), Some(p), Some(salt)) = (slot.n, slot.r, slot.p, &slot.salt) else {
+			continue;
+		};
+		let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, 32)
+			.map_err(|err| format!("Invalid scrypt parameters: {err}"))?;
+		let mut derived = [0u8; 32];
+		scrypt::scrypt(password.as_bytes(), &hex(salt)?, &params, &mut derived)
+			.map_err(|err| format!("Failed to derive key: {err}"))?;
+		if let Ok(key) = decrypt(&derived, &slot.key_params, &hex(&slot.key)?) {
+			return Ok(key);
+		}
+	}
+	Err("Invalid password for Aegis backup".into())
+}
+
+/// Import an Aegis backup, plain or encrypted with `password`
+pub fn import_aegis(bytes: &[u8], password: Option<&str>) -> Result<Vec<Entry>, String> {
+	let backup: AegisBackup = serde_json::from_slice(bytes)
+		.map_err(|err| format!("Failed to parse Aegis backup: {err}"))?;
+
+	let db: AegisDb = match (&backup.header.slots, &backup.header.params, &backup.db) {
+		(Some(slots), Some(params), serde_json::Value::String(db)) => {
+			let password =
+				password.ok_or("This Aegis backup is encrypted, a password is required")?;
+			let key = aegis_master_key(slots, password)?;
+			let ciphertext = base64::engine::general_purpose::STANDARD
+				.decode(db)
+				.map_err(|err| format!("Invalid Aegis vault: {err}"))?;
+			let plaintext = decrypt(&key, params, &ciphertext)?;
+			serde_json::from_slice(&plaintext)
+				.map_err(|err| format!("Failed to parse Aegis vault: {err}"))?
+		}
+		(_, _, db) => serde_json::from_value(db.clone())
+			.map_err(|err| format!("Failed to parse Aegis vault: {err}"))?,
+	};
+
+	Ok(db
+		.entries
+		.into_iter()
+		.filter_map(|entry| {
+			let mut tags: Vec<String> = entry.group.into_iter().collect();
+			tags.extend(entry.groups.iter().filter_map(|uuid| {
+				db.groups
+					.iter()
+					.find(|group| group.uuid == *uuid)
+					.map(|group| group.name.clone())
+			}));
+			OtpAccount {
+				kind: entry.kind,
+				issuer: entry.issuer,
+				account: entry.name,
+				secret: entry.info.secret,
+				algorithm: entry.info.algo,
+				digits: entry.info.digits,
+				period: entry.info.period,
+				note: entry.note,
+				tags,
+			}
+			.into_entry()
+		})
+		.collect())
+}
+
+#[derive(Deserialize)]
+struct AndOtpEntry {
+	secret: String,
+	#[serde(default)]
+	issuer: String,
+	#[serde(default)]
+	label: String,
+	#[serde(default = "default_digits")]
+	digits: u32,
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default = "default_algorithm")]
+	algorithm: String,
+	#[serde(default = "default_period")]
+	period: u32,
+	#[serde(default)]
+	tags: Vec<String>,
+}
+
+/// Import a plain andOTP backup
+pub fn import_andotp(bytes: &[u8]) -> Result<Vec<Entry>, String> {
+	let entries: Vec<AndOtpEntry> = serde_json::from_slice(bytes)
+		.map_err(|err| format!("Failed to parse andOTP backup: {err}"))?;
+
+	Ok(entries
+		.into_iter()
+		.filter_map(|entry| {
+			// Labels are either `account` or `issuer:account`
+			let (issuer, account) = match entry.label.split_once(':') {
+				Some((issuer, account)) if entry.issuer.is_empty() || issuer == entry.issuer => {
+					(issuer.to_string(), account.trim().to_string())
+				}
+				_ => (entry.issuer, entry.label),
+			};
+			OtpAccount {
+				kind: entry.kind,
+				issuer,
+				account,
+				secret: entry.secret,
+				algorithm: entry.algorithm,
+				digits: entry.digits,
+				period: entry.period,
+				note: String::default(),
+				tags: entry.tags,
+			}
+			.into_entry()
+		})
+		.collect())
+}
+
+/// Add the OTP fields of `imported` entries to matching existing `entries`
+///
+/// An entry matches when its name contains the issuer and, if the account is known, one of its
+/// fields holds the account name. Entries without a single match are added as new entries.
+pub fn attach(entries: &mut Vec<Entry>, imported: Vec<Entry>) {
+	for new in imported {
+		let issuer = new.name.to_lowercase();
+		let account = new
+			.fields
+			.iter()
+			.find(|field| field.name == "Username")
+			.and_then(|field| field.value.text())
+			.map(str::to_string);
+		let otp = new
+			.fields
+			.iter()
+			.filter(|field| {
+				matches!(
+					field.value,
+					FieldValue::TOTPRFC6238(_) | FieldValue::TOTPSteam(_)
+				)
+			})
+			.cloned()
+			.collect::<Vec<_>>();
+
+		let candidates = entries
+			.iter()
+			.enumerate()
+			.filter(|(_, entry)| !issuer.is_empty() && entry.name.to_lowercase().contains(&issuer))
+			.map(|(id, _)| id)
+			.collect::<Vec<_>>();
+		let by_account = candidates
+			.iter()
+			.copied()
+			.filter(|id| {
+				account.as_ref().is_some_and(|account| {
+					entries[*id]
+						.fields
+						.iter()
+						.any(|field| field.value.text() == Some(account.as_str()))
+				})
+			})
+			.collect::<Vec<_>>();
+		let target = match (by_account.as_slice(), candidates.as_slice()) {
+			([id], _) => Some(*id),
+			([], [id]) => Some(*id),
+			_ => None,
+		};
+
+		match target {
+			Some(id) => {
+				let entry = &mut entries[id];
+				let previous = entry.revision();
+				for field in otp {
+					if !entry.fields.iter().any(|other| other.value == field.value) {
+						entry.fields.push(field);
+					}
+				}
+				entry.commit(previous);
+			}
+			None => entries.push(new),
+		}
+	}
+}
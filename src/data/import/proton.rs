@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+#[derive(Deserialize)]
+struct ProtonExport {
+	#[serde(default)]
+	encrypted: bool,
+	vaults: BTreeMap<String, ProtonVault>,
+}
+
+#[derive(Deserialize)]
+struct ProtonVault {
+	name: String,
+	items: Vec<ProtonItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtonItem {
+	data: ProtonItemData,
+	create_time: Option<i64>,
+	modify_time: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtonItemData {
+	metadata: ProtonMetadata,
+	#[serde(default)]
+	extra_fields: Vec<ProtonExtraField>,
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default)]
+	content: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct ProtonMetadata {
+	name: String,
+	#[serde(default)]
+	note: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtonExtraField {
+	field_name: String,
+	#[serde(rename = "type")]
+	kind: String,
+	data: ProtonExtraFieldData,
+}
+
+#[derive(Deserialize)]
+struct ProtonExtraFieldData {
+	#[serde(default)]
+	content: String,
+}
+
+/// Get non-empty string `key` from the item content
+fn content_str<'a>(content: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+	content
+		.get(key)
+		.and_then(|value| value.as_str())
+		.filter(|value| !value.is_empty())
+}
+
+fn item_fields(data: &ProtonItemData) -> Vec<Field> {
+	let content = &data.content;
+	let mut fields = vec![];
+	match data.kind.as_str() {
+		"login" => {
+			if let Some(email) = content_str(content, "itemEmail") {
+				fields.push(imported_field(
+					"E-Mail",
+					FieldValue::Email(email.into()),
+					false,
+				));
+			}
+			if let Some(username) =
+				content_str(content, "itemUsername").or(content_str(content, "username"))
+			{
+				fields.push(imported_field(
+					"Username",
+					FieldValue::Text(username.into()),
+					false,
+				));
+			}
+			if let Some(password) = content_str(content, "password") {
+				fields.push(imported_field(
+					"Password",
+					FieldValue::Text(password.into()),
+					true,
+				));
+			}
+			if let Some(urls) = content.get("urls").and_then(|urls| urls.as_array()) {
+				for url in urls.iter().filter_map(|url| url.as_str()) {
+					fields.push(imported_field("URL", FieldValue::Url(url.into()), false));
+				}
+			}
+			if let Some(totp) = content_str(content, "totpUri") {
+				fields.push(imported_field(
+					"TOTP",
+					FieldValue::TOTPRFC6238(totp.into()),
+					true,
+				));
+			}
+		}
+		"creditCard" => {
+			for (key, name, hidden) in [
+				("cardholderName", "Cardholder", false),
+				("number", "Number", true),
+				("expirationDate", "Expiration", false),
+				("verificationNumber", "CVV", true),
+				("pin", "PIN", true),
+			] {
+				if let Some(value) = content_str(content, key) {
+					fields.push(imported_field(name, FieldValue::Text(value.into()), hidden));
+				}
+			}
+		}
+		"alias" => {}
+		_ => {
+			// Identity and unknown items: keep every string value
+			if let Some(object) = content.as_object() {
+				for (key, value) in object {
+					if let Some(value) = value.as_str().filter(|value| !value.is_empty()) {
+						fields.push(imported_field(key, FieldValue::Text(value.into()), false));
+					}
+				}
+			}
+		}
+	}
+	if !data.metadata.note.is_empty() {
+		fields.push(imported_field(
+			"Note",
+			FieldValue::Note(data.metadata.note.clone()),
+			false,
+		));
+	}
+	for extra in &data.extra_fields {
+		let value = match extra.kind.as_str() {
+			"totp" => FieldValue::TOTPRFC6238(extra.data.content.clone()),
+			_ => FieldValue::Text(extra.data.content.clone()),
+		};
+		fields.push(imported_field(
+			&extra.field_name,
+			value,
+			extra.kind != "text",
+		));
+	}
+	fields
+}
+
+/// Import a Proton Pass JSON export
+pub fn import(bytes: &[u8]) -> Result<Vec<Entry>, String> {
+	let export: ProtonExport = serde_json::from_slice(bytes)
+		.map_err(|err| format!("Failed to parse Proton Pass export: {err}"))?;
+	if export.encrypted {
+		return Err(
+			"Encrypted Proton Pass exports are not supported, export without PGP encryption".into(),
+		);
+	}
+
+	let mut entries = vec![];
+	for vault in export.vaults.values() {
+		for item in &vault.items {
+			entries.push(imported_entry(
+				format!("{}/{}", vault.name, item.data.metadata.name),
+				item_fields(&item.data),
+				vec![item.data.kind.clone()],
+				item.create_time
+					.and_then(|time| DateTime::from_timestamp(time, 0)),
+				item.modify_time
+					.and_then(|time| DateTime::from_timestamp(time, 0)),
+			));
+		}
+	}
+	Ok(entries)
+}
@@ -0,0 +1,124 @@
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::data::database::header_aad;
+use crate::data::database::open;
+use crate::data::database::seal;
+use crate::data::database::Data;
+use crate::data::database::Database;
+use crate::data::database::Version;
+use crate::data::file::write_atomic;
+use crate::data::migration;
+
+pub static JOURNAL_MAGIC: &[u8] = b"\xFF\x00\x91PASSK-JOURNAL\n";
+
+/// Path of the journal file for the database at `path`
+pub fn journal_path(path: &Path) -> PathBuf {
+	let mut name = path.file_name().unwrap_or_default().to_os_string();
+	name.push(".journal");
+	path.with_file_name(name)
+}
+
+/// AAD for the journal, binds it to the database header
+fn journal_aad(db: &Database) -> Result<Vec<u8>, String> {
+	let mut aad = header_aad(db)?;
+	aad.extend_from_slice(JOURNAL_MAGIC);
+	Ok(aad)
+}
+
+/// Write the unsaved state of the database next to it, sealed with the session key
+///
+/// The data is written in the current layout, along with its version since the database may still
+/// be in the layout of a previous one.
+pub fn write_journal(path: &Path, db: &Database, key: &[u8], data: &Data) -> Result<(), String> {
+	let path = journal_path(path);
+	let payload =
+		bincode2::serialize(data).map_err(|err| format!("Failed to serialize data: {err}"))?;
+	let plaintext = bincode2::serialize(&(Version::CURRENT, payload))
+		.map_err(|err| format!("Failed to serialize data: {err}"))?;
+	let blob = seal(&db.cipher, key, &plaintext, &journal_aad(db)?)?;
+
+	write_atomic(&path, |file, tmp| {
+		file.write_all(JOURNAL_MAGIC)
+			.map_err(|err| format!("Failed to write MAGIC to '{}': {err}", tmp.display()))?;
+		file.write_all(&blob)
+			.map_err(|err| format!("Failed to write journal to '{}': {err}", tmp.display()))
+	})
+}
+
+/// Read the journal for the database at `path`, if any
+pub fn read_journal(path: &Path, db: &Database, key: &[u8]) -> Result<Option<Data>, String> {
+	let path = journal_path(path);
+	if !path.exists() {
+		return Ok(None);
+	}
+	let bytes = std::fs::read(&path)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	if !bytes.starts_with(JOURNAL_MAGIC) {
+		return Err(format!(
+			"Failed to verify MAGIC number in '{}'",
+			path.display()
+		));
+	}
+	let plaintext = open(
+		&db.cipher,
+		key,
+		&bytes[JOURNAL_MAGIC.len()..],
+		&journal_aad(db)?,
+	)?;
+	let (version, payload): (Version, Vec<u8>) = bincode2::deserialize(&plaintext)
+		.map_err(|err| format!("Failed to deserialize '{}': {err}", path.display()))?;
+	let data = migration::decode(version, &payload)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	Ok(Some(data))
+}
+
+/// Remove the journal once its content has been saved or discarded
+pub fn remove_journal(path: &Path) -> Result<(), String> {
+	let path = journal_path(path);
+	if !path.exists() {
+		return Ok(());
+	}
+	std::fs::remove_file(&path)
+		.map_err(|err| format!("Failed to remove '{}': {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::os::unix::fs::PermissionsExt;
+
+	use super::*;
+	use crate::data::database::Argon2Params;
+	use crate::data::database::CipherData;
+
+	#[test]
+	fn journal_round_trip() {
+		let dir = std::env::temp_dir().join(format!("passk-journal-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("test.passk");
+		let db = Database::new(CipherData::XChaCha20Poly1305V1 {}, Argon2Params::default());
+		let key = [7u8; 32];
+		let data = Data {
+			iteration: 42,
+			..Default::default()
+		};
+
+		write_journal(&path, &db, &key, &data).unwrap();
+		let mode = std::fs::metadata(journal_path(&path))
+			.unwrap()
+			.permissions()
+			.mode();
+		assert_eq!(mode & 0o777, 0o600);
+
+		let read = read_journal(&path, &db, &key).unwrap().unwrap();
+		assert_eq!(read.iteration, 42);
+		assert_eq!(read.modified_at, data.modified_at);
+		// Sealed with the session key
+		assert!(read_journal(&path, &db, &[8u8; 32]).is_err());
+
+		remove_journal(&path).unwrap();
+		assert!(read_journal(&path, &db, &key).unwrap().is_none());
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
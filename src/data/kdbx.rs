@@ -0,0 +1,522 @@
+//! KeePass KDBX 4 files
+//!
+//! This module handles the container: outer header, key derivation, HMAC block stream,
+//! encryption, compression and inner header. The XML document inside is read by
+//! [`crate::data::import::kdbx`] and written by [`crate::data::export::kdbx`].
+//!
+//! Only password-protected files are supported, KeePass key files are not.
+use std::io::Read;
+use std::io::Write;
+
+use aes::cipher::BlockDecryptMut;
+use aes::cipher::BlockEncrypt;
+use aes::cipher::KeyInit;
+use aes::cipher::KeyIvInit;
+use aes::cipher::StreamCipher;
+use aes::Aes256;
+use argon2::Argon2;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chacha20::ChaCha20;
+use chrono::DateTime;
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use hmac::Hmac;
+use hmac::Mac;
+use sha2::Digest;
+use sha2::Sha256;
+use sha2::Sha512;
+
+use crate::data::secret::wipe;
+use crate::data::secret::SecretBuffer;
+
+const SIGNATURE_1: u32 = 0x9aa2d903;
+const SIGNATURE_2: u32 = 0xb54bfb67;
+/// Written version, 4.0
+const VERSION: u32 = 0x0004_0000;
+
+const CIPHER_AES256: [u8; 16] = [
+	0x31, 0xc1, 0xf2, 0xe6, 0xbf, 0x71, 0x43, 0x50, 0xbe, 0x58, 0x05, 0x21, 0x6a, 0xfc, 0x5a, 0xff,
+];
+const CIPHER_CHACHA20: [u8; 16] = [
+	0xd6, 0x03, 0x8a, 0x2b, 0x8b, 0x6f, 0x4c, 0xb5, 0xa5, 0x24, 0x33, 0x9a, 0x31, 0xdb, 0xb5, 0x9a,
+];
+const KDF_AES: [u8; 16] = [
+	0xc9, 0xd9, 0xf3, 0x9a, 0x62, 0x8a, 0x44, 0x60, 0xbf, 0x74, 0x0d, 0x08, 0xc1, 0x8a, 0x4f, 0xea,
+];
+const KDF_ARGON2D: [u8; 16] = [
+	0xef, 0x63, 0x6d, 0xdf, 0x8c, 0x29, 0x44, 0x4b, 0x91, 0xf7, 0xa9, 0xa4, 0x03, 0xe3, 0x0a, 0x0c,
+];
+const KDF_ARGON2ID: [u8; 16] = [
+	0x9e, 0x29, 0x8b, 0x19, 0x56, 0xdb, 0x47, 0x73, 0xb2, 0x3d, 0xfc, 0x3e, 0xc6, 0xf0, 0xa1, 0xe6,
+];
+
+/// Outer header fields
+const HEADER_END: u8 = 0;
+const HEADER_CIPHER: u8 = 2;
+const HEADER_COMPRESSION: u8 = 3;
+const HEADER_MASTER_SEED: u8 = 4;
+const HEADER_IV: u8 = 7;
+const HEADER_KDF: u8 = 11;
+
+/// Inner header fields
+const INNER_END: u8 = 0;
+const INNER_STREAM_ID: u8 = 1;
+const INNER_STREAM_KEY: u8 = 2;
+const INNER_BINARY: u8 = 3;
+
+/// Inner random stream protecting values in the XML document
+const STREAM_CHACHA20: u32 = 3;
+
+/// Variant dictionary value types
+const VARIANT_END: u8 = 0;
+const VARIANT_U32: u8 = 0x04;
+const VARIANT_U64: u8 = 0x05;
+const VARIANT_BYTES: u8 = 0x42;
+
+/// Size of the blocks of the HMAC block stream
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Key derivation of written files
+const ARGON2_MEMORY: u64 = 64 * 1024 * 1024;
+const ARGON2_ITERATIONS: u64 = 2;
+const ARGON2_PARALLELISM: u32 = 2;
+
+/// Decrypted content of a KDBX file
+pub struct Kdbx {
+	/// XML document
+	pub xml: String,
+	/// Attachments, referenced by index from the document
+	pub binaries: Vec<Vec<u8>>,
+	/// Key of the inner random stream
+	stream_key: SecretBuffer,
+}
+
+impl Default for Kdbx {
+	/// Empty content with a random stream key
+	fn default() -> Self {
+		let mut stream_key = SecretBuffer::new(64);
+		rand::fill(&mut stream_key[..]);
+		Self {
+			xml: String::default(),
+			binaries: vec![],
+			stream_key,
+		}
+	}
+}
+
+impl Drop for Kdbx {
+	fn drop(&mut self) {
+		// SAFETY: only zeros are written, which is valid UTF-8
+		wipe(unsafe { self.xml.as_bytes_mut() });
+	}
+}
+
+impl Kdbx {
+	/// Inner random stream, protected values must be processed in document order
+	pub fn stream(&self) -> InnerStream {
+		let hash = Sha512::digest(&self.stream_key[..]);
+		InnerStream(ChaCha20::new(hash[..32].into(), hash[32..44].into()))
+	}
+}
+
+/// Stream encrypting the values marked as protected in the XML document
+pub struct InnerStream(ChaCha20);
+
+impl InnerStream {
+	/// Decrypt the base64 content of a protected value
+	pub fn unprotect(&mut self, value: &str) -> Result<String, String> {
+		let mut bytes = BASE64_STANDARD
+			.decode(value.trim())
+			.map_err(|err| format!("Invalid protected value: {err}"))?;
+		self.0.apply_keystream(&mut bytes);
+		String::from_utf8(bytes).map_err(|err| {
+			let mut bytes = err.into_bytes();
+			wipe(&mut bytes);
+			"Invalid protected value".to_string()
+		})
+	}
+
+	/// Encrypt `value` to the base64 content of a protected value
+	pub fn protect(&mut self, value: &str) -> String {
+		let mut bytes = value.as_bytes().to_vec();
+		self.0.apply_keystream(&mut bytes);
+		BASE64_STANDARD.encode(bytes)
+	}
+}
+
+/// Seconds between 0001-01-01, the epoch of KDBX 4 times, and the Unix epoch
+const EPOCH_OFFSET: i64 = 62_135_596_800;
+
+/// Time in a KDBX 4 document, base64 of the seconds since 0001-01-01
+pub fn format_time(time: DateTime<Utc>) -> String {
+	BASE64_STANDARD.encode((time.timestamp() + EPOCH_OFFSET).to_le_bytes())
+}
+
+/// Parse a time of a KDBX 4 document, or an ISO 8601 time of older documents
+pub fn parse_time(text: &str) -> Option<DateTime<Utc>> {
+	let text = text.trim();
+	if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+		return Some(time.to_utc());
+	}
+	let seconds = BASE64_STANDARD.decode(text).ok()?.try_into().ok()?;
+	DateTime::from_timestamp(i64::from_le_bytes(seconds) - EPOCH_OFFSET, 0)
+}
+
+/// Little-endian reader over a byte slice
+struct Cursor<'a> {
+	bytes: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+	fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+		if self.bytes.len() < len {
+			return Err("Unexpected end of file".into());
+		}
+		let (taken, rest) = self.bytes.split_at(len);
+		self.bytes = rest;
+		Ok(taken)
+	}
+
+	fn u8(&mut self) -> Result<u8, String> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn u32(&mut self) -> Result<u32, String> {
+		Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+	}
+
+	/// Length-prefixed value
+	fn sized(&mut self) -> Result<&'a [u8], String> {
+		let len = self.u32()?;
+		self.take(len as usize)
+	}
+}
+
+/// Key-value parameters, the KDF parameters of the header
+struct VariantDictionary(Vec<(String, u8, Vec<u8>)>);
+
+impl VariantDictionary {
+	fn parse(bytes: &[u8]) -> Result<Self, String> {
+		let mut cursor = Cursor { bytes };
+		let version = u16::from_le_bytes(cursor.take(2)?.try_into().unwrap());
+		if version >> 8 != 1 {
+			return Err(format!("Unsupported KDF parameters version {version:#06x}"));
+		}
+		let mut items = vec![];
+		loop {
+			let kind = cursor.u8()?;
+			if kind == VARIANT_END {
+				return Ok(Self(items));
+			}
+			let name = String::from_utf8_lossy(cursor.sized()?).into_owned();
+			items.push((name, kind, cursor.sized()?.to_vec()));
+		}
+	}
+
+	fn bytes(&self, name: &str) -> Result<&[u8], String> {
+		self.0
+			.iter()
+			.find(|(other, _, _)| other == name)
+			.map(|(_, _, value)| value.as_slice())
+			.ok_or_else(|| format!("Missing KDF parameter '{name}'"))
+	}
+
+	/// Integer parameter, stored as a `u32` or `u64`
+	fn integer(&self, name: &str) -> Result<u64, String> {
+		let value = self.bytes(name)?;
+		match value.len() {
+			4 => Ok(u32::from_le_bytes(value.try_into().unwrap()).into()),
+			8 => Ok(u64::from_le_bytes(value.try_into().unwrap())),
+			_ => Err(format!("Invalid KDF parameter '{name}'")),
+		}
+	}
+
+	fn push(&mut self, name: &str, kind: u8, value: &[u8]) {
+		self.0.push((name.into(), kind, value.to_vec()));
+	}
+
+	fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = 0x0100u16.to_le_bytes().to_vec();
+		for (name, kind, value) in &self.0 {
+			bytes.push(*kind);
+			bytes.extend((name.len() as u32).to_le_bytes());
+			bytes.extend(name.as_bytes());
+			bytes.extend((value.len() as u32).to_le_bytes());
+			bytes.extend(value);
+		}
+		bytes.push(VARIANT_END);
+		bytes
+	}
+}
+
+/// Derive the transformed key from the password
+fn transform_key(kdf: &VariantDictionary, password: &str) -> Result<SecretBuffer, String> {
+	let composite = SecretBuffer::from(&Sha256::digest(Sha256::digest(password.as_bytes()))[..]);
+	let mut key = SecretBuffer::new(32);
+	let uuid = kdf.bytes("$UUID")?;
+	if uuid == KDF_ARGON2D || uuid == KDF_ARGON2ID {
+		let algorithm = match uuid == KDF_ARGON2D {
+			true => argon2::Algorithm::Argon2d,
+			false => argon2::Algorithm::Argon2id,
+		};
+		let version = match kdf.integer("V")? {
+			0x10 => argon2::Version::V0x10,
+			_ => argon2::Version::V0x13,
+		};
+		let params = argon2::Params::new(
+			(kdf.integer("M")? / 1024) as u32,
+			kdf.integer("I")? as u32,
+			kdf.integer("P")? as u32,
+			Some(32),
+		)
+		.map_err(|err| format!("Invalid argon2 parameters: {err}"))?;
+		Argon2::new(algorithm, version, params)
+			.hash_password_into(&composite, kdf.bytes("S")?, &mut key)
+			.map_err(|err| format!("Failed to hash password: {err}"))?;
+	} else if uuid == KDF_AES {
+		let cipher = Aes256::new_from_slice(kdf.bytes("S")?)
+			.map_err(|_| "Invalid AES-KDF seed".to_string())?;
+		let mut blocks = SecretBuffer::from(&composite[..]);
+		for _ in 0..kdf.integer("R")? {
+			for block in blocks.chunks_exact_mut(16) {
+				cipher.encrypt_block(block.into());
+			}
+		}
+		key.copy_from_slice(&Sha256::digest(&blocks[..]));
+	} else {
+		return Err("Unsupported key derivation function".into());
+	}
+	Ok(key)
+}
+
+/// Keys derived from the master seed and the transformed key
+struct Keys {
+	cipher: SecretBuffer,
+	hmac: SecretBuffer,
+}
+
+impl Keys {
+	fn new(master_seed: &[u8], transformed: &[u8]) -> Self {
+		let mut hash = Sha256::new();
+		hash.update(master_seed);
+		hash.update(transformed);
+		let cipher = SecretBuffer::from(&hash.finalize()[..]);
+		let mut hash = Sha512::new();
+		hash.update(master_seed);
+		hash.update(transformed);
+		hash.update([1]);
+		let hmac = SecretBuffer::from(&hash.finalize()[..]);
+		Self { cipher, hmac }
+	}
+
+	/// HMAC of the block `index` of the block stream, `u64::MAX` for the header
+	fn block_hmac(&self, index: u64) -> Hmac<Sha256> {
+		let mut hash = Sha512::new();
+		hash.update(index.to_le_bytes());
+		hash.update(&self.hmac[..]);
+		let key = SecretBuffer::from(&hash.finalize()[..]);
+		<Hmac<Sha256> as Mac>::new_from_slice(&key).unwrap()
+	}
+}
+
+/// Decrypt the KDBX file `bytes` with `password`
+pub fn read(bytes: &[u8], password: &str) -> Result<Kdbx, String> {
+	let mut cursor = Cursor { bytes };
+	if cursor.u32()? != SIGNATURE_1 || cursor.u32()? != SIGNATURE_2 {
+		return Err("Not a KeePass database".into());
+	}
+	let version = cursor.u32()?;
+	if version >> 16 != 4 {
+		return Err(format!(
+			"KDBX {}.{} files are not supported, save the database as KDBX 4 first",
+			version >> 16,
+			version & 0xffff
+		));
+	}
+
+	let mut cipher = None;
+	let mut compressed = false;
+	let mut master_seed = None;
+	let mut iv = None;
+	let mut kdf = None;
+	loop {
+		let id = cursor.u8()?;
+		let value = cursor.sized()?;
+		match id {
+			HEADER_END => break,
+			HEADER_CIPHER => cipher = Some(value),
+			HEADER_COMPRESSION => compressed = value.first().is_some_and(|flags| *flags != 0),
+			HEADER_MASTER_SEED => master_seed = Some(value),
+			HEADER_IV => iv = Some(value),
+			HEADER_KDF => kdf = Some(VariantDictionary::parse(value)?),
+			_ => {}
+		}
+	}
+	let header = &bytes[..bytes.len() - cursor.bytes.len()];
+	let (Some(cipher), Some(master_seed), Some(iv), Some(kdf)) = (cipher, master_seed, iv, kdf)
+	else {
+		return Err("Incomplete database header".into());
+	};
+
+	if cursor.take(32)? != Sha256::digest(header).as_slice() {
+		return Err("Corrupted database header".into());
+	}
+	let transformed = transform_key(&kdf, password)?;
+	let keys = Keys::new(master_seed, &transformed);
+	keys.block_hmac(u64::MAX)
+		.chain_update(header)
+		.verify_slice(cursor.take(32)?)
+		.map_err(|_| "Invalid password".to_string())?;
+
+	// HMAC block stream
+	let mut encrypted = vec![];
+	for index in 0u64.. {
+		let hmac = cursor.take(32)?;
+		let len = cursor.u32()?;
+		let block = cursor.take(len as usize)?;
+		keys.block_hmac(index)
+			.chain_update(index.to_le_bytes())
+			.chain_update(len.to_le_bytes())
+			.chain_update(block)
+			.verify_slice(hmac)
+			.map_err(|_| format!("Corrupted block {index}"))?;
+		if block.is_empty() {
+			break;
+		}
+		encrypted.extend_from_slice(block);
+	}
+
+	let mut payload = if cipher == CIPHER_AES256 {
+		cbc::Decryptor::<Aes256>::new_from_slices(&keys.cipher, iv)
+			.map_err(|_| "Invalid encryption IV".to_string())?
+			.decrypt_padded_vec_mut::<aes::cipher::block_padding::Pkcs7>(&encrypted)
+			.map_err(|_| "Failed to decrypt database".to_string())?
+	} else if cipher == CIPHER_CHACHA20 {
+		let mut stream = ChaCha20::new_from_slices(&keys.cipher, iv)
+			.map_err(|_| "Invalid encryption IV".to_string())?;
+		stream.apply_keystream(&mut encrypted);
+		encrypted
+	} else {
+		return Err("Unsupported cipher, only AES-256 and ChaCha20 are supported".into());
+	};
+	if compressed {
+		let mut decompressed = vec![];
+		let result = GzDecoder::new(payload.as_slice()).read_to_end(&mut decompressed);
+		wipe(&mut payload);
+		result.map_err(|err| format!("Failed to decompress database: {err}"))?;
+		payload = decompressed;
+	}
+
+	let mut kdbx = Kdbx::default();
+	let mut cursor = Cursor { bytes: &payload };
+	loop {
+		let id = cursor.u8()?;
+		let value = cursor.sized()?;
+		match id {
+			INNER_END => break,
+			INNER_STREAM_ID if value != STREAM_CHACHA20.to_le_bytes() => {
+				return Err("Unsupported inner random stream".into())
+			}
+			INNER_STREAM_KEY => kdbx.stream_key = SecretBuffer::from(value),
+			// The first byte holds flags
+			INNER_BINARY => kdbx
+				.binaries
+				.push(value.get(1..).unwrap_or_default().to_vec()),
+			_ => {}
+		}
+	}
+	kdbx.xml = String::from_utf8(cursor.bytes.to_vec())
+		.map_err(|_| "The database document is not valid UTF-8".to_string())?;
+	wipe(&mut payload);
+	Ok(kdbx)
+}
+
+/// Encrypt `kdbx` with `password` to a KDBX 4 file
+///
+/// Files are encrypted with ChaCha20, with an argon2id key.
+pub fn write(kdbx: &Kdbx, password: &str) -> Result<Vec<u8>, String> {
+	let mut master_seed = [0u8; 32];
+	rand::fill(&mut master_seed);
+	let mut iv = [0u8; 12];
+	rand::fill(&mut iv);
+	let mut salt = [0u8; 32];
+	rand::fill(&mut salt);
+	let mut kdf = VariantDictionary(vec![]);
+	kdf.push("$UUID", VARIANT_BYTES, &KDF_ARGON2ID);
+	kdf.push("S", VARIANT_BYTES, &salt);
+	kdf.push("P", VARIANT_U32, &ARGON2_PARALLELISM.to_le_bytes());
+	kdf.push("M", VARIANT_U64, &ARGON2_MEMORY.to_le_bytes());
+	kdf.push("I", VARIANT_U64, &ARGON2_ITERATIONS.to_le_bytes());
+	kdf.push("V", VARIANT_U32, &0x13u32.to_le_bytes());
+
+	let mut header = vec![];
+	header.extend(SIGNATURE_1.to_le_bytes());
+	header.extend(SIGNATURE_2.to_le_bytes());
+	header.extend(VERSION.to_le_bytes());
+	let fields: [(u8, &[u8]); 6] = [
+		(HEADER_CIPHER, &CIPHER_CHACHA20),
+		(HEADER_COMPRESSION, &1u32.to_le_bytes()),
+		(HEADER_MASTER_SEED, &master_seed),
+		(HEADER_IV, &iv),
+		(HEADER_KDF, &kdf.to_bytes()),
+		(HEADER_END, b"\r\n\r\n"),
+	];
+	for (id, value) in fields {
+		header.push(id);
+		header.extend((value.len() as u32).to_le_bytes());
+		header.extend(value);
+	}
+
+	let transformed = transform_key(&kdf, password)?;
+	let keys = Keys::new(&master_seed, &transformed);
+
+	// Inner header and document
+	let mut inner = vec![];
+	let mut push = |id: u8, value: &[&[u8]]| {
+		inner.push(id);
+		let len = value.iter().map(|part| part.len()).sum::<usize>();
+		inner.extend((len as u32).to_le_bytes());
+		value.iter().for_each(|part| inner.extend(*part));
+	};
+	push(INNER_STREAM_ID, &[&STREAM_CHACHA20.to_le_bytes()]);
+	push(INNER_STREAM_KEY, &[&kdbx.stream_key]);
+	for binary in &kdbx.binaries {
+		push(INNER_BINARY, &[&[0], binary]);
+	}
+	push(INNER_END, &[]);
+	inner.extend(kdbx.xml.as_bytes());
+	let mut encoder = GzEncoder::new(vec![], Compression::default());
+	let compressed = encoder.write_all(&inner).and_then(|_| encoder.finish());
+	wipe(&mut inner);
+	let mut encrypted = compressed.map_err(|err| format!("Failed to compress database: {err}"))?;
+	ChaCha20::new_from_slices(&keys.cipher, &iv)
+		.unwrap()
+		.apply_keystream(&mut encrypted);
+
+	let mut bytes = header.clone();
+	bytes.extend(Sha256::digest(&header));
+	bytes.extend(
+		keys.block_hmac(u64::MAX)
+			.chain_update(&header)
+			.finalize()
+			.into_bytes(),
+	);
+	let blocks = encrypted.chunks(BLOCK_SIZE).chain([&[][..]]);
+	for (index, block) in (0u64..).zip(blocks) {
+		let len = (block.len() as u32).to_le_bytes();
+		let hmac = keys
+			.block_hmac(index)
+			.chain_update(index.to_le_bytes())
+			.chain_update(len)
+			.chain_update(block)
+			.finalize()
+			.into_bytes();
+		bytes.extend(hmac);
+		bytes.extend(len);
+		bytes.extend(block);
+	}
+	Ok(bytes)
+}
@@ -0,0 +1,147 @@
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::data::secret::wipe;
+use crate::data::secret::SecretBuffer;
+
+/// Secret Service client used to access the keyring
+const SECRET_TOOL: &str = "secret-tool";
+
+/// Key cached in the keyring
+pub struct CachedKey {
+	pub key: SecretBuffer,
+	/// Key file used to unlock the database
+	pub key_file: Option<PathBuf>,
+	pub expires_at: DateTime<Utc>,
+}
+
+/// Serialized form of [`CachedKey`]
+#[derive(Serialize, Deserialize)]
+struct StoredKey {
+	key: String,
+	key_file: Option<PathBuf>,
+	expires_at: DateTime<Utc>,
+}
+
+/// Keyring attributes identifying the database at `path`
+fn attributes(path: &Path) -> [String; 4] {
+	let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+	[
+		"application".into(),
+		"passk".into(),
+		"database".into(),
+		path.display().to_string(),
+	]
+}
+
+fn secret_tool(command: &str, path: &Path) -> Command {
+	let mut cmd = Command::new(SECRET_TOOL);
+	cmd.arg(command).stderr(Stdio::piped());
+	if command == "store" {
+		cmd.arg(format!("--label=PassK key for {}", path.display()));
+	}
+	cmd.args(attributes(path));
+	cmd
+}
+
+/// Cache `key` in the keyring for `duration`
+pub fn store(
+	path: &Path,
+	key: &[u8],
+	key_file: Option<&Path>,
+	duration: TimeDelta,
+) -> Result<(), String> {
+	let mut stored = serde_json::to_vec(&StoredKey {
+		key: BASE64_STANDARD.encode(key),
+		key_file: key_file.map(Path::to_path_buf),
+		expires_at: Utc::now() + duration,
+	})
+	.map_err(|err| format!("Failed to serialize key: {err}"))?;
+
+	let child = secret_tool("store", path)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::null())
+		.spawn()
+		.map_err(|err| format!("Failed to run {SECRET_TOOL}: {err}"));
+	let result = child.and_then(|mut child| {
+		child
+			.stdin
+			.take()
+			.unwrap()
+			.write_all(&stored)
+			.map_err(|err| format!("Failed to write to {SECRET_TOOL}: {err}"))?;
+		let output = child
+			.wait_with_output()
+			.map_err(|err| format!("Failed to run {SECRET_TOOL}: {err}"))?;
+		if !output.status.success() {
+			return Err(format!(
+				"Failed to store key in keyring: {}",
+				String::from_utf8_lossy(&output.stderr).trim()
+			));
+		}
+		Ok(())
+	});
+	wipe(&mut stored);
+	result
+}
+
+/// Get the key cached for the database at `path`
+///
+/// Expired keys are removed from the keyring.
+pub fn lookup(path: &Path) -> Result<Option<CachedKey>, String> {
+	let output = secret_tool("lookup", path)
+		.stdin(Stdio::null())
+		.output()
+		.map_err(|err| format!("Failed to run {SECRET_TOOL}: {err}"))?;
+	// secret-tool fails when no item matches
+	if !output.status.success() || output.stdout.is_empty() {
+		return Ok(None);
+	}
+
+	let mut stdout = output.stdout;
+	let stored = serde_json::from_slice::<StoredKey>(&stdout);
+	wipe(&mut stdout);
+	let stored = stored.map_err(|err| format!("Invalid key in keyring: {err}"))?;
+	if stored.expires_at <= Utc::now() {
+		clear(path)?;
+		return Ok(None);
+	}
+
+	let mut key = BASE64_STANDARD
+		.decode(&stored.key)
+		.map_err(|err| format!("Invalid key in keyring: {err}"))?;
+	let cached = CachedKey {
+		key: SecretBuffer::from(key.as_slice()),
+		key_file: stored.key_file,
+		expires_at: stored.expires_at,
+	};
+	wipe(&mut key);
+	Ok(Some(cached))
+}
+
+/// Remove the key cached for the database at `path`
+pub fn clear(path: &Path) -> Result<(), String> {
+	let output = secret_tool("clear", path)
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.output()
+		.map_err(|err| format!("Failed to run {SECRET_TOOL}: {err}"))?;
+	if !output.status.success() {
+		return Err(format!(
+			"Failed to clear keyring: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		));
+	}
+	Ok(())
+}
@@ -0,0 +1,183 @@
+//! Data saved by previous format versions
+//!
+//! The [`Data`] is serialized with bincode, which writes the fields of structs by position, without
+//! their names. A field added to the data changes its layout, so each change bumps the [`Version`]
+//! of the database header and the previous layouts are kept here to read older databases. They are
+//! converted to the current structs on load, the fields they lack taking their default value, and
+//! written in the current layout on the next save.
+//!
+//! Versions from [`Version::V2`] to [`Version::V11`] were only written by development builds, before
+//! the first release, so their layouts are not kept.
+use chrono::DateTime;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::data::database::Data;
+use crate::data::database::Version;
+use crate::data::entry::Entry;
+use crate::data::entry::EntryTag;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+
+/// [`Field`] in [`Version::V1`]
+#[derive(Deserialize)]
+struct FieldV1 {
+	name: String,
+	value: FieldValue,
+	hidden: bool,
+	date_added: DateTime<Utc>,
+	date_modified: DateTime<Utc>,
+	date_accessed: DateTime<Utc>,
+}
+
+impl From<FieldV1> for Field {
+	fn from(field: FieldV1) -> Self {
+		Self {
+			name: field.name,
+			value: field.value,
+			hidden: field.hidden,
+			date_added: field.date_added,
+			date_modified: field.date_modified,
+			date_accessed: field.date_accessed,
+			..Default::default()
+		}
+	}
+}
+
+/// [`Entry`] in [`Version::V1`]
+#[derive(Deserialize)]
+struct EntryV1 {
+	name: String,
+	fields: Vec<FieldV1>,
+	tags: Vec<EntryTag>,
+	created_at: DateTime<Utc>,
+	modified_at: DateTime<Utc>,
+	accessed_at: DateTime<Utc>,
+}
+
+impl From<EntryV1> for Entry {
+	fn from(entry: EntryV1) -> Self {
+		Self {
+			name: entry.name,
+			fields: entry.fields.into_iter().map(Into::into).collect(),
+			tags: entry.tags,
+			icon: None,
+			favorite: false,
+			created_at: entry.created_at,
+			modified_at: entry.modified_at,
+			accessed_at: entry.accessed_at,
+			history: vec![],
+		}
+	}
+}
+
+/// [`Data`] in [`Version::V1`]
+#[derive(Deserialize)]
+struct DataV1 {
+	iteration: u64,
+	entries: Vec<EntryV1>,
+	created_at: DateTime<Utc>,
+	modified_at: DateTime<Utc>,
+}
+
+impl From<DataV1> for Data {
+	fn from(data: DataV1) -> Self {
+		Self {
+			iteration: data.iteration,
+			entries: data.entries.into_iter().map(Into::into).collect(),
+			created_at: data.created_at,
+			modified_at: data.modified_at,
+			..Default::default()
+		}
+	}
+}
+
+/// Deserialize `plaintext` as the layout `T`, then convert it to the current layout
+fn parse<T: DeserializeOwned + Into<Data>>(plaintext: &[u8]) -> Result<Data, String> {
+	bincode2::deserialize::<T>(plaintext)
+		.map(Into::into)
+		.map_err(|err| format!("Failed to deserialize database: {err}"))
+}
+
+/// Read `plaintext`, data saved in the layout of `version`
+pub fn decode(version: Version, plaintext: &[u8]) -> Result<Data, String> {
+	match version {
+		Version::V1 => parse::<DataV1>(plaintext),
+		Version::V2
+		| Version::V3
+		| Version::V4
+		| Version::V5
+		| Version::V6
+		| Version::V7
+		| Version::V8
+		| Version::V9
+		| Version::V10
+		| Version::V11 => Err(format!(
+			"Data version {version:?} was only written by development builds and can't be read"
+		)),
+		Version::V12 => parse::<Data>(plaintext),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::TimeZone;
+
+	use super::*;
+
+	/// Data in the layout of [`Version::V1`], with a single entry
+	fn v1_blob() -> Vec<u8> {
+		let date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+		let field = (
+			"Password".to_string(),
+			FieldValue::Text("hunter2".into()),
+			true,
+			date,
+			date,
+			date,
+		);
+		let tag = EntryTag {
+			name: "work".into(),
+			icon: None,
+			color: Some(0xff0000),
+		};
+		let entry = ("mail".to_string(), vec![field], vec![tag], date, date, date);
+		bincode2::serialize(&(7u64, vec![entry], date, date)).unwrap()
+	}
+
+	#[test]
+	fn decode_v1() {
+		let data = decode(Version::V1, &v1_blob()).unwrap();
+		assert_eq!(data.iteration, 7);
+		assert_eq!(data.entries.len(), 1);
+		let entry = &data.entries[0];
+		assert_eq!(entry.name, "mail");
+		assert_eq!(entry.tags[0].name, "work");
+		assert!(entry.icon.is_none() && !entry.favorite && entry.history.is_empty());
+		let field = &entry.fields[0];
+		assert_eq!(field.name, "Password");
+		assert_eq!(field.value.text(), Some("hunter2"));
+		assert!(field.hidden);
+		assert!(data.trash.is_empty());
+	}
+
+	#[test]
+	fn decode_current() {
+		let mut data = decode(Version::V1, &v1_blob()).unwrap();
+		data.entries[0].favorite = true;
+		let plaintext = bincode2::serialize(&data).unwrap();
+		let decoded = decode(Version::CURRENT, &plaintext).unwrap();
+		assert_eq!(decoded.iteration, 7);
+		assert!(decoded.entries[0].favorite);
+		assert_eq!(decoded.entries[0].fields[0].value.text(), Some("hunter2"));
+	}
+
+	#[test]
+	fn development_versions_are_rejected() {
+		for version in [Version::V2, Version::V6, Version::V11] {
+			assert!(decode(version, &v1_blob()).is_err());
+		}
+		assert!(decode(Version::V1, &[1, 2, 3]).is_err());
+	}
+}
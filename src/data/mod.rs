@@ -1,4 +1,24 @@
 pub mod field;
 pub mod entry;
+pub mod tag;
+pub mod template;
 pub mod database;
+pub mod migration;
 pub mod file;
+pub mod journal;
+pub mod keyring;
+pub mod agent;
+pub mod secret;
+pub mod stats;
+pub mod health;
+pub mod quick_unlock;
+pub mod yubikey;
+pub mod attachment;
+pub mod otp;
+pub mod qr;
+pub mod audit;
+pub mod import;
+pub mod export;
+pub mod kdbx;
+pub mod ssh;
+pub mod document;
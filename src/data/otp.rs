@@ -0,0 +1,289 @@
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
+use sha1::Sha1;
+use sha2::Sha256;
+use sha2::Sha512;
+
+/// Scheme of Key URIs, as used by authenticator apps
+pub const OTPAUTH_SCHEME: &str = "otpauth://";
+
+/// Default number of digits of a code
+pub const DEFAULT_DIGITS: u32 = 6;
+/// Default validity of a TOTP code, in seconds
+pub const DEFAULT_PERIOD: u64 = 30;
+
+/// Characters of Steam Guard codes
+const STEAM_ALPHABET: &[u8] = b"23456789BCDFGHJKMNPQRTVWXY";
+/// Number of characters of a Steam Guard code
+const STEAM_DIGITS: u32 = 5;
+
+/// Decode RFC 4648 base32, ignoring case, spaces and padding
+pub fn base32_decode(input: &str) -> Result<Vec<u8>, String> {
+	let mut bytes = vec![];
+	let mut buffer = 0u64;
+	let mut bits = 0;
+	for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+		let value = match c.to_ascii_uppercase() {
+			c @ 'A'..='Z' => c as u64 - 'A' as u64,
+			c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+			_ => return Err(format!("Invalid base32 character '{c}'")),
+		};
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	if bytes.is_empty() {
+		return Err("Secret is empty".into());
+	}
+	Ok(bytes)
+}
+
+/// Hash function of the HMAC
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OtpAlgorithm {
+	#[default]
+	Sha1,
+	Sha256,
+	Sha512,
+}
+
+impl OtpAlgorithm {
+	fn hmac(&self, key: &[u8], message: &[u8]) -> Vec<u8> {
+		fn compute<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+			let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+			mac.update(message);
+			mac.finalize().into_bytes().to_vec()
+		}
+		match self {
+			OtpAlgorithm::Sha1 => compute::<Hmac<Sha1>>(key, message),
+			OtpAlgorithm::Sha256 => compute::<Hmac<Sha256>>(key, message),
+			OtpAlgorithm::Sha512 => compute::<Hmac<Sha512>>(key, message),
+		}
+	}
+}
+
+impl TryFrom<&str> for OtpAlgorithm {
+	type Error = String;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value.to_ascii_uppercase().as_str() {
+			"SHA1" => Ok(OtpAlgorithm::Sha1),
+			"SHA256" => Ok(OtpAlgorithm::Sha256),
+			"SHA512" => Ok(OtpAlgorithm::Sha512),
+			_ => Err(format!("Unsupported algorithm '{value}'")),
+		}
+	}
+}
+
+/// RFC 4226 dynamic truncation of the HMAC of `counter`
+fn truncate(secret: &[u8], counter: u64, algorithm: OtpAlgorithm) -> u32 {
+	let digest = algorithm.hmac(secret, &counter.to_be_bytes());
+	let offset = (digest[digest.len() - 1] & 0xf) as usize;
+	u32::from_be_bytes([
+		digest[offset] & 0x7f,
+		digest[offset + 1],
+		digest[offset + 2],
+		digest[offset + 3],
+	])
+}
+
+/// Compute the RFC 4226 HOTP value for `counter`
+pub fn hotp(secret: &[u8], counter: u64, digits: u32, algorithm: OtpAlgorithm) -> String {
+	let code = truncate(secret, counter, algorithm) as u64 % 10u64.pow(digits);
+	format!("{code:0width$}", width = digits as usize)
+}
+
+/// Compute the Steam Guard code for `counter`
+fn steam(secret: &[u8], counter: u64) -> String {
+	let mut value = truncate(secret, counter, OtpAlgorithm::Sha1) as usize;
+	(0..STEAM_DIGITS)
+		.map(|_| {
+			let c = STEAM_ALPHABET[value % STEAM_ALPHABET.len()] as char;
+			value /= STEAM_ALPHABET.len();
+			c
+		})
+		.collect()
+}
+
+/// Compute the HOTP code of a base32 `secret` for `counter`, with the default parameters
+pub fn generate_hotp(secret: &str, counter: u64) -> Result<String, String> {
+	let secret = base32_decode(secret)?;
+	Ok(hotp(
+		&secret,
+		counter,
+		DEFAULT_DIGITS,
+		OtpAlgorithm::default(),
+	))
+}
+
+/// RFC 6238 time-based one-time password generator
+#[derive(Debug, Clone)]
+pub struct Totp {
+	secret: Vec<u8>,
+	pub digits: u32,
+	/// Validity of a code, in seconds
+	pub period: u64,
+	pub algorithm: OtpAlgorithm,
+	pub issuer: Option<String>,
+	pub account: Option<String>,
+	/// Generate Steam Guard codes instead of digits
+	pub steam: bool,
+}
+
+impl Totp {
+	/// Parse the value of a TOTP field, a base32 secret or an `otpauth://totp/` URI
+	pub fn parse(value: &str) -> Result<Self, String> {
+		let value = value.trim();
+		if value.starts_with(OTPAUTH_SCHEME) {
+			return Self::from_uri(value);
+		}
+		Ok(Self {
+			secret: base32_decode(value)?,
+			digits: DEFAULT_DIGITS,
+			period: DEFAULT_PERIOD,
+			algorithm: OtpAlgorithm::default(),
+			issuer: None,
+			account: None,
+			steam: false,
+		})
+	}
+
+	/// Parse the value of a Steam TOTP field
+	///
+	/// The secret is either the base64 `shared_secret` of Steam authenticator files, base32 or an
+	/// `otpauth://totp/` URI.
+	pub fn parse_steam(value: &str) -> Result<Self, String> {
+		let value = value.trim();
+		let mut totp = if value.starts_with(OTPAUTH_SCHEME) {
+			Self::from_uri(value)?
+		} else {
+			let secret = base32_decode(value).or_else(|_| {
+				BASE64_STANDARD
+					.decode(value)
+					.map_err(|err| format!("Invalid Steam secret: {err}"))
+			})?;
+			Self {
+				secret,
+				digits: STEAM_DIGITS,
+				period: DEFAULT_PERIOD,
+				algorithm: OtpAlgorithm::Sha1,
+				issuer: None,
+				account: None,
+				steam: true,
+			}
+		};
+		totp.steam = true;
+		totp.digits = STEAM_DIGITS;
+		Ok(totp)
+	}
+
+	/// Parse a Key URI, e.g. `otpauth://totp/Issuer:account?secret=...&issuer=Issuer`
+	pub fn from_uri(uri: &str) -> Result<Self, String> {
+		let rest = uri
+			.strip_prefix(OTPAUTH_SCHEME)
+			.ok_or_else(|| format!("Expected an {OTPAUTH_SCHEME} URI"))?;
+		let (kind, rest) = rest.split_once('/').unwrap_or((rest, ""));
+		if !kind.eq_ignore_ascii_case("totp") {
+			return Err(format!("Unsupported OTP type '{kind}', expected 'totp'"));
+		}
+		let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+		let label = percent_decode(label)?;
+		let (mut issuer, account) = match label.split_once(':') {
+			Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim()),
+			None => (None, label.trim()),
+		};
+		let mut totp = Self {
+			secret: vec![],
+			digits: DEFAULT_DIGITS,
+			period: DEFAULT_PERIOD,
+			algorithm: OtpAlgorithm::default(),
+			issuer: None,
+			account: (!account.is_empty()).then(|| account.to_string()),
+			steam: false,
+		};
+		for param in query.split('&').filter(|param| !param.is_empty()) {
+			let (key, value) = param.split_once('=').unwrap_or((param, ""));
+			let value = percent_decode(value)?;
+			match key {
+				"secret" => totp.secret = base32_decode(&value)?,
+				"issuer" => issuer = Some(value),
+				"algorithm" => totp.algorithm = OtpAlgorithm::try_from(value.as_str())?,
+				"digits" => {
+					totp.digits = value
+						.parse()
+						.ok()
+						.filter(|digits| (6..=10).contains(digits))
+						.ok_or_else(|| format!("Invalid number of digits '{value}'"))?
+				}
+				"period" => {
+					totp.period = value
+						.parse()
+						.ok()
+						.filter(|period| *period != 0)
+						.ok_or_else(|| format!("Invalid period '{value}'"))?
+				}
+				_ => {}
+			}
+		}
+		if totp.secret.is_empty() {
+			return Err("Missing secret in URI".into());
+		}
+		totp.issuer = issuer.filter(|issuer| !issuer.is_empty());
+		Ok(totp)
+	}
+
+	fn step(&self, time: DateTime<Utc>) -> u64 {
+		time.timestamp().max(0) as u64 / self.period
+	}
+
+	/// Code valid at `time`
+	pub fn code(&self, time: DateTime<Utc>) -> String {
+		if self.steam {
+			return steam(&self.secret, self.step(time));
+		}
+		hotp(&self.secret, self.step(time), self.digits, self.algorithm)
+	}
+
+	/// Seconds until the code valid at `time` expires
+	pub fn remaining(&self, time: DateTime<Utc>) -> u64 {
+		self.period - time.timestamp().max(0) as u64 % self.period
+	}
+}
+
+/// Decode `%XX` escapes and `+` in a URI component
+fn percent_decode(input: &str) -> Result<String, String> {
+	let mut bytes = Vec::with_capacity(input.len());
+	let mut iter = input.bytes();
+	while let Some(byte) = iter.next() {
+		match byte {
+			b'%' => {
+				let hex = [iter.next(), iter.next()];
+				let value = match hex {
+					[Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+						.ok()
+						.and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+					_ => None,
+				};
+				bytes.push(value.ok_or_else(|| format!("Invalid escape in '{input}'"))?);
+			}
+			b'+' => bytes.push(b' '),
+			_ => bytes.push(byte),
+		}
+	}
+	String::from_utf8(bytes).map_err(|err| format!("Invalid UTF-8 in '{input}': {err}"))
+}
+
+/// Split a code in two groups for readability
+pub fn format_code(code: &str) -> String {
+	let (left, right) = code.split_at(code.len() / 2);
+	format!("{left} {right}")
+}
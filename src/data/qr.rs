@@ -0,0 +1,25 @@
+use std::path::Path;
+
+/// Decode the first QR code found in the image at `path`
+pub fn decode(path: &Path) -> Result<String, String> {
+	let image = image::open(path)
+		.map_err(|err| format!("Failed to read image '{}': {err}", path.display()))?
+		.to_luma8();
+	let mut prepared = rqrr::PreparedImage::prepare(image);
+	let grids = prepared.detect_grids();
+	if grids.is_empty() {
+		return Err(format!("No QR code found in '{}'", path.display()));
+	}
+	let mut error = None;
+	for grid in grids {
+		match grid.decode() {
+			Ok((_, content)) => return Ok(content),
+			Err(err) => error = Some(err),
+		}
+	}
+	Err(format!(
+		"Failed to decode QR code in '{}': {}",
+		path.display(),
+		error.unwrap()
+	))
+}
@@ -0,0 +1,97 @@
+use argon2::Argon2;
+
+use crate::data::database::open;
+use crate::data::database::seal;
+use crate::data::database::CipherData;
+use crate::data::secret::wipe;
+use crate::data::secret::SecretBuffer;
+
+/// Number of wrong PINs after which the wrapped key is destroyed
+pub const MAX_PIN_ATTEMPTS: u8 = 3;
+/// Minimum length of a PIN
+pub const MIN_PIN_LENGTH: usize = 4;
+
+/// Argon2id memory cost in KiB, higher than the database defaults since PINs are short
+const PIN_KDF_MEMORY: u32 = 256 * 1024;
+/// Argon2id iterations
+const PIN_KDF_ITERATIONS: u32 = 4;
+/// Additional data binding the wrapped key to its purpose
+const AAD: &[u8] = b"passk-quick-unlock";
+
+/// Database key wrapped under a short PIN, kept for the duration of a session
+///
+/// The wrapped key only lives in memory. It is destroyed after [`MAX_PIN_ATTEMPTS`] wrong PINs,
+/// after which the master password is required again.
+pub struct QuickUnlock {
+	salt: [u8; 16],
+	wrapped: Vec<u8>,
+	attempts: u8,
+}
+
+fn derive_pin_key(pin: &str, salt: &[u8]) -> Result<SecretBuffer, String> {
+	let params = argon2::Params::new(PIN_KDF_MEMORY, PIN_KDF_ITERATIONS, 1, Some(32))
+		.map_err(|err| format!("Failed to build argon2 params: {err}"))?;
+	let argon = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+	let mut key = SecretBuffer::new(32);
+	argon
+		.hash_password_into(pin.as_bytes(), salt, &mut key)
+		.map_err(|err| format!("Failed to hash PIN: {err}"))?;
+	Ok(key)
+}
+
+impl QuickUnlock {
+	/// Wrap `key` under `pin`
+	pub fn new(key: &SecretBuffer, pin: &str) -> Result<Self, String> {
+		if pin.chars().count() < MIN_PIN_LENGTH {
+			return Err(format!(
+				"PIN must be at least {MIN_PIN_LENGTH} characters long"
+			));
+		}
+		let mut salt = [0u8; 16];
+		rand::fill(&mut salt);
+		let pin_key = derive_pin_key(pin, &salt)?;
+		let wrapped = seal(&CipherData::XChaCha20Poly1305V1 {}, &pin_key, key, AAD)?;
+		Ok(Self {
+			salt,
+			wrapped,
+			attempts: MAX_PIN_ATTEMPTS,
+		})
+	}
+
+	/// Unwrap the key with `pin`
+	///
+	/// Every failure uses up an attempt, see [`QuickUnlock::attempts_left`].
+	pub fn unlock(&mut self, pin: &str) -> Result<SecretBuffer, String> {
+		if self.attempts == 0 {
+			return Err("Quick unlock is disabled".into());
+		}
+		let pin_key = derive_pin_key(pin, &self.salt)?;
+		match open(
+			&CipherData::XChaCha20Poly1305V1 {},
+			&pin_key,
+			&self.wrapped,
+			AAD,
+		) {
+			Ok(mut plaintext) => {
+				let key = SecretBuffer::from(plaintext.as_slice());
+				wipe(&mut plaintext);
+				self.attempts = MAX_PIN_ATTEMPTS;
+				Ok(key)
+			}
+			Err(_) => {
+				self.attempts -= 1;
+				if self.attempts == 0 {
+					wipe(&mut self.wrapped);
+					self.wrapped.clear();
+					return Err("Wrong PIN, quick unlock is now disabled".into());
+				}
+				Err(format!("Wrong PIN, {} attempts left", self.attempts))
+			}
+		}
+	}
+
+	/// Remaining PIN attempts, 0 once the key was destroyed
+	pub fn attempts_left(&self) -> u8 {
+		self.attempts
+	}
+}
@@ -0,0 +1,142 @@
+use std::alloc::Layout;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::atomic::compiler_fence;
+use std::sync::atomic::Ordering;
+
+/// Size of a memory page, used to give every secret its own pages
+fn page_size() -> usize {
+	#[cfg(unix)]
+	{
+		let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+		if size > 0 {
+			return size as usize;
+		}
+	}
+	4096
+}
+
+/// Buffer for key material
+///
+/// The buffer lives on its own pages which are locked in memory (where supported) so they are
+/// never swapped to disk, and wiped when dropped. When the pages can't be locked, e.g because
+/// `RLIMIT_MEMLOCK` is too low, the buffer is still usable and only wiped on drop.
+pub struct SecretBuffer {
+	ptr: *mut u8,
+	len: usize,
+	layout: Layout,
+	locked: bool,
+}
+
+// The buffer is uniquely owned
+unsafe impl Send for SecretBuffer {}
+unsafe impl Sync for SecretBuffer {}
+
+impl SecretBuffer {
+	/// Allocate a zeroed buffer of `len` bytes
+	pub fn new(len: usize) -> Self {
+		let page = page_size();
+		let size = len.max(1).div_ceil(page) * page;
+		let layout = Layout::from_size_align(size, page).expect("Invalid secret layout");
+		let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+		if ptr.is_null() {
+			std::alloc::handle_alloc_error(layout);
+		}
+
+		#[cfg(unix)]
+		let locked = unsafe {
+			let locked = libc::mlock(ptr as *const libc::c_void, size) == 0;
+			#[cfg(target_os = "linux")]
+			libc::madvise(ptr as *mut libc::c_void, size, libc::MADV_DONTDUMP);
+			locked
+		};
+		#[cfg(not(unix))]
+		let locked = false;
+
+		Self {
+			ptr,
+			len,
+			layout,
+			locked,
+		}
+	}
+
+	/// Whether the buffer is protected from being swapped to disk
+	pub fn is_locked(&self) -> bool {
+		self.locked
+	}
+}
+
+/// Overwrite `bytes` with zeros, without the write being optimized out
+pub fn wipe(bytes: &mut [u8]) {
+	for byte in bytes.iter_mut() {
+		unsafe { std::ptr::write_volatile(byte, 0) };
+	}
+	compiler_fence(Ordering::SeqCst);
+}
+
+impl From<&[u8]> for SecretBuffer {
+	fn from(value: &[u8]) -> Self {
+		let mut buffer = Self::new(value.len());
+		buffer.copy_from_slice(value);
+		buffer
+	}
+}
+
+impl Deref for SecretBuffer {
+	type Target = [u8];
+
+	fn deref(&self) -> &Self::Target {
+		unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+	}
+}
+
+impl DerefMut for SecretBuffer {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+	}
+}
+
+impl Clone for SecretBuffer {
+	fn clone(&self) -> Self {
+		Self::from(self.deref())
+	}
+}
+
+impl PartialEq for SecretBuffer {
+	fn eq(&self, other: &Self) -> bool {
+		// Constant time for equal lengths
+		self.len == other.len
+			&& self
+				.iter()
+				.zip(other.iter())
+				.fold(0u8, |acc, (a, b)| acc | (a ^ b))
+				== 0
+	}
+}
+
+impl Eq for SecretBuffer {}
+
+impl std::fmt::Debug for SecretBuffer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SecretBuffer")
+			.field("len", &self.len)
+			.field("locked", &self.locked)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Drop for SecretBuffer {
+	fn drop(&mut self) {
+		for i in 0..self.layout.size() {
+			unsafe { std::ptr::write_volatile(self.ptr.add(i), 0) };
+		}
+		compiler_fence(Ordering::SeqCst);
+
+		#[cfg(unix)]
+		if self.locked {
+			unsafe { libc::munlock(self.ptr as *const libc::c_void, self.layout.size()) };
+		}
+		unsafe { std::alloc::dealloc(self.ptr, self.layout) };
+	}
+}
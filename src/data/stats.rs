@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::data::attachment;
+use crate::data::entry::Entry;
+use crate::data::field::FieldValue;
+
+/// Number of passwords listed in [`Stats::oldest_passwords`]
+pub const OLDEST_PASSWORDS: usize = 5;
+
+/// Hidden field that was not modified for a long time
+#[derive(Debug, Clone)]
+pub struct StalePassword {
+	pub entry: String,
+	pub field: String,
+	pub modified_at: DateTime<Utc>,
+}
+
+/// Statistics about the content of a database
+#[derive(Debug, Default, Clone)]
+pub struct Stats {
+	pub entries: usize,
+	pub fields: usize,
+	/// Number of fields of each kind, in kind order, kinds without fields are omitted
+	pub kinds: Vec<(&'static str, usize)>,
+	/// Number of entries for each tag, most used first
+	pub tags: Vec<(String, usize)>,
+	/// Hidden fields that were not modified for the longest time, oldest first
+	pub oldest_passwords: Vec<StalePassword>,
+	/// Total size of the attachments
+	pub attachments_size: u64,
+	/// Size of the database file, if it was saved
+	pub database_size: Option<u64>,
+}
+
+impl Stats {
+	/// Compute the statistics of `entries`, `database_size` is the size of the database file
+	pub fn compute(entries: &[Entry], database_size: Option<u64>) -> Self {
+		let mut kinds: Vec<(&'static str, usize)> = vec![];
+		let mut tags: HashMap<&str, usize> = HashMap::new();
+		let mut passwords = vec![];
+		let mut attachments_size = 0;
+
+		for entry in entries {
+			for field in &entry.fields {
+				let id = field.value.get_id();
+				if kinds.len() <= id {
+					kinds.resize(id + 1, ("", 0));
+				}
+				kinds[id].0 = field.value.kind_name();
+				kinds[id].1 += 1;
+
+				if let FieldValue::Binary { base64, .. } = &field.value {
+					attachments_size += attachment::size(base64);
+				}
+				if field.hidden {
+					passwords.push(StalePassword {
+						entry: entry.name.clone(),
+						field: field.name.clone(),
+						modified_at: field.date_modified,
+					});
+				}
+			}
+			for tag in &entry.tags {
+				*tags.entry(tag.name.as_str()).or_default() += 1;
+			}
+		}
+
+		let mut tags = tags
+			.into_iter()
+			.map(|(name, count)| (name.to_string(), count))
+			.collect::<Vec<_>>();
+		tags.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+		passwords.sort_by_key(|password| password.modified_at);
+		passwords.truncate(OLDEST_PASSWORDS);
+
+		Self {
+			entries: entries.len(),
+			fields: entries.iter().map(|entry| entry.fields.len()).sum(),
+			kinds: kinds.into_iter().filter(|(_, count)| *count != 0).collect(),
+			tags,
+			oldest_passwords: passwords,
+			attachments_size,
+			database_size,
+		}
+	}
+}
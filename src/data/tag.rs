@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::data::entry::Entry;
+use crate::data::entry::EntryTag;
+
+/// Default color of tags without a registered color
+pub const DEFAULT_TAG_COLOR: u32 = 0xDEA13B;
+
+/// Canonical tag definitions shared by all entries
+///
+/// Tags are matched case-insensitively, so that a tag keeps the same name, icon and color on every
+/// entry.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TagRegistry {
+	tags: Vec<EntryTag>,
+}
+
+impl TagRegistry {
+	/// Get the definition of tag `name`
+	pub fn get(&self, name: &str) -> Option<&EntryTag> {
+		let name = name.trim();
+		self.tags
+			.iter()
+			.find(|tag| tag.name.eq_ignore_ascii_case(name))
+	}
+
+	/// Resolve tag `name` to its canonical definition, or a plain tag if unknown
+	pub fn resolve(&self, name: &str) -> EntryTag {
+		self.get(name).cloned().unwrap_or_else(|| EntryTag {
+			name: name.trim().to_string(),
+			icon: None,
+			color: None,
+		})
+	}
+
+	/// Add or replace the definition of `tag`
+	pub fn register(&mut self, tag: EntryTag) {
+		match self
+			.tags
+			.iter_mut()
+			.find(|other| other.name.eq_ignore_ascii_case(&tag.name))
+		{
+			Some(other) => *other = tag,
+			None => self.tags.push(tag),
+		}
+	}
+
+	/// Register the tags of `entries` that are not defined yet
+	pub fn sync(&mut self, entries: &[Entry]) {
+		for tag in entries.iter().flat_map(|entry| entry.tags.iter()) {
+			if self.get(&tag.name).is_none() {
+				self.tags.push(tag.clone());
+			}
+		}
+	}
+
+	/// Replace the tags of `entries` by their canonical definition
+	pub fn apply(&self, entries: &mut [Entry]) {
+		for tag in entries.iter_mut().flat_map(|entry| entry.tags.iter_mut()) {
+			if let Some(canonical) = self.get(&tag.name) {
+				*tag = canonical.clone();
+			}
+		}
+	}
+
+	/// Registered tags, sorted by name
+	pub fn sorted(&self) -> Vec<&EntryTag> {
+		let mut tags = self.tags.iter().collect::<Vec<_>>();
+		tags.sort_by_key(|tag| tag.name.to_lowercase());
+		tags
+	}
+}
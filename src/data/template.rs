@@ -0,0 +1,141 @@
+use chrono::Utc;
+
+use crate::data::entry::Entry;
+use crate::data::entry::ENTRY_ICONS;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+
+/// Templates for new entries
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EntryTemplate {
+	#[default]
+	Empty,
+	Login,
+	CreditCard,
+	SshKey,
+	WiFi,
+}
+
+impl TryFrom<usize> for EntryTemplate {
+	type Error = &'static str;
+
+	fn try_from(value: usize) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(EntryTemplate::Empty),
+			1 => Ok(EntryTemplate::Login),
+			2 => Ok(EntryTemplate::CreditCard),
+			3 => Ok(EntryTemplate::SshKey),
+			4 => Ok(EntryTemplate::WiFi),
+			_ => Err("Invalid value"),
+		}
+	}
+}
+
+impl TryFrom<&str> for EntryTemplate {
+	type Error = String;
+
+	/// Parse a template name, ignoring case, spaces and dashes
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		let normalize = |name: &str| {
+			name.chars()
+				.filter(|c| !matches!(c, ' ' | '-' | '_'))
+				.collect::<String>()
+				.to_lowercase()
+		};
+		let name = normalize(value);
+		EntryTemplate::ALL
+			.into_iter()
+			.find(|template| normalize(template.name()) == name)
+			.ok_or_else(|| format!("Unknown template '{value}'"))
+	}
+}
+
+impl EntryTemplate {
+	pub const ALL: [EntryTemplate; 5] = [
+		EntryTemplate::Empty,
+		EntryTemplate::Login,
+		EntryTemplate::CreditCard,
+		EntryTemplate::SshKey,
+		EntryTemplate::WiFi,
+	];
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			EntryTemplate::Empty => "Empty",
+			EntryTemplate::Login => "Login",
+			EntryTemplate::CreditCard => "Credit Card",
+			EntryTemplate::SshKey => "SSH Key",
+			EntryTemplate::WiFi => "Wi-Fi",
+		}
+	}
+
+	/// Name of the built-in icon, see [`ENTRY_ICONS`]
+	pub fn icon(&self) -> Option<&'static str> {
+		let name = match self {
+			EntryTemplate::Empty => return None,
+			EntryTemplate::Login => "key",
+			EntryTemplate::CreditCard => "card",
+			EntryTemplate::SshKey => "terminal",
+			EntryTemplate::WiFi => "wifi",
+		};
+		ENTRY_ICONS
+			.iter()
+			.find(|(icon, _)| *icon == name)
+			.map(|(_, glyph)| *glyph)
+	}
+
+	/// Empty fields created by the template
+	pub fn fields(&self) -> Vec<Field> {
+		let field = |name: &str, value: FieldValue, hidden: bool| Field {
+			name: name.to_string(),
+			value,
+			hidden,
+			..Default::default()
+		};
+		let text = || FieldValue::Text(String::default());
+		match self {
+			EntryTemplate::Empty => vec![],
+			EntryTemplate::Login => vec![
+				field("Username", text(), false),
+				field("Password", text(), true),
+				field("URL", FieldValue::Url(String::default()), false),
+			],
+			EntryTemplate::CreditCard => vec![
+				field("Cardholder", text(), false),
+				field("Card Number", text(), true),
+				field("Expiry (MM/YY)", text(), false),
+				field("CVV", text(), true),
+				field("PIN", text(), true),
+			],
+			EntryTemplate::SshKey => vec![
+				field("Host", text(), false),
+				field("Username", text(), false),
+				field("Private Key", text(), true),
+				field("Public Key", text(), false),
+				field("Passphrase", text(), true),
+			],
+			EntryTemplate::WiFi => vec![
+				field("SSID", text(), false),
+				field("Password", text(), true),
+				field("Security", text(), false),
+			],
+		}
+	}
+
+	/// Create a new entry named `name` with the fields of the template
+	pub fn entry(&self, name: String) -> Entry {
+		let now = Utc::now();
+		Entry {
+			name,
+			fields: self.fields(),
+			tags: vec![],
+			icon: self.icon().map(str::to_string),
+			favorite: false,
+			created_at: now,
+			modified_at: now,
+			accessed_at: now,
+			history: vec![],
+		}
+	}
+}
@@ -0,0 +1,94 @@
+use std::process::Command;
+use std::process::Stdio;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::data::secret::wipe;
+use crate::data::secret::SecretBuffer;
+
+/// Program used to query the token
+const YKCHALRESP: &str = "ykchalresp";
+/// Length of a HMAC-SHA1 response
+const RESPONSE_LEN: usize = 20;
+
+/// Check that `slot` is a valid YubiKey OTP slot
+pub fn check_slot(slot: u8) -> Result<u8, String> {
+	match slot {
+		1 | 2 => Ok(slot),
+		_ => Err(format!("Invalid YubiKey slot {slot}, expected 1 or 2")),
+	}
+}
+
+/// Parse a YubiKey slot, an empty string means no YubiKey
+pub fn parse_slot(slot: &str) -> Result<Option<u8>, String> {
+	let slot = slot.trim();
+	if slot.is_empty() {
+		return Ok(None);
+	}
+	let slot = slot
+		.parse::<u8>()
+		.map_err(|_| format!("Invalid YubiKey slot '{slot}', expected 1 or 2"))?;
+	check_slot(slot).map(Some)
+}
+
+/// Send `challenge` to the HMAC-SHA1 slot of the plugged YubiKey, using `ykchalresp`
+///
+/// This blocks until the token is touched if the slot requires it.
+pub fn challenge_response(slot: u8, challenge: &[u8]) -> Result<SecretBuffer, String> {
+	check_slot(slot)?;
+	let hex = challenge
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect::<String>();
+	let output = Command::new(YKCHALRESP)
+		.arg(format!("-{slot}"))
+		.arg("-H")
+		.arg("-x")
+		.arg(hex)
+		.stdin(Stdio::null())
+		.output()
+		.map_err(|err| format!("Failed to run {YKCHALRESP}: {err}"))?;
+	if !output.status.success() {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		return Err(format!(
+			"YubiKey challenge-response failed: {}",
+			stderr.trim()
+		));
+	}
+
+	let mut stdout = output.stdout;
+	let result = decode_response(&stdout);
+	wipe(&mut stdout);
+	result
+}
+
+fn decode_response(stdout: &[u8]) -> Result<SecretBuffer, String> {
+	let hex = stdout.trim_ascii();
+	if hex.len() != RESPONSE_LEN * 2 {
+		return Err("Unexpected YubiKey response length".into());
+	}
+	let mut response = SecretBuffer::new(RESPONSE_LEN);
+	for (byte, pair) in response.iter_mut().zip(hex.chunks(2)) {
+		let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+		let (Some(high), Some(low)) = (digit(pair[0]), digit(pair[1])) else {
+			return Err("Invalid YubiKey response".into());
+		};
+		*byte = (high << 4) | low;
+	}
+	Ok(response)
+}
+
+/// Mix the derived `key` with the token `response` into a key of the same length
+pub fn mix(key: &[u8], response: &[u8]) -> SecretBuffer {
+	let mut mixed = SecretBuffer::new(key.len());
+	for (counter, chunk) in mixed.chunks_mut(32).enumerate() {
+		let digest = Sha256::new()
+			.chain_update((counter as u32).to_be_bytes())
+			.chain_update(key)
+			.chain_update(response)
+			.finalize();
+		chunk.copy_from_slice(&digest[..chunk.len()]);
+	}
+	mixed
+}
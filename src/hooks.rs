@@ -0,0 +1,253 @@
+//! User-defined hooks
+//!
+//! Hooks are shell commands configured in `$XDG_CONFIG_HOME/passk/hooks.json`, alone or with their
+//! options:
+//! ```json
+//! {
+//!   "post-save": ["rsync \"$PASSK_DATABASE\" backup:passk/"],
+//!   "on-copy": [
+//!     "notify-send \"Copied $PASSK_FIELD of $PASSK_ENTRY\"",
+//!     { "command": "printf %s \"$PASSK_VALUE\" | wl-copy --primary", "pass-value": true }
+//!   ]
+//! }
+//! ```
+//! The event context is passed through the `PASSK_EVENT`, `PASSK_DATABASE`, `PASSK_ENTRY` and
+//! `PASSK_FIELD` environment variables. The only secret exposed is the copied value, in
+//! `PASSK_VALUE` rather than on the command line, where other users could read it, and only to the
+//! `on-copy` hooks that set `pass-value`.
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::sync::RwLock;
+
+use serde::Deserialize;
+
+use crate::config::config_dir;
+
+/// Command run on an event
+#[derive(Debug, Deserialize)]
+#[serde(from = "HookConfig")]
+struct Hook {
+	command: String,
+	/// Whether the secret value of the event is passed through `PASSK_VALUE`
+	pass_value: bool,
+}
+
+/// Hook as configured, the command alone or with its options
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HookConfig {
+	Command(String),
+	#[serde(rename_all = "kebab-case")]
+	Options {
+		command: String,
+		#[serde(default)]
+		pass_value: bool,
+	},
+}
+
+impl From<HookConfig> for Hook {
+	fn from(config: HookConfig) -> Self {
+		match config {
+			HookConfig::Command(command) => Self {
+				command,
+				pass_value: false,
+			},
+			HookConfig::Options {
+				command,
+				pass_value,
+			} => Self {
+				command,
+				pass_value,
+			},
+		}
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Hooks {
+	#[serde(default)]
+	post_save: Vec<Hook>,
+	#[serde(default)]
+	on_unlock: Vec<Hook>,
+	#[serde(default)]
+	on_copy: Vec<Hook>,
+}
+
+static HOOKS: LazyLock<Result<Hooks, String>> = LazyLock::new(|| {
+	let Some(path) = config_dir().map(|dir| dir.join("hooks.json")) else {
+		return Ok(Hooks::default());
+	};
+	if !path.exists() {
+		return Ok(Hooks::default());
+	}
+	let bytes = std::fs::read(&path)
+		.map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	serde_json::from_slice(&bytes)
+		.map_err(|err| format!("Failed to parse '{}': {err}", path.display()))
+});
+
+/// Path of the database in view, for events that do not name theirs
+static DATABASE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+	PostSave,
+	OnUnlock,
+	OnCopy,
+}
+
+impl HookEvent {
+	pub fn name(&self) -> &'static str {
+		match self {
+			HookEvent::PostSave => "post-save",
+			HookEvent::OnUnlock => "on-unlock",
+			HookEvent::OnCopy => "on-copy",
+		}
+	}
+}
+
+/// Context of an event
+#[derive(Default)]
+pub struct HookContext<'a> {
+	/// Database of the event, the one in view when `None`
+	pub database: Option<&'a Path>,
+	pub entry: Option<&'a str>,
+	pub field: Option<&'a str>,
+	/// Secret value, passed through `PASSK_VALUE` to the hooks that set `pass-value`
+	pub value: Option<&'a str>,
+}
+
+/// Set the path of the database in view, called whenever it changes
+pub fn set_database(path: &Path) {
+	*DATABASE.write().unwrap() = Some(path.to_path_buf());
+}
+
+/// Error encountered when loading the hooks configuration
+pub fn load_error() -> Option<&'static str> {
+	HOOKS.as_ref().err().map(|err| err.as_str())
+}
+
+/// Run all hooks registered for `event` in the background
+pub fn run_hooks(event: HookEvent, ctx: &HookContext) {
+	let Ok(hooks) = HOOKS.as_ref() else {
+		return;
+	};
+	let commands = match event {
+		HookEvent::PostSave => &hooks.post_save,
+		HookEvent::OnUnlock => &hooks.on_unlock,
+		HookEvent::OnCopy => &hooks.on_copy,
+	};
+	let database = ctx
+		.database
+		.map(Path::to_path_buf)
+		.or_else(|| DATABASE.read().unwrap().clone());
+	for hook in commands {
+		// Reap the child without blocking the UI
+		if let Ok(mut child) = command(hook, event, ctx, database.as_deref()).spawn() {
+			std::thread::spawn(move || child.wait());
+		}
+	}
+}
+
+/// Process running `hook` for `event`, with the context of the event in its environment
+fn command(hook: &Hook, event: HookEvent, ctx: &HookContext, database: Option<&Path>) -> Command {
+	let mut process = Command::new("sh");
+	process
+		.arg("-c")
+		.arg(&hook.command)
+		.env("PASSK_EVENT", event.name())
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null());
+	if let Some(database) = database {
+		process.env("PASSK_DATABASE", database);
+	}
+	if let Some(entry) = ctx.entry {
+		process.env("PASSK_ENTRY", entry);
+	}
+	if let Some(field) = ctx.field {
+		process.env("PASSK_FIELD", field);
+	}
+	if let Some(value) = ctx.value.filter(|_| hook.pass_value) {
+		process.env("PASSK_VALUE", value);
+	}
+	process
+}
+
+#[cfg(test)]
+mod tests {
+	use std::ffi::OsStr;
+
+	use super::*;
+
+	/// Environment set for `hook` when a password is copied
+	fn copy_env(hook: &Hook) -> Vec<(String, Option<String>)> {
+		let ctx = HookContext {
+			database: None,
+			entry: Some("mail"),
+			field: Some("Password"),
+			value: Some("secret"),
+		};
+		command(
+			hook,
+			HookEvent::OnCopy,
+			&ctx,
+			Some(Path::new("/tmp/db.passk")),
+		)
+		.get_envs()
+		.map(|(key, value)| {
+			(
+				key.to_string_lossy().into_owned(),
+				value
+					.map(OsStr::to_string_lossy)
+					.map(|value| value.into_owned()),
+			)
+		})
+		.collect()
+	}
+
+	#[test]
+	fn hook_config() {
+		let hooks: Hooks = serde_json::from_str(
+			r#"{
+				"on-copy": ["true", { "command": "cat", "pass-value": true }],
+				"post-save": [{ "command": "sync" }]
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(hooks.on_copy.len(), 2);
+		assert_eq!(hooks.on_copy[0].command, "true");
+		assert!(!hooks.on_copy[0].pass_value);
+		assert_eq!(hooks.on_copy[1].command, "cat");
+		assert!(hooks.on_copy[1].pass_value);
+		assert!(!hooks.post_save[0].pass_value);
+		assert!(hooks.on_unlock.is_empty());
+	}
+
+	#[test]
+	fn value_is_opt_in() {
+		let mut hook = Hook {
+			command: "true".into(),
+			pass_value: false,
+		};
+		let env = copy_env(&hook);
+		let get = |key: &str| {
+			env.iter()
+				.find(|(name, _)| name == key)
+				.and_then(|(_, value)| value.clone())
+		};
+		assert_eq!(get("PASSK_EVENT").as_deref(), Some("on-copy"));
+		assert_eq!(get("PASSK_DATABASE").as_deref(), Some("/tmp/db.passk"));
+		assert_eq!(get("PASSK_ENTRY").as_deref(), Some("mail"));
+		assert_eq!(get("PASSK_FIELD").as_deref(), Some("Password"));
+		assert!(env.iter().all(|(name, _)| name != "PASSK_VALUE"));
+
+		hook.pass_value = true;
+		let env = copy_env(&hook);
+		assert!(env.contains(&("PASSK_VALUE".into(), Some("secret".into()))));
+	}
+}
@@ -0,0 +1,369 @@
+//! Local HTTP API
+//!
+//! Opt-in server bound to `127.0.0.1`, exposing the unlocked database to local tools. Every
+//! request must carry an `Authorization: Bearer <token>` header, the token is generated at startup
+//! and written to a file readable only by the current user.
+//!
+//! Endpoints:
+//! - `GET /search?q=<filter>`: names of the entries matching the Explorer filter syntax
+//! - `GET /field?entry=<name>&field=<name>`: value of a field
+//! - `GET /generate?length=<n>&charset=<alphanumeric|alphabet|base86>`: random password, of at most
+//!   [`MAX_LENGTH`] characters
+//!
+//! The server stops when the [`HttpApi`] is dropped, as the database is locked.
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::config::config_dir;
+use crate::data::entry::Entry;
+use crate::ui::explorer::ExplorerFilter;
+use crate::ui::field_generator::generate;
+use crate::ui::field_generator::CharsetKind;
+
+/// Entries shared with the server thread
+pub type SharedEntries = Arc<Mutex<Vec<Entry>>>;
+
+pub struct HttpApi {
+	pub port: u16,
+	pub token_path: PathBuf,
+	pub entries: SharedEntries,
+	/// Set to stop the server thread
+	stop: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>,
+}
+
+/// Path of the file holding the API token
+fn token_path() -> Result<PathBuf, String> {
+	std::env::var_os("XDG_RUNTIME_DIR")
+		.map(|dir| PathBuf::from(dir).join("passk"))
+		.or_else(config_dir)
+		.map(|dir| dir.join("http-token"))
+		.ok_or("Failed to find a directory for the API token".into())
+}
+
+/// Time given to a client to send its request or read the response, connections are served one at
+/// a time
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest password returned by `/generate`
+const MAX_LENGTH: usize = 1024;
+
+impl HttpApi {
+	/// Start the server on `port` in a background thread
+	pub fn start(port: u16, entries: Vec<Entry>) -> Result<Self, String> {
+		let mut token = [0u8; 32];
+		rand::fill(&mut token);
+		let token = token.iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+		let token_path = token_path()?;
+		if let Some(parent) = token_path.parent() {
+			std::fs::create_dir_all(parent)
+				.map_err(|err| format!("Failed to create '{}': {err}", parent.display()))?;
+		}
+		let mut file = std::fs::OpenOptions::new()
+			.write(true)
+			.create(true)
+			.truncate(true)
+			.mode(0o600)
+			.open(&token_path)
+			.map_err(|err| format!("Failed to create '{}': {err}", token_path.display()))?;
+		file.write_all(token.as_bytes())
+			.map_err(|err| format!("Failed to write '{}': {err}", token_path.display()))?;
+
+		let listener = TcpListener::bind(("127.0.0.1", port))
+			.map_err(|err| format!("Failed to listen on 127.0.0.1:{port}: {err}"))?;
+		// Port chosen by the system when `port` is 0
+		let port = listener.local_addr().map_or(port, |addr| addr.port());
+		let entries = Arc::new(Mutex::new(entries));
+		let shared = entries.clone();
+		let stop = Arc::new(AtomicBool::new(false));
+		let stopped = stop.clone();
+		let thread = std::thread::spawn(move || {
+			for stream in listener.incoming().filter_map(|stream| stream.ok()) {
+				// Woken up by the connection made on drop
+				if stopped.load(Ordering::Acquire) {
+					break;
+				}
+				if stream.set_read_timeout(Some(IO_TIMEOUT)).is_err()
+					|| stream.set_write_timeout(Some(IO_TIMEOUT)).is_err()
+				{
+					continue;
+				}
+				let _ = handle(stream, &token, &shared);
+			}
+		});
+
+		Ok(Self {
+			port,
+			token_path,
+			entries,
+			stop,
+			thread: Some(thread),
+		})
+	}
+
+	/// Replace the entries served by the API
+	pub fn update(&self, entries: Vec<Entry>) {
+		*self.entries.lock().unwrap() = entries;
+	}
+}
+
+impl Drop for HttpApi {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_file(&self.token_path);
+		self.entries.lock().unwrap().clear();
+
+		// Wake up the server thread, blocked waiting for a connection
+		self.stop.store(true, Ordering::Release);
+		if TcpStream::connect(("127.0.0.1", self.port)).is_ok() {
+			if let Some(thread) = self.thread.take() {
+				let _ = thread.join();
+			}
+		}
+	}
+}
+
+/// Compare tokens in constant time
+fn token_eq(a: &str, b: &str) -> bool {
+	a.len() == b.len()
+		&& a.bytes()
+			.zip(b.bytes())
+			.fold(0u8, |acc, (a, b)| acc | (a ^ b))
+			== 0
+}
+
+fn percent_decode(input: &str) -> String {
+	let bytes = input.as_bytes();
+	let mut out = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'+' => out.push(b' '),
+			b'%' if i + 2 < bytes.len() => {
+				match std::str::from_utf8(&bytes[i + 1..i + 3])
+					.ok()
+					.and_then(|hex| u8::from_str_radix(hex, 16).ok())
+				{
+					Some(b) => {
+						out.push(b);
+						i += 2;
+					}
+					None => out.push(b'%'),
+				}
+			}
+			b => out.push(b),
+		}
+		i += 1;
+	}
+	String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Get query parameter `key`
+fn query_param(query: &str, key: &str) -> Option<String> {
+	query
+		.split('&')
+		.filter_map(|pair| pair.split_once('='))
+		.find(|(k, _)| *k == key)
+		.map(|(_, v)| percent_decode(v))
+}
+
+fn respond(stream: &mut TcpStream, status: &str, body: serde_json::Value) -> std::io::Result<()> {
+	let body = body.to_string();
+	write!(
+		stream,
+		"HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+		body.len()
+	)
+}
+
+fn handle(mut stream: TcpStream, token: &str, entries: &SharedEntries) -> std::io::Result<()> {
+	let mut reader = BufReader::new(stream.try_clone()?);
+	let mut request_line = String::new();
+	reader.read_line(&mut request_line)?;
+
+	// Headers
+	let mut authorized = false;
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+			break;
+		}
+		if let Some((name, value)) = line.split_once(':') {
+			if name.eq_ignore_ascii_case("authorization") {
+				authorized = value
+					.trim()
+					.strip_prefix("Bearer ")
+					.is_some_and(|value| token_eq(value.trim(), token));
+			}
+		}
+	}
+	if !authorized {
+		return respond(
+			&mut stream,
+			"401 Unauthorized",
+			json!({"error": "Invalid token"}),
+		);
+	}
+
+	let mut parts = request_line.split_whitespace();
+	let (Some("GET"), Some(target)) = (parts.next(), parts.next()) else {
+		return respond(
+			&mut stream,
+			"405 Method Not Allowed",
+			json!({"error": "Only GET is supported"}),
+		);
+	};
+	let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+	match path {
+		"/search" => {
+			let filter = ExplorerFilter::from(query_param(query, "q").unwrap_or_default().as_str());
+			let names = entries
+				.lock()
+				.unwrap()
+				.iter()
+				.filter(|ent| filter.filter(ent))
+				.map(|ent| ent.name.clone())
+				.collect::<Vec<_>>();
+			respond(&mut stream, "200 OK", json!({ "entries": names }))
+		}
+		"/field" => {
+			let (Some(entry), Some(field)) =
+				(query_param(query, "entry"), query_param(query, "field"))
+			else {
+				return respond(
+					&mut stream,
+					"400 Bad Request",
+					json!({"error": "Missing 'entry' or 'field' parameter"}),
+				);
+			};
+			let value = entries
+				.lock()
+				.unwrap()
+				.iter()
+				.find(|ent| ent.name == entry)
+				.and_then(|ent| ent.fields.iter().find(|f| f.name == field))
+				.and_then(|f| f.value.text().map(str::to_string));
+			match value {
+				Some(value) => respond(&mut stream, "200 OK", json!({ "value": value })),
+				None => respond(
+					&mut stream,
+					"404 Not Found",
+					json!({"error": "No such entry or field"}),
+				),
+			}
+		}
+		"/generate" => {
+			let length = query_param(query, "length")
+				.and_then(|length| length.parse::<usize>().ok())
+				.unwrap_or(32)
+				.min(MAX_LENGTH);
+			let charset = match query_param(query, "charset").as_deref() {
+				Some("alphabet") => CharsetKind::Alpha,
+				Some("base86") => CharsetKind::Base86,
+				_ => CharsetKind::Alphanum,
+			};
+			match generate(&charset.chars().unwrap(), length) {
+				Some(value) => respond(&mut stream, "200 OK", json!({ "value": value })),
+				None => respond(
+					&mut stream,
+					"400 Bad Request",
+					json!({"error": "Invalid length"}),
+				),
+			}
+		}
+		_ => respond(
+			&mut stream,
+			"404 Not Found",
+			json!({"error": "Unknown endpoint"}),
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Read;
+
+	use super::*;
+	use crate::data::field::FieldValue;
+	use crate::data::import::imported_entry;
+	use crate::data::import::imported_field;
+
+	const TOKEN: &str = "0123abcd";
+
+	/// Status line and JSON body of the response to `GET target`
+	fn get(target: &str, token: &str) -> (String, serde_json::Value) {
+		let field = imported_field("Password", FieldValue::Text("hunter2".into()), true);
+		let entry = imported_entry("mail".into(), vec![field], vec![], None, None);
+		let entries = Arc::new(Mutex::new(vec![entry]));
+
+		let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+		let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+		write!(
+			client,
+			"GET {target} HTTP/1.1\r\nAuthorization: Bearer {token}\r\n\r\n"
+		)
+		.unwrap();
+		let (server, _) = listener.accept().unwrap();
+		handle(server, TOKEN, &entries).unwrap();
+
+		let mut response = String::new();
+		client.read_to_string(&mut response).unwrap();
+		let (head, body) = response.split_once("\r\n\r\n").unwrap();
+		let status = head.lines().next().unwrap().to_string();
+		(status, serde_json::from_str(body).unwrap())
+	}
+
+	#[test]
+	fn requires_token() {
+		let (status, _) = get("/search?q=", "wrong");
+		assert_eq!(status, "HTTP/1.1 401 Unauthorized");
+	}
+
+	#[test]
+	fn field() {
+		let (status, body) = get("/field?entry=mail&field=Password", TOKEN);
+		assert_eq!(status, "HTTP/1.1 200 OK");
+		assert_eq!(body["value"], "hunter2");
+
+		let (status, _) = get("/field?entry=mail&field=Username", TOKEN);
+		assert_eq!(status, "HTTP/1.1 404 Not Found");
+		let (status, _) = get("/field?entry=mail", TOKEN);
+		assert_eq!(status, "HTTP/1.1 400 Bad Request");
+	}
+
+	#[test]
+	fn search() {
+		let (_, body) = get("/search?q=ma", TOKEN);
+		assert_eq!(body["entries"], json!(["mail"]));
+	}
+
+	#[test]
+	fn generate() {
+		let (status, body) = get("/generate?length=12", TOKEN);
+		assert_eq!(status, "HTTP/1.1 200 OK");
+		assert_eq!(body["value"].as_str().unwrap().chars().count(), 12);
+
+		let (_, body) = get("/generate?length=1000000", TOKEN);
+		assert_eq!(body["value"].as_str().unwrap().chars().count(), MAX_LENGTH);
+	}
+
+	#[test]
+	fn unknown_endpoint() {
+		let (status, _) = get("/entries", TOKEN);
+		assert_eq!(status, "HTTP/1.1 404 Not Found");
+	}
+}
@@ -0,0 +1,146 @@
+//! Icons of the interface
+//!
+//! Icons are nerd font glyphs, which render as unknown characters without a patched font. An ASCII
+//! replacement is used instead when `icons` is set to `ascii` in the configuration, or when it is
+//! `auto` and the terminal is unlikely to display them, see [`IconSet`].
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::config::config;
+use crate::config::IconSet;
+use crate::data::entry::ENTRY_ICONS;
+
+/// Icon, with its nerd font glyph and ASCII replacement
+#[derive(Debug, Clone, Copy)]
+pub struct Icon {
+	pub nerd: &'static str,
+	pub ascii: &'static str,
+}
+
+impl Icon {
+	pub const fn new(nerd: &'static str, ascii: &'static str) -> Self {
+		Self { nerd, ascii }
+	}
+
+	/// Representation of the icon in the selected icon set
+	pub fn get(&self) -> &'static str {
+		if *ASCII {
+			self.ascii
+		} else {
+			self.nerd
+		}
+	}
+}
+
+impl fmt::Display for Icon {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.get())
+	}
+}
+
+impl IconSet {
+	/// Whether ASCII icons are used, `auto` picks them on the Linux console and without a UTF-8
+	/// locale
+	fn ascii(self) -> bool {
+		match self {
+			IconSet::Nerd => false,
+			IconSet::Ascii => true,
+			IconSet::Auto => {
+				if std::env::var("TERM").is_ok_and(|term| term == "linux") {
+					return true;
+				}
+				let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+					.iter()
+					.filter_map(|var| std::env::var(var).ok())
+					.find(|value| !value.is_empty())
+					.unwrap_or_default()
+					.to_ascii_lowercase();
+				!locale.contains("utf-8") && !locale.contains("utf8")
+			}
+		}
+	}
+}
+
+static ASCII: LazyLock<bool> = LazyLock::new(|| config().icons.ascii());
+
+/// Icon of an entry or tag, as set by the user
+///
+/// In ASCII mode, built-in icons are replaced by the first letter of their name and other glyphs
+/// are kept as is.
+pub fn glyph(icon: &str) -> &str {
+	if !*ASCII {
+		return icon;
+	}
+	ENTRY_ICONS
+		.iter()
+		.find(|(_, glyph)| *glyph == icon)
+		.map(|(name, _)| &name[..1])
+		.unwrap_or(icon)
+}
+
+pub const LOCKED: Icon = Icon::new("󰌾", "L");
+pub const UNLOCKED: Icon = Icon::new("󰌿", "U");
+pub const UNSAVED: Icon = Icon::new("󰽂", "*");
+pub const COPIED: Icon = Icon::new("󱓥", "C");
+pub const WARNING: Icon = Icon::new("󰀦", "!");
+pub const EXPIRING: Icon = Icon::new("󰔟", "~");
+/// Password not changed for a long time
+pub const OLD: Icon = Icon::new("󰅐", "z");
+pub const TIMER: Icon = Icon::new("󱎫", "T");
+pub const ATTACHMENT: Icon = Icon::new("󰁦", "@");
+pub const RECOVERY_CODES: Icon = Icon::new("󰦯", "#");
+pub const DATABASE: Icon = Icon::new("󰆼", "D");
+pub const SORT_ASCENDING: Icon = Icon::new("󰒼", "^");
+pub const SORT_DESCENDING: Icon = Icon::new("󰒽", "v");
+pub const CHECKED: Icon = Icon::new("󰄵", "[x]");
+pub const UNCHECKED: Icon = Icon::new("󰄱", "[ ]");
+/// Separator of the items of a line
+pub const SEPARATOR: Icon = Icon::new("•", "|");
+/// Entry marked for a bulk action
+pub const MARKED: Icon = Icon::new("󰄬", "*");
+/// Health of the passwords of an entry
+pub const HEALTH: Icon = Icon::new("●", "o");
+/// Hidden field shown for a moment
+pub const REVEALED: Icon = Icon::new("󰈈", "?");
+/// Favorite entry
+pub const FAVORITE: Icon = Icon::new("󰓎", "F");
+/// Recently used entries
+pub const RECENT: Icon = Icon::new("󰋚", "R");
+/// Indicator of a closed completion menu or a collapsed group
+pub const COLLAPSED: Icon = Icon::new("", "<");
+/// Indicator of an open completion menu or an expanded group
+pub const EXPANDED: Icon = Icon::new("", "v");
+
+// Entry kinds
+pub const LOGIN: Icon = Icon::new("󰌋", "l");
+pub const CREDIT_CARD: Icon = Icon::new("󰆛", "c");
+pub const SSH_KEY: Icon = Icon::new("󰆍", "s");
+pub const WIFI: Icon = Icon::new("󰖩", "w");
+pub const ONE_TIME: Icon = Icon::new("󰐲", "o");
+pub const NOTE: Icon = Icon::new("󰠮", "n");
+
+// Field kinds
+pub const TEXT: Icon = Icon::new("󰅍", "T");
+pub const URL: Icon = Icon::new("", "U");
+pub const PHONE: Icon = Icon::new("󰥒", "P");
+pub const EMAIL: Icon = Icon::new("󰇰", "M");
+pub const OTP: Icon = Icon::new("󰐲", "O");
+
+// Import and export formats
+pub const CSV: Icon = Icon::new("󰈛", "C");
+pub const JSON: Icon = Icon::new("󰘦", "J");
+pub const BITWARDEN: Icon = Icon::new("󰞀", "B");
+pub const KEY: Icon = Icon::new("󰌋", "K");
+pub const PLUGIN: Icon = Icon::new("󰏗", "P");
+pub const BROWSER: Icon = Icon::new("󰈹", "W");
+pub const AUTHENTICATOR: Icon = Icon::new("󰦝", "A");
+
+// Sharing and CSV columns
+pub const FILTER: Icon = Icon::new("󰈲", "F");
+pub const TAG: Icon = Icon::new("󰓹", "+");
+pub const COLUMN: Icon = Icon::new("󰓫", "|");
+
+// Generator charsets
+pub const CHARSET: Icon = Icon::new("󱅈", "a");
+pub const SYMBOLS: Icon = Icon::new("󰟵", "%");
+pub const PRESET: Icon = Icon::new("󰒓", "*");
@@ -0,0 +1,403 @@
+//! Key bindings of every screen
+//!
+//! Help bars and the help overlay are generated from these definitions, the keys themselves are
+//! handled by each component.
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::style::theme;
+
+pub struct Binding {
+	/// Keys, as displayed
+	pub keys: &'static str,
+	pub action: &'static str,
+	/// Whether the binding is shown in the help bar of the screen, all bindings are listed in
+	/// the help overlay
+	pub short: bool,
+}
+
+/// Bindings of a screen
+pub struct Keymap {
+	pub screen: &'static str,
+	pub bindings: &'static [Binding],
+}
+
+/// Binding shown in the help bar
+const fn key(keys: &'static str, action: &'static str) -> Binding {
+	Binding {
+		keys,
+		action,
+		short: true,
+	}
+}
+
+/// Binding only listed in the help overlay
+const fn more(keys: &'static str, action: &'static str) -> Binding {
+	Binding {
+		keys,
+		action,
+		short: false,
+	}
+}
+
+pub const GLOBAL: Keymap = Keymap {
+	screen: "Global",
+	bindings: &[
+		more("F1 ?", "help"),
+		more("C-z", "suspend, locks the session"),
+		more("C-s", "save the database"),
+		more("M-← M-→", "previous, next database"),
+		more("M-1..9", "database by position"),
+	],
+};
+
+pub const HELP: Keymap = Keymap {
+	screen: "Help",
+	bindings: &[
+		key("⮁", "scroll"),
+		more("pgup pgdn", "scroll by page"),
+		more("g G", "top, bottom"),
+		key("esc", "close"),
+	],
+};
+
+pub const EXPLORER: Keymap = Keymap {
+	screen: "Explorer",
+	bindings: &[
+		key("⮁", "navigate"),
+		more("j k C-n C-p tab", "navigate"),
+		key("/", "filter"),
+		key("'", "jump"),
+		more("' a..z", "next entry starting with the letter, until esc"),
+		key("a", "add"),
+		key("d del", "delete"),
+		key("r", "rename"),
+		more("F2", "rename"),
+		more("tab", "while renaming, rename the next entry"),
+		more("R", "rename a folder"),
+		key("u", "undo"),
+		key("C-r", "redo"),
+		more("t", "entry tags"),
+		more("m", "move to another database"),
+		more("o", "next sort"),
+		more("space", "mark the entry"),
+		more("C-a", "mark the filtered entries"),
+		more("esc", "clear the marks"),
+		more("d t r s", "delete, tag, move, share the marked entries"),
+		more("O", "reverse the sort"),
+		key("h", "tag sidebar"),
+		more("←", "tag sidebar, when shown"),
+		key("f", "favorite"),
+		more("F", "all, favorite or recently used entries"),
+		key("g", "group by tag"),
+		more("z", "collapse or expand the group"),
+		more("enter space", "on a group heading, collapse or expand it"),
+		key("c", "copy password"),
+		key("b", "copy username"),
+		more("C", "copy username"),
+		key("y", "copy name"),
+		key("v", "editor"),
+		key("i", "import"),
+		key("T", "tags"),
+		key("L", "log"),
+		key("x", "trash"),
+		key("S", "stats"),
+		key("D", "properties"),
+		key("s", "share"),
+		key("P", "password"),
+		key("E", "export"),
+		key("U", "PIN"),
+		key("C-s", "save"),
+		key("C-l", "lock"),
+		key("q", "quit"),
+		key("enter", "open"),
+		more("e", "open"),
+		key("?", "help"),
+	],
+};
+
+pub const FILTER: Keymap = Keymap {
+	screen: "Filter",
+	bindings: &[
+		more("↓ tab esc C-n", "entries"),
+		more("+tag tag:tag", "only entries with the tag"),
+		more("\"a b\"", "phrase"),
+	],
+};
+
+pub const TAG_SIDEBAR: Keymap = Keymap {
+	screen: "Tag Sidebar",
+	bindings: &[
+		more("j k ↑ ↓", "navigate"),
+		more("enter space", "add the tag to the filter, or remove it"),
+		more("→ l tab esc", "entries"),
+		more("h", "hide the sidebar"),
+	],
+};
+
+pub const ENTRY: Keymap = Keymap {
+	screen: "Entry",
+	bindings: &[
+		key("⮁", "navigate"),
+		more("j k C-n C-p tab", "navigate"),
+		more("pgup pgdn", "navigate by page"),
+		key("/", "filter"),
+		more("esc", "clear the filter"),
+		key("S-⮁", "reorder"),
+		key("a", "add"),
+		key("e", "edit"),
+		more("enter", "edit"),
+		key("d", "delete"),
+		key("y", "yank"),
+		more("C-c", "yank"),
+		key("Y", "yank name"),
+		key("v", "reveal"),
+		key("w", "save file"),
+		key("o", "open file"),
+		key("i", "icon"),
+		key("r", "recovery codes"),
+		key("h", "history"),
+		key("H", "field history"),
+		key("t", "timestamps"),
+		more("esc q", "close"),
+	],
+};
+
+pub const FIELD_EDITOR: Keymap = Keymap {
+	screen: "Field Editor",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "submit"),
+		more("C-s", "submit from a note"),
+		key("space", "toggle"),
+		key("C-g", "generate"),
+	],
+};
+
+pub const RECOVERY_CODES: Keymap = Keymap {
+	screen: "Recovery Codes",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "close"),
+		key("space", "mark used"),
+		more("u", "mark used"),
+		key("y", "yank"),
+	],
+};
+
+pub const ENTRY_HISTORY: Keymap = Keymap {
+	screen: "Entry History",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "close"),
+		key("enter", "restore"),
+	],
+};
+
+pub const FIELD_HISTORY: Keymap = Keymap {
+	screen: "Field History",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "close"),
+		key("y", "yank"),
+		key("enter", "restore"),
+	],
+};
+
+pub const ENTRY_ICON: Keymap = Keymap {
+	screen: "Entry Icon",
+	bindings: &[key("esc", "cancel"), key("enter", "set icon")],
+};
+
+pub const SAVE_ATTACHMENT: Keymap = Keymap {
+	screen: "Save Attachment",
+	bindings: &[key("esc", "cancel"), key("enter", "save")],
+};
+
+pub const AUDIT_LOG: Keymap = Keymap {
+	screen: "Audit Log",
+	bindings: &[
+		key("⮁", "navigate"),
+		more("pgup pgdn", "navigate by page"),
+		more("g G", "first, last event"),
+		key("esc", "close"),
+	],
+};
+
+pub const TRASH: Keymap = Keymap {
+	screen: "Trash",
+	bindings: &[
+		key("⮁", "navigate"),
+		more("pgup pgdn", "navigate by page"),
+		more("g G", "first, last entry"),
+		key("r", "restore"),
+		more("enter", "restore"),
+		key("d", "delete"),
+		more("del", "delete"),
+		key("E", "empty"),
+		key("esc", "close"),
+	],
+};
+
+pub const STATS: Keymap = Keymap {
+	screen: "Statistics",
+	bindings: &[
+		key("⮁", "scroll"),
+		more("g G", "top, bottom"),
+		key("esc", "close"),
+	],
+};
+
+pub const NEW_ENTRY: Keymap = Keymap {
+	screen: "New Entry",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "create"),
+	],
+};
+
+pub const IMPORT: Keymap = Keymap {
+	screen: "Import",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "import"),
+	],
+};
+
+pub const CSV_MAPPING: Keymap = Keymap {
+	screen: "CSV Columns",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "back"),
+		key("enter", "import"),
+	],
+};
+
+pub const SHARE: Keymap = Keymap {
+	screen: "Share",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "share"),
+	],
+};
+
+pub const TAG_REGISTRY: Keymap = Keymap {
+	screen: "Tags",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "save tag"),
+	],
+};
+
+pub const CHANGE_PASSWORD: Keymap = Keymap {
+	screen: "Change Password",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "change"),
+	],
+};
+
+pub const QUICK_UNLOCK_SETUP: Keymap = Keymap {
+	screen: "Quick Unlock",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "enable"),
+	],
+};
+
+pub const PIN_PROMPT: Keymap = Keymap {
+	screen: "PIN",
+	bindings: &[key("esc", "use password"), key("enter", "unlock")],
+};
+
+pub const EXPORT: Keymap = Keymap {
+	screen: "Export",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "export"),
+	],
+};
+
+pub const TRANSFER: Keymap = Keymap {
+	screen: "Move Entry",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "move"),
+	],
+};
+
+pub const RECENT: Keymap = Keymap {
+	screen: "Recent Databases",
+	bindings: &[
+		key("⮁", "navigate"),
+		more("1..9", "database by position"),
+		key("esc", "quit"),
+		key("enter", "open"),
+	],
+};
+
+pub const PROPERTIES: Keymap = Keymap {
+	screen: "Properties",
+	bindings: &[
+		key("⮁", "navigate"),
+		key("esc", "cancel"),
+		key("enter", "save"),
+	],
+};
+
+/// Every screen, in the order of the help overlay
+pub const KEYMAPS: [&Keymap; 27] = [
+	&GLOBAL,
+	&EXPLORER,
+	&FILTER,
+	&TAG_SIDEBAR,
+	&ENTRY,
+	&FIELD_EDITOR,
+	&RECOVERY_CODES,
+	&ENTRY_HISTORY,
+	&FIELD_HISTORY,
+	&ENTRY_ICON,
+	&SAVE_ATTACHMENT,
+	&AUDIT_LOG,
+	&TRASH,
+	&STATS,
+	&NEW_ENTRY,
+	&IMPORT,
+	&CSV_MAPPING,
+	&SHARE,
+	&TAG_REGISTRY,
+	&CHANGE_PASSWORD,
+	&QUICK_UNLOCK_SETUP,
+	&PIN_PROMPT,
+	&EXPORT,
+	&PROPERTIES,
+	&TRANSFER,
+	&RECENT,
+	&HELP,
+];
+
+/// Help bar of `keymap`
+pub fn help_line(keymap: &Keymap) -> Line<'static> {
+	let spans = keymap
+		.bindings
+		.iter()
+		.filter(|binding| binding.short)
+		.flat_map(|binding| {
+			[
+				Span::from(binding.keys).bold().fg(theme().success),
+				format!(" ({}) ", binding.action).fg(theme().text),
+			]
+		})
+		.collect::<Vec<_>>();
+	Line::from(spans)
+}
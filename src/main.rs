@@ -1,57 +1,199 @@
-use core::panic;
+use std::cell::Cell;
 use std::cell::OnceCell;
-use std::env;
+use std::collections::VecDeque;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::LazyLock;
+use std::time::Duration;
+use std::time::Instant;
 
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use clap::Parser;
 use clipboard_rs::ClipboardContext;
-use color_eyre::eyre;
 use color_eyre::Result;
 use crossterm::event::Event;
+use crossterm::event::DisableBracketedPaste;
+use crossterm::event::DisableFocusChange;
+use crossterm::event::DisableMouseCapture;
+use crossterm::event::EnableBracketedPaste;
+use crossterm::event::EnableFocusChange;
+use crossterm::event::EnableMouseCapture;
 use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use crossterm::event::{self};
-use getopts::Options;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Position;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
 use ratatui::text::Text;
 use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
 use ratatui::DefaultTerminal;
 use ratatui::Frame;
 
-use crate::data::database::decrypt_database;
-use crate::data::database::encrypt_database;
+use crate::cli::Cli;
+use crate::commands::edit::edit_entry;
+use crate::commands::host;
+use crate::config::config;
+use crate::data::agent;
+use crate::data::attachment;
+use crate::data::audit::AuditAction;
+use crate::data::database::decrypt_database_with_key;
+use crate::data::database::derive_key;
+use crate::data::database::encrypt_database_with_key;
+use crate::data::database::rekey;
+use crate::data::export::export;
 use crate::data::database::CipherData;
 use crate::data::database::Data;
 use crate::data::database::Database;
+use crate::data::database::DatabaseMetadata;
 use crate::data::database::KdfData;
+use crate::data::entry::Entry;
+use crate::data::database::SessionState;
 use crate::data::file::load_database;
+use crate::data::file::load_header;
+use crate::data::file::lock_database;
 use crate::data::file::save_database;
+use crate::data::file::DatabaseLock;
+use crate::data::file::LockState;
 use crate::data::file::PasskVersion;
+use crate::data::quick_unlock::QuickUnlock;
+use crate::data::quick_unlock::MAX_PIN_ATTEMPTS;
+use crate::data::secret::SecretBuffer;
+use crate::data::stats::Stats;
+use crate::data::journal::read_journal;
+use crate::data::journal::remove_journal;
+use crate::data::journal::write_journal;
+use crate::data::keyring;
+use crate::hooks::run_hooks;
+use crate::hooks::HookContext;
+use crate::hooks::HookEvent;
+use crate::style::theme;
+use crate::http::HttpApi;
+use crate::ui::change_password::ChangePassword;
 use crate::ui::explorer::Explorer;
+use crate::ui::export::ExportForm;
+use crate::ui::help::HelpView;
 use crate::ui::password::PasswordPrompt;
+use crate::ui::properties::DatabaseProperties;
+use crate::ui::quick_unlock::PinPrompt;
+use crate::ui::quick_unlock::QuickUnlockSetup;
+use crate::ui::recent::RecentView;
+use crate::ui::screen::AppScreen;
+use crate::ui::screen::ScreenSignal;
+use crate::ui::screen::ScreenStack;
+use crate::ui::stats::StatsView;
+use crate::ui::status_bar::StatusBar;
+use crate::ui::transfer::TransferForm;
+use crate::widgets::confirm::Confirm;
 use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
 use crate::widgets::form::FormSignal;
 use crate::widgets::popup::Popup;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::ComponentRenderCtx;
 
+pub mod cli;
+pub mod clipboard;
+pub mod commands;
+pub mod config;
 pub mod data;
+pub mod hooks;
+pub mod http;
+pub mod icons;
+pub mod keymap;
+pub mod plugin;
+pub mod signals;
+pub mod state;
+pub mod status;
 pub mod style;
 pub mod ui;
 pub mod widgets;
 
+/// Interval between ticks, which update the time-dependent state and redraw the interface
+///
+/// Ticks are delivered whether or not events are received, see [`App::tick`].
+const TICK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Smallest terminal the interface is drawn in, a notice is shown instead below it
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 16;
+
 pub static CLIPBOARD_CTX: LazyLock<ClipboardContext> =
 	LazyLock::new(|| ClipboardContext::new().unwrap());
 
 struct App {
+	/// Name displayed in the unlock prompts
+	name: String,
 	db: Database,
 	path: PathBuf,
-	password: OnceCell<String>,
+	/// Lock held on the database, `None` when opened read-only
+	lock: Option<DatabaseLock>,
+	/// Prompt shown when the database is already opened by another instance
+	lock_prompt: Option<Confirm<'static>>,
+	key: OnceCell<SecretBuffer>,
+	/// Key file used to unlock the database
+	key_file: Option<PathBuf>,
 	data: OnceCell<Data>,
 	explorer: OnceCell<Explorer>,
 	password_prompt: Option<PasswordPrompt>,
+	/// Key wrapped under a PIN, to unlock a locked session
+	quick_unlock: Option<QuickUnlock>,
+	pin_prompt: Option<PinPrompt>,
+	/// Set when the session was locked, changes are then restored from the journal
+	locked: bool,
+	/// Set when changes were written to the journal but not saved to the database
+	dirty: bool,
+	/// Navigation state when the session was locked, restored on unlock
+	session: Option<SessionState>,
+	/// Duration without input after which the session is locked, never when `None`
+	lock_after: Option<Duration>,
+	last_input: Instant,
+	/// Duration the key is cached in the OS keyring, caching is disabled when `None`
+	keyring: Option<TimeDelta>,
+	/// When the key cached in the keyring expires, it is then removed from the keyring
+	keyring_expires_at: Option<DateTime<Utc>>,
+	/// Forms and views opened over the explorer
+	screens: ScreenStack<AppScreen>,
+	/// Key bindings, over every other screen
+	help: Option<HelpView>,
+
+	/// Port for the local HTTP API, started on unlock
+	http_port: Option<u16>,
+	http: Option<HttpApi>,
+	/// Approval prompt for a site requesting logins through the browser extension
+	host_prompt: Option<(Confirm<'static>, String)>,
 
+	/// Recovery prompt for a journal left by a previous session
+	recover: Option<(Confirm<'static>, Data)>,
 	message: Option<Popup<'static>>,
 }
 
+/// Check that `password` unlocks the database whose key is `key`
+fn check_password(
+	db: &Database,
+	key: &OnceCell<SecretBuffer>,
+	key_file: Option<&Path>,
+	password: &str,
+) -> Result<(), String> {
+	let derived = derive_key(&db.kdf, password, key_file)
+		.map_err(|err| format!("Failed to derive key: {err}"))?;
+	if Some(&derived) != key.get() {
+		return Err("Password is incorrect".into());
+	}
+	Ok(())
+}
+
 impl App {
 	fn error(&mut self, message: String) {
 		self.message = Some(Popup::new(
@@ -60,139 +202,924 @@ impl App {
 		));
 	}
 
-	fn get_data(&mut self) -> (String, Data, Database) {
-		let password = self.password.get().cloned().unwrap();
+	fn get_data(&mut self) -> (SecretBuffer, Data, Database) {
+		let key = self.key.get().cloned().unwrap();
 		let mut data = self.data.get().cloned().unwrap();
-		data.entries = self
-			.explorer
-			.get()
-			.map(|explorer| explorer.submit())
-			.unwrap();
+		let explorer = self.explorer.get().unwrap();
+		data.entries = explorer.submit();
+		data.trash = explorer.trash();
+		data.tags = explorer.tags();
+		data.log = explorer.log();
+		data.session = explorer.session();
 		let db = self.db.clone();
 
-		(password, data, db)
+		(key, data, db)
+	}
+
+	/// Write unsaved changes to the journal
+	fn write_journal(&mut self) {
+		if self.read_only() {
+			return;
+		}
+		let (key, mut data, db) = self.get_data();
+		data.modified_at = Utc::now();
+		if let Err(err) = write_journal(&self.path, &db, &key, &data) {
+			self.error(format!("Failed to write journal: {err}"));
+			return;
+		}
+		self.set_dirty(true);
+	}
+
+	/// Save the changes of the entries with autosave, or write them to the journal
+	fn commit_changes(&mut self) {
+		if !config().autosave || self.read_only() {
+			self.write_journal();
+			return;
+		}
+		if let Err(err) = self.save() {
+			self.error(err);
+		}
+	}
+
+	/// Whether changes are not saved to the database yet, shown in the explorer and status bar
+	fn set_dirty(&mut self, dirty: bool) {
+		self.dirty = dirty;
+		if let Some(explorer) = self.explorer.get_mut() {
+			explorer.set_dirty(dirty);
+		}
+	}
+
+	/// Whether changes are kept from being written to disk
+	fn read_only(&self) -> bool {
+		self.lock.is_none()
+	}
+
+	/// Encrypt and write the database, then drop the journal
+	fn save(&mut self) -> Result<(), String> {
+		let (key, data, db) = self.get_data();
+		self.save_with(data, db, &key)
+	}
+
+	/// Encrypt `data` in `db` with `key` and write it, `db` replaces the database header only once
+	/// it is saved
+	fn save_with(
+		&mut self,
+		mut data: Data,
+		mut db: Database,
+		key: &SecretBuffer,
+	) -> Result<(), String> {
+		if self.read_only() {
+			return Err("Database is opened read-only".into());
+		}
+		data.iteration += 1;
+		if let Some(metadata) = &mut db.metadata {
+			metadata.modified_at = Utc::now();
+		}
+		encrypt_database_with_key(&data, &mut db, key)
+			.map_err(|err| format!("Failed to encrypt database: {err}"))?;
+		save_database(&db, &self.path).map_err(|err| format!("Failed to save database: {err}"))?;
+		// The journal is bound to the header as saved
+		self.db = db;
+		self.data.get_mut().unwrap().iteration = data.iteration;
+		run_hooks(
+			HookEvent::PostSave,
+			&HookContext {
+				database: Some(&self.path),
+				..Default::default()
+			},
+		);
+		self.set_dirty(false);
+		remove_journal(&self.path).map_err(|err| format!("Failed to remove journal: {err}"))
+	}
+
+	/// Re-encrypt the database under a new password and save it
+	fn change_password(&mut self) {
+		let Some(AppScreen::ChangePassword(form)) = self.screens.top_mut() else {
+			return;
+		};
+		let change = form.submit();
+		if let Err(err) = check_password(
+			&self.db,
+			&self.key,
+			self.key_file.as_deref(),
+			&change.current,
+		) {
+			form.set_error("Invalid Password".into(), err);
+			return;
+		}
+		let (db, key) = match rekey(
+			&self.db,
+			&change.new,
+			change.key_file.as_deref(),
+			change.yubikey,
+		) {
+			Ok(rekeyed) => rekeyed,
+			Err(err) => {
+				form.set_error(
+					"Invalid Password".into(),
+					format!("Failed to derive key: {err}"),
+				);
+				return;
+			}
+		};
+		self.screens.pop();
+		if let Some(explorer) = self.explorer.get_mut() {
+			explorer.record(None, AuditAction::PasswordChanged);
+		}
+		// The session keeps the current key until the database is saved with the new one
+		let (_, data, _) = self.get_data();
+		match self.save_with(data, db, &key) {
+			Ok(()) => {
+				self.key = OnceCell::from(key);
+				self.key_file = change.key_file;
+				self.cache_key();
+				let mut message = "The database was re-encrypted with the new password".to_string();
+				// The PIN wraps the previous key, and is not known to re-wrap the new one
+				if self.quick_unlock.take().is_some() {
+					message.push_str(", quick unlock was disabled and needs to be set up again");
+				}
+				self.message = Some(Popup::new(
+					"Master Password".into(),
+					Paragraph::new(Text::from(message)),
+				));
+			}
+			Err(err) => self.error(err),
+		}
+	}
+
+	/// Export the decrypted entries after checking the master password
+	fn export(&mut self) {
+		let Some(AppScreen::Export(form)) = self.screens.top_mut() else {
+			return;
+		};
+		let Some(request) = form.submit() else {
+			return;
+		};
+		if let Err(err) = check_password(
+			&self.db,
+			&self.key,
+			self.key_file.as_deref(),
+			&request.password,
+		) {
+			form.set_error("Invalid Password".into(), err);
+			return;
+		}
+		let entries = self.explorer.get().unwrap().submit();
+		if let Err(err) = export(
+			request.format,
+			&request.path,
+			&entries,
+			Some(&request.password),
+		) {
+			form.set_error("Export Failed".into(), err);
+			return;
+		}
+		self.screens.pop();
+		self.explorer.get_mut().unwrap().record(
+			None,
+			AuditAction::Exported {
+				format: request.format.name().into(),
+			},
+		);
+		self.message = Some(Popup::new(
+			"Export".into(),
+			Paragraph::new(Text::from(format!(
+				"Exported {} entries to '{}'",
+				entries.len(),
+				request.path.display()
+			))),
+		));
+	}
+
+	/// Title shown in the explorer, the database name or its file name
+	fn title(&self) -> String {
+		match &self.db.metadata {
+			Some(metadata) if !metadata.name.is_empty() => metadata.name.clone(),
+			_ => self.name.trim_start_matches('/').to_string(),
+		}
+	}
+
+	/// Write the metadata edited in the properties form to the database header
+	fn save_properties(&mut self) {
+		let Some(AppScreen::Properties(form)) = self.screens.top_mut() else {
+			return;
+		};
+		let metadata = form.submit();
+		self.screens.pop();
+		let previous = self.db.metadata.replace(metadata);
+		if let Err(err) = self.save() {
+			self.db.metadata = previous;
+			self.error(err);
+			return;
+		}
+		let title = self.title();
+		self.explorer.get_mut().unwrap().set_title(title);
+	}
+
+	/// Write the changes of the entries made outside of the explorer input
+	fn sync_entries(&mut self) {
+		let Some(explorer) = self.explorer.get_mut() else {
+			return;
+		};
+		if !explorer.take_modified() {
+			return;
+		}
+		if let Some(http) = &self.http {
+			http.update(explorer.submit());
+		}
+		self.commit_changes();
+	}
+
+	/// Add an entry moved or copied from another database
+	fn add_entry(&mut self, entry: Entry) -> Result<(), String> {
+		if self.read_only() {
+			return Err(format!("'{}' is opened read-only", self.title()));
+		}
+		let Some(explorer) = self.explorer.get_mut() else {
+			return Err(format!("'{}' is locked", self.title()));
+		};
+		explorer.add_entry(entry)?;
+		self.sync_entries();
+		Ok(())
+	}
+
+	/// Remove an entry moved to another database
+	fn remove_entry(&mut self, name: &str) -> Result<(), String> {
+		let Some(explorer) = self.explorer.get_mut() else {
+			return Err(format!("'{}' is locked", self.title()));
+		};
+		explorer.remove_entry(name)?;
+		self.sync_entries();
+		Ok(())
+	}
+
+	/// Start a session on the decrypted `data`
+	fn open_session(&mut self, key: SecretBuffer, mut data: Data) {
+		let resumed = std::mem::take(&mut self.locked);
+		// Changes made before locking were written to the journal
+		if resumed && !self.read_only() {
+			match read_journal(&self.path, &self.db, &key) {
+				Ok(Some(journal)) => data = journal,
+				Ok(None) => {}
+				Err(err) => self.error(format!("Failed to read journal: {err}")),
+			}
+		}
+		self.key = OnceCell::from(key);
+		let mut explorer = Explorer::new(
+			std::mem::take(&mut data.entries),
+			std::mem::take(&mut data.tags),
+			std::mem::take(&mut data.log),
+		)
+		.with_trash(std::mem::take(&mut data.trash));
+		explorer.restore_session(self.session.as_ref().unwrap_or(&data.session));
+		self.session = None;
+		explorer.set_title(self.title());
+		explorer.set_dirty(self.dirty);
+		explorer.record(None, AuditAction::Unlocked);
+		self.explorer = OnceCell::from(explorer);
+		self.data = OnceCell::from(data);
+		run_hooks(
+			HookEvent::OnUnlock,
+			&HookContext {
+				database: Some(&self.path),
+				..Default::default()
+			},
+		);
+		self.start_http();
+		if !resumed {
+			self.check_journal();
+		}
+	}
+
+	/// Unlock with the key cached in the keyring, if enabled
+	fn unlock_cached(&mut self) {
+		if self.keyring.is_none() {
+			return;
+		}
+		let cached = match keyring::lookup(&self.path) {
+			Ok(Some(cached)) => cached,
+			Ok(None) => return,
+			Err(err) => {
+				self.error(format!("Failed to read keyring: {err}"));
+				return;
+			}
+		};
+		match decrypt_database_with_key(&self.db, &cached.key) {
+			Ok(data) => {
+				self.key_file = cached.key_file;
+				self.keyring_expires_at = Some(cached.expires_at);
+				self.password_prompt = None;
+				self.open_session(cached.key, data);
+			}
+			// The password was changed since the key was cached
+			Err(_) => self.forget_key(),
+		}
+	}
+
+	/// Cache the session key in the keyring, if enabled
+	fn cache_key(&mut self) {
+		let Some(duration) = self.keyring else {
+			return;
+		};
+		match keyring::store(
+			&self.path,
+			self.key.get().unwrap(),
+			self.key_file.as_deref(),
+			duration,
+		) {
+			Ok(()) => self.keyring_expires_at = Some(Utc::now() + duration),
+			Err(err) => self.error(format!("Failed to cache key: {err}")),
+		}
+	}
+
+	/// Remove the session key from the keyring, if enabled
+	fn forget_key(&mut self) {
+		if self.keyring.is_none() {
+			return;
+		}
+		self.keyring_expires_at = None;
+		if let Err(err) = keyring::clear(&self.path) {
+			self.error(format!("Failed to clear keyring: {err}"));
+		}
+	}
+
+	/// Lock the session, dropping the key and decrypted data
+	///
+	/// Unsaved changes are kept in the journal and the key is removed from the keyring. The
+	/// session is unlocked with the PIN when quick unlock is enabled, or with the master password
+	/// otherwise.
+	fn lock(&mut self) {
+		// A session without unsaved changes leaves the journal alone
+		if self.dirty {
+			self.write_journal();
+		}
+		self.session = self.explorer.get().map(Explorer::session);
+		self.forget_key();
+		self.http = None;
+		self.screens.clear();
+		self.recover = None;
+		self.host_prompt = None;
+		self.explorer = OnceCell::default();
+		self.data = OnceCell::default();
+		self.key = OnceCell::default();
+		self.locked = true;
+		if self.quick_unlock.is_some() {
+			self.pin_prompt = Some(PinPrompt::new(self.name.clone()));
+		} else {
+			self.password_prompt = Some(PasswordPrompt::new(self.name.clone(), false));
+		}
+	}
+
+	/// Apply the form submitted in the top-most screen
+	fn submit_screen(&mut self) {
+		match self.screens.top_mut() {
+			Some(AppScreen::ChangePassword(_)) => self.change_password(),
+			Some(AppScreen::QuickUnlockSetup(_)) => self.enable_quick_unlock(),
+			Some(AppScreen::Export(_)) => self.export(),
+			Some(AppScreen::Properties(_)) => self.save_properties(),
+			Some(AppScreen::Stats(_)) | None => {}
+		}
+	}
+
+	/// Wrap the key under the PIN of the quick unlock form
+	fn enable_quick_unlock(&mut self) {
+		let Some(AppScreen::QuickUnlockSetup(form)) = self.screens.top_mut() else {
+			return;
+		};
+		match QuickUnlock::new(self.key.get().unwrap(), &form.submit()) {
+			Ok(quick_unlock) => {
+				self.quick_unlock = Some(quick_unlock);
+				self.screens.pop();
+				self.message = Some(Popup::new(
+					"Quick Unlock".into(),
+					Paragraph::new(Text::from(format!(
+						"Quick unlock is enabled for this session, press C-l to lock. The PIN is disabled after {MAX_PIN_ATTEMPTS} wrong attempts."
+					))),
+				));
+			}
+			Err(err) => form.set_error("Quick Unlock".into(), err),
+		}
+	}
+
+	/// Start the HTTP API if it was requested
+	fn start_http(&mut self) {
+		let Some(port) = self.http_port else {
+			return;
+		};
+		let entries = self.explorer.get().unwrap().submit();
+		match HttpApi::start(port, entries) {
+			Ok(http) => {
+				self.message = Some(Popup::new(
+					"HTTP API".into(),
+					Paragraph::new(Text::from(format!(
+						"Listening on 127.0.0.1:{}, the access token was written to '{}'",
+						http.port,
+						http.token_path.display()
+					))),
+				));
+				self.http = Some(http);
+			}
+			Err(err) => self.error(format!("Failed to start HTTP API: {err}")),
+		}
+	}
+
+	/// Ask the user to approve a site requesting logins through the browser extension
+	fn check_host_request(&mut self) {
+		if self.host_prompt.is_some()
+			|| self.recover.is_some()
+			|| self.message.is_some()
+			|| self.explorer.get().is_none()
+		{
+			return;
+		}
+		let Some(site) = host::pending_request() else {
+			return;
+		};
+		let mut confirm = Confirm::new(
+			"Browser Extension".into(),
+			Paragraph::new(Text::from(format!(
+				"The browser extension requests the logins of '{site}'. Allow this site?"
+			))),
+		);
+		confirm.set_selected(1);
+		self.host_prompt = Some((confirm, site));
+	}
+
+	/// Look for a journal left by a crashed session and offer to replay it
+	fn check_journal(&mut self) {
+		// The journal belongs to the instance holding the lock
+		if self.read_only() {
+			return;
+		}
+		let key = self.key.get().unwrap();
+		match read_journal(&self.path, &self.db, key) {
+			Ok(Some(data)) => {
+				let confirm = Confirm::new(
+					"Recover Unsaved Changes".into(),
+					Paragraph::new(Text::from(format!(
+						"Found unsaved changes from a previous session ({} entries, last modified {}). Restore them?",
+						data.entries.len(),
+						data.modified_at.format("%Y-%m-%d %H:%M:%S")
+					))),
+				);
+				self.recover = Some((confirm, data));
+			}
+			Ok(None) => {}
+			Err(err) => self.error(format!("Failed to read journal: {err}")),
+		}
 	}
 
-	pub fn new(name: String, path: PathBuf) -> Result<Self, String> {
+	pub fn new(
+		name: String,
+		path: PathBuf,
+		cipher: CipherData,
+		yubikey: Option<u8>,
+		readonly: bool,
+		keyring: Option<TimeDelta>,
+		http_port: Option<u16>,
+	) -> Result<Self, String> {
 		let (db, new) = if !path.exists() {
-			let mut salt = [0u8; 16];
-			rand::fill(&mut salt);
-			(
-				Database {
-					version: data::database::Version::V1,
-					cipher: CipherData::XChaCha20Poly1305V1 {},
-					kdf: KdfData::Argon2Id {
-						salt,
-						memory: 65536,
-						iterations: 2,
-						key_len: CipherData::XChaCha20Poly1305V1 {}.key_len() as u16,
-						parallelism: 2,
-					},
-					blob: vec![],
-				},
-				true,
-			)
+			let mut db = Database::new(cipher, config().kdf);
+			if let Some(slot) = yubikey {
+				db.kdf = db.kdf.with_yubikey(slot);
+			}
+			(db, true)
 		} else {
 			(load_database(&path)?, false)
 		};
-		Ok(Self {
+		let (lock, lock_prompt) = if readonly {
+			(None, None)
+		} else {
+			match lock_database(&path)? {
+				LockState::Acquired(lock) => (Some(lock), None),
+				LockState::Held { pid } => {
+					let mut confirm = Confirm::new(
+						"Database In Use".into(),
+						Paragraph::new(Text::from(format!(
+							"'{}' is already opened by another instance (pid {pid}). Open it read-only?",
+							path.display()
+						))),
+					);
+					confirm.set_selected(1);
+					(None, Some(confirm))
+				}
+			}
+		};
+		let errors = [
+			config::load_error().map(|err| format!("Failed to load configuration: {err}")),
+			hooks::load_error().map(|err| format!("Failed to load hooks: {err}")),
+		];
+		let errors = errors.into_iter().flatten().collect::<Vec<_>>();
+		let message = (!errors.is_empty())
+			.then(|| Popup::new("Error".into(), Paragraph::new(Text::from(errors.join("\n")))));
+		let mut app = Self {
 			db,
 			path,
-			password: OnceCell::default(),
+			lock,
+			lock_prompt,
+			key: OnceCell::default(),
+			key_file: None,
 			data: OnceCell::default(),
 			explorer: OnceCell::default(),
-			password_prompt: Some(PasswordPrompt::new(name, new)),
-			message: None,
-		})
+			password_prompt: Some(PasswordPrompt::new(name.clone(), new)),
+			name,
+			quick_unlock: None,
+			pin_prompt: None,
+			locked: false,
+			// New databases are written on exit
+			dirty: new,
+			session: None,
+			lock_after: None,
+			last_input: Instant::now(),
+			keyring,
+			keyring_expires_at: None,
+			screens: ScreenStack::default(),
+			help: None,
+			http_port,
+			http: None,
+			host_prompt: None,
+			recover: None,
+			message,
+		};
+		if !new {
+			app.unlock_cached();
+		}
+		Ok(app)
 	}
 
-	fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
-		loop {
-			terminal.draw(|frame| self.draw(frame))?;
+	/// Lock the session after `duration` without input
+	pub fn with_lock_after(mut self, duration: Option<Duration>) -> Self {
+		self.lock_after = duration;
+		self
+	}
 
-			if let Event::Key(key) = event::read()? {
-				// Password prompt
-				if let Some(password) = &mut self.password_prompt {
-					match password.input_form(&key) {
-						Some(FormSignal::Return) => {}
-						Some(FormSignal::Exit) => return Ok(()),
-						_ => continue,
-					}
-					let Some(pwd) = password.submit() else {
-						return Ok(());
-					};
-					let mut data = if password.is_new() {
-						// Create default data
-						Data::default()
-					} else {
-						// Decrypt data
-						match decrypt_database(&self.db, pwd.as_str()) {
-							Ok(data) => data,
-							Err(err) => {
-								password.set_error(
-									"Invalid Password".into(),
-									format!("Failed to decrypt database: {err}"),
-								);
-								continue;
-							}
-						}
-					};
-					self.password.set(pwd).unwrap();
-					self.explorer
-						.set(Explorer::new(std::mem::take(&mut data.entries)))
-						.map_err(|_| ())
-						.unwrap();
-					self.data.set(data).unwrap();
-					self.password_prompt = None;
-					continue;
+	/// Lock the session if no key was pressed for too long
+	fn check_idle(&mut self) {
+		let Some(duration) = self.lock_after else {
+			return;
+		};
+		if self.explorer.get().is_some() && self.last_input.elapsed() >= duration {
+			self.lock();
+			status::post("Locked after a period without input");
+		}
+	}
+
+	/// Send a mouse event to the top-most component, in the order keys are dispatched
+	///
+	/// Returns the keys of the equivalent keyboard action, see [`Component::mouse_input`].
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		if self.help.is_some() || self.lock_prompt.is_some() {
+			return vec![];
+		}
+		if let Some(form) = &mut self.password_prompt {
+			return FormExt::mouse_input(form, event);
+		}
+		if let Some(form) = &mut self.pin_prompt {
+			return FormExt::mouse_input(form, event);
+		}
+		if self.recover.is_some() || self.message.is_some() || self.host_prompt.is_some() {
+			return vec![];
+		}
+		if let Some(keys) = self.screens.mouse_input(event) {
+			return keys;
+		}
+		match self.explorer.get_mut() {
+			Some(explorer) => explorer.mouse_input(event),
+			None => vec![],
+		}
+	}
+
+	/// Send pasted text to the focused text input, in the order keys are dispatched
+	fn paste(&mut self, text: &str) {
+		if self.help.is_some() || self.lock_prompt.is_some() {
+			return;
+		}
+		if let Some(form) = &mut self.password_prompt {
+			FormExt::paste(form, text);
+			return;
+		}
+		if let Some(form) = &mut self.pin_prompt {
+			FormExt::paste(form, text);
+			return;
+		}
+		if self.recover.is_some() || self.message.is_some() || self.host_prompt.is_some() {
+			return;
+		}
+		if self.screens.paste(text).is_some() {
+			return;
+		}
+		if let Some(explorer) = self.explorer.get_mut() {
+			explorer.paste(text);
+		}
+	}
+
+	/// Update the state that changes with time: idle lock, expiry of the cached key and the components
+	fn tick(&mut self) {
+		self.check_idle();
+		// Removed even while the session stays unlocked
+		if self
+			.keyring_expires_at
+			.is_some_and(|expires_at| expires_at <= Utc::now())
+		{
+			self.forget_key();
+		}
+		if let Some(explorer) = self.explorer.get_mut() {
+			explorer.tick();
+		}
+	}
+
+	/// Handle a key, returns whether the database was closed
+	fn input(&mut self, key: KeyEvent, terminal: &mut DefaultTerminal) -> Result<bool> {
+		// Help, available from every screen
+		if key.code == KeyCode::F(1) && self.help.is_none() {
+			self.help = Some(HelpView::default());
+			return Ok(false);
+		}
+		if let Some(view) = &mut self.help {
+			if !view.input(&key) {
+				self.help = None;
+			}
+			return Ok(false);
+		}
+		// Read-only prompt
+		if let Some(confirm) = &mut self.lock_prompt {
+			confirm.input(&key);
+			match confirm.submit() {
+				Some(true) => self.lock_prompt = None,
+				Some(false) => return Ok(true),
+				None => {}
+			}
+			return Ok(false);
+		}
+		// Password prompt
+		if let Some(password) = &mut self.password_prompt {
+			match password.input_form(&key) {
+				Some(FormSignal::Return) => {}
+				Some(FormSignal::Exit) => return Ok(true),
+				_ => return Ok(false),
+			}
+			let Some(pwd) = password.submit() else {
+				return Ok(true);
+			};
+			let key_file = password.key_file();
+			if password.is_new() && key_file.is_some() {
+				self.db.kdf = KdfData::KeyFile {
+					kdf: Box::new(self.db.kdf.clone()),
+				};
+			}
+			let key = match derive_key(&self.db.kdf, pwd.as_str(), key_file.as_deref()) {
+				Ok(key) => key,
+				Err(err) => {
+					password.set_error(
+						"Invalid Password".into(),
+						format!("Failed to derive key: {err}"),
+					);
+					return Ok(false);
 				}
-				// Message
-				if let Some(message) = &mut self.message {
-					if !message.input(&key) {
-						self.message = None;
+			};
+			let data = if password.is_new() {
+				// Create default data
+				Data::default()
+			} else {
+				// Decrypt data
+				match decrypt_database_with_key(&self.db, &key) {
+					Ok(data) => data,
+					Err(err) => {
+						password.set_error(
+							"Invalid Password".into(),
+							format!("Failed to decrypt database: {err}"),
+						);
+						return Ok(false);
 					}
-					continue;
 				}
-				// Explorer
-				if let Some(explorer) = self.explorer.get_mut() {
-					if explorer.input(&key) {
-						continue;
+			};
+			self.key_file = key_file;
+			self.password_prompt = None;
+			self.open_session(key, data);
+			self.cache_key();
+			return Ok(false);
+		}
+		// PIN prompt
+		if let Some(prompt) = &mut self.pin_prompt {
+			match prompt.input_form(&key) {
+				Some(FormSignal::Return) => {}
+				Some(FormSignal::Exit) => {
+					self.pin_prompt = None;
+					self.password_prompt = Some(PasswordPrompt::new(self.name.clone(), false));
+					return Ok(false);
+				}
+				_ => return Ok(false),
+			}
+			let quick_unlock = self.quick_unlock.as_mut().unwrap();
+			let unlocked = quick_unlock.unlock(&prompt.submit()).and_then(|key| {
+				let data = decrypt_database_with_key(&self.db, &key)
+					.map_err(|err| format!("Failed to decrypt database: {err}"))?;
+				Ok((key, data))
+			});
+			match unlocked {
+				Ok((key, data)) => {
+					self.pin_prompt = None;
+					self.open_session(key, data);
+				}
+				Err(err) if quick_unlock.attempts_left() == 0 => {
+					self.quick_unlock = None;
+					self.pin_prompt = None;
+					let mut password = PasswordPrompt::new(self.name.clone(), false);
+					password.set_error("Quick Unlock".into(), err);
+					self.password_prompt = Some(password);
+				}
+				Err(err) => prompt.set_error("Invalid PIN".into(), err),
+			}
+			return Ok(false);
+		}
+		// Journal recovery
+		if let Some((confirm, _)) = &mut self.recover {
+			confirm.input(&key);
+			match confirm.submit() {
+				Some(true) => {
+					let (_, mut data) = self.recover.take().unwrap();
+					let mut explorer = Explorer::new(
+						std::mem::take(&mut data.entries),
+						std::mem::take(&mut data.tags),
+						std::mem::take(&mut data.log),
+					)
+					.with_trash(std::mem::take(&mut data.trash));
+					explorer.restore_session(&data.session);
+					explorer.set_title(self.title());
+					*self.explorer.get_mut().unwrap() = explorer;
+					*self.data.get_mut().unwrap() = data;
+					self.set_dirty(true);
+				}
+				Some(false) => {
+					self.recover = None;
+					if let Err(err) = remove_journal(&self.path) {
+						self.error(format!("Failed to remove journal: {err}"));
 					}
 				}
-
-				if let KeyCode::Char('q') = key.code {
-					let (password, data, mut db) = self.get_data();
-					db.blob = match encrypt_database(&data, &self.db, &password) {
-						Ok(blob) => blob,
-						Err(err) => {
-							self.error(format!("Failed to encrypt database: {err}"));
-							continue;
+				None => {}
+			}
+			return Ok(false);
+		}
+		// Message
+		if let Some(message) = &mut self.message {
+			if message.input(&key) {
+				self.message = None;
+			}
+			return Ok(false);
+		}
+		// Browser extension approval
+		if let Some((confirm, site)) = &mut self.host_prompt {
+			confirm.input(&key);
+			let answer = match confirm.submit() {
+				Some(true) => host::approve(site),
+				Some(false) => host::deny(),
+				None => return Ok(false),
+			};
+			self.host_prompt = None;
+			if let Err(err) = answer {
+				self.error(err);
+			}
+			return Ok(false);
+		}
+		// Screens over the explorer
+		match self.screens.input(&key) {
+			Some(ScreenSignal::Submitted) => {
+				self.submit_screen();
+				return Ok(false);
+			}
+			Some(_) => return Ok(false),
+			None => {}
+		}
+		// Save
+		if key.code == KeyCode::Char('s')
+			&& key.modifiers.contains(KeyModifiers::CONTROL)
+			&& self.explorer.get().is_some()
+		{
+			match self.save() {
+				Ok(()) => status::post(format!("Saved '{}'", self.path.display())),
+				Err(err) => self.error(err),
+			}
+			return Ok(false);
+		}
+		// Explorer
+		if let Some(explorer) = self.explorer.get_mut() {
+			if explorer.input(&key) {
+				// External editor
+				let mut error = None;
+				if let Some(entry) = explorer.take_external_edit() {
+					let tags = explorer.tags();
+					match suspend(terminal, || edit_entry(&entry, &tags))? {
+						Ok(Some(edited)) => {
+							error = explorer.apply_external_edit(&entry.name, edited).err()
 						}
-					};
-					if let Err(err) = save_database(&db, &self.path) {
-						self.error(format!("Failed to save database: {err}"));
-						continue;
+						Ok(None) => {}
+						Err(err) => error = Some(err),
+					}
+				}
+				if explorer.take_modified() {
+					if let Some(http) = &self.http {
+						http.update(explorer.submit());
 					}
-					return Ok(());
+					self.commit_changes();
+				}
+				if let Some(err) = error {
+					self.error(err);
+				}
+				return Ok(false);
+			}
+		}
+
+		match key.code {
+			KeyCode::Char('q') if self.read_only() => return Ok(true),
+			KeyCode::Char('q') => {
+				if let Err(err) = self.save() {
+					self.error(err);
+					return Ok(false);
 				}
+				return Ok(true);
+			}
+			KeyCode::Char('P') if self.read_only() => {
+				self.error("Database is opened read-only".into())
+			}
+			KeyCode::Char('P') => self.screens.push(AppScreen::ChangePassword(ChangePassword::new(
+				self.db.kdf.yubikey_slot(),
+			))),
+			KeyCode::Char('E') => self.screens.push(AppScreen::Export(ExportForm::new())),
+			KeyCode::Char('U') => self
+				.screens
+				.push(AppScreen::QuickUnlockSetup(QuickUnlockSetup::new())),
+			KeyCode::Char('D') if self.read_only() => {
+				self.error("Database is opened read-only".into())
 			}
+			KeyCode::Char('D') => {
+				let metadata = self.db.metadata.clone().unwrap_or_else(|| DatabaseMetadata {
+					created_at: self.data.get().unwrap().created_at,
+					..Default::default()
+				});
+				self.screens
+					.push(AppScreen::Properties(DatabaseProperties::new(metadata)));
+			}
+			KeyCode::Char('S') => {
+				let entries = self.explorer.get().unwrap().submit();
+				let size = std::fs::metadata(&self.path).map(|meta| meta.len()).ok();
+				self.screens
+					.push(AppScreen::Stats(StatsView::new(Stats::compute(&entries, size))));
+			}
+			KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+				self.lock()
+			}
+			KeyCode::Char('?') => self.help = Some(HelpView::default()),
+			_ => {}
 		}
+		Ok(false)
 	}
 
-	fn draw(&self, frame: &mut Frame) {
+	fn draw(&self, frame: &mut Frame, area: Rect) {
+		if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+			let notice = Text::from(vec![
+				Line::from("Terminal too small".bold()),
+				Line::from(format!(
+					"{}x{}, needs at least {MIN_WIDTH}x{MIN_HEIGHT}",
+					area.width, area.height
+				)),
+			])
+			.centered();
+			let [area] = area.layout(&Layout::vertical([Constraint::Length(2)]).flex(Flex::Center));
+			frame.render_widget(Paragraph::new(notice).wrap(Wrap { trim: true }), area);
+			return;
+		}
+
+		let [main_area, status_area] =
+			area.layout(&Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]));
 		let mut overlays = vec![];
 		let mut ctx = ComponentRenderCtx {
-			area: frame.area(),
+			area: main_area,
 			selected: false,
 			queue: &mut overlays,
 			depth: 0,
 			cursor: None,
 		};
+		// Read-only prompt
+		if let Some(confirm) = &self.lock_prompt {
+			ctx.selected = true;
+			confirm.render(frame, &mut ctx);
+		}
 		// Password prompt
-		if let Some(password) = &self.password_prompt {
+		else if let Some(password) = &self.password_prompt {
 			ctx.selected = true;
 			password.render_form(frame, &mut ctx);
 		}
+		// PIN prompt
+		else if let Some(prompt) = &self.pin_prompt {
+			let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
+			let vertical =
+				Layout::vertical([Constraint::Length(prompt.height() + 1)]).flex(Flex::Center);
+			let [area] = area.layout(&horizontal);
+			let [area] = area.layout(&vertical);
+			ctx.area = area;
+			ctx.selected = true;
+			prompt.render_form(frame, &mut ctx);
+		}
 		// Message
 		else if let Some(message) = &self.message {
 			ctx.selected = true;
@@ -201,6 +1128,41 @@ impl App {
 		// Explorer
 		else if let Some(explorer) = self.explorer.get() {
 			explorer.render(frame, &mut ctx);
+			// Journal recovery
+			if let Some((confirm, _)) = &self.recover {
+				ctx.area = area;
+				confirm.render(frame, &mut ctx);
+			}
+			// Screens
+			ctx.area = area;
+			self.screens.render(frame, &mut ctx);
+			// Browser extension approval
+			if let Some((confirm, _)) = &self.host_prompt {
+				ctx.area = area;
+				ctx.selected = true;
+				confirm.render(frame, &mut ctx);
+			}
+		}
+
+		// Status bar
+		let unlocked = self.explorer.get().is_some();
+		let status = StatusBar {
+			name: &self.name,
+			dirty: self.dirty,
+			read_only: self.read_only(),
+			lock_in: self
+				.lock_after
+				.filter(|_| unlocked)
+				.map(|duration| duration.saturating_sub(self.last_input.elapsed())),
+		};
+		ctx.area = status_area;
+		status.render(frame, &mut ctx);
+
+		// Help
+		if let Some(view) = &self.help {
+			ctx.area = area;
+			view.render(frame, &mut ctx);
+			return;
 		}
 
 		if let Some((_, cursor)) = ctx.cursor {
@@ -209,9 +1171,341 @@ impl App {
 	}
 }
 
-fn print_usage(program: &str, opts: Options) {
-	let brief = format!("Usage: {program} [OPTIONS] path/to/database.pk");
-	print!("{}", opts.usage(&brief));
+/// Databases opened in the session, each in a tab with its own unlock state
+struct Tabs {
+	apps: Vec<App>,
+	active: usize,
+	/// Destination prompt of an entry moved to another database
+	transfer: Option<TransferForm>,
+	/// Area of the tab bar, for the mouse
+	bar_area: Cell<Rect>,
+	/// Keys of mouse actions not yet processed
+	pending: VecDeque<KeyEvent>,
+}
+
+impl Tabs {
+	fn new(apps: Vec<App>) -> Self {
+		let mut tabs = Self {
+			apps,
+			active: 0,
+			transfer: None,
+			bar_area: Cell::default(),
+			pending: VecDeque::new(),
+		};
+		tabs.select(0);
+		tabs
+	}
+
+	/// Switch to the tab at `index`, if it exists
+	fn select(&mut self, index: usize) {
+		if let Some(app) = self.apps.get(index) {
+			self.active = index;
+			hooks::set_database(&app.path);
+		}
+	}
+
+	/// Lock every unlocked database
+	fn lock(&mut self) {
+		for app in &mut self.apps {
+			if app.explorer.get().is_some() {
+				app.lock();
+			}
+		}
+	}
+
+	/// Lock the sessions and clear the screen, then stop the process until it is continued
+	///
+	/// Decrypted values are then never left on screen or in the memory of a stopped process.
+	fn stop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+		self.lock();
+		terminal.clear()?;
+		suspend(terminal, signals::stop)
+	}
+
+	/// Update the state that changes with time: clipboard clearing, browser extension requests
+	/// and every database
+	fn tick(&mut self) {
+		clipboard::clear_expired();
+		attachment::remove_expired();
+		for app in &mut self.apps {
+			app.tick();
+		}
+		// Requests are approved in the database in view
+		self.apps[self.active].check_host_request();
+	}
+
+	/// Ask where to move `entry`, among the other unlocked and writable databases
+	fn open_transfer(&mut self, entry: Entry) {
+		let targets = self
+			.apps
+			.iter()
+			.enumerate()
+			.filter(|(index, app)| {
+				*index != self.active && app.explorer.get().is_some() && !app.read_only()
+			})
+			.map(|(index, app)| (index, app.title()))
+			.collect::<Vec<_>>();
+		if targets.is_empty() {
+			self.apps[self.active]
+				.error("Open and unlock another database to move entries to it".into());
+			return;
+		}
+		self.transfer = Some(TransferForm::new(entry, targets));
+	}
+
+	/// Move or copy the entry of the transfer form to its destination
+	fn transfer(&mut self) {
+		let Some(form) = self.transfer.take() else {
+			return;
+		};
+		let Some(transfer) = form.submit() else {
+			return;
+		};
+		let name = form.entry().name.clone();
+		let target = self.apps[transfer.target].title();
+		if let Err(err) = self.apps[transfer.target].add_entry(form.entry().clone()) {
+			self.apps[self.active].error(err);
+			return;
+		}
+		if transfer.keep {
+			status::post(format!("Copied '{name}' to '{target}'"));
+			return;
+		}
+		match self.apps[self.active].remove_entry(&name) {
+			Ok(()) => status::post(format!("Moved '{name}' to '{target}'")),
+			Err(err) => self.apps[self.active].error(err),
+		}
+	}
+
+	/// Handle a key, returns whether every database was closed
+	fn input(&mut self, key: KeyEvent, terminal: &mut DefaultTerminal) -> Result<bool> {
+		// Job control, the terminal does not send SIGTSTP in raw mode
+		if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+			self.stop(terminal)?;
+			return Ok(false);
+		}
+		// Entry transfer
+		if let Some(form) = &mut self.transfer {
+			match form.input_form(&key) {
+				Some(FormSignal::Return) => self.transfer(),
+				Some(FormSignal::Exit) => self.transfer = None,
+				_ => {}
+			}
+			return Ok(false);
+		}
+		// Tab switching
+		if key.modifiers.contains(KeyModifiers::ALT) {
+			let count = self.apps.len();
+			let index = match key.code {
+				KeyCode::Left => Some((self.active + count - 1) % count),
+				KeyCode::Right => Some((self.active + 1) % count),
+				KeyCode::Char(c @ '1'..='9') => Some(c as usize - '1' as usize),
+				_ => None,
+			};
+			if let Some(index) = index {
+				self.select(index);
+				return Ok(false);
+			}
+		}
+
+		let active = self.active;
+		if self.apps[active].input(key, terminal)? {
+			self.apps.remove(active);
+			if self.apps.is_empty() {
+				return Ok(true);
+			}
+			self.select(active.min(self.apps.len() - 1));
+			return Ok(false);
+		}
+		if let Some(entry) = self.apps[active]
+			.explorer
+			.get_mut()
+			.and_then(Explorer::take_transfer)
+		{
+			self.open_transfer(entry);
+		}
+		Ok(false)
+	}
+
+	/// Send a mouse event to the transfer form, the tab bar or the database in view
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		if let Some(form) = &mut self.transfer {
+			return FormExt::mouse_input(form, event);
+		}
+		let area = self.bar_area.get();
+		if !area.contains(Position::new(event.column, event.row)) {
+			return self.apps[self.active].mouse_input(event);
+		}
+		if event.kind == MouseEventKind::Down(MouseButton::Left) {
+			let mut end = area.x;
+			let clicked = self.labels().iter().position(|label| {
+				end += label.width() as u16;
+				event.column < end
+			});
+			if let Some(index) = clicked {
+				self.select(index);
+			}
+		}
+		vec![]
+	}
+
+	/// Send pasted text to the transfer form or the database in view
+	fn paste(&mut self, text: &str) {
+		match &mut self.transfer {
+			Some(form) => {
+				FormExt::paste(form, text);
+			}
+			None => self.apps[self.active].paste(text),
+		}
+	}
+
+	/// Labels of the tab bar, with the position and lock state of each database
+	fn labels(&self) -> Vec<Span<'static>> {
+		self.apps
+			.iter()
+			.enumerate()
+			.map(|(index, app)| {
+				let icon = if app.explorer.get().is_some() {
+					icons::UNLOCKED
+				} else {
+					icons::LOCKED
+				};
+				let label = Span::from(format!(" {} {icon} {} ", index + 1, app.title()));
+				if index == self.active {
+					label.bold().fg(theme().base).bg(theme().accent)
+				} else {
+					label.fg(theme().text)
+				}
+			})
+			.collect()
+	}
+
+	fn draw(&self, frame: &mut Frame) {
+		let mut area = frame.area();
+		// The tab bar is only shown with several databases
+		if self.apps.len() > 1 {
+			let [bar_area, app_area] =
+				area.layout(&Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]));
+			frame.render_widget(Line::from(self.labels()).bg(theme().help_bg), bar_area);
+			self.bar_area.set(bar_area);
+			area = app_area;
+		} else {
+			self.bar_area.set(Rect::default());
+		}
+		self.apps[self.active].draw(frame, area);
+
+		// Entry transfer
+		if let Some(form) = &self.transfer {
+			let mut overlays = vec![];
+			let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
+			let vertical =
+				Layout::vertical([Constraint::Length(form.height() + 1)]).flex(Flex::Center);
+			let [form_area] = area.layout(&horizontal);
+			let [form_area] = form_area.layout(&vertical);
+			let mut ctx = ComponentRenderCtx {
+				area: form_area,
+				selected: true,
+				queue: &mut overlays,
+				depth: 0,
+				cursor: None,
+			};
+			form.render_form(frame, &mut ctx);
+			if let Some((_, cursor)) = ctx.cursor {
+				frame.set_cursor_position(cursor);
+			}
+		}
+	}
+
+	fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+		let mut next_tick = Instant::now();
+		loop {
+			// Unsaved changes are kept in the journals
+			if signals::take_hangup() {
+				self.lock();
+				return Ok(());
+			}
+			if signals::take_suspend() {
+				self.stop(&mut terminal)?;
+			}
+			if Instant::now() >= next_tick {
+				self.tick();
+				next_tick = Instant::now() + TICK_INTERVAL;
+			}
+			if self.pending.is_empty() {
+				terminal.draw(|frame| self.draw(frame))?;
+			}
+
+			let key = match self.pending.pop_front() {
+				Some(key) => key,
+				None => {
+					// Wait for an event until the next tick
+					if !event::poll(next_tick.saturating_duration_since(Instant::now()))? {
+						continue;
+					}
+					match event::read()? {
+						Event::Key(key) => {
+							self.apps[self.active].last_input = Instant::now();
+							key
+						}
+						// Layouts are computed on every draw, which also resizes the buffers
+						Event::Resize(..) => continue,
+						Event::FocusLost if config().lock_on_focus_lost => {
+							self.lock();
+							continue;
+						}
+						// Pasted text only goes to text inputs, as is
+						Event::Paste(text) => {
+							self.apps[self.active].last_input = Instant::now();
+							self.paste(&text);
+							continue;
+						}
+						// Clicks and scrolling are replayed as the equivalent keys
+						Event::Mouse(mouse) => {
+							self.apps[self.active].last_input = Instant::now();
+							let keys = self.mouse_input(&mouse);
+							self.pending.extend(keys);
+							continue;
+						}
+						_ => continue,
+					}
+				}
+			};
+			if self.input(key, &mut terminal)? {
+				return Ok(());
+			}
+		}
+	}
+}
+
+/// Enable the terminal events handled by the interface
+fn enable_events() -> Result<()> {
+	crossterm::execute!(std::io::stdout(), EnableBracketedPaste, EnableFocusChange)?;
+	// Capturing the mouse prevents selecting text in the terminal
+	if config().mouse {
+		crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+	}
+	Ok(())
+}
+
+fn disable_events() -> Result<()> {
+	crossterm::execute!(
+		std::io::stdout(),
+		DisableBracketedPaste,
+		DisableFocusChange,
+		DisableMouseCapture
+	)?;
+	Ok(())
+}
+
+/// Leave the terminal to the program run by `f`, then restore the interface
+fn suspend<T>(terminal: &mut DefaultTerminal, f: impl FnOnce() -> T) -> Result<T> {
+	disable_events()?;
+	ratatui::restore();
+	let result = f();
+	*terminal = ratatui::init();
+	enable_events()?;
+	terminal.clear()?;
+	Ok(result)
 }
 
 fn print_version() {
@@ -229,42 +1523,173 @@ PassK version: {}\n",
 	)
 }
 
-fn main() -> Result<()> {
-	let args: Vec<String> = env::args().collect();
-	let program = &args[0];
-
-	let mut opts = Options::new();
-	opts.optflag("h", "help", "Display help menu");
-	opts.optflag("v", "version", "Display program version");
+/// Print the header of the database at `path`, without decrypting it
+fn print_info(path: &Path) -> Result<(), String> {
+	let header = load_header(path)?;
+	println!("Path: {}", path.display());
+	if let Some(metadata) = &header.metadata {
+		println!("Name: {}", metadata.name);
+		println!("Description: {}", metadata.description);
+		println!("Created: {}", metadata.created_at.format("%Y-%m-%d %H:%M:%S"));
+		println!("Modified: {}", metadata.modified_at.format("%Y-%m-%d %H:%M:%S"));
+	}
+	println!("Cipher: {}", header.cipher.name());
+	println!("KDF: {}", header.kdf.describe());
+	println!("Blob: {} bytes", header.blob_len);
+	Ok(())
+}
 
-	let m = match opts.parse(&args[1..]) {
-		Ok(m) => m,
-		Err(err) => {
-			panic!("{err}");
+/// Let the user pick one of the `recent` databases, `None` if cancelled
+fn pick_recent(recent: Vec<PathBuf>) -> Result<Option<PathBuf>> {
+	let mut terminal = ratatui::init();
+	let mut view = RecentView::new(recent);
+	// The terminal is restored even when drawing or reading events fails
+	let result = (|| -> Result<Option<PathBuf>> {
+		loop {
+			terminal.draw(|frame| {
+				let mut overlays = vec![];
+				let mut ctx = ComponentRenderCtx {
+					area: frame.area(),
+					selected: true,
+					queue: &mut overlays,
+					depth: 0,
+					cursor: None,
+				};
+				view.render(frame, &mut ctx);
+			})?;
+			if let Event::Key(key) = event::read()? {
+				if !view.input(&key) {
+					return Ok(view.submit().map(Path::to_path_buf));
+				}
+			}
 		}
-	};
-	if m.opt_present("h") {
-		print_usage(&program, opts);
+	})();
+	ratatui::restore();
+	result
+}
+
+fn main() -> Result<()> {
+	let cli = Cli::parse();
+	if cli.version {
+		print_version();
 		return Ok(());
 	}
-	if m.opt_present("v") {
-		print_version();
+	if let Some(dir) = cli.config {
+		if !dir.is_dir() {
+			eprintln!("Configuration directory '{}' does not exist", dir.display());
+			std::process::exit(1);
+		}
+		config::set_config_dir(dir);
+	}
+	if let Some(command) = cli.command {
+		if let Err(err) = commands::run(command) {
+			eprintln!("{err}");
+			std::process::exit(1);
+		}
 		return Ok(());
 	}
-	if m.free.len() != 1
-	{
-		eprintln!("Expected path to a database file");
-		return Ok(())
+
+	let mut paths = cli.paths;
+	if paths.is_empty() {
+		paths.extend(config().database.clone());
+	}
+	if paths.is_empty() {
+		let recent = state::recent();
+		if !recent.is_empty() {
+			match pick_recent(recent)? {
+				Some(path) => paths.push(path),
+				None => return Ok(()),
+			}
+		}
+	}
+	if paths.is_empty() {
+		if let Some(err) = config::load_error() {
+			eprintln!("{err}");
+		}
+		eprintln!("No database given, pass its path or set `database` in config.toml");
+		std::process::exit(1);
+	}
+	for path in &paths {
+		if cli.create && path.exists() {
+			eprintln!("Database '{}' already exists", path.display());
+			std::process::exit(1);
+		}
+		// Missing databases are created, unless they are only inspected
+		if !path.exists() && (cli.readonly || cli.info || cli.lock) {
+			eprintln!("Database '{}' does not exist", path.display());
+			std::process::exit(1);
+		}
 	}
 
-	let path = PathBuf::from(&m.free[0]);
+	if cli.info {
+		for (index, path) in paths.iter().enumerate() {
+			if index != 0 {
+				println!();
+			}
+			if let Err(err) = print_info(path) {
+				eprintln!("{err}");
+				std::process::exit(1);
+			}
+		}
+		return Ok(());
+	}
+	if cli.lock {
+		for path in &paths {
+			if let Err(err) = agent::stop(path).and_then(|_| keyring::clear(path)) {
+				eprintln!("{err}");
+				std::process::exit(1);
+			}
+		}
+		return Ok(());
+	}
 
-	let name = &args[1][args[1].rfind('/').unwrap_or(0)..];
+	let cipher = cli.cipher.unwrap_or(CipherData::XChaCha20Poly1305V1 {});
+	let keyring = cli.keyring.map(|minutes| TimeDelta::minutes(minutes.into()));
+	let lock_after = Some(cli.lock_after.unwrap_or(config().lock_after))
+		.filter(|seconds| *seconds != 0)
+		.map(Duration::from_secs);
+	clipboard::set_clear_after(
+		Some(cli.clear_clipboard.unwrap_or(config().clear_clipboard))
+			.filter(|seconds| *seconds != 0)
+			.map(Duration::from_secs),
+	);
+	// Open the databases before taking over the terminal, so errors are readable
+	let mut apps = vec![];
+	for (index, path) in paths.into_iter().enumerate() {
+		let name = path
+			.file_name()
+			.map(|name| name.to_string_lossy().into_owned())
+			.unwrap_or_else(|| path.display().to_string());
+		// The HTTP API serves the first database only
+		let http = cli.http.filter(|_| index == 0);
+		match App::new(
+			name,
+			path,
+			cipher.clone(),
+			cli.yubikey,
+			cli.readonly,
+			keyring,
+			http,
+		) {
+			Ok(app) => {
+				// The list of recent databases is a convenience, failing to update it is ignored
+				let _ = state::add_recent(&app.path);
+				apps.push(app.with_lock_after(lock_after))
+			}
+			Err(err) => {
+				eprintln!("{err}");
+				std::process::exit(1);
+			}
+		}
+	}
 
 	let terminal = ratatui::init();
-	let app_result = App::new(name.into(), path)
-		.map_err(|err| eyre::eyre!(err))?
-		.run(terminal);
+	enable_events()?;
+	signals::install();
+	let app_result = Tabs::new(apps).run(terminal);
+	clipboard::clear_pending();
+	attachment::remove_opened();
+	disable_events()?;
 	ratatui::restore();
 	app_result
 }
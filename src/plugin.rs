@@ -0,0 +1,322 @@
+//! External plugins
+//!
+//! Plugins are executables placed in `$XDG_CONFIG_HOME/passk/plugins`. Each request spawns the
+//! plugin, writes a single JSON request line to its stdin and reads a single JSON response from
+//! its stdout:
+//! - `{"method":"describe"}` -> `{"name":..,"importers":[..],"exporters":[..],"renderers":[..]}`
+//! - `{"method":"import","format":..,"path":..}` -> `{"entries":[..]}`
+//! - `{"method":"export","format":..,"path":..,"entries":[..]}` -> `{}`
+//! - `{"method":"render","field":{..}}` -> `{"text":..}`
+//!
+//! Any response may instead be `{"error":..}`.
+//!
+//! Fields are rendered in the background, and plugins that do not answer a `describe` or `render`
+//! request within [`REQUEST_TIMEOUT`] are killed.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::config_dir;
+use crate::data::entry::Entry;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::data::import::imported_entry;
+use crate::data::import::imported_field;
+
+/// Time given to a plugin to answer the requests made while drawing the interface
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Interval between checks of whether a plugin exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Discovered plugins
+pub static PLUGINS: LazyLock<Vec<Plugin>> = LazyLock::new(discover);
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PluginDescription {
+	pub name: String,
+	/// Import formats provided by the plugin
+	#[serde(default)]
+	pub importers: Vec<String>,
+	/// Export formats provided by the plugin
+	#[serde(default)]
+	pub exporters: Vec<String>,
+	/// Binary field mimetypes the plugin can render
+	#[serde(default)]
+	pub renderers: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+enum Request<'a> {
+	Describe,
+	Import {
+		format: &'a str,
+		path: &'a Path,
+	},
+	Export {
+		format: &'a str,
+		path: &'a Path,
+		entries: &'a [Entry],
+	},
+	Render {
+		field: &'a Field,
+	},
+}
+
+#[derive(Deserialize)]
+struct PluginField {
+	name: String,
+	value: FieldValue,
+	#[serde(default)]
+	hidden: bool,
+}
+
+#[derive(Deserialize)]
+struct PluginEntry {
+	name: String,
+	#[serde(default)]
+	fields: Vec<PluginField>,
+	#[serde(default)]
+	tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ImportResponse {
+	entries: Vec<PluginEntry>,
+}
+
+#[derive(Deserialize)]
+struct RenderResponse {
+	text: String,
+}
+
+pub struct Plugin {
+	path: PathBuf,
+	pub description: PluginDescription,
+	/// Rendered fields, keyed by hash, `None` while the plugin runs or when it failed
+	render_cache: Mutex<HashMap<u64, Option<String>>>,
+}
+
+impl Plugin {
+	/// Run a single request
+	fn request<R: for<'de> Deserialize<'de>>(
+		&self,
+		request: &Request,
+		timeout: Option<Duration>,
+	) -> Result<R, String> {
+		request_plugin(&self.path, request, timeout)
+	}
+
+	pub fn import(&self, format: &str, path: &Path) -> Result<Vec<Entry>, String> {
+		let response: ImportResponse = self.request(&Request::Import { format, path }, None)?;
+		Ok(response
+			.entries
+			.into_iter()
+			.map(|ent| {
+				let fields = ent
+					.fields
+					.into_iter()
+					.map(|field| imported_field(&field.name, field.value, field.hidden))
+					.collect();
+				imported_entry(ent.name, fields, ent.tags, None, None)
+			})
+			.collect())
+	}
+
+	pub fn export(&self, format: &str, path: &Path, entries: &[Entry]) -> Result<(), String> {
+		let _: serde_json::Value = self.request(
+			&Request::Export {
+				format,
+				path,
+				entries,
+			},
+			None,
+		)?;
+		Ok(())
+	}
+
+	/// Text of `field` rendered by the plugin
+	///
+	/// The plugin is run in the background the first time a value is rendered, `None` is returned
+	/// until it answers. Failures are cached too, so each value is only sent to the plugin once.
+	pub fn render(&'static self, field: &Field) -> Option<String> {
+		let mut hasher = DefaultHasher::new();
+		serde_json::to_string(&field.value).ok()?.hash(&mut hasher);
+		let hash = hasher.finish();
+		let mut cache = self.render_cache.lock().unwrap();
+		if let Some(text) = cache.get(&hash) {
+			return text.clone();
+		}
+		cache.insert(hash, None);
+
+		let field = field.clone();
+		std::thread::spawn(move || {
+			let response: Result<RenderResponse, String> =
+				self.request(&Request::Render { field: &field }, Some(REQUEST_TIMEOUT));
+			if let Ok(response) = response {
+				self.render_cache
+					.lock()
+					.unwrap()
+					.insert(hash, Some(response.text));
+			}
+		});
+		None
+	}
+}
+
+/// Wait at most `timeout` for `child` to exit, returns `None` after killing it if it did not
+fn wait_timeout(child: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+	let deadline = Instant::now() + timeout;
+	while Instant::now() < deadline {
+		if let Some(status) = child.try_wait()? {
+			return Ok(Some(status));
+		}
+		std::thread::sleep(POLL_INTERVAL);
+	}
+	let _ = child.kill();
+	child.wait()?;
+	Ok(None)
+}
+
+fn request_plugin<R: for<'de> Deserialize<'de>>(
+	path: &Path,
+	request: &Request,
+	timeout: Option<Duration>,
+) -> Result<R, String> {
+	let mut child = Command::new(path)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.map_err(|err| format!("Failed to run plugin '{}': {err}", path.display()))?;
+	let mut payload = serde_json::to_vec(request)
+		.map_err(|err| format!("Failed to serialize plugin request: {err}"))?;
+	payload.push(b'\n');
+
+	// The pipes are handled in threads so that a plugin that neither reads its input nor exits
+	// can still be timed out. Errors writing the request show in the response
+	let mut stdin = child.stdin.take().unwrap();
+	std::thread::spawn(move || stdin.write_all(&payload));
+	let mut stdout = child.stdout.take().unwrap();
+	let reader = std::thread::spawn(move || {
+		let mut output = vec![];
+		stdout.read_to_end(&mut output).map(|_| output)
+	});
+	let status = match timeout {
+		Some(timeout) => wait_timeout(&mut child, timeout),
+		None => child.wait().map(Some),
+	}
+	.map_err(|err| format!("Failed to wait for plugin '{}': {err}", path.display()))?
+	.ok_or_else(|| format!("Plugin '{}' did not answer in time", path.display()))?;
+	if !status.success() {
+		return Err(format!("Plugin '{}' exited with {status}", path.display()));
+	}
+	let output = reader
+		.join()
+		.unwrap()
+		.map_err(|err| format!("Failed to read from plugin '{}': {err}", path.display()))?;
+
+	let response: serde_json::Value = serde_json::from_slice(&output)
+		.map_err(|err| format!("Invalid response from plugin '{}': {err}", path.display()))?;
+	if let Some(error) = response.get("error").and_then(|error| error.as_str()) {
+		return Err(format!("Plugin '{}': {error}", path.display()));
+	}
+	serde_json::from_value(response)
+		.map_err(|err| format!("Invalid response from plugin '{}': {err}", path.display()))
+}
+
+/// Directory plugins are loaded from
+pub fn plugin_dir() -> Option<PathBuf> {
+	config_dir().map(|dir| dir.join("plugins"))
+}
+
+/// Find and describe all plugins, plugins that fail to describe themselves are ignored
+fn discover() -> Vec<Plugin> {
+	let Some(dir) = plugin_dir() else {
+		return vec![];
+	};
+	let Ok(read_dir) = std::fs::read_dir(dir) else {
+		return vec![];
+	};
+	let mut paths = read_dir
+		.filter_map(|ent| ent.ok())
+		.map(|ent| ent.path())
+		.filter(|path| {
+			path.metadata()
+				.is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+		})
+		.collect::<Vec<_>>();
+	paths.sort();
+
+	paths
+		.into_iter()
+		.filter_map(|path| {
+			let description =
+				request_plugin(&path, &Request::Describe, Some(REQUEST_TIMEOUT)).ok()?;
+			Some(Plugin {
+				path,
+				description,
+				render_cache: Mutex::default(),
+			})
+		})
+		.collect()
+}
+
+/// Import formats provided by plugins: (plugin, format)
+pub static PLUGIN_IMPORTERS: LazyLock<Vec<(usize, &'static str)>> = LazyLock::new(|| {
+	PLUGINS
+		.iter()
+		.enumerate()
+		.flat_map(|(id, plugin)| {
+			plugin
+				.description
+				.importers
+				.iter()
+				.map(move |format| (id, format.as_str()))
+		})
+		.collect()
+});
+
+/// Export formats provided by plugins: (plugin, format)
+pub static PLUGIN_EXPORTERS: LazyLock<Vec<(usize, &'static str)>> = LazyLock::new(|| {
+	PLUGINS
+		.iter()
+		.enumerate()
+		.flat_map(|(id, plugin)| {
+			plugin
+				.description
+				.exporters
+				.iter()
+				.map(move |format| (id, format.as_str()))
+		})
+		.collect()
+});
+
+/// Text of `field` rendered by a plugin able to, once it answered, see [`Plugin::render`]
+pub fn render_field(field: &Field) -> Option<String> {
+	let FieldValue::Binary { mimetype, .. } = &field.value else {
+		return None;
+	};
+	PLUGINS
+		.iter()
+		.find(|plugin| plugin.description.renderers.contains(mimetype))
+		.and_then(|plugin| plugin.render(field))
+}
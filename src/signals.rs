@@ -0,0 +1,52 @@
+//! Job control and hangup signals received by the interface
+//!
+//! Handlers only record the signals, the event loop checks them with [`take_suspend`] and
+//! [`take_hangup`] so the session is locked before the process stops or exits.
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static SUSPEND: AtomicBool = AtomicBool::new(false);
+static HANGUP: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn handle(signal: libc::c_int) {
+	match signal {
+		libc::SIGTSTP => SUSPEND.store(true, Ordering::SeqCst),
+		_ => HANGUP.store(true, Ordering::SeqCst),
+	}
+}
+
+/// Record SIGTSTP, SIGHUP and SIGTERM instead of stopping or terminating the process
+pub fn install() {
+	// SAFETY: the handler only stores to atomics, which is async-signal-safe
+	unsafe {
+		for signal in [libc::SIGTSTP, libc::SIGHUP, libc::SIGTERM] {
+			libc::signal(
+				signal,
+				handle as extern "C" fn(libc::c_int) as libc::sighandler_t,
+			);
+		}
+	}
+}
+
+/// Whether the process was asked to stop since the last call
+pub fn take_suspend() -> bool {
+	SUSPEND.swap(false, Ordering::SeqCst)
+}
+
+/// Whether the terminal was closed or the process asked to terminate
+pub fn take_hangup() -> bool {
+	HANGUP.swap(false, Ordering::SeqCst)
+}
+
+/// Stop the process until it is continued, like the default SIGTSTP action
+pub fn stop() {
+	// SAFETY: the default action is restored for the duration of the stop only
+	unsafe {
+		libc::signal(libc::SIGTSTP, libc::SIG_DFL);
+		libc::raise(libc::SIGTSTP);
+		libc::signal(
+			libc::SIGTSTP,
+			handle as extern "C" fn(libc::c_int) as libc::sighandler_t,
+		);
+	}
+}
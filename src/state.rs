@@ -0,0 +1,85 @@
+//! State kept between runs
+//!
+//! The state is stored in `$XDG_STATE_HOME/passk/state.json`: the recently opened databases,
+//! offered on startup when no database is given, and the order of the entries.
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::data::entry::EntrySort;
+
+/// Number of databases remembered
+const RECENT_COUNT: usize = 10;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct State {
+	/// Recently opened databases, most recent first
+	pub recent: Vec<PathBuf>,
+	/// Order of the entries in the explorer
+	pub sort: EntrySort,
+}
+
+/// Directory holding passk's state: `$XDG_STATE_HOME/passk`
+fn state_dir() -> Option<PathBuf> {
+	let state = std::env::var_os("XDG_STATE_HOME")
+		.map(PathBuf::from)
+		.or_else(|| {
+			std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state"))
+		})?;
+	Some(state.join("passk"))
+}
+
+fn read(path: &Path) -> Result<State, String> {
+	if !path.exists() {
+		return Ok(State::default());
+	}
+	let bytes =
+		std::fs::read(path).map_err(|err| format!("Failed to read '{}': {err}", path.display()))?;
+	serde_json::from_slice(&bytes)
+		.map_err(|err| format!("Failed to parse '{}': {err}", path.display()))
+}
+
+/// Saved state, the defaults are used when it is absent or invalid
+pub fn load() -> State {
+	state_dir()
+		.and_then(|dir| read(&dir.join("state.json")).ok())
+		.unwrap_or_default()
+}
+
+/// Modify the saved state with `f`
+pub fn update(f: impl FnOnce(&mut State)) -> Result<(), String> {
+	let dir = state_dir().ok_or("Neither XDG_STATE_HOME nor HOME is set")?;
+	let path = dir.join("state.json");
+	let mut state = read(&path).unwrap_or_default();
+	f(&mut state);
+
+	std::fs::create_dir_all(&dir)
+		.map_err(|err| format!("Failed to create '{}': {err}", dir.display()))?;
+	let content = serde_json::to_vec_pretty(&state)
+		.map_err(|err| format!("Failed to serialize the state: {err}"))?;
+	std::fs::write(&path, content)
+		.map_err(|err| format!("Failed to write '{}': {err}", path.display()))
+}
+
+/// Recently opened databases that still exist, most recent first
+pub fn recent() -> Vec<PathBuf> {
+	load()
+		.recent
+		.into_iter()
+		.filter(|database| database.exists())
+		.collect()
+}
+
+/// Move `database` to the top of the recent databases
+pub fn add_recent(database: &Path) -> Result<(), String> {
+	let database = std::path::absolute(database)
+		.map_err(|err| format!("Failed to resolve '{}': {err}", database.display()))?;
+	update(|state| {
+		state.recent.retain(|other| *other != database);
+		state.recent.insert(0, database);
+		state.recent.truncate(RECENT_COUNT);
+	})
+}
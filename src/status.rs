@@ -0,0 +1,28 @@
+//! Messages of the status bar
+//!
+//! Components post short messages with [`post`], the status bar shows the last one until it
+//! expires.
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Duration a message stays in the status bar
+pub const MESSAGE_DURATION: Duration = Duration::from_secs(5);
+
+/// Last posted message and the time it was posted at
+static MESSAGE: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// Show `message` in the status bar, replacing the previous one
+pub fn post(message: impl Into<String>) {
+	*MESSAGE.lock().unwrap() = Some((message.into(), Instant::now()));
+}
+
+/// Message to show, unless it expired
+pub fn message() -> Option<String> {
+	MESSAGE
+		.lock()
+		.unwrap()
+		.as_ref()
+		.filter(|(_, posted)| posted.elapsed() < MESSAGE_DURATION)
+		.map(|(message, _)| message.clone())
+}
@@ -1,9 +1,269 @@
+//! Colors of the interface
+//!
+//! The palette is selected by `theme` in the configuration, see [`theme`]. Its colors are
+//! reduced to the closest ones the terminal can display, see [`ColorDepth`].
+use std::sync::LazyLock;
+
 use ratatui::style::Color;
 
-pub const HELP_LINE_BG: Color = Color::from_u32(0x161616);
-/// Background for the entry editor: Color1, Color2, Selected
-pub const ENTRY_BG: [Color; 3] = [
-	Color::from_u32(0x322b44),
-	Color::from_u32(0x241f31),
-	Color::from_u32(0x5d507f),
+use crate::config::config;
+use crate::config::ThemeName;
+
+/// Colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+	TrueColor,
+	Indexed256,
+	Ansi16,
+}
+
+/// Approximate values of the 16 ANSI colors
+const ANSI_COLORS: [(Color, [u8; 3]); 16] = [
+	(Color::Black, [0, 0, 0]),
+	(Color::Red, [128, 0, 0]),
+	(Color::Green, [0, 128, 0]),
+	(Color::Yellow, [128, 128, 0]),
+	(Color::Blue, [0, 0, 128]),
+	(Color::Magenta, [128, 0, 128]),
+	(Color::Cyan, [0, 128, 128]),
+	(Color::Gray, [192, 192, 192]),
+	(Color::DarkGray, [128, 128, 128]),
+	(Color::LightRed, [255, 0, 0]),
+	(Color::LightGreen, [0, 255, 0]),
+	(Color::LightYellow, [255, 255, 0]),
+	(Color::LightBlue, [0, 0, 255]),
+	(Color::LightMagenta, [255, 0, 255]),
+	(Color::LightCyan, [0, 255, 255]),
+	(Color::White, [255, 255, 255]),
 ];
+
+/// Levels of each channel in the 6x6x6 color cube of 256-color terminals
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+	a.iter()
+		.zip(b)
+		.map(|(a, b)| (*a as i32 - b as i32).pow(2) as u32)
+		.sum()
+}
+
+/// Index of the closest color of the 256-color palette, from its cube or its gray ramp
+fn nearest_indexed(rgb: [u8; 3]) -> u8 {
+	let level = |channel: u8| {
+		(0..CUBE_LEVELS.len())
+			.min_by_key(|index| CUBE_LEVELS[*index].abs_diff(channel))
+			.unwrap()
+	};
+	let [r, g, b] = rgb.map(level);
+	let cube = [CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]];
+	// Gray ramp from 8 to 238, by steps of 10
+	let average = rgb.iter().map(|channel| *channel as u32).sum::<u32>() / 3;
+	let step = (average.saturating_sub(3) / 10).min(23) as u8;
+	let gray = 8 + step * 10;
+	if distance(rgb, [gray; 3]) < distance(rgb, cube) {
+		232 + step
+	} else {
+		16 + 36 * r as u8 + 6 * g as u8 + b as u8
+	}
+}
+
+impl ColorDepth {
+	/// Detect the colors supported by the terminal from `COLORTERM` and `TERM`
+	pub fn detect() -> Self {
+		let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+		if colorterm == "truecolor" || colorterm == "24bit" {
+			return ColorDepth::TrueColor;
+		}
+		let term = std::env::var("TERM").unwrap_or_default();
+		if term.ends_with("-direct") {
+			ColorDepth::TrueColor
+		} else if term.contains("256color") {
+			ColorDepth::Indexed256
+		} else {
+			ColorDepth::Ansi16
+		}
+	}
+
+	/// Closest color to `color` that can be displayed, named and indexed colors are kept
+	pub fn quantize(self, color: Color) -> Color {
+		let Color::Rgb(r, g, b) = color else {
+			return color;
+		};
+		match self {
+			ColorDepth::TrueColor => color,
+			ColorDepth::Indexed256 => Color::Indexed(nearest_indexed([r, g, b])),
+			ColorDepth::Ansi16 => {
+				ANSI_COLORS
+					.iter()
+					.min_by_key(|(_, ansi)| distance([r, g, b], *ansi))
+					.unwrap()
+					.0
+			}
+		}
+	}
+}
+
+/// Palette of the interface
+pub struct Theme {
+	/// Main text
+	pub text: Color,
+	/// Text over accent backgrounds, and background of the completion menus
+	pub base: Color,
+	/// Titles, names and selected items
+	pub accent: Color,
+	/// Selected values and warnings
+	pub highlight: Color,
+	/// Errors and destructive actions
+	pub error: Color,
+	/// Keys of the help bars and successful results
+	pub success: Color,
+	/// Codes, counters and unsaved changes
+	pub special: Color,
+	pub link: Color,
+	/// Secondary text
+	pub muted: Color,
+	/// Separators and placeholders
+	pub dim: Color,
+	/// Details of the entries in lists
+	pub faint: Color,
+	/// Folders of the entry names, and the `/` separating them
+	pub folder: [Color; 2],
+	pub scrollbar: Color,
+	/// Background of the help bars and status bar
+	pub help_bg: Color,
+	/// Background of the forms
+	pub form_bg: Color,
+	/// Border of the forms
+	pub form_border: Color,
+	/// Background of the prompts and popups
+	pub prompt_bg: Color,
+	/// Background of the selected items
+	pub selection_bg: Color,
+	/// Background for the entry editor: Color1, Color2, Selected
+	pub entry_bg: [Color; 3],
+}
+
+impl Theme {
+	pub const DARK: Theme = Theme {
+		text: Color::White,
+		base: Color::Black,
+		accent: Color::Cyan,
+		highlight: Color::Yellow,
+		error: Color::Red,
+		success: Color::Green,
+		special: Color::Magenta,
+		link: Color::Blue,
+		muted: Color::from_u32(0x7f7f7f),
+		dim: Color::DarkGray,
+		faint: Color::from_u32(0x4f4f4f),
+		folder: [Color::from_u32(0xafafaf), Color::from_u32(0xaf5f5f)],
+		scrollbar: Color::from_u32(0x7f7faf),
+		help_bg: Color::from_u32(0x161616),
+		form_bg: Color::from_u32(0x2f2f2f),
+		form_border: Color::from_u32(0x1a1a1f),
+		prompt_bg: Color::from_u32(0x1f1f1f),
+		selection_bg: Color::from_u32(0x241f31),
+		entry_bg: [
+			Color::from_u32(0x322b44),
+			Color::from_u32(0x241f31),
+			Color::from_u32(0x5d507f),
+		],
+	};
+
+	pub const LIGHT: Theme = Theme {
+		text: Color::from_u32(0x1c1c1c),
+		base: Color::from_u32(0xfafafa),
+		accent: Color::from_u32(0x005f87),
+		highlight: Color::from_u32(0x875f00),
+		error: Color::from_u32(0xaf0000),
+		success: Color::from_u32(0x007f00),
+		special: Color::from_u32(0x8700af),
+		link: Color::from_u32(0x0000af),
+		muted: Color::from_u32(0x6c6c6c),
+		dim: Color::from_u32(0x9e9e9e),
+		faint: Color::from_u32(0xb2b2b2),
+		folder: [Color::from_u32(0x585858), Color::from_u32(0xaf5f5f)],
+		scrollbar: Color::from_u32(0x8787af),
+		help_bg: Color::from_u32(0xdadada),
+		form_bg: Color::from_u32(0xe4e4e4),
+		form_border: Color::from_u32(0xc6c6c6),
+		prompt_bg: Color::from_u32(0xeeeeee),
+		selection_bg: Color::from_u32(0xdcd7ee),
+		entry_bg: [
+			Color::from_u32(0xe6e1f3),
+			Color::from_u32(0xf1eef8),
+			Color::from_u32(0xc5bbe3),
+		],
+	};
+
+	pub const HIGH_CONTRAST: Theme = Theme {
+		text: Color::White,
+		base: Color::Black,
+		accent: Color::LightCyan,
+		highlight: Color::LightYellow,
+		error: Color::LightRed,
+		success: Color::LightGreen,
+		special: Color::LightMagenta,
+		link: Color::LightBlue,
+		muted: Color::Gray,
+		dim: Color::Gray,
+		faint: Color::Gray,
+		folder: [Color::White, Color::LightRed],
+		scrollbar: Color::White,
+		help_bg: Color::Black,
+		form_bg: Color::Black,
+		form_border: Color::White,
+		prompt_bg: Color::Black,
+		selection_bg: Color::Blue,
+		entry_bg: [Color::Black, Color::Black, Color::Blue],
+	};
+}
+
+impl Theme {
+	/// Palette with the colors reduced to `depth`
+	fn quantized(&self, depth: ColorDepth) -> Theme {
+		let color = |color| depth.quantize(color);
+		Theme {
+			text: color(self.text),
+			base: color(self.base),
+			accent: color(self.accent),
+			highlight: color(self.highlight),
+			error: color(self.error),
+			success: color(self.success),
+			special: color(self.special),
+			link: color(self.link),
+			muted: color(self.muted),
+			dim: color(self.dim),
+			faint: color(self.faint),
+			folder: self.folder.map(color),
+			scrollbar: color(self.scrollbar),
+			help_bg: color(self.help_bg),
+			form_bg: color(self.form_bg),
+			form_border: color(self.form_border),
+			prompt_bg: color(self.prompt_bg),
+			selection_bg: color(self.selection_bg),
+			entry_bg: self.entry_bg.map(color),
+		}
+	}
+}
+
+static COLOR_DEPTH: LazyLock<ColorDepth> = LazyLock::new(ColorDepth::detect);
+
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+	let theme = match config().theme {
+		ThemeName::Dark => &Theme::DARK,
+		ThemeName::Light => &Theme::LIGHT,
+		ThemeName::HighContrast => &Theme::HIGH_CONTRAST,
+	};
+	theme.quantized(*COLOR_DEPTH)
+});
+
+/// Palette selected in the configuration
+pub fn theme() -> &'static Theme {
+	&THEME
+}
+
+/// Color of a user-defined value, such as a tag color, reduced to what the terminal can display
+pub fn rgb(value: u32) -> Color {
+	COLOR_DEPTH.quantize(Color::from_u32(value))
+}
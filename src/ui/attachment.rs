@@ -0,0 +1,201 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::attachment;
+use crate::data::field::Field;
+use crate::data::field::FieldValue;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+
+/// Save the content of an attachment field to disk
+pub struct SaveAttachment {
+	style: FormStyle,
+	title: String,
+	value: FieldValue,
+
+	field_path: Labeled<'static, TextInput<'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	popup: Option<Popup<'static>>,
+}
+
+impl SaveAttachment {
+	pub fn new(field: &Field) -> Self {
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			title: format!("Save Attachment: {}", field.name),
+			value: field.value.clone(),
+			field_path: Labeled::new(
+				"File".into(),
+				TextInput::new()
+					.style(&TEXTINPUT_STYLE)
+					.with_input(field.name.clone()),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	fn save(&mut self) -> bool {
+		let path = PathBuf::from(self.field_path.inner.get_input().trim());
+		let result = if path.as_os_str().is_empty() {
+			Err("No file given".into())
+		} else {
+			attachment::save(&self.value, &path)
+		};
+		if let Err(err) = result {
+			self.popup = Some(Popup::new(
+				"Save Failed".into(),
+				Paragraph::new(Text::from(err)),
+			));
+			return false;
+		}
+		true
+	}
+}
+
+impl Form for SaveAttachment {
+	fn component_count(&self) -> usize {
+		1
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_path),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_path),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter if self.save() => Some(FormSignal::Return),
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(self.title.as_str())
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::SAVE_ATTACHMENT);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::widgets::ListState;
+use ratatui::Frame;
+
+use crate::data::audit::AuditEvent;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Read-only view of the audit log
+pub struct AuditLogView {
+	/// Events, newest first
+	events: Vec<AuditEvent>,
+	selected: usize,
+	list_state: RefCell<ListState>,
+}
+
+impl AuditLogView {
+	pub fn new(events: &[AuditEvent]) -> Self {
+		Self {
+			events: events.iter().rev().cloned().collect(),
+			selected: 0,
+			list_state: RefCell::default(),
+		}
+	}
+
+	fn move_selected(&mut self, offset: isize) {
+		self.selected = self
+			.selected
+			.saturating_add_signed(offset)
+			.min(self.events.len().saturating_sub(1));
+	}
+}
+
+impl Component for AuditLogView {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.move_selected(-1),
+			KeyCode::Down | KeyCode::Char('j') => self.move_selected(1),
+			KeyCode::PageUp => self.move_selected(-16),
+			KeyCode::PageDown => self.move_selected(16),
+			KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+			KeyCode::End | KeyCode::Char('G') => self.move_selected(isize::MAX),
+			KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(format!("Audit Log ({} events)", self.events.len()))
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::AUDIT_LOG);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		if self.events.is_empty() {
+			frame.render_widget(
+				Line::from("No recorded events".italic().fg(theme().dim)),
+				body,
+			);
+			return;
+		}
+
+		let events = self
+			.events
+			.iter()
+			.enumerate()
+			.map(|(id, event)| {
+				let mut spans = vec![
+					" ".into(),
+					event
+						.at
+						.format("%Y-%m-%d %H:%M:%S")
+						.to_string()
+						.fg(theme().muted),
+					"  ".into(),
+				];
+				if let Some(entry) = &event.entry {
+					spans.push(entry.clone().bold().fg(theme().accent));
+					spans.push(" ".into());
+				}
+				spans.push(event.action.description().fg(theme().text));
+				let item = ListItem::new(Line::from(spans));
+				if id == self.selected {
+					item.bg(theme().entry_bg[2])
+				} else {
+					item.bg(theme().entry_bg[id % 2])
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut list_state = self.list_state.borrow_mut();
+		list_state.select(Some(self.selected));
+		frame.render_stateful_widget(List::new(events), body, &mut *list_state);
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
@@ -0,0 +1,262 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::yubikey;
+use crate::keymap;
+use crate::style::theme;
+use crate::ui::password::PasswordFormatter;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::text_input_custom::CustomTextInput;
+use crate::widgets::text_input_custom::CustomTextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static PASSWORD_INPUT_STYLE: LazyLock<CustomTextInputStyle> =
+	LazyLock::new(|| CustomTextInputStyle {
+		padding: [0, 0],
+		markers: ["".into(), "".into()],
+		style: Some(Style::default().fg(theme().text)),
+		style_selected: None,
+	});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+
+type PasswordInput = Labeled<'static, CustomTextInput<'static, PasswordFormatter>>;
+
+fn password_input(label: &'static str) -> PasswordInput {
+	Labeled::new(
+		label.into(),
+		CustomTextInput::new(PasswordFormatter { hidden: true }).style(&PASSWORD_INPUT_STYLE),
+	)
+	.style(&LABEL_STYLE)
+}
+
+/// Result of the [`ChangePassword`] form
+pub struct PasswordChange {
+	pub current: String,
+	pub new: String,
+	pub key_file: Option<PathBuf>,
+	/// YubiKey slot required by the new key
+	pub yubikey: Option<u8>,
+}
+
+/// Form to change the master password
+pub struct ChangePassword {
+	style: FormStyle,
+
+	current: PasswordInput,
+	new: PasswordInput,
+	confirm: PasswordInput,
+	key_file: Labeled<'static, TextInput<'static>>,
+	yubikey: Labeled<'static, TextInput<'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+	popup: Option<Popup<'static>>,
+}
+
+impl Default for ChangePassword {
+	fn default() -> Self {
+		Self::new(None)
+	}
+}
+
+impl ChangePassword {
+	/// Create the form, `yubikey` is the slot currently required by the database
+	pub fn new(yubikey: Option<u8>) -> Self {
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			current: password_input("Current Password"),
+			new: password_input("New Password"),
+			confirm: password_input("Confirm New Password"),
+			key_file: Labeled::new(
+				"New Key File (optional)".into(),
+				TextInput::new().style(&TEXTINPUT_STYLE),
+			)
+			.style(&LABEL_STYLE),
+			yubikey: Labeled::new(
+				"YubiKey Slot (optional)".into(),
+				TextInput::new()
+					.style(&TEXTINPUT_STYLE)
+					.with_input(yubikey.map(|slot| slot.to_string()).unwrap_or_default()),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	pub fn submit(&self) -> PasswordChange {
+		let key_file = self.key_file.inner.get_input().trim();
+		PasswordChange {
+			current: self.current.inner.submit(),
+			new: self.new.inner.submit(),
+			key_file: (!key_file.is_empty()).then(|| PathBuf::from(key_file)),
+			yubikey: yubikey::parse_slot(self.yubikey.inner.get_input())
+				.ok()
+				.flatten(),
+		}
+	}
+
+	pub fn set_error(&mut self, title: String, message: String) {
+		self.popup = Some(Popup::new(title, Paragraph::new(Text::from(message))));
+	}
+}
+
+impl Form for ChangePassword {
+	fn component_count(&self) -> usize {
+		5
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.current),
+			1 => Some(&self.new),
+			2 => Some(&self.confirm),
+			3 => Some(&self.key_file),
+			4 => Some(&self.yubikey),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.current),
+			1 => Some(&mut self.new),
+			2 => Some(&mut self.confirm),
+			3 => Some(&mut self.key_file),
+			4 => Some(&mut self.yubikey),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => {
+				if self.new.inner.get_input().is_empty() {
+					self.set_error("Invalid Password".into(), "Password is empty!".into());
+					None
+				} else if self.new.inner.get_input() != self.confirm.inner.get_input() {
+					self.set_error("Invalid Passwords".into(), "Passwords do not match!".into());
+					None
+				} else if let Err(err) = yubikey::parse_slot(self.yubikey.inner.get_input()) {
+					self.set_error("Invalid YubiKey".into(), err);
+					None
+				} else {
+					Some(FormSignal::Return)
+				}
+			}
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Change Master Password")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::CHANGE_PASSWORD);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
@@ -0,0 +1,353 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::entry::Entry;
+use crate::data::import::csv;
+use crate::data::import::csv::CsvMapping;
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::checkbox::Checkbox;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Number of rows shown in the preview
+const PREVIEW_ROWS: usize = 3;
+/// Height of the preview, with its border and the remaining row count
+const PREVIEW_HEIGHT: u16 = PREVIEW_ROWS as u16 + 3;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+type ColumnBox = Labeled<'static, ComboBox<'static, 'static>>;
+
+/// Value of the combo box item for unmapped targets
+const NO_COLUMN: &str = "(none)";
+
+/// Map the columns of a CSV file to entry data, with a preview of the result
+pub struct CsvMappingForm {
+	style: FormStyle,
+	rows: Vec<Vec<String>>,
+
+	name: ColumnBox,
+	username: ColumnBox,
+	password: ColumnBox,
+	url: ColumnBox,
+	tags: ColumnBox,
+	extra: Checkbox<'static>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	popup: Option<Popup<'static>>,
+	imported: Vec<Entry>,
+}
+
+fn column_box(label: &str, items: &[ComboItem], column: Option<usize>) -> ColumnBox {
+	let input = match column {
+		Some(column) => items[column + 1].value.clone(),
+		None => NO_COLUMN.into(),
+	};
+	Labeled::new(
+		label.to_string().into(),
+		ComboBox::from_items(items.to_vec())
+			.style(&COMBOBOX_STYLE)
+			.with_input(input),
+	)
+	.style(&LABEL_STYLE)
+}
+
+impl CsvMappingForm {
+	/// Create the form for parsed CSV `rows`, the first row is the header
+	pub fn new(rows: Vec<Vec<String>>) -> Self {
+		let header = rows.first().cloned().unwrap_or_default();
+		let mut items = vec![ComboItem {
+			kind: String::default(),
+			icon: "  ".into(),
+			value: NO_COLUMN.into(),
+		}];
+		items.extend(header.iter().enumerate().map(|(id, column)| ComboItem {
+			kind: format!("#{}", id + 1),
+			icon: format!("{} ", icons::COLUMN),
+			// Values must be unique to be matched back to a column
+			value: if header[..id].contains(column) || column.is_empty() {
+				format!("{column} #{}", id + 1)
+			} else {
+				column.clone()
+			},
+		}));
+		let guess = CsvMapping::guess(&header);
+
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			name: column_box("Name", &items, guess.name),
+			username: column_box("Username", &items, guess.username),
+			password: column_box("Password", &items, guess.password),
+			url: column_box("URL", &items, guess.url),
+			tags: column_box("Tags", &items, guess.tags),
+			extra: Checkbox::new(true, "Import other columns as fields".into()),
+			rows,
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+			imported: vec![],
+		}
+	}
+
+	/// Take the imported entries
+	pub fn submit(&mut self) -> Vec<Entry> {
+		std::mem::take(&mut self.imported)
+	}
+
+	/// Height of the form and preview
+	pub fn full_height(&self) -> u16 {
+		self.height() + 1 + PREVIEW_HEIGHT
+	}
+
+	fn mapping(&self) -> CsvMapping {
+		let column = |field: &ColumnBox| field.inner.submit().and_then(|id| id.checked_sub(1));
+		let mut mapping = CsvMapping {
+			name: column(&self.name),
+			username: column(&self.username),
+			password: column(&self.password),
+			url: column(&self.url),
+			tags: column(&self.tags),
+			extra: vec![],
+		};
+		if self.extra.value() {
+			let columns = self.rows.first().map_or(0, |header| header.len());
+			let mapped = [
+				mapping.name,
+				mapping.username,
+				mapping.password,
+				mapping.url,
+				mapping.tags,
+			];
+			mapping.extra = (0..columns)
+				.filter(|column| !mapped.contains(&Some(*column)))
+				.collect();
+		}
+		mapping
+	}
+
+	fn preview(&self) -> Text<'static> {
+		let rows = &self.rows[..self.rows.len().min(PREVIEW_ROWS + 1)];
+		let entries = match csv::import(rows, &self.mapping()) {
+			Ok(entries) => entries,
+			Err(err) => return Text::from(err.fg(theme().error)),
+		};
+		let mut lines = entries
+			.into_iter()
+			.map(|entry| {
+				let mut spans = vec![Span::from(entry.name).bold().fg(theme().accent)];
+				for field in entry.fields {
+					spans.push(" | ".fg(theme().dim));
+					spans.push(format!("{}: ", field.name).fg(theme().text));
+					spans.push(if field.hidden {
+						"*****".fg(theme().error)
+					} else {
+						field.value.text().unwrap_or_default().to_string().italic()
+					});
+				}
+				Line::from(spans)
+			})
+			.collect::<Vec<_>>();
+		if self.rows.len() > PREVIEW_ROWS + 1 {
+			lines.push(Line::from(
+				format!("... {} more rows", self.rows.len() - PREVIEW_ROWS - 1).fg(theme().dim),
+			));
+		}
+		Text::from(lines)
+	}
+}
+
+impl Form for CsvMappingForm {
+	fn component_count(&self) -> usize {
+		6
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.name),
+			1 => Some(&self.username),
+			2 => Some(&self.password),
+			3 => Some(&self.url),
+			4 => Some(&self.tags),
+			5 => Some(&self.extra),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.name),
+			1 => Some(&mut self.username),
+			2 => Some(&mut self.password),
+			3 => Some(&mut self.url),
+			4 => Some(&mut self.tags),
+			5 => Some(&mut self.extra),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => match csv::import(&self.rows, &self.mapping()) {
+				Ok(entries) => {
+					self.imported = entries;
+					Some(FormSignal::Return)
+				}
+				Err(err) => {
+					self.popup = Some(Popup::new(
+						"Import Failed".into(),
+						Paragraph::new(Text::from(err)),
+					));
+					None
+				}
+			},
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("CSV Columns")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::CSV_MAPPING);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		// Preview
+		let preview_area = Rect {
+			x: area.x + 1,
+			y: area.bottom().saturating_sub(PREVIEW_HEIGHT + 1),
+			width: area.width.saturating_sub(2),
+			height: PREVIEW_HEIGHT,
+		};
+		frame.render_widget(
+			Paragraph::new(self.preview())
+				.block(Block::bordered().title("Preview").fg(theme().muted)),
+			preview_area,
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3 + PREVIEW_HEIGHT);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
@@ -1,38 +1,144 @@
 use core::panic;
-use std::cell::RefCell;
+use std::cell::Cell;
+use std::sync::LazyLock;
+use std::time::Duration;
+use std::time::Instant;
 
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use ratatui::layout::Constraint;
 use ratatui::layout::Flex;
 use ratatui::layout::Layout;
-use ratatui::style::Color;
+use ratatui::layout::Position;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::text::Text;
 use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
 use ratatui::widgets::Clear;
 use ratatui::widgets::List;
 use ratatui::widgets::ListItem;
-use ratatui::widgets::ListState;
 use ratatui::widgets::Paragraph;
-use ratatui::widgets::ScrollbarState;
 use ratatui::Frame;
 
+use crate::clipboard;
+use crate::data::attachment;
 use crate::data::entry::Entry;
+use crate::data::entry::EntryRevision;
 use crate::data::field::Field;
+use crate::data::field::Expiry;
 use crate::data::field::FieldValue;
-use crate::style::ENTRY_BG;
-use crate::style::HELP_LINE_BG;
+use crate::data::otp;
+use crate::data::otp::Totp;
+use crate::hooks::run_hooks;
+use crate::hooks::HookContext;
+use crate::hooks::HookEvent;
+use crate::icons;
+use crate::keymap;
+use crate::plugin::render_field;
+use crate::style::theme;
+use crate::ui::attachment::SaveAttachment;
+use crate::ui::entry_history::EntryHistory;
+use crate::ui::entry_icon::EntryIconForm;
+use crate::ui::explorer::ExplorerFilter;
 use crate::ui::field_editor::FieldEditor;
+use crate::ui::field_history::FieldHistory;
+use crate::ui::recovery_codes::RecoveryCodesView;
+use crate::ui::screen::form_signal;
+use crate::ui::screen::Screen;
+use crate::ui::screen::ScreenSignal;
+use crate::ui::screen::ScreenStack;
 use crate::widgets::confirm::Confirm;
 use crate::widgets::form::Form;
-use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormExt;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::toast::Toast;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::ComponentRenderCtx;
 
+static FILTER_LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered().border_type(ratatui::widgets::BorderType::Thick)),
+	},
+	style: Some(Style::default().fg(theme().base).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
+});
+static FILTER_INPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
+});
+
+/// Time a hidden field stays revealed
+const REVEAL_DURATION: Duration = Duration::from_secs(5);
+
+/// Date in local time followed by the time elapsed since `now`
+fn format_date(date: DateTime<Utc>, now: DateTime<Utc>) -> Vec<Span<'static>> {
+	let elapsed = now - date;
+	let (count, unit) = if elapsed.num_minutes() < 1 {
+		(0, "")
+	} else if elapsed.num_hours() < 1 {
+		(elapsed.num_minutes(), "minute")
+	} else if elapsed.num_days() < 1 {
+		(elapsed.num_hours(), "hour")
+	} else if elapsed.num_days() < 30 {
+		(elapsed.num_days(), "day")
+	} else if elapsed.num_days() < 365 {
+		(elapsed.num_days() / 30, "month")
+	} else {
+		(elapsed.num_days() / 365, "year")
+	};
+	let relative = match count {
+		0 => " (just now)".into(),
+		1 => format!(" (1 {unit} ago)"),
+		_ => format!(" ({count} {unit}s ago)"),
+	};
+	vec![
+		date.with_timezone(&Local)
+			.format("%Y-%m-%d %H:%M")
+			.to_string()
+			.fg(theme().muted),
+		relative.italic().fg(theme().dim),
+	]
+}
+
+/// Toast confirming that `what` was copied, with the time left before the clipboard is cleared
+fn copied_toast(what: String) -> Toast<'static> {
+	let mut message = vec![format!("{} Copied {what}", icons::COPIED).fg(theme().text)];
+	if let Some(remaining) = clipboard::remaining() {
+		message.push(", clipboard cleared in ".fg(theme().text));
+		message.push(
+			format!("{}s", remaining.as_secs_f32().round())
+				.bold()
+				.fg(theme().highlight),
+		);
+	}
+	Toast::new(Line::from(message))
+}
+
+/// Part of a field copied to the clipboard
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CopiedPart {
+	Value,
+	Name,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum ConfirmAction {
@@ -40,98 +146,317 @@ pub enum ConfirmAction {
 	Quit,
 }
 
+/// Screen opened over the fields of the entry
+#[allow(clippy::large_enum_variant)]
+pub enum EntryScreen {
+	Confirm(ConfirmAction, Confirm<'static>),
+	Field(FieldEditor),
+	SaveAttachment(SaveAttachment),
+	Icon(EntryIconForm),
+	History(EntryHistory),
+	FieldHistory(FieldHistory),
+	RecoveryCodes(RecoveryCodesView),
+}
+
+/// Render `form` in the middle of the frame, with a render context of its own
+fn render_form_centered(form: &impl Form, height: u16, frame: &mut Frame) {
+	let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+	let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
+	let [area] = frame.area().layout(&vertical);
+	let [area] = area.layout(&horizontal);
+	let mut queue = vec![];
+	let mut ctx = ComponentRenderCtx {
+		area,
+		selected: false,
+		queue: &mut queue,
+		depth: 0,
+		cursor: None,
+	};
+	form.render_form(frame, &mut ctx);
+	if let Some((_, cursor)) = ctx.cursor {
+		frame.set_cursor_position(cursor);
+	}
+}
+
+impl Screen for EntryScreen {
+	fn input(&mut self, key: &KeyEvent) -> ScreenSignal {
+		// The views are submitted when closed, with what was chosen in them
+		let open = match self {
+			EntryScreen::Confirm(_, confirm) => {
+				confirm.input(key);
+				confirm.submit().is_none()
+			}
+			EntryScreen::Field(editor) => return form_signal(editor.input_form(key)),
+			EntryScreen::SaveAttachment(form) => {
+				return match form.input_form(key) {
+					Some(_) => ScreenSignal::Closed,
+					None => ScreenSignal::Handled,
+				};
+			}
+			EntryScreen::Icon(form) => return form_signal(form.input_form(key)),
+			EntryScreen::History(history) => history.input(key),
+			EntryScreen::FieldHistory(view) => view.input(key),
+			EntryScreen::RecoveryCodes(view) => view.input(key),
+		};
+		if open {
+			ScreenSignal::Handled
+		} else {
+			ScreenSignal::Submitted
+		}
+	}
+
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		match self {
+			EntryScreen::Field(editor) => FormExt::mouse_input(editor, event),
+			EntryScreen::SaveAttachment(form) => FormExt::mouse_input(form, event),
+			EntryScreen::Icon(form) => FormExt::mouse_input(form, event),
+			_ => vec![],
+		}
+	}
+
+	fn paste(&mut self, text: &str) -> bool {
+		match self {
+			EntryScreen::Field(editor) => FormExt::paste(editor, text),
+			EntryScreen::SaveAttachment(form) => FormExt::paste(form, text),
+			EntryScreen::Icon(form) => FormExt::paste(form, text),
+			_ => false,
+		}
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		match self {
+			EntryScreen::Confirm(_, confirm) => confirm.render(frame, ctx),
+			EntryScreen::Field(editor) => render_form_centered(editor, 20, frame),
+			EntryScreen::SaveAttachment(form) => {
+				render_form_centered(form, form.height() + 1, frame)
+			}
+			EntryScreen::Icon(form) => render_form_centered(form, form.height() + 1, frame),
+			EntryScreen::History(history) => {
+				ctx.area = frame.area();
+				history.render(frame, ctx);
+			}
+			EntryScreen::FieldHistory(view) => {
+				ctx.area = frame.area();
+				view.render(frame, ctx);
+			}
+			EntryScreen::RecoveryCodes(view) => {
+				ctx.area = frame.area();
+				view.render(frame, ctx);
+			}
+		}
+		ctx.area = area;
+	}
+}
+
 pub struct EntryEditor {
 	entry: Entry,
+	/// State of the entry when the editor was opened
+	original: EntryRevision,
 
-	copied: Option<usize>,
+	/// Field copied to the clipboard, until the clipboard is cleared
+	copied: Option<(usize, CopiedPart)>,
+	/// Names of the fields copied since the last [`EntryEditor::take_copied`]
+	copied_fields: Vec<String>,
+	/// HOTP secrets and next counters of the codes generated since the last
+	/// [`EntryEditor::take_counters`]
+	counters: Vec<(String, u64)>,
 	selected: Option<usize>,
+	/// Filter of the fields, matched against their names and the values of visible fields as in the
+	/// explorer
+	filter_field: Labeled<'static, TextInput<'static>>,
+	/// Whether the filter has the focus
+	filtering: bool,
+	/// Hidden field shown in clear, until the instant it is hidden again
+	revealed: Option<(usize, Instant)>,
+	/// Show the dates of the entry and of the selected field below the fields
+	timestamps: bool,
 
 	modified: bool,
 	save: bool,
-	confirm: Option<Confirm<'static>>,
-	confirm_action: Option<ConfirmAction>,
-
-	editor: Option<FieldEditor>,
+	/// Screens opened over the fields, the top-most one receives the input
+	screens: ScreenStack<EntryScreen>,
+	/// Remaining validity of the last copied TOTP code
+	toast: Option<Toast<'static>>,
 
-	list_state: RefCell<ListState>,
-	scrollbar: RefCell<ScrollbarState>,
+	/// Area of the fields, for the mouse
+	list_area: Cell<Rect>,
 }
 
 impl EntryEditor {
 	pub fn new(entry: Entry) -> Self {
-		let len = entry.fields.len();
 		Self {
+			original: entry.revision(),
 			entry,
 			copied: None,
+			copied_fields: vec![],
+			counters: vec![],
 			selected: None,
+			filter_field: Labeled::new("Filter".into(), TextInput::new().style(&FILTER_INPUT_STYLE))
+				.style(&FILTER_LABEL_STYLE),
+			filtering: false,
+			revealed: None,
+			timestamps: false,
 			modified: false,
 			save: true,
-			confirm: None,
-			confirm_action: None,
-			editor: None,
-			list_state: RefCell::default(),
-			scrollbar: RefCell::new(ScrollbarState::new(len).position(0)),
+			screens: ScreenStack::default(),
+			toast: None,
+			list_area: Cell::default(),
 		}
 	}
 
+	/// Fields matching the filter, in their order
+	fn visible_fields(&self) -> Vec<usize> {
+		let filter = ExplorerFilter::from(self.filter_field.inner.get_input().as_str());
+		self.entry
+			.fields
+			.iter()
+			.enumerate()
+			.filter(|(_, field)| {
+				// Hidden values can't be found
+				let value = field.value.text().filter(|_| !field.hidden);
+				filter.score_text(&field.name).is_some()
+					|| value.is_some_and(|value| filter.score_text(value).is_some())
+			})
+			.map(|(id, _)| id)
+			.collect()
+	}
+
+	/// Move the selection by `offset` visible fields
+	///
+	/// When the selected field is no longer visible, the closest visible field before it is
+	/// selected instead.
 	pub fn move_selected(&mut self, offset: i32) {
-		if self.entry.fields.is_empty() {
+		self.revealed = None;
+		let visible = self.visible_fields();
+		if visible.is_empty() {
 			self.selected = None;
 			return;
 		}
 
-		if offset > 0 {
-			if let Some(selected) = self.selected {
-				self.selected = Some(std::cmp::min(
-					self.entry.fields.len() - 1,
-					selected + offset as usize,
-				));
-			} else {
-				self.selected = Some(std::cmp::min(
-					self.entry.fields.len() - 1,
-					(offset as usize).saturating_sub(1),
-				));
-			}
-		} else if offset < 0 {
-			if let Some(selected) = self.selected {
-				self.selected = Some(selected.saturating_sub((-offset) as usize));
-			}
-		}
+		let position = match self.selected {
+			Some(selected) => match visible.iter().position(|id| *id == selected) {
+				Some(position) => position.saturating_add_signed(offset as isize),
+				None => visible
+					.partition_point(|id| *id < selected)
+					.saturating_sub(1),
+			},
+			None if offset > 0 => offset as usize - 1,
+			None => return,
+		};
+		self.selected = Some(visible[position.min(visible.len() - 1)]);
 	}
 
-	fn field_preview(
+	pub(crate) fn field_preview(
 		width: u16,
 		field: Option<&Field>,
 		selected: bool,
-		yanked: bool,
+		yanked: Option<CopiedPart>,
+		revealed: bool,
 		id: usize,
 	) -> ListItem<'_> {
 		let sep = std::cmp::max((width as f32 * 0.3) as u16, 20);
 
 		let item = if let Some(field) = field {
-			let name = field.name.as_str().bold();
+			let now = Utc::now();
+			let expiry = field.expiry(now);
+			let name = match expiry {
+				Some(Expiry::Expired) => field.name.as_str().bold().fg(theme().error),
+				Some(Expiry::Soon) => field.name.as_str().bold().fg(theme().highlight),
+				None => field.name.as_str().bold(),
+			};
 
-			let value: Span = if field.hidden {
-				"*****".fg(Color::Red)
+			// Seconds left for the current TOTP code, or number of lines of notes
+			let mut detail = Span::from("");
+			let value: Span = if field.hidden && !revealed {
+				"*****".fg(theme().error)
 			} else {
 				match &field.value {
 					FieldValue::Text(s) => s.as_str().italic(),
-					FieldValue::Url(s) => s.as_str().underlined().fg(Color::Blue), // TODO HYPERLINK
-					FieldValue::Phone(s) => s.as_str().bold().fg(Color::Yellow),
-					FieldValue::Email(s) => s.as_str().underlined().fg(Color::Green), // TODO HYPERLINK
-					FieldValue::TOTPRFC6238(_) => todo!(),
-					FieldValue::TOTPSteam(_) => todo!(),
-					FieldValue::TwoFactorRecovery(_two_facodes) => todo!(),
-					FieldValue::Binary {
-						mimetype: _,
-						base64: _,
-					} => todo!(),
+					FieldValue::Url(s) => s.as_str().underlined().fg(theme().link), // TODO HYPERLINK
+					FieldValue::Phone(s) => s.as_str().bold().fg(theme().highlight),
+					FieldValue::Email(s) => s.as_str().underlined().fg(theme().success), // TODO HYPERLINK
+					FieldValue::Note(s) => {
+						let lines = s.lines().count();
+						if lines > 1 {
+							detail = format!(" ({lines} lines)").fg(theme().dim);
+						}
+						s.lines().next().unwrap_or_default().italic()
+					}
+					FieldValue::TOTPRFC6238(secret) => match Totp::parse(secret) {
+						Ok(totp) => {
+							let remaining = totp.remaining(now);
+							detail = format!(" {} {remaining}s", icons::TIMER).fg(
+								if remaining <= 5 {
+									theme().error
+								} else {
+									theme().dim
+								},
+							);
+							otp::format_code(&totp.code(now)).bold().fg(theme().special)
+						}
+						Err(err) => format!("Invalid TOTP secret: {err}").fg(theme().error),
+					},
+					FieldValue::TOTPSteam(secret) => match Totp::parse_steam(secret) {
+						Ok(totp) => {
+							let remaining = totp.remaining(now);
+							detail = format!(" {} {remaining}s", icons::TIMER).fg(
+								if remaining <= 5 {
+									theme().error
+								} else {
+									theme().dim
+								},
+							);
+							totp.code(now).bold().fg(theme().special)
+						}
+						Err(err) => format!("Invalid Steam secret: {err}").fg(theme().error),
+					},
+					FieldValue::HOTP { counter, .. } => {
+						format!("HOTP #{counter}").fg(theme().special)
+					}
+					FieldValue::TwoFactorRecovery(codes) => format!(
+						"{} {}/{} unused",
+						icons::RECOVERY_CODES,
+						codes.iter().filter(|code| !code.is_used()).count(),
+						codes.len()
+					)
+					.fg(theme().special),
+					FieldValue::Binary { mimetype, base64 } => render_field(field)
+						.unwrap_or_else(|| {
+							format!(
+								"{} {mimetype} ({})",
+								icons::ATTACHMENT,
+								attachment::format_size(attachment::size(base64))
+							)
+						})
+						.italic(),
 				}
 			};
-			let modifiers = if yanked {
-				" 󱓥".fg(Color::Red)
-			} else {
-				Span::from("")
+			let modifiers = match yanked {
+				Some(part) => {
+					let part = match part {
+						CopiedPart::Value => "value",
+						CopiedPart::Name => "name",
+					};
+					let remaining = clipboard::remaining()
+						.map(|remaining| format!(" {}s", remaining.as_secs_f32().round()))
+						.unwrap_or_default();
+					format!(" {} {part}{remaining}", icons::COPIED).fg(theme().error)
+				}
+				None if revealed => format!(" {}", icons::REVEALED).fg(theme().error),
+				None => Span::from(""),
+			};
+			let expiry = match (expiry, field.expires_at) {
+				(Some(Expiry::Expired), _) => format!(" {} expired", icons::WARNING)
+					.fg(theme().error)
+					.bold(),
+				(Some(Expiry::Soon), Some(expires_at)) => format!(
+					" {} expires in {}d",
+					icons::EXPIRING,
+					(expires_at - now).num_days()
+				)
+				.fg(theme().highlight),
+				_ => Span::from(""),
 			};
 
 			let padding_width = (sep).saturating_sub(1 + name.width() as u16);
@@ -144,27 +469,219 @@ impl EntryEditor {
 				" ".into(),
 				name,
 				spacer,
-				"| ".fg(Color::DarkGray),
+				"| ".fg(theme().dim),
 				value,
+				detail,
 				modifiers,
+				expiry,
 			]))
 		} else {
 			ListItem::new(Line::from(vec![]))
 		};
 
 		if selected {
-			item.bg(ENTRY_BG[2])
+			item.bg(theme().entry_bg[2])
 		} else {
-			item.bg(ENTRY_BG[id % 2])
+			item.bg(theme().entry_bg[id % 2])
 		}
 	}
 
+	fn copy_selected(&mut self) {
+		let Some(selected) = self.selected else {
+			return;
+		};
+		self.copied = Some((selected, CopiedPart::Value));
+		let field = &mut self.entry.fields[selected];
+		field.value.copy_to_clipboard();
+		field.date_accessed = Utc::now();
+		let totp = match &field.value {
+			FieldValue::TOTPRFC6238(secret) => Totp::parse(secret).ok(),
+			FieldValue::TOTPSteam(secret) => Totp::parse_steam(secret).ok(),
+			_ => None,
+		};
+		self.toast = Some(match totp {
+			Some(totp) => Toast::new(Line::from(vec![
+				format!("{} Copied code of {}, valid for ", icons::COPIED, field.name)
+					.fg(theme().text),
+				format!("{}s", totp.remaining(Utc::now())).bold().fg(theme().highlight),
+			])),
+			None => copied_toast(field.name.clone()),
+		});
+		// Each HOTP code is used once
+		if let FieldValue::HOTP { secret, counter } = &mut field.value {
+			*counter += 1;
+			self.counters.push((secret.clone(), *counter));
+		}
+		let field = &self.entry.fields[selected];
+		self.copied_fields.push(field.name.clone());
+		run_hooks(
+			HookEvent::OnCopy,
+			&HookContext {
+				entry: Some(&self.entry.name),
+				field: Some(&field.name),
+				value: field.value.text(),
+				..Default::default()
+			},
+		);
+	}
+
+	/// Copy the name of the selected field
+	fn copy_selected_name(&mut self) {
+		let Some(selected) = self.selected else {
+			return;
+		};
+		let name = self.entry.fields[selected].name.clone();
+		self.toast = Some(match clipboard::copy(name.clone()) {
+			Ok(()) => {
+				self.copied = Some((selected, CopiedPart::Name));
+				copied_toast(format!("name of {name}"))
+			}
+			Err(err) => Toast::new(Line::from(err.fg(theme().error))),
+		});
+	}
+
+	/// Take the names of the fields copied to the clipboard
+	pub fn take_copied(&mut self) -> Vec<String> {
+		std::mem::take(&mut self.copied_fields)
+	}
+
+	/// Take the HOTP secrets and counters advanced by generating codes
+	///
+	/// Counters must be persisted even if the other changes are discarded.
+	pub fn take_counters(&mut self) -> Vec<(String, u64)> {
+		std::mem::take(&mut self.counters)
+	}
+
+	/// Dates of the entry and of the selected field
+	fn render_timestamps(&self, frame: &mut Frame, area: Rect) {
+		let now = Utc::now();
+		let separator = || format!(" {} ", icons::SEPARATOR).fg(theme().faint);
+		let dates = |label: &str, dates: [(&'static str, DateTime<Utc>); 3]| {
+			let mut spans = vec![format!(" {label} ").bold().fg(theme().text)];
+			for (index, (name, date)) in dates.into_iter().enumerate() {
+				if index != 0 {
+					spans.push(separator());
+				}
+				spans.push(format!("{name} ").fg(theme().text));
+				spans.extend(format_date(date, now));
+			}
+			Line::from(spans)
+		};
+
+		let entry = dates(
+			"Entry",
+			[
+				("created", self.entry.created_at),
+				("modified", self.entry.modified_at),
+				("accessed", self.entry.accessed_at),
+			],
+		);
+		let field = match self.selected.map(|selected| &self.entry.fields[selected]) {
+			Some(field) => dates(
+				field.name.as_str(),
+				[
+					("added", field.date_added),
+					("modified", field.date_modified),
+					("accessed", field.date_accessed),
+				],
+			),
+			None => Line::from(" No field selected".italic().fg(theme().dim)),
+		};
+		let block = Block::default()
+			.borders(Borders::TOP)
+			.title("Timestamps")
+			.fg(theme().form_border);
+		let inner = block.inner(area);
+		frame.render_widget(block, area);
+		frame.render_widget(Paragraph::new(vec![entry, field]), inner);
+	}
+
 	pub fn submit(&self) -> Option<Entry> {
 		if !self.save {
 			return None;
 		}
 
-		Some(self.entry.clone())
+		let mut entry = self.entry.clone();
+		if self.modified {
+			entry.commit(self.original.clone());
+		}
+		Some(entry)
+	}
+
+	/// Apply `screen`, popped once submitted, returns whether the editor stays open
+	fn submit_screen(&mut self, screen: EntryScreen) -> bool {
+		match screen {
+			EntryScreen::Confirm(action, confirm) => match (action, confirm.submit()) {
+				(ConfirmAction::Delete, Some(true)) => {
+					let selected = self.selected.unwrap();
+					self.entry.fields.remove(selected);
+					self.move_selected(-1);
+					self.modified = true;
+				}
+				(ConfirmAction::Quit, Some(save)) => {
+					self.save = save;
+					return false;
+				}
+				_ => {}
+			},
+			EntryScreen::Field(editor) => {
+				// TODO: Popup with error
+				if let Some(mut field) = editor.submit() {
+					if let Some(selected) = self.selected {
+						let previous = std::mem::take(&mut self.entry.fields[selected]);
+						field.inherit_history(previous);
+						self.entry.fields[selected] = field;
+					} else {
+						self.entry.fields.push(field);
+					}
+				}
+			}
+			EntryScreen::Icon(form) => {
+				if let Ok(icon) = form.submit() {
+					if icon != self.entry.icon {
+						self.entry.icon = icon;
+						self.modified = true;
+					}
+				}
+			}
+			EntryScreen::History(history) => {
+				if let Some(revision) = history.submit() {
+					self.entry.fields = revision.fields.clone();
+					self.entry.tags = revision.tags.clone();
+					self.selected = None;
+					self.revealed = None;
+					self.modified = true;
+				}
+			}
+			EntryScreen::FieldHistory(view) => {
+				let selected = self.selected.unwrap();
+				let field = &mut self.entry.fields[selected];
+				if let Some(index) = view.submit() {
+					let revision = field.history.remove(index);
+					field.set_value(revision.value);
+					self.modified = true;
+				}
+				if view.copied() {
+					self.copied = Some((selected, CopiedPart::Value));
+					self.copied_fields.push(field.name.clone());
+				}
+			}
+			EntryScreen::RecoveryCodes(view) => {
+				let selected = self.selected.unwrap();
+				let field = &mut self.entry.fields[selected];
+				if let Some(updated) = view.submit() {
+					field.value = FieldValue::TwoFactorRecovery(updated.to_vec());
+					field.date_modified = Utc::now();
+					self.modified = true;
+				}
+				if view.copied() {
+					self.copied = Some((selected, CopiedPart::Value));
+					self.copied_fields.push(field.name.clone());
+				}
+			}
+			EntryScreen::SaveAttachment(_) => {}
+		}
+		true
 	}
 }
 
@@ -173,64 +690,44 @@ impl Component for EntryEditor {
 		let ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
 		let shift_pressed = key.modifiers.contains(KeyModifiers::SHIFT);
 
-		// Confirm
-		if let Some(confirm) = &mut self.confirm {
-			confirm.input(key);
-			match confirm.submit() {
-				Some(true) => {
-					let action = self.confirm_action.unwrap();
-					match action {
-						ConfirmAction::Delete => {
-							let selected = self.selected.unwrap();
-							self.entry.fields.remove(selected);
-							self.move_selected(-1);
-						}
-						ConfirmAction::Quit => {
-							self.save = true;
-							return false;
-						}
-					}
-				}
-				Some(false) => {
-					let action = self.confirm_action.unwrap();
-					if action == ConfirmAction::Quit {
-     							self.save = false;
-     							return false;
-     						}
+		// Screens over the fields
+		match self.screens.input(key) {
+			Some(ScreenSignal::Submitted) => {
+				if let Some(screen) = self.screens.pop() {
+					return self.submit_screen(screen);
 				}
-				None => return true,
+				return true;
 			}
-			self.confirm = None;
-			self.confirm_action = None;
-			return true;
+			Some(_) => return true,
+			None => {}
 		}
 
-		// Field editor
-		if let Some(editor) = &mut self.editor {
-			match editor.input_form(key) {
-				Some(FormSignal::Exit) => self.editor = None,
-				Some(FormSignal::Return) => {
-					// TODO: Popup with error
-					if let Some(field) = editor.submit() {
-						if let Some(selected) = self.selected {
-							self.entry.fields[selected] = field;
-						} else {
-							self.entry.fields.push(field);
-						}
-					}
-					self.editor = None;
+		// Filter
+		if self.filtering {
+			if self.filter_field.inner.input(key) {
+				// Select the first match
+				self.selected = None;
+				self.move_selected(1);
+				return true;
+			}
+			match key.code {
+				KeyCode::Down | KeyCode::Tab | KeyCode::Esc | KeyCode::Enter => {
+					self.filtering = false
 				}
+				KeyCode::Char('n') if ctrl_pressed => self.filtering = false,
 				_ => {}
 			}
 			return true;
 		}
 
 		match key.code {
-			// Reorder
+			// Reorder, with the previous or next visible field
 			KeyCode::Up | KeyCode::Char('k') if shift_pressed => {
 				if let Some(selected) = self.selected {
-					if selected != 0 {
-						self.entry.fields.swap(selected, selected - 1);
+					let visible = self.visible_fields();
+					let position = visible.iter().position(|id| *id == selected);
+					if let Some(previous) = position.filter(|position| *position != 0) {
+						self.entry.fields.swap(selected, visible[previous - 1]);
 						self.move_selected(-1);
 						self.modified = true;
 					}
@@ -238,13 +735,16 @@ impl Component for EntryEditor {
 			}
 			KeyCode::Down | KeyCode::Char('j') if shift_pressed => {
 				if let Some(selected) = self.selected {
-					if selected + 1 != self.entry.fields.len() {
-						self.entry.fields.swap(selected, selected + 1);
+					let visible = self.visible_fields();
+					let position = visible.iter().position(|id| *id == selected);
+					if let Some(next) = position.filter(|position| position + 1 != visible.len()) {
+						self.entry.fields.swap(selected, visible[next + 1]);
 						self.move_selected(1);
 						self.modified = true;
 					}
 				}
 			}
+			KeyCode::Char('/') => self.filtering = true,
 
 			// Movement
 			KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => self.move_selected(-1),
@@ -255,46 +755,109 @@ impl Component for EntryEditor {
 			KeyCode::PageDown => self.move_selected(16),
 
 			// Copy
-			KeyCode::Char('y') => {
-				if let Some(selected) = self.selected {
-					self.copied = self.selected;
-					self.entry.fields[selected].value.copy_to_clipboard();
-				}
-			}
-			KeyCode::Char('c') if ctrl_pressed => {
-				if let Some(selected) = self.selected {
-					self.copied = self.selected;
-					self.entry.fields[selected].value.copy_to_clipboard();
-				}
-			}
+			KeyCode::Char('y') => self.copy_selected(),
+			KeyCode::Char('c') if ctrl_pressed => self.copy_selected(),
+			KeyCode::Char('Y') => self.copy_selected_name(),
 			// Edit
 			KeyCode::Char('e') | KeyCode::Enter => {
 				if let Some(selected) = self.selected {
 					let field = &self.entry.fields[selected];
-					self.editor = Some(
-						FieldEditor::new(format!("Edit Field: {}", field.name)).with_value(field),
-					);
+					let editor =
+						FieldEditor::new(format!("Edit Field: {}", field.name)).with_value(field);
+					self.screens.push(EntryScreen::Field(editor));
 					self.modified = true;
 				}
 			}
 			// Add
 			KeyCode::Char('a') => {
 				self.selected = None;
-				self.editor = Some(FieldEditor::new("New Field".into()));
+				self.revealed = None;
+				self.screens
+					.push(EntryScreen::Field(FieldEditor::new("New Field".into())));
 				self.modified = true;
 			}
+			// Save attachment
+			KeyCode::Char('w') => {
+				if let Some(selected) = self.selected {
+					let field = &self.entry.fields[selected];
+					if matches!(field.value, FieldValue::Binary { .. }) {
+						let form = SaveAttachment::new(field);
+						self.screens.push(EntryScreen::SaveAttachment(form));
+					}
+				}
+			}
+			// Open attachment
+			KeyCode::Char('o') => {
+				if let Some(selected) = self.selected {
+					let field = &self.entry.fields[selected];
+					if matches!(field.value, FieldValue::Binary { .. }) {
+						let message = match attachment::open(&field.value) {
+							Ok(()) => format!(
+								"{} Opened {}, removed in {}s",
+								icons::ATTACHMENT,
+								field.name,
+								attachment::OPEN_DURATION.as_secs()
+							)
+							.fg(theme().text),
+							Err(err) => err.fg(theme().error),
+						};
+						self.toast = Some(Toast::new(Line::from(message)));
+					}
+				}
+			}
+			// Icon
+			KeyCode::Char('i') => {
+				let form = EntryIconForm::new(&self.entry.name, self.entry.icon.as_deref());
+				self.screens.push(EntryScreen::Icon(form));
+			}
+			// Recovery codes
+			KeyCode::Char('r') => {
+				if let Some(selected) = self.selected {
+					let field = &self.entry.fields[selected];
+					if let FieldValue::TwoFactorRecovery(codes) = &field.value {
+						let view = RecoveryCodesView::new(field.name.clone(), codes.clone());
+						self.screens.push(EntryScreen::RecoveryCodes(view));
+					}
+				}
+			}
+			// History
+			KeyCode::Char('h') => {
+				let history = EntryHistory::new(self.entry.name.clone(), self.entry.history.clone());
+				self.screens.push(EntryScreen::History(history));
+			}
+			// Reveal
+			KeyCode::Char('v') => {
+				if let Some(selected) = self.selected {
+					self.revealed = match self.revealed {
+						Some((revealed, _)) if revealed == selected => None,
+						_ if self.entry.fields[selected].hidden => {
+							Some((selected, Instant::now() + REVEAL_DURATION))
+						}
+						_ => None,
+					};
+				}
+			}
+			// Timestamps
+			KeyCode::Char('t') => self.timestamps = !self.timestamps,
+			KeyCode::Char('H') => {
+				if let Some(selected) = self.selected {
+					let view = FieldHistory::new(&self.entry.fields[selected]);
+					self.screens.push(EntryScreen::FieldHistory(view));
+				}
+			}
 			// Delete
 			KeyCode::Delete | KeyCode::Char('d') => {
 				if let Some(selected) = self.selected {
 					let field = &self.entry.fields[selected];
-					self.confirm = Some(Confirm::new(
+					let confirm = Confirm::new(
 						format!("Delete field {}", field.name),
 						Paragraph::new(Text::from(format!(
 							"Really delete field '{}'?",
 							field.name
 						))),
-					));
-					self.confirm_action = Some(ConfirmAction::Delete);
+					);
+					self.screens
+						.push(EntryScreen::Confirm(ConfirmAction::Delete, confirm));
 				}
 			}
 			/*
@@ -306,20 +869,25 @@ impl Component for EntryEditor {
 			self.entry.fields[self.selected as usize]
 			.name
 			.clone()
-			.fg(Color::Blue),
+			.fg(theme().link),
 			"'?".into(),
 			]);
 			self.confirm = Some(ConfirmDialog::new(title, vec![ListItem::from(desc)]));
 			}
 			}
 			*/
+			KeyCode::Esc if !self.filter_field.inner.get_input().is_empty() => {
+				self.filter_field.inner.set_input(String::default());
+				self.move_selected(0);
+			}
 			KeyCode::Esc | KeyCode::Char('q') => {
 				if self.modified {
-					self.confirm = Some(Confirm::new(
+					let confirm = Confirm::new(
 						"Save Changes".into(),
 						Paragraph::new(Text::from("Exit and save changes?")),
-					));
-					self.confirm_action = Some(ConfirmAction::Quit);
+					);
+					self.screens
+						.push(EntryScreen::Confirm(ConfirmAction::Quit, confirm));
 				} else {
 					return false;
 				}
@@ -329,46 +897,109 @@ impl Component for EntryEditor {
 		true
 	}
 
+	fn tick(&mut self) {
+		if self.toast.as_ref().is_some_and(Toast::is_expired) {
+			self.toast = None;
+		}
+		if self
+			.revealed
+			.is_some_and(|(_, until)| Instant::now() >= until)
+		{
+			self.revealed = None;
+		}
+		// The clipboard was cleared
+		if clipboard::clears() && clipboard::remaining().is_none() {
+			self.copied = None;
+		}
+	}
+
+	fn paste(&mut self, text: &str) -> bool {
+		self.screens.paste(text).unwrap_or(false)
+	}
+
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		if let Some(keys) = self.screens.mouse_input(event) {
+			return keys;
+		}
+
+		let area = self.list_area.get();
+		if !area.contains(Position::new(event.column, event.row)) {
+			return vec![];
+		}
+		match event.kind {
+			MouseEventKind::ScrollDown => self.move_selected(1),
+			MouseEventKind::ScrollUp => self.move_selected(-1),
+			MouseEventKind::Down(MouseButton::Left) => {
+				let visible = self.visible_fields();
+				if let Some(index) = visible.get((event.row - area.y) as usize).copied() {
+					// Clicking the selected field edits it
+					if self.selected == Some(index) {
+						return vec![KeyCode::Enter.into()];
+					}
+					self.selected = Some(index);
+					self.revealed = None;
+				}
+			}
+			_ => {}
+		}
+		vec![]
+	}
+
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let attachments = self
+			.entry
+			.fields
+			.iter()
+			.filter(|field| matches!(field.value, FieldValue::Binary { .. }))
+			.count();
 		let title = Line::from(vec![
-			self.entry.name.as_str().fg(Color::Cyan).bold(),
+			self.entry
+				.icon
+				.as_ref()
+				.map(|icon| format!("{} ", icons::glyph(icon)).fg(theme().accent))
+				.unwrap_or_default(),
+			self.entry.name.as_str().fg(theme().accent).bold(),
+			if attachments != 0 {
+				format!(" {} {attachments}", icons::ATTACHMENT).fg(theme().dim)
+			} else {
+				"".into()
+			},
 			if self.modified {
-				"󰽂 ".fg(Color::Magenta).bold()
+				format!("{} ", icons::UNSAVED).fg(theme().special).bold()
 			} else {
 				"  ".into()
 			},
 		]);
-		let help = Line::from(vec![
-			" ⮁".bold().fg(Color::Green),
-			" (navigate) ".into(),
-			"S-⮁".bold().fg(Color::Green),
-			" (reorder) ".into(),
-			"a".bold().fg(Color::Green),
-			" (add) ".into(),
-			"e".bold().fg(Color::Green),
-			" (edit) ".into(),
-			"d".bold().fg(Color::Green),
-			" (delete) ".into(),
-			"y".bold().fg(Color::Green),
-			" (yank)".into(),
-		])
-		.bg(HELP_LINE_BG);
-
-		let vertical = Layout::vertical([Constraint::Length(1), Constraint::Percentage(100)]);
-		let [help_area, content_area] = vertical.areas(ctx.area);
-
-		let mut items = self
-			.entry
-			.fields
+		let help = keymap::help_line(&keymap::ENTRY).bg(theme().help_bg);
+
+		// The filter is only shown once used
+		let filtered = self.filtering || !self.filter_field.inner.get_input().is_empty();
+		let vertical = Layout::vertical([
+			Constraint::Length(1),
+			Constraint::Length(if filtered {
+				self.filter_field.height()
+			} else {
+				0
+			}),
+			Constraint::Fill(1),
+			Constraint::Length(if self.timestamps { 3 } else { 0 }),
+		]);
+		let [help_area, filter_area, content_area, timestamps_area] = vertical.areas(ctx.area);
+
+		let visible = self.visible_fields();
+		let mut items = visible
 			.iter()
 			.enumerate()
-			.map(|(id, ent)| {
+			.map(|(row, id)| {
 				Self::field_preview(
 					content_area.width,
-					Some(ent),
-					Some(id) == self.selected,
-					Some(id) == self.copied,
-					id,
+					Some(&self.entry.fields[*id]),
+					Some(*id) == self.selected,
+					self.copied
+						.and_then(|(copied, part)| (copied == *id).then_some(part)),
+					self.revealed
+						.is_some_and(|(revealed, _)| revealed == *id),
+					row,
 				)
 			})
 			.collect::<Vec<_>>();
@@ -377,6 +1008,7 @@ impl Component for EntryEditor {
 				content_area.width,
 				None,
 				false,
+				None,
 				false,
 				items.len(),
 			));
@@ -388,41 +1020,30 @@ impl Component for EntryEditor {
 		);
 		frame.render_widget(Clear, ctx.area);
 		frame.render_widget(help, help_area);
+		if filtered {
+			let (area, selected) = (ctx.area, ctx.selected);
+			ctx.area = filter_area;
+			ctx.selected = self.filtering;
+			self.filter_field.render(frame, ctx);
+			(ctx.area, ctx.selected) = (area, selected);
+		}
 		frame.render_widget(messages, content_area);
-
-		// Field editor
-		if let Some(editor) = &self.editor {
-			let _title = format!(
-				"{} > {}",
-				self.entry.name,
-				if let Some(selected) = self.selected {
-					&self.entry.fields[selected].name
-				} else {
-					"New Field"
-				}
-			);
-			let area = frame.area();
-			let vertical = Layout::vertical([Constraint::Length(20)]).flex(Flex::Center);
-			let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
-			let [area] = area.layout(&vertical);
-			let [area] = area.layout(&horizontal);
-			let mut queue = vec![];
-			let mut ctx = ComponentRenderCtx {
-				area,
-				selected: false,
-				queue: &mut queue,
-				depth: 0,
-				cursor: None,
-			};
-			editor.render_form(frame, &mut ctx);
-			if let Some((_, cursor)) = ctx.cursor {
-				frame.set_cursor_position(cursor);
-			}
+		// Below the title
+		self.list_area.set(Rect {
+			y: content_area.y + 1,
+			height: content_area.height.saturating_sub(1),
+			..content_area
+		});
+		if self.timestamps {
+			self.render_timestamps(frame, timestamps_area);
 		}
 
-		// Confirm
-		if let Some(confirm) = &self.confirm {
-			confirm.render(frame, ctx);
+		// Screens
+		self.screens.render(frame, ctx);
+
+		// Toast
+		if let Some(toast) = &self.toast {
+			toast.render(frame, ctx);
 		}
 	}
 
@@ -0,0 +1,161 @@
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+
+use crate::data::entry::EntryRevision;
+use crate::keymap;
+use crate::style::theme;
+use crate::ui::entry::EntryEditor;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Browse the previous revisions of an entry
+pub struct EntryHistory {
+	name: String,
+	/// Revisions, oldest first
+	revisions: Vec<EntryRevision>,
+	/// Selected position in the displayed list, newest first
+	selected: usize,
+	restore: Option<usize>,
+}
+
+impl EntryHistory {
+	pub fn new(name: String, revisions: Vec<EntryRevision>) -> Self {
+		Self {
+			name,
+			revisions,
+			selected: 0,
+			restore: None,
+		}
+	}
+
+	fn selected_revision(&self) -> Option<&EntryRevision> {
+		self.revisions.iter().rev().nth(self.selected)
+	}
+
+	/// Revision to restore, if one was picked
+	pub fn submit(&self) -> Option<&EntryRevision> {
+		self.restore.and_then(|index| self.revisions.get(index))
+	}
+}
+
+impl Component for EntryHistory {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+			KeyCode::Down | KeyCode::Char('j') => {
+				self.selected = (self.selected + 1).min(self.revisions.len().saturating_sub(1))
+			}
+			KeyCode::Enter => {
+				if !self.revisions.is_empty() {
+					self.restore = Some(self.revisions.len() - 1 - self.selected);
+				}
+				return false;
+			}
+			KeyCode::Esc | KeyCode::Char('q') => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(format!("History: {}", self.name))
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::ENTRY_HISTORY);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		if self.revisions.is_empty() {
+			frame.render_widget(
+				Line::from("No previous revisions".italic().fg(theme().dim)),
+				body,
+			);
+			return;
+		}
+		let [list_area, fields_area] =
+			Layout::horizontal([Constraint::Length(22), Constraint::Fill(1)]).areas(body);
+
+		let revisions = self
+			.revisions
+			.iter()
+			.rev()
+			.enumerate()
+			.map(|(id, revision)| {
+				let item = ListItem::new(Line::from(vec![
+					" ".into(),
+					revision
+						.modified_at
+						.format("%Y-%m-%d %H:%M:%S")
+						.to_string()
+						.fg(theme().text),
+				]));
+				if id == self.selected {
+					item.bg(theme().entry_bg[2])
+				} else {
+					item.bg(theme().entry_bg[id % 2])
+				}
+			})
+			.collect::<Vec<_>>();
+		frame.render_widget(List::new(revisions), list_area);
+
+		if let Some(revision) = self.selected_revision() {
+			let fields = revision
+				.fields
+				.iter()
+				.enumerate()
+				.map(|(id, field)| {
+					EntryEditor::field_preview(
+						fields_area.width,
+						Some(field),
+						false,
+						None,
+						false,
+						id,
+					)
+				})
+				.collect::<Vec<_>>();
+			frame.render_widget(List::new(fields), fields_area);
+		}
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
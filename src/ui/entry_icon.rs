@@ -0,0 +1,242 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::data::entry::ENTRY_ICONS;
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static ICONS: LazyLock<Vec<ComboItem>> = LazyLock::new(|| {
+	ENTRY_ICONS
+		.iter()
+		.map(|(name, icon)| ComboItem {
+			kind: String::default(),
+			icon: format!("{} ", icons::glyph(icon)),
+			value: name.to_string(),
+		})
+		.collect()
+});
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+/// Pick the icon of an entry, from the built-in set or as a custom glyph
+pub struct EntryIconForm {
+	style: FormStyle,
+	title: String,
+
+	field_icon: Labeled<'static, ComboBox<'static, 'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	popup: Option<Popup<'static>>,
+}
+
+impl EntryIconForm {
+	pub fn new(name: &str, icon: Option<&str>) -> Self {
+		// Show built-in icons by name
+		let input = icon
+			.map(|icon| {
+				ENTRY_ICONS
+					.iter()
+					.find(|(_, glyph)| *glyph == icon)
+					.map_or(icon, |(name, _)| name)
+					.to_string()
+			})
+			.unwrap_or_default();
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			title: format!("Icon: {name}"),
+			field_icon: Labeled::new(
+				"Icon (name or glyph, empty for none)".into(),
+				ComboBox::new(ICONS.as_slice())
+					.style(&COMBOBOX_STYLE)
+					.with_input(input),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	/// Get the selected icon, `None` to remove the icon
+	pub fn submit(&self) -> Result<Option<String>, String> {
+		if let Some(id) = self.field_icon.inner.submit() {
+			return Ok(Some(ENTRY_ICONS[id].1.to_string()));
+		}
+		let input = self.field_icon.inner.get_input().trim();
+		match input.graphemes(true).count() {
+			0 => Ok(None),
+			1 => Ok(Some(input.to_string())),
+			_ => Err(format!("Unknown icon '{input}'")),
+		}
+	}
+}
+
+impl Form for EntryIconForm {
+	fn component_count(&self) -> usize {
+		1
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_icon),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_icon),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => match self.submit() {
+				Ok(_) => Some(FormSignal::Return),
+				Err(err) => {
+					self.popup = Some(Popup::new(
+						"Invalid Icon".into(),
+						Paragraph::new(Text::from(err)),
+					));
+					None
+				}
+			},
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(self.title.as_str())
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::ENTRY_ICON);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
@@ -1,8 +1,9 @@
+use std::cell::Cell;
 use std::sync::LazyLock;
 
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
-use ratatui::style::Color;
+use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::symbols::border::QUADRANT_OUTSIDE;
@@ -11,6 +12,8 @@ use ratatui::widgets::Clear;
 use ratatui::Frame;
 
 use crate::data::entry::EntryTag;
+use crate::data::tag::TagRegistry;
+use crate::style::theme;
 use crate::widgets::form::Form;
 use crate::widgets::form::FormExt;
 use crate::widgets::form::FormSignal;
@@ -28,13 +31,13 @@ static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
 	display: LabelDisplay::Block {
 		block: Box::new(Block::bordered()),
 	},
-	style: Some(Style::default().fg(Color::White)),
+	style: Some(Style::default().fg(theme().text)),
 	style_selected: None,
 });
 static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
 	padding: [0, 0],
 	markers: ["".into(), "".into()],
-	style: Some(Style::default().fg(Color::White)),
+	style: Some(Style::default().fg(theme().text)),
 	style_selected: None,
 });
 
@@ -42,10 +45,11 @@ pub struct EntryTagEditor {
 	style: FormStyle,
 	title: String,
 	input: Labeled<'static, TextInput<'static>>,
+	body_area: Cell<Rect>,
 }
 
 impl EntryTagEditor {
-	pub fn new(title: String, tags: &Vec<EntryTag>) -> Self {
+	pub fn new(title: String, tags: &[EntryTag]) -> Self {
 		let input = tags
 			.iter()
 			.map(|x| x.name.as_str())
@@ -54,35 +58,23 @@ impl EntryTagEditor {
 		Self {
 			style: FormStyle {
 				border: true,
-				bg: Color::from_u32(0x2f2f2f),
+				bg: theme().form_bg,
 			},
 			title,
 			input: Labeled::new("Tags".into(), TextInput::new().with_input(input).style(&TEXTINPUT_STYLE)).style(&LABEL_STYLE),
+			body_area: Cell::default(),
 		}
 	}
 
-	pub fn submit(&self) -> Option<Vec<EntryTag>> {
-		let mut result = vec![];
-		let mut rest = &self.input.inner.get_input()[..];
-		// TODO: Create a global tag registry to source icons/colors froms
-		loop {
-			if let Some(next) = rest.find(',') {
-				rest.trim();
-				result.push(EntryTag {
-					name: rest[..next].to_string(),
-					icon: None,
-					color: None,
-				});
-				rest = &rest[next + 1..];
-			} else {
-				rest.trim();
-				result.push(EntryTag {
-					name: rest.to_string(),
-					icon: None,
-					color: None,
-				});
-				break;
+	/// Parse the tags, resolved through `registry`
+	pub fn submit(&self, registry: &TagRegistry) -> Option<Vec<EntryTag>> {
+		let mut result: Vec<EntryTag> = vec![];
+		for name in self.input.inner.get_input().split(',') {
+			let tag = registry.resolve(name);
+			if tag.name.is_empty() || result.iter().any(|other| other.name == tag.name) {
+				continue;
 			}
+			result.push(tag);
 		}
 		Some(result)
 	}
@@ -117,6 +109,10 @@ impl Form for EntryTagEditor {
 
 	fn set_scroll(&self, _scroll: u16) {}
 
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
 	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
 		if FormExt::input(self, key) {
 			return None;
@@ -133,10 +129,10 @@ impl Form for EntryTagEditor {
 		let border = Block::bordered()
 			.border_set(QUADRANT_OUTSIDE)
 			.title(self.title.clone())
-			.title_style(Style::default().fg(Color::White))
+			.title_style(Style::default().fg(theme().text))
 			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
 			.bg(self.style.bg)
-			.fg(Color::from_u32(0x1a1a1f));
+			.fg(theme().form_border);
 		frame.render_widget(Clear, area);
 		frame.render_widget(border, area);
 		ctx.area.x += 1;
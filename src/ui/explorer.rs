@@ -1,14 +1,23 @@
 use core::panic;
+use std::cell::Cell;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::sync::LazyLock;
 
+use chrono::TimeDelta;
 use chrono::Utc;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use ratatui::layout::Constraint;
 use ratatui::layout::Flex;
 use ratatui::layout::Layout;
+use ratatui::layout::Position;
+use ratatui::layout::Rect;
 use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -16,6 +25,7 @@ use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::text::Text;
 use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
 use ratatui::widgets::Clear;
 use ratatui::widgets::List;
 use ratatui::widgets::ListItem;
@@ -26,80 +36,263 @@ use ratatui::widgets::ScrollbarOrientation;
 use ratatui::widgets::ScrollbarState;
 use ratatui::Frame;
 
+use crate::clipboard;
+use crate::config::config;
+use crate::config::EntryColumn;
+use crate::data::audit::AuditAction;
+use crate::data::audit::AuditLog;
+use crate::data::database::SessionState;
 use crate::data::entry::Entry;
+use crate::data::entry::EntryKind;
+use crate::data::entry::EntrySort;
 use crate::data::entry::EntryTag;
-use crate::style::ENTRY_BG;
-use crate::style::HELP_LINE_BG;
+use crate::data::entry::TrashedEntry;
+use crate::data::export::export_encrypted;
+use crate::data::field::FieldValue;
+use crate::data::health;
+use crate::data::import::otp;
+use crate::data::tag::TagRegistry;
+use crate::data::tag::DEFAULT_TAG_COLOR;
+use crate::hooks::run_hooks;
+use crate::hooks::HookContext;
+use crate::hooks::HookEvent;
+use crate::icons;
+use crate::keymap;
+use crate::state;
+use crate::status;
+use crate::style::rgb;
+use crate::style::theme;
+use crate::ui::audit_log::AuditLogView;
 use crate::ui::entry::EntryEditor;
 use crate::ui::entry_tag_editor::EntryTagEditor;
+use crate::ui::import::ImportForm;
+use crate::ui::new_entry::NewEntryForm;
+use crate::ui::screen::form_signal;
+use crate::ui::screen::render_centered;
+use crate::ui::screen::Screen;
+use crate::ui::screen::ScreenSignal;
+use crate::ui::screen::ScreenStack;
+use crate::ui::share::ShareForm;
+use crate::ui::share::ShareRequest;
+use crate::ui::share::ShareSelection;
+use crate::ui::tag_registry::TagRegistryForm;
+use crate::ui::trash::TrashAction;
+use crate::ui::trash::TrashView;
+use crate::ui::undo::UndoStack;
+use crate::widgets::columns;
+use crate::widgets::columns::ColumnWidth;
 use crate::widgets::confirm::Confirm;
 use crate::widgets::form::Form;
 use crate::widgets::form::FormExt;
-use crate::widgets::form::FormSignal;
 use crate::widgets::label::LabelDisplay;
 use crate::widgets::label::LabelStyle;
 use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
 use crate::widgets::text_input::TextInput;
 use crate::widgets::text_input::TextInputStyle;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::ComponentRenderCtx;
 
+/// Filter of the entries, parsed from the filter box
+///
+/// Words are fuzzily matched against the entry name, so `ghub` finds `sites/github`. Double quotes
+/// group words into a phrase that must appear as is, and `+tag` or `tag:tag` only keep the entries
+/// with the tag, e.g. `"work mail" +2fa`.
 #[derive(Default)]
 pub struct ExplorerFilter {
-	pub name: String,
+	/// Words of the name, lowercase
+	pub terms: Vec<String>,
+	/// Phrases of the name, lowercase
+	pub phrases: Vec<String>,
+	/// Required tags, lowercase
 	pub tags: Vec<String>,
 }
 
+/// Split `query` into words, with whether they were quoted
+///
+/// Quotes may start anywhere in a word and an unterminated quote extends to the end of the query.
+fn split_query(query: &str) -> Vec<(String, bool)> {
+	let mut words = vec![];
+	let mut word = String::new();
+	let mut quoted = false;
+	let mut in_quotes = false;
+	for c in query.chars() {
+		match c {
+			'"' => {
+				quoted |= word.is_empty();
+				in_quotes = !in_quotes;
+			}
+			c if c.is_whitespace() && !in_quotes => {
+				if !word.is_empty() {
+					words.push((std::mem::take(&mut word), quoted));
+				}
+				quoted = false;
+			}
+			c => word.push(c),
+		}
+	}
+	if !word.is_empty() {
+		words.push((word, quoted));
+	}
+	words
+}
+
 impl From<&str> for ExplorerFilter {
 	fn from(value: &str) -> Self {
 		let mut filter = Self::default();
-		let mut rest = value;
-		loop {
-			while rest.starts_with(" ") || rest.starts_with("\t") {
-				rest = &rest[1..];
-			}
-			if rest.is_empty() {
-				break;
-			}
-			let end = rest
-				.chars()
-				.position(|c| c == ' ' || c == '\t')
-				.unwrap_or(rest.len());
-			if rest.starts_with("+") {
-				filter.tags.push(String::from(&rest[1..end]));
-			} else {
-				if !filter.name.is_empty() {
-					filter.name.push(' ');
-				}
-				filter.name += &rest[..end];
+		for (word, quoted) in split_query(value) {
+			let word = word.to_lowercase();
+			// Quoted words are always part of the name
+			let tag = word.strip_prefix('+').or_else(|| word.strip_prefix("tag:"));
+			match tag {
+				// Tag being typed
+				Some("") if !quoted => {}
+				Some(tag) if !quoted => filter.tags.push(tag.to_string()),
+				_ if quoted => filter.phrases.push(word),
+				_ => filter.terms.push(word),
 			}
-			rest = &rest[end..];
 		}
 		filter
 	}
 }
 
+/// Score of `pattern` matched as a subsequence of `text`, with the positions of the matched
+/// characters, `None` if it does not match
+///
+/// Both are expected in lowercase. As in fzf, characters matched at the start of a word or right
+/// after the previous match are rewarded, and characters skipped between matches are penalized.
+fn fuzzy_match(pattern: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+	const MATCH: i32 = 16;
+	const BOUNDARY: i32 = 8;
+	const CONSECUTIVE: i32 = 8;
+	const GAP_START: i32 = 3;
+	const GAP_EXTENSION: i32 = 1;
+
+	let text = text.chars().collect::<Vec<_>>();
+	let bonus = |pos: usize| {
+		if pos == 0 || matches!(text[pos - 1], '/' | ' ' | '-' | '_' | '.' | ':' | '@') {
+			BOUNDARY
+		} else {
+			0
+		}
+	};
+
+	// For each character of the pattern, best score with it matched at each position, along with
+	// the position of the previous character
+	let mut rows: Vec<Vec<Option<(i32, usize)>>> = vec![];
+	for c in pattern.chars() {
+		let previous = rows.last();
+		let mut current = vec![None; text.len()];
+		// Best score of the previous characters followed by a gap, ending before the position
+		let mut gapped: Option<(i32, usize)> = None;
+		for pos in 0..text.len() {
+			if let Some(previous) = previous.filter(|_| pos >= 2) {
+				let start = previous[pos - 2].map(|(score, _)| (score - GAP_START, pos - 2));
+				gapped = gapped
+					.map(|(score, from)| (score - GAP_EXTENSION, from))
+					.max(start);
+			}
+			if text[pos] != c {
+				continue;
+			}
+			let before = match previous {
+				None => Some((0, 0)),
+				Some(previous) => {
+					let consecutive = (pos >= 1)
+						.then(|| previous[pos - 1])
+						.flatten()
+						.map(|(score, _)| (score + CONSECUTIVE, pos - 1));
+					consecutive.max(gapped)
+				}
+			};
+			current[pos] = before.map(|(score, from)| (score + MATCH + bonus(pos), from));
+		}
+		rows.push(current);
+	}
+
+	let Some(last) = rows.last() else {
+		return Some((0, vec![]));
+	};
+	let (mut pos, (score, _)) = last
+		.iter()
+		.enumerate()
+		.filter_map(|(pos, cell)| Some((pos, (*cell)?)))
+		.max_by_key(|(_, (score, _))| *score)?;
+	// Follow the previous characters back from the best last one
+	let mut positions = vec![pos];
+	for row in rows[1..].iter().rev() {
+		pos = row[pos]?.1;
+		positions.push(pos);
+	}
+	positions.reverse();
+	Some((score, positions))
+}
+
 impl ExplorerFilter {
-	pub fn filter(&self, ent: &Entry) -> bool {
-		// Tags
-		for tag in &self.tags {
-			let mut found = false;
-			for ent_tag in &ent.tags {
-				if tag == &ent_tag.name {
-					found = true;
-					break;
+	/// Score of `ent`, higher for better matches, `None` if it is filtered out
+	pub fn score(&self, ent: &Entry) -> Option<i32> {
+		let tagged = self.tags.iter().all(|tag| {
+			ent.tags
+				.iter()
+				.any(|ent_tag| ent_tag.name.to_lowercase() == *tag)
+		});
+		if !tagged {
+			return None;
+		}
+		self.score_text(&ent.name)
+	}
+
+	/// Score of `text` matched by the words and phrases, ignoring the tags, `None` if it does not
+	/// match
+	pub fn score_text(&self, text: &str) -> Option<i32> {
+		let text = text.to_lowercase();
+		let phrases = self
+			.phrases
+			.iter()
+			.all(|phrase| text.contains(phrase.as_str()));
+		if !phrases {
+			return None;
+		}
+		self.terms
+			.iter()
+			.map(|term| Some(fuzzy_match(term, &text)?.0))
+			.sum()
+	}
+
+	/// Characters of `name` matched by the words and phrases, by position
+	pub fn highlights(&self, name: &str) -> Vec<bool> {
+		// Position in `name` of each character of its lowercase version
+		let owners = name
+			.chars()
+			.enumerate()
+			.flat_map(|(pos, c)| c.to_lowercase().map(move |_| pos))
+			.collect::<Vec<_>>();
+		let lower = name
+			.chars()
+			.flat_map(char::to_lowercase)
+			.collect::<String>();
+		let mut matched = vec![false; name.chars().count()];
+		for phrase in &self.phrases {
+			if let Some(start) = lower.find(phrase.as_str()) {
+				let start = lower[..start].chars().count();
+				for pos in start..start + phrase.chars().count() {
+					matched[owners[pos]] = true;
 				}
 			}
-			if !found {
-				return false;
+		}
+		for term in &self.terms {
+			for pos in fuzzy_match(term, &lower)
+				.map(|(_, positions)| positions)
+				.unwrap_or_default()
+			{
+				matched[owners[pos]] = true;
 			}
 		}
+		matched
+	}
 
-		// Name
-		ent.name
-			.to_lowercase()
-			.as_str()
-			.contains(self.name.to_lowercase().as_str())
+	pub fn filter(&self, ent: &Entry) -> bool {
+		self.score(ent).is_some()
 	}
 }
 
@@ -109,12 +302,70 @@ pub enum ActiveWidget {
 	#[default]
 	Search,
 	Content,
+	/// Tag sidebar
+	Tags,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum ConfirmAction {
 	Delete,
+	DeleteMarked,
+	/// Move the entries of folder `from` to folder `to`
+	RenameFolder {
+		from: String,
+		to: String,
+	},
+}
+
+/// Entries listed by the explorer
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExplorerView {
+	#[default]
+	All,
+	/// Entries marked as favorite
+	Favorites,
+	/// Entries opened or copied from, most recent first
+	Recent,
+}
+
+/// Number of entries listed by [`ExplorerView::Recent`]
+const RECENT_ENTRIES: usize = 20;
+
+impl ExplorerView {
+	fn next(self) -> Self {
+		match self {
+			ExplorerView::All => ExplorerView::Favorites,
+			ExplorerView::Favorites => ExplorerView::Recent,
+			ExplorerView::Recent => ExplorerView::All,
+		}
+	}
+
+	/// Whether `ent` is listed, entries accessed since their creation are recently used
+	fn contains(self, ent: &Entry) -> bool {
+		match self {
+			ExplorerView::All => true,
+			ExplorerView::Favorites => ent.favorite,
+			ExplorerView::Recent => ent.accessed_at > ent.created_at,
+		}
+	}
+}
+
+/// Row of the entry list
+#[derive(Clone, PartialEq, Eq)]
+enum Row {
+	/// Entry, by index
+	Entry(usize),
+	/// Heading of the entries with a tag, `None` for the untagged entries, with their number
+	Group(Option<String>, usize),
+}
+
+/// Action applied to the marked entries, from the text typed in its prompt
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+	/// Add `tag` or `+tag` and remove `-tag`
+	Tag,
+	/// Move the entries under a folder, given as a prefix of their names
+	Move,
 }
 
 static SEARCH_LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
@@ -122,87 +373,302 @@ static SEARCH_LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
 	display: LabelDisplay::Block {
 		block: Box::new(Block::bordered().border_type(ratatui::widgets::BorderType::Thick)),
 	},
-	style: Some(
-		Style::default()
-			.fg(Color::Black)
-			.bg(Color::from_u32(0x241f31)),
-	),
-	style_selected: Some(
-		Style::default()
-			.fg(Color::Cyan)
-			.bg(Color::from_u32(0x241f31)),
-	),
+	style: Some(Style::default().fg(theme().base).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
 });
 static SEARCH_INPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
 	padding: [0, 0],
 	markers: ["".into(), "".into()],
-	style: Some(
-		Style::default()
-			.fg(Color::White)
-			.bg(Color::from_u32(0x241f31)),
-	),
-	style_selected: Some(
-		Style::default()
-			.fg(Color::Cyan)
-			.bg(Color::from_u32(0x241f31)),
-	),
+	style: Some(Style::default().fg(theme().text).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
 });
 static NEWENTRY_LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
 	padding: [0, 0],
 	display: LabelDisplay::Block {
 		block: Box::new(Block::bordered().border_type(ratatui::widgets::BorderType::Thick)),
 	},
-	style: Some(
-		Style::default()
-			.fg(Color::Black)
-			.bg(Color::from_u32(0x241f31)),
-	),
-	style_selected: Some(
-		Style::default()
-			.fg(Color::Cyan)
-			.bg(Color::from_u32(0x241f31)),
-	),
+	style: Some(Style::default().fg(theme().base).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
 });
 static NEWENTRY_INPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
 	padding: [0, 0],
 	markers: ["".into(), "".into()],
-	style: Some(
-		Style::default()
-			.fg(Color::White)
-			.bg(Color::from_u32(0x241f31)),
-	),
+	style: Some(Style::default().fg(theme().text).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
+});
+static RENAME_INPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text).bg(theme().selection_bg)),
 	style_selected: Some(
 		Style::default()
-			.fg(Color::Cyan)
-			.bg(Color::from_u32(0x241f31)),
+			.fg(theme().highlight)
+			.bg(theme().selection_bg)
+			.bold(),
 	),
 });
 
+/// Width of `column` in the entry list
+fn column_width(column: EntryColumn) -> ColumnWidth {
+	match column {
+		EntryColumn::Health => ColumnWidth::Fixed(1),
+		EntryColumn::Name => ColumnWidth::Fill(3),
+		EntryColumn::Tags => ColumnWidth::Fill(1),
+		EntryColumn::Fields => ColumnWidth::Fixed(5),
+		EntryColumn::Modified => ColumnWidth::Fixed(10),
+	}
+}
+
+/// Prompt for a folder, filled with `folder`
+fn folder_prompt(label: String, folder: String) -> Labeled<'static, TextInput<'static>> {
+	Labeled::new(
+		label.into(),
+		TextInput::new()
+			.style(&NEWENTRY_INPUT_STYLE)
+			.with_input(folder),
+	)
+	.style(&NEWENTRY_LABEL_STYLE)
+}
+
+/// Screen opened over the entry list
+#[allow(clippy::large_enum_variant)]
+pub enum ExplorerScreen {
+	NewEntry(NewEntryForm),
+	/// Name of the selected entry, edited in its row
+	Rename {
+		input: TextInput<'static>,
+		/// Whether to continue with the next entry once renamed
+		next: bool,
+		/// Area of the name in the selected row, set when the list is rendered
+		area: Cell<Rect>,
+	},
+	Bulk(BulkAction, Labeled<'static, TextInput<'static>>),
+	/// Folder rename, with the folder once chosen
+	Folder(Option<String>, Labeled<'static, TextInput<'static>>),
+	Editor(EntryEditor),
+	TagEditor(EntryTagEditor),
+	TagRegistry(TagRegistryForm),
+	AuditLog(AuditLogView),
+	Trash(TrashView),
+	Import(ImportForm),
+	Share(ShareForm),
+	Popup(Popup<'static>),
+	Confirm(ConfirmAction, Confirm<'static>),
+}
+
+impl Screen for ExplorerScreen {
+	fn input(&mut self, key: &KeyEvent) -> ScreenSignal {
+		match self {
+			ExplorerScreen::NewEntry(form) => form_signal(form.input_form(key)),
+			ExplorerScreen::Rename { input, next, .. } => match key.code {
+				KeyCode::Esc => ScreenSignal::Closed,
+				KeyCode::Enter | KeyCode::Tab => {
+					*next = key.code == KeyCode::Tab;
+					ScreenSignal::Submitted
+				}
+				_ => {
+					input.input(key);
+					ScreenSignal::Handled
+				}
+			},
+			ExplorerScreen::Bulk(_, input) | ExplorerScreen::Folder(_, input) => {
+				if key.code == KeyCode::Esc {
+					ScreenSignal::Closed
+				} else if input.input(key) {
+					ScreenSignal::Handled
+				} else {
+					ScreenSignal::Submitted
+				}
+			}
+			// Closing the editor submits the entry
+			ExplorerScreen::Editor(editor) => {
+				if editor.input(key) {
+					ScreenSignal::Handled
+				} else {
+					ScreenSignal::Submitted
+				}
+			}
+			ExplorerScreen::TagEditor(editor) => form_signal(editor.input_form(key)),
+			ExplorerScreen::TagRegistry(form) => form_signal(form.input_form(key)),
+			ExplorerScreen::AuditLog(view) => {
+				if view.input(key) {
+					ScreenSignal::Handled
+				} else {
+					ScreenSignal::Closed
+				}
+			}
+			ExplorerScreen::Trash(view) => {
+				if view.input(key) {
+					ScreenSignal::Handled
+				} else {
+					ScreenSignal::Closed
+				}
+			}
+			ExplorerScreen::Import(form) => form_signal(form.input_form(key)),
+			ExplorerScreen::Share(form) => form_signal(form.input_form(key)),
+			ExplorerScreen::Popup(popup) => {
+				if popup.input(key) {
+					ScreenSignal::Closed
+				} else {
+					ScreenSignal::Handled
+				}
+			}
+			ExplorerScreen::Confirm(_, confirm) => {
+				confirm.input(key);
+				if confirm.submit().is_some() {
+					ScreenSignal::Submitted
+				} else {
+					ScreenSignal::Handled
+				}
+			}
+		}
+	}
+
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		match self {
+			ExplorerScreen::NewEntry(form) => FormExt::mouse_input(form, event),
+			ExplorerScreen::Editor(editor) => editor.mouse_input(event),
+			ExplorerScreen::TagEditor(form) => FormExt::mouse_input(form, event),
+			ExplorerScreen::TagRegistry(form) => FormExt::mouse_input(form, event),
+			ExplorerScreen::Import(form) => FormExt::mouse_input(form, event),
+			ExplorerScreen::Share(form) => FormExt::mouse_input(form, event),
+			_ => vec![],
+		}
+	}
+
+	fn paste(&mut self, text: &str) -> bool {
+		match self {
+			ExplorerScreen::NewEntry(form) => FormExt::paste(form, text),
+			ExplorerScreen::Rename { input, .. } => input.paste(text),
+			ExplorerScreen::Bulk(_, input) | ExplorerScreen::Folder(_, input) => input.paste(text),
+			ExplorerScreen::Editor(editor) => editor.paste(text),
+			ExplorerScreen::TagEditor(form) => FormExt::paste(form, text),
+			ExplorerScreen::TagRegistry(form) => FormExt::paste(form, text),
+			ExplorerScreen::Import(form) => FormExt::paste(form, text),
+			ExplorerScreen::Share(form) => FormExt::paste(form, text),
+			_ => false,
+		}
+	}
+
+	fn tick(&mut self) {
+		if let ExplorerScreen::Editor(editor) = self {
+			editor.tick();
+		}
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let full = ctx.area;
+		match self {
+			ExplorerScreen::NewEntry(form) => render_centered(form, form.height() + 1, frame, ctx),
+			ExplorerScreen::Rename { input, area, .. } => {
+				if area.get().is_empty() {
+					return;
+				}
+				ctx.area = area.get();
+				ctx.selected = true;
+				input.render(frame, ctx);
+			}
+			ExplorerScreen::Bulk(_, input) | ExplorerScreen::Folder(_, input) => {
+				let horizontal =
+					Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
+				let vertical = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center);
+				let [area] = ctx.area.layout(&horizontal);
+				let [area] = area.layout(&vertical);
+				ctx.area = area;
+				ctx.selected = true;
+				input.render(frame, ctx);
+			}
+			ExplorerScreen::Editor(editor) => editor.render(frame, ctx),
+			ExplorerScreen::TagEditor(editor) => {
+				render_centered(editor, editor.height(), frame, ctx)
+			}
+			ExplorerScreen::TagRegistry(form) => {
+				render_centered(form, form.height() + 1, frame, ctx)
+			}
+			ExplorerScreen::AuditLog(view) => view.render(frame, ctx),
+			ExplorerScreen::Trash(view) => view.render(frame, ctx),
+			ExplorerScreen::Import(form) => render_centered(form, form.height() + 1, frame, ctx),
+			ExplorerScreen::Share(form) => render_centered(form, form.height() + 1, frame, ctx),
+			ExplorerScreen::Popup(popup) => {
+				ctx.area = frame.area();
+				popup.render(frame, ctx);
+			}
+			ExplorerScreen::Confirm(_, confirm) => confirm.render(frame, ctx),
+		}
+		ctx.area = full;
+	}
+}
+
 pub struct Explorer {
+	/// Title shown in the help bar
+	title: String,
+	/// Whether changes are not saved to the database yet, shown next to the title
+	dirty: bool,
 	entries: Vec<Entry>,
+	/// Deleted entries, most recently deleted last
+	trash: Vec<TrashedEntry>,
+	tags: TagRegistry,
+	log: AuditLog,
 	filtered_entries: Vec<usize>,
+	/// Listed rows, the filtered entries under the headings of their tags when grouped
+	rows: Vec<Row>,
 	active: ActiveWidget,
+	/// Selected row
 	selected: usize,
 
 	filter_field: Labeled<'static, TextInput<'static>>,
 	list_state: RefCell<ListState>,
 	scrollbar: RefCell<ScrollbarState>,
+	/// Areas of the filter and of the entries, for the mouse
+	filter_area: Cell<Rect>,
+	list_area: Cell<Rect>,
+
+	/// Whether the tag sidebar is shown
+	tag_sidebar: bool,
+	/// Selected tag of the sidebar, in the order of [`TagRegistry::sorted`]
+	tag_selected: usize,
+	tag_list_state: RefCell<ListState>,
+	tag_area: Cell<Rect>,
 
-	new_entry: Option<Labeled<'static, TextInput<'static>>>,
-	rename: Option<Labeled<'static, TextInput<'static>>>,
-	editor: Option<EntryEditor>,
-	tag_editor: Option<EntryTagEditor>,
+	/// Screens opened over the entry list, the top-most one receives the input
+	screens: ScreenStack<ExplorerScreen>,
 
-	confirm_action: Option<ConfirmAction>,
-	confirm: Option<Confirm<'static>>,
+	/// Changes of the entries that can be undone
+	history: UndoStack,
+	/// Set when entries were modified, see [`Explorer::take_modified`]
+	modified: bool,
+	/// Set when the current entry should be edited in the external editor
+	external_edit: bool,
+	/// Set when the current entry should be moved to another database
+	transfer: bool,
+	/// Order of the entries, when the filter does not rank them
+	sort: EntrySort,
+	view: ExplorerView,
+	/// Whether the entries are grouped under their tags, entries with several tags are listed
+	/// under each
+	grouped: bool,
+	/// Collapsed groups, by tag
+	collapsed: BTreeSet<Option<String>>,
+	/// Names of the entries marked for bulk actions
+	marked: BTreeSet<String>,
+	/// Whether typed characters jump to the entries starting with them
+	jump: bool,
 }
 
 impl Explorer {
-	pub fn new(entries: Vec<Entry>) -> Self {
+	pub fn new(mut entries: Vec<Entry>, mut tags: TagRegistry, log: AuditLog) -> Self {
+		tags.sync(&entries);
+		tags.apply(&mut entries);
 		let len = entries.len();
-		Self {
+		let mut explorer = Self {
+			title: "PassK".into(),
+			dirty: false,
 			entries,
+			trash: vec![],
+			tags,
+			log,
 			filtered_entries: (0..len).collect(),
+			rows: vec![],
 			active: Default::default(),
 			selected: 0,
 			filter_field: Labeled::new(
@@ -212,292 +678,1851 @@ impl Explorer {
 			.style(&SEARCH_LABEL_STYLE),
 			list_state: RefCell::default(),
 			scrollbar: RefCell::new(ScrollbarState::new(len).position(0)),
-			new_entry: None,
-			rename: None,
-			editor: None,
-			tag_editor: None,
-			confirm_action: None,
-			confirm: None,
+			filter_area: Cell::default(),
+			list_area: Cell::default(),
+			tag_sidebar: false,
+			tag_selected: 0,
+			tag_list_state: RefCell::default(),
+			tag_area: Cell::default(),
+			screens: ScreenStack::default(),
+			history: UndoStack::default(),
+			modified: false,
+			external_edit: false,
+			transfer: false,
+			sort: state::load().sort,
+			view: ExplorerView::default(),
+			grouped: false,
+			collapsed: BTreeSet::new(),
+			marked: BTreeSet::new(),
+			jump: false,
+		};
+		explorer.update_filter();
+		explorer
+	}
+
+	/// Set the deleted entries
+	pub fn with_trash(mut self, trash: Vec<TrashedEntry>) -> Self {
+		self.trash = trash;
+		self
+	}
+
+	/// Sort the entries by `sort`, keeping the selected entry, and remember it for the next runs
+	fn set_sort(&mut self, sort: EntrySort) {
+		let current = self.current_id();
+		self.sort = sort;
+		self.update_filter();
+		if let Some(id) = current {
+			self.select_id(id);
+		}
+		let direction = if sort.descending {
+			"descending"
+		} else {
+			"ascending"
+		};
+		match state::update(|state| state.sort = sort) {
+			Ok(()) => status::post(format!("Sorted by {} ({direction})", sort.key)),
+			Err(err) => status::post(format!("Failed to remember the sort: {err}")),
 		}
 	}
 
-	fn move_cursor(&mut self, offset: i32) {
-		if self.filtered_entries.is_empty() {
-			self.list_state.borrow_mut().select(None);
-			self.selected = 0;
-			return;
+	/// List the entries of `view`, keeping the selected entry when it is listed
+	fn set_view(&mut self, view: ExplorerView) {
+		let current = self.current_id();
+		self.view = view;
+		self.selected = 0;
+		self.update_filter();
+		if let Some(id) = current {
+			self.select_id(id);
 		}
-		if offset > 0 {
-			self.selected = std::cmp::min(
-				self.selected + offset as usize,
-				self.filtered_entries.len() - 1,
-			);
-		} else if offset < 0 {
-			self.selected = self.selected.saturating_sub((-offset) as usize);
+	}
+
+	/// Group the entries under their tags, or list them again, keeping the selected entry
+	fn toggle_grouped(&mut self) {
+		let current = self.current_id();
+		self.grouped = !self.grouped;
+		self.selected = 0;
+		self.update_filter();
+		if let Some(id) = current {
+			self.select_id(id);
 		}
+		status::post(if self.grouped {
+			"Grouped the entries by tag"
+		} else {
+			"Listed the entries without groups"
+		});
+	}
 
-		let scrollbar = self.scrollbar.borrow().position(self.selected);
-		*self.scrollbar.borrow_mut() = scrollbar;
+	/// Collapse the group of the selected row, or expand it if it is collapsed, its heading gets
+	/// selected
+	fn toggle_group(&mut self) {
+		let Some((pos, tag)) = self
+			.rows
+			.iter()
+			.enumerate()
+			.take(self.selected + 1)
+			.rev()
+			.find_map(|(pos, row)| match row {
+				Row::Group(tag, _) => Some((pos, tag.clone())),
+				Row::Entry(_) => None,
+			})
+		else {
+			return;
+		};
+		if !self.collapsed.remove(&tag) {
+			self.collapsed.insert(tag);
+		}
+		self.selected = pos;
+		self.update_filter();
 	}
 
-	fn update_filter(&mut self) {
-		let filter = ExplorerFilter::from(self.filter_field.inner.get_input().as_str());
-		self.filtered_entries.clear();
+	/// Rows of the filtered entries under the headings of their tags, sorted by name, the
+	/// untagged entries are last
+	fn group_rows(&self) -> Vec<Row> {
+		let mut groups = BTreeMap::<String, (String, Vec<usize>)>::new();
+		let mut untagged = vec![];
+		for id in &self.filtered_entries {
+			let tags = &self.entries[*id].tags;
+			if tags.is_empty() {
+				untagged.push(*id);
+			}
+			for tag in tags {
+				groups
+					.entry(tag.name.to_lowercase())
+					.or_insert_with(|| (tag.name.clone(), vec![]))
+					.1
+					.push(*id);
+			}
+		}
+		let groups = groups
+			.into_values()
+			.map(|(tag, ids)| (Some(tag), ids))
+			.chain((!untagged.is_empty()).then_some((None, untagged)));
 
-		for (id, ent) in self.entries.iter().enumerate() {
-			if filter.filter(ent) {
-				self.filtered_entries.push(id);
+		let mut rows = vec![];
+		for (tag, ids) in groups {
+			let collapsed = self.collapsed.contains(&tag);
+			rows.push(Row::Group(tag, ids.len()));
+			if !collapsed {
+				rows.extend(ids.into_iter().map(Row::Entry));
 			}
 		}
+		rows
 	}
 
-	fn format_entry(ent: Option<&Entry>, selected: bool, id: usize) -> ListItem<'_> {
-		fn format_tag(tag: &EntryTag) -> Span<'_> {
-			let style = Style::default()
-				.fg(Color::from_u32(tag.color.unwrap_or(0xDEA13B)))
-				.italic();
-			if let Some(icon) = &tag.icon {
-				Span::styled(format!("+{} {icon}", tag.name), style)
-			} else {
-				Span::styled(format!("+{}", tag.name), style)
-			}
+	/// Add the selected entry to the favorites, or remove it
+	fn toggle_favorite(&mut self) {
+		if self.current_id().is_none() {
+			return;
 		}
+		let name = self.current_entry().name.clone();
+		self.history
+			.checkpoint(&self.entries, &self.trash, format!("favorite of '{name}'"));
+		let entry = self.current_entry_mut();
+		entry.favorite = !entry.favorite;
+		status::post(if entry.favorite {
+			format!("Added '{name}' to the favorites")
+		} else {
+			format!("Removed '{name}' from the favorites")
+		});
+		self.modified = true;
+		self.update_filter();
+		self.select_entry(&name);
+	}
 
-		let bg = ENTRY_BG[if selected { 2 } else { id % 2 }];
-		let Some(ent) = ent else {
-			return ListItem::from(Line::from("")).bg(bg);
+	/// Names of the tags listed in the sidebar
+	fn sidebar_tags(&self) -> Vec<String> {
+		self.tags
+			.sorted()
+			.into_iter()
+			.map(|tag| tag.name.clone())
+			.collect()
+	}
+
+	/// Require tag `name` in the filter, or remove it from the filter if it is already required
+	fn toggle_filter_tag(&mut self, name: &str) {
+		let input = self.filter_field.inner.get_input();
+		let name_lower = name.to_lowercase();
+		let is_tag = |word: &&str| {
+			let word = word.to_lowercase();
+			word.strip_prefix('+').or_else(|| word.strip_prefix("tag:"))
+				== Some(name_lower.as_str())
 		};
-		let mut comp = vec![" ".into()];
+		let input = if input.split_whitespace().any(|word| is_tag(&word)) {
+			input
+				.split_whitespace()
+				.filter(|word| !is_tag(word))
+				.collect::<Vec<_>>()
+				.join(" ")
+		} else if input.is_empty() || input.ends_with(char::is_whitespace) {
+			format!("{input}+{name}")
+		} else {
+			format!("{input} +{name}")
+		};
+		self.filter_field.inner.set_input(input);
+		self.selected = 0;
+		self.update_filter();
+	}
 
-		// Name
-		let mut rest = &ent.name[..];
-		loop {
-			let Some(next) = rest.find('/') else {
-				comp.push(Span::styled(rest, Style::default().fg(Color::Green).bold()));
-				break;
-			};
-			comp.push(Span::styled(
-				&rest[..next],
-				Style::default().fg(Color::from_u32(0xafafaf)),
-			));
-			comp.push(Span::styled(
-				"/",
-				Style::default().fg(Color::from_u32(0xaf5f5f)).bold(),
-			));
-			rest = &rest[next + 1..]
+	/// Show the tag sidebar and focus it, or hide it when it is focused
+	fn toggle_tag_sidebar(&mut self) {
+		if self.tag_sidebar && self.active == ActiveWidget::Tags {
+			self.tag_sidebar = false;
+			self.active = ActiveWidget::Content;
+		} else {
+			self.tag_sidebar = true;
+			self.active = ActiveWidget::Tags;
 		}
+	}
 
-		comp.push(" ".into());
-		// Fields
-		comp.push(Span::styled(
-			format!("({})", ent.fields.len()),
-			Style::default().fg(Color::from_u32(0x4f4f4f)).italic(),
-		));
+	/// Select the next entry whose name, or the last component of its name, starts with `c`,
+	/// wrapping around
+	fn jump_to(&mut self, c: char) {
+		let prefix = c.to_lowercase().collect::<String>();
+		let starts = |id: usize| {
+			let name = self.entries[id].name.to_lowercase();
+			let base = name.rsplit('/').next().unwrap_or(&name);
+			name.starts_with(&prefix) || base.starts_with(&prefix)
+		};
+		let len = self.rows.len();
+		let Some(pos) = (1..=len)
+			.map(|step| (self.selected + step) % len)
+			.find(|pos| matches!(self.rows[*pos], Row::Entry(id) if starts(id)))
+		else {
+			status::post(format!("No entry starts with '{c}'"));
+			return;
+		};
+		self.selected = pos;
+		self.move_cursor(0);
+	}
 
-		// Tags
-		for tag in &ent.tags {
-			comp.push(" ".into());
-			comp.push(format_tag(tag));
+	/// Mark or unmark the selected entry
+	fn toggle_mark(&mut self) {
+		if self.current_id().is_none() {
+			return;
+		}
+		let name = self.current_entry().name.clone();
+		if !self.marked.remove(&name) {
+			self.marked.insert(name);
 		}
+		self.move_cursor(1);
+	}
 
-		ListItem::from(Line::from(comp)).bg(bg)
+	/// Mark the filtered entries, or unmark them if they are all marked already
+	fn toggle_mark_all(&mut self) {
+		let names = self
+			.filtered_entries
+			.iter()
+			.map(|id| self.entries[*id].name.clone())
+			.collect::<Vec<_>>();
+		if names.iter().all(|name| self.marked.contains(name)) {
+			for name in &names {
+				self.marked.remove(name);
+			}
+		} else {
+			self.marked.extend(names);
+		}
 	}
 
-	fn current_entry(&self) -> &Entry {
-		&self.entries[self.filtered_entries[self.selected]]
+	/// Open the prompt of a bulk action
+	fn open_bulk(&mut self, action: BulkAction) {
+		let label = match action {
+			BulkAction::Tag => format!("Tags of {} entries (+tag -tag)", self.marked.len()),
+			BulkAction::Move => format!("Move {} entries under", self.marked.len()),
+		};
+		let input = Labeled::new(label.into(), TextInput::new().style(&NEWENTRY_INPUT_STYLE))
+			.style(&NEWENTRY_LABEL_STYLE);
+		self.screens.push(ExplorerScreen::Bulk(action, input));
 	}
 
-	fn current_entry_mut(&mut self) -> &mut Entry {
-		&mut self.entries[self.filtered_entries[self.selected]]
+	/// Copy the field named `name` of the selected entry, without opening it
+	fn copy_field(&mut self, name: &str) {
+		let Some(index) = self.current_id() else {
+			return;
+		};
+		let entry = &mut self.entries[index];
+		let Some(field) = entry
+			.fields
+			.iter_mut()
+			.find(|field| field.name.eq_ignore_ascii_case(name))
+		else {
+			status::post(format!("'{}' has no {name} field", entry.name));
+			return;
+		};
+		let Some(text) = field.value.clipboard_text() else {
+			status::post(format!(
+				"The {} field of '{}' is invalid",
+				field.name, entry.name
+			));
+			return;
+		};
+		if let Err(err) = clipboard::copy(text) {
+			status::post(err);
+			return;
+		}
+		entry.accessed_at = Utc::now();
+		// Each HOTP code is used once
+		if let FieldValue::HOTP { counter, .. } = &mut field.value {
+			*counter += 1;
+			self.modified = true;
+		}
+		status::post(match clipboard::remaining() {
+			Some(remaining) => format!(
+				"Copied the {} of '{}', cleared in {}s",
+				field.name,
+				entry.name,
+				remaining.as_secs_f32().round()
+			),
+			None => format!("Copied the {} of '{}'", field.name, entry.name),
+		});
+		run_hooks(
+			HookEvent::OnCopy,
+			&HookContext {
+				entry: Some(&entry.name),
+				field: Some(&field.name),
+				value: field.value.text(),
+				..Default::default()
+			},
+		);
+		let field = field.name.clone();
+		self.log
+			.record(Some(&entry.name), AuditAction::FieldCopied { field });
 	}
 
-	pub fn submit(&self) -> Vec<Entry> {
-		self.entries.clone()
+	/// Copy the full name of the selected entry, to refer to it in scripts
+	fn copy_name(&mut self) {
+		if self.current_id().is_none() {
+			return;
+		}
+		let name = self.current_entry().name.clone();
+		if let Err(err) = clipboard::copy(name.clone()) {
+			status::post(err);
+			return;
+		}
+		status::post(match clipboard::remaining() {
+			Some(remaining) => format!(
+				"Copied the name '{name}', cleared in {}s",
+				remaining.as_secs_f32().round()
+			),
+			None => format!("Copied the name '{name}'"),
+		});
 	}
-}
 
-impl Component for Explorer {
-	fn input(&mut self, key: &KeyEvent) -> bool {
-		let ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
+	/// Move the selected entry to the trash, the next one gets selected
+	fn delete_current(&mut self) {
+		if self.current_id().is_none() {
+			return;
+		}
+		let action = format!("deletion of '{}'", self.current_entry().name);
+		self.history.checkpoint(&self.entries, &self.trash, action);
+		let entry = self.entries.remove(self.current_index());
+		status::post(format!("Moved '{}' to the trash", entry.name));
+		self.log
+			.record(Some(&entry.name), AuditAction::EntryTrashed);
+		self.trash.push(TrashedEntry {
+			entry,
+			deleted_at: Utc::now(),
+		});
+		self.modified = true;
+		// Clamps the selection and the scrollbar to the remaining entries
+		self.update_filter();
+	}
 
-		// Entry editor
-		if let Some(editor) = &mut self.editor {
-			if !editor.input(key) {
-				if let Some(ent) = editor.submit() {
-					self.entries[self.selected] = ent;
+	/// Move the marked entries to the trash
+	fn delete_marked(&mut self) {
+		let marked = std::mem::take(&mut self.marked);
+		self.history.checkpoint(
+			&self.entries,
+			&self.trash,
+			format!("deletion of {} entries", marked.len()),
+		);
+		let (deleted, entries): (Vec<_>, Vec<_>) = std::mem::take(&mut self.entries)
+			.into_iter()
+			.partition(|entry| marked.contains(&entry.name));
+		self.entries = entries;
+		status::post(format!("Moved {} entries to the trash", deleted.len()));
+		let now = Utc::now();
+		for entry in deleted {
+			self.log
+				.record(Some(&entry.name), AuditAction::EntryTrashed);
+			self.trash.push(TrashedEntry {
+				entry,
+				deleted_at: now,
+			});
+		}
+		self.modified = true;
+		self.update_filter();
+	}
+
+	/// Apply a change requested from the trash view
+	fn apply_trash_action(&mut self, action: TrashAction) {
+		match action {
+			TrashAction::Restore(index) => {
+				let name = self.trash[index].entry.name.clone();
+				if self.entries.iter().any(|entry| entry.name == name) {
+					status::post(format!("Entry '{name}' already exists"));
+					return;
 				}
-				self.editor = None;
+				self.history.checkpoint(
+					&self.entries,
+					&self.trash,
+					format!("restoration of '{name}'"),
+				);
+				let trashed = self.trash.remove(index);
+				self.entries.push(trashed.entry);
+				self.tags.sync(&self.entries);
+				self.tags.apply(&mut self.entries);
+				self.log.record(Some(&name), AuditAction::EntryRestored);
+				status::post(format!("Restored '{name}'"));
+				self.update_filter();
+				self.select_entry(&name);
 			}
-			return true;
-		}
-		// Tag editor
-		if let Some(editor) = &mut self.tag_editor {
-			match editor.input_form(key) {
-				Some(FormSignal::Return) => {
-					if let Some(tags) = editor.submit() {
-						self.entries[self.selected].tags = tags;
-					} else { /* TODO */
-					};
-					self.tag_editor = None
+			TrashAction::Delete(index) => {
+				let name = self.trash[index].entry.name.clone();
+				self.history.checkpoint(
+					&self.entries,
+					&self.trash,
+					format!("permanent deletion of '{name}'"),
+				);
+				self.trash.remove(index);
+				self.log.record(Some(&name), AuditAction::EntryDeleted);
+				status::post(format!("Permanently deleted '{name}'"));
+			}
+			TrashAction::Empty => {
+				self.history
+					.checkpoint(&self.entries, &self.trash, "emptying of the trash".into());
+				let trash = std::mem::take(&mut self.trash);
+				for trashed in &trash {
+					self.log
+						.record(Some(&trashed.entry.name), AuditAction::EntryDeleted);
 				}
-				Some(FormSignal::Exit) => self.tag_editor = None,
-				_ => {}
+				status::post(format!("Permanently deleted {} entries", trash.len()));
 			}
-			return true;
 		}
-		// New entry
-		if let Some(new_entry) = &mut self.new_entry {
-			if key.code == KeyCode::Esc {
-				self.new_entry = None;
-			} else if !new_entry.input(key) {
-				let name = new_entry.inner.submit();
-				if !name.trim().is_empty() {
-					let now = Utc::now();
-					self.entries.push(Entry {
-						name,
-						fields: vec![],
-						tags: vec![],
-						created_at: now,
-						modified_at: now,
-						accessed_at: now,
-					});
-					self.new_entry = None;
-					self.update_filter();
-				}
+		self.modified = true;
+		if let Some(ExplorerScreen::Trash(view)) = self.screens.top_mut() {
+			view.set_trash(&self.trash);
+		}
+	}
+
+	/// Add and remove tags of the marked entries, from `input` such as `+work -old`
+	fn tag_marked(&mut self, input: &str) {
+		let mut add: Vec<EntryTag> = vec![];
+		let mut remove: Vec<String> = vec![];
+		for word in input.split_whitespace() {
+			match word.strip_prefix('-') {
+				Some(name) => remove.push(name.to_string()),
+				None => add.push(self.tags.resolve(word.trim_start_matches('+'))),
 			}
-			return true;
 		}
-		// Rename
-		if let Some(rename) = &mut self.rename {
-			if key.code == KeyCode::Esc {
-				self.rename = None;
-			} else if !rename.input(key) {
-				let name = rename.inner.submit();
-				if !name.trim().is_empty() {
-					self.current_entry_mut().name = name;
-					self.rename = None;
-					self.update_filter();
-				}
+		add.retain(|tag| !tag.name.is_empty());
+		if add.is_empty() && remove.is_empty() {
+			return;
+		}
+		self.history.checkpoint(
+			&self.entries,
+			&self.trash,
+			format!("tags of {} entries", self.marked.len()),
+		);
+		for tag in &add {
+			if self.tags.get(&tag.name).is_none() {
+				self.tags.register(tag.clone());
 			}
-			return true;
 		}
-		// Confirm
-		if let Some(confirm) = &mut self.confirm {
-			confirm.input(key);
-			match confirm.submit() {
-				Some(true) => {
-					if self.confirm_action == Some(ConfirmAction::Delete) {
-						self.entries.remove(self.filtered_entries[self.selected]);
-						self.update_filter();
-						self.move_cursor(-1);
-					}
-					self.confirm = None;
-				}
-				Some(false) => {
-					self.confirm = None;
+		for entry in &mut self.entries {
+			if !self.marked.contains(&entry.name) {
+				continue;
+			}
+			let previous = entry.revision();
+			entry.tags.retain(|tag| {
+				!remove
+					.iter()
+					.any(|name| tag.name.eq_ignore_ascii_case(name))
+			});
+			for tag in &add {
+				if !entry.tags.iter().any(|other| other.name == tag.name) {
+					entry.tags.push(tag.clone());
 				}
-				_ => {}
 			}
-			return true;
+			entry.commit(previous);
+			self.log.record(Some(&entry.name), AuditAction::EntryEdited);
 		}
+		status::post(format!("Updated the tags of {} entries", self.marked.len()));
+		self.modified = true;
+		self.update_filter();
+	}
 
-		if self.active == ActiveWidget::Search {
-			if self.filter_field.inner.input(key) {
-				self.update_filter();
-				return true;
+	/// Move the marked entries under `folder`, keeping the last component of their names
+	fn move_marked(&mut self, folder: &str) -> Result<(), String> {
+		let folder = folder.trim().trim_matches('/');
+		let renamed = |name: &str| {
+			let base = name.rsplit('/').next().unwrap_or(name);
+			if folder.is_empty() {
+				base.to_string()
+			} else {
+				format!("{folder}/{base}")
 			}
-			match key.code {
-				KeyCode::Down | KeyCode::Tab | KeyCode::Esc => self.active = ActiveWidget::Content,
-				KeyCode::Char('n') if ctrl_pressed => self.active = ActiveWidget::Content,
-				_ => return false,
+		};
+		// Names must stay unique, among the moved entries and with the others
+		let mut names = self
+			.entries
+			.iter()
+			.filter(|entry| !self.marked.contains(&entry.name))
+			.map(|entry| entry.name.clone())
+			.collect::<BTreeSet<_>>();
+		for name in &self.marked {
+			let new = renamed(name);
+			if !names.insert(new.clone()) {
+				return Err(format!("Entry '{new}' already exists"));
+			}
+		}
+
+		self.history.checkpoint(
+			&self.entries,
+			&self.trash,
+			format!("move of {} entries", self.marked.len()),
+		);
+		let mut marked = BTreeSet::new();
+		for entry in &mut self.entries {
+			if !self.marked.contains(&entry.name) {
+				continue;
+			}
+			let name = renamed(&entry.name);
+			if name != entry.name {
+				let from = std::mem::replace(&mut entry.name, name);
+				self.log
+					.record(Some(&entry.name), AuditAction::EntryRenamed { from });
+			}
+			marked.insert(entry.name.clone());
+		}
+		status::post(format!("Moved {} entries", marked.len()));
+		self.marked = marked;
+		self.modified = true;
+		self.update_filter();
+		Ok(())
+	}
+
+	/// Ask for the folder to rename, starting from the folder of the selected entry
+	fn open_folder_rename(&mut self) {
+		let folder = self
+			.current_id()
+			.and_then(|id| {
+				let (folder, _) = self.entries[id].name.rsplit_once('/')?;
+				Some(folder.to_string())
+			})
+			.unwrap_or_default();
+		self.screens.push(ExplorerScreen::Folder(
+			None,
+			folder_prompt("Folder to rename".into(), folder),
+		));
+	}
+
+	/// Old and new names of the entries in folder `from` once moved to folder `to`
+	fn folder_renames(&self, from: &str, to: &str) -> Result<Vec<(String, String)>, String> {
+		if from.is_empty() {
+			return Err("The folder is empty".into());
+		}
+		if from == to {
+			return Err(format!("The entries are already in '{from}/'"));
+		}
+		let prefix = format!("{from}/");
+		let renames = self
+			.entries
+			.iter()
+			.filter_map(|entry| {
+				let rest = entry.name.strip_prefix(&prefix)?;
+				let name = if to.is_empty() {
+					rest.to_string()
+				} else {
+					format!("{to}/{rest}")
+				};
+				Some((entry.name.clone(), name))
+			})
+			.collect::<Vec<_>>();
+		if renames.is_empty() {
+			return Err(format!("No entries in '{prefix}'"));
+		}
+		// Names must stay unique, among the moved entries and with the others
+		let mut names = self
+			.entries
+			.iter()
+			.filter(|entry| !entry.name.starts_with(&prefix))
+			.map(|entry| entry.name.as_str())
+			.collect::<BTreeSet<_>>();
+		for (_, name) in &renames {
+			if !names.insert(name.as_str()) {
+				return Err(format!("Entry '{name}' already exists"));
+			}
+		}
+		Ok(renames)
+	}
+
+	/// Ask to confirm the rename of folder `from` to `to`, with a preview of the new names
+	fn confirm_folder_rename(&mut self, from: String, to: String, renames: &[(String, String)]) {
+		const PREVIEW: usize = 8;
+
+		let mut lines = vec![
+			Line::from(format!("Rename {} entries?", renames.len())),
+			Line::default(),
+		];
+		for (old, new) in renames.iter().take(PREVIEW) {
+			lines.push(Line::from(vec![
+				old.clone().fg(theme().muted),
+				" -> ".into(),
+				new.clone().fg(theme().success),
+			]));
+		}
+		if renames.len() > PREVIEW {
+			lines.push(Line::from(
+				format!("and {} more", renames.len() - PREVIEW).italic(),
+			));
+		}
+		let mut confirm = Confirm::new("Rename Folder".into(), Paragraph::new(Text::from(lines)));
+		confirm.set_selected(1);
+		self.screens.push(ExplorerScreen::Confirm(
+			ConfirmAction::RenameFolder { from, to },
+			confirm,
+		));
+	}
+
+	/// Move the entries of folder `from` to folder `to`, the selected entry stays selected
+	fn rename_folder(&mut self, from: &str, to: &str) -> Result<(), String> {
+		let renames = self
+			.folder_renames(from, to)?
+			.into_iter()
+			.collect::<BTreeMap<_, _>>();
+		let current = self.current_id().map(|id| {
+			let name = &self.entries[id].name;
+			renames.get(name).unwrap_or(name).clone()
+		});
+		self.history.checkpoint(
+			&self.entries,
+			&self.trash,
+			format!("rename of folder '{from}/'"),
+		);
+		let now = Utc::now();
+		for entry in &mut self.entries {
+			let Some(name) = renames.get(&entry.name) else {
+				continue;
+			};
+			let from = std::mem::replace(&mut entry.name, name.clone());
+			entry.modified_at = now;
+			self.log
+				.record(Some(&entry.name), AuditAction::EntryRenamed { from });
+		}
+		self.marked = std::mem::take(&mut self.marked)
+			.into_iter()
+			.map(|name| renames.get(&name).cloned().unwrap_or(name))
+			.collect();
+		status::post(format!(
+			"Moved {} entries from '{from}/' to '{to}/'",
+			renames.len()
+		));
+		self.modified = true;
+		self.update_filter();
+		if let Some(name) = current {
+			self.select_entry(&name);
+		}
+		Ok(())
+	}
+
+	fn move_cursor(&mut self, offset: i32) {
+		if self.rows.is_empty() {
+			self.list_state.borrow_mut().select(None);
+			self.selected = 0;
+			return;
+		}
+		if offset > 0 {
+			self.selected = std::cmp::min(self.selected + offset as usize, self.rows.len() - 1);
+		} else if offset < 0 {
+			self.selected = self.selected.saturating_sub((-offset) as usize);
+		}
+
+		let scrollbar = self.scrollbar.borrow().position(self.selected);
+		*self.scrollbar.borrow_mut() = scrollbar;
+	}
+
+	fn update_filter(&mut self) {
+		let filter = ExplorerFilter::from(self.filter_field.inner.get_input().as_str());
+		let mut scores = self
+			.entries
+			.iter()
+			.enumerate()
+			.filter(|(_, ent)| self.view.contains(ent))
+			.filter_map(|(id, ent)| Some((id, filter.score(ent)?)))
+			.collect::<Vec<_>>();
+		if self.view == ExplorerView::Recent {
+			scores.sort_by_key(|(id, _)| std::cmp::Reverse(self.entries[*id].accessed_at));
+			scores.truncate(RECENT_ENTRIES);
+		} else {
+			scores
+				.sort_by(|(a, _), (b, _)| self.sort.compare(&self.entries[*a], &self.entries[*b]));
+		}
+		// Best matches first, entries with the same score keep the sort order
+		scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+		self.filtered_entries = scores.into_iter().map(|(id, _)| id).collect();
+		self.rows = if self.grouped {
+			self.group_rows()
+		} else {
+			self.filtered_entries
+				.iter()
+				.copied()
+				.map(Row::Entry)
+				.collect()
+		};
+		// Entries removed or renamed, e.g. by undo, are no longer marked
+		if !self.marked.is_empty() {
+			let names = self
+				.entries
+				.iter()
+				.map(|entry| entry.name.as_str())
+				.collect::<BTreeSet<_>>();
+			self.marked.retain(|name| names.contains(name.as_str()));
+		}
+		self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+		self.move_cursor(0);
+	}
+
+	/// Icon and name of `ent`, with the characters matching `filter` underlined, followed by the
+	/// favorite, expiration and password age indicators
+	fn format_name(ent: &Entry, filter: &ExplorerFilter) -> Vec<Span<'static>> {
+		// Icon, or the icon of its kind, padded so names stay aligned
+		let mut comp = vec![match &ent.icon {
+			Some(icon) => Span::styled(
+				format!("{} ", icons::glyph(icon)),
+				Style::default().fg(theme().accent),
+			),
+			None => {
+				let icon = match ent.kind() {
+					EntryKind::Login => icons::LOGIN,
+					EntryKind::CreditCard => icons::CREDIT_CARD,
+					EntryKind::SshKey => icons::SSH_KEY,
+					EntryKind::WiFi => icons::WIFI,
+					EntryKind::Otp => icons::ONE_TIME,
+					EntryKind::Note => icons::NOTE,
+				};
+				Span::styled(format!("{icon} "), Style::default().fg(theme().muted))
+			}
+		}];
+
+		// Name
+		let matched = filter.highlights(&ent.name);
+		let base = ent.name.rfind('/').map_or(0, |pos| pos + 1);
+		let mut name: Vec<(Style, String)> = vec![];
+		for (pos, (byte, c)) in ent.name.char_indices().enumerate() {
+			let mut style = if c == '/' {
+				Style::default().fg(theme().folder[1]).bold()
+			} else if byte < base {
+				Style::default().fg(theme().folder[0])
+			} else {
+				Style::default().fg(theme().success).bold()
+			};
+			if matched[pos] {
+				style = style.fg(theme().highlight).bold().underlined();
+			}
+			match name.last_mut() {
+				Some((last, text)) if *last == style => text.push(c),
+				_ => name.push((style, c.to_string())),
+			}
+		}
+		comp.extend(
+			name.into_iter()
+				.map(|(style, text)| Span::styled(text, style)),
+		);
+		if ent.favorite {
+			comp.push(Span::styled(
+				format!(" {}", icons::FAVORITE),
+				Style::default().fg(theme().highlight),
+			));
+		}
+
+		// Expiration
+		let (expired, soon) = ent.expiry_counts(Utc::now());
+		if expired != 0 {
+			comp.push(Span::styled(
+				format!(" {} {expired}", icons::WARNING),
+				Style::default().fg(theme().error).bold(),
+			));
+		} else if soon != 0 {
+			comp.push(Span::styled(
+				format!(" {} {soon}", icons::EXPIRING),
+				Style::default().fg(theme().highlight),
+			));
+		}
+
+		// Password age, once older than configured
+		let max_age = config().password_max_age;
+		if let Some(age) = ent.password_age(Utc::now()) {
+			let days = age.num_days();
+			if max_age != 0 && days > max_age as i64 {
+				let age = if days >= 365 {
+					format!("{}y", days / 365)
+				} else {
+					format!("{}mo", days / 30)
+				};
+				comp.push(Span::styled(
+					format!(" {} {age}", icons::OLD),
+					Style::default().fg(theme().highlight).italic(),
+				));
+			}
+		}
+		comp
+	}
+
+	/// Row of `ent`, with `columns` cut or padded to their width, empty rows pad the list
+	fn format_entry<'e>(
+		ent: Option<&'e Entry>,
+		tags: &TagRegistry,
+		filter: &ExplorerFilter,
+		columns: &[(EntryColumn, u16)],
+		health: Option<u8>,
+		marked: bool,
+		bg: Color,
+	) -> ListItem<'e> {
+		fn format_tag(tag: &EntryTag) -> Span<'static> {
+			let style = Style::default()
+				.fg(rgb(tag.color.unwrap_or(DEFAULT_TAG_COLOR)))
+				.italic();
+			if let Some(icon) = &tag.icon {
+				Span::styled(format!("+{} {}", tag.name, icons::glyph(icon)), style)
+			} else {
+				Span::styled(format!("+{}", tag.name), style)
+			}
+		}
+
+		let Some(ent) = ent else {
+			return ListItem::from(Line::from("")).bg(bg);
+		};
+		let mut comp = vec![if marked {
+			icons::MARKED.get().bold().fg(theme().special)
+		} else {
+			" ".into()
+		}];
+		for (pos, (column, width)) in columns.iter().enumerate() {
+			if pos != 0 {
+				comp.push(" ".into());
+			}
+			let cell = match column {
+				// Health of the passwords, from its score out of 100
+				EntryColumn::Health => match health {
+					Some(score) => {
+						let color = match score {
+							80.. => theme().success,
+							50.. => theme().highlight,
+							_ => theme().error,
+						};
+						vec![icons::HEALTH.get().fg(color)]
+					}
+					None => vec![],
+				},
+				EntryColumn::Name => Self::format_name(ent, filter),
+				EntryColumn::Tags => {
+					let mut spans = vec![];
+					for tag in &ent.tags {
+						if !spans.is_empty() {
+							spans.push(" ".into());
+						}
+						spans.push(format_tag(tags.get(&tag.name).unwrap_or(tag)));
+					}
+					spans
+				}
+				EntryColumn::Fields => vec![Span::styled(
+					format!("({})", ent.fields.len()),
+					Style::default().fg(theme().faint).italic(),
+				)],
+				EntryColumn::Modified => vec![ent
+					.modified_at
+					.format("%Y-%m-%d")
+					.to_string()
+					.fg(theme().muted)],
+			};
+			comp.extend(columns::fit(cell, *width));
+		}
+
+		ListItem::from(Line::from(comp)).bg(bg)
+	}
+
+	/// Heading of the group of `count` entries with `tag`, `None` for the untagged entries
+	fn format_group(
+		&self,
+		tag: &Option<String>,
+		count: usize,
+		selected: bool,
+	) -> ListItem<'static> {
+		let indicator = if self.collapsed.contains(tag) {
+			icons::COLLAPSED
+		} else {
+			icons::EXPANDED
+		};
+		let mut line = vec![format!("{indicator} ").fg(theme().muted)];
+		match tag.as_ref().and_then(|tag| self.tags.get(tag)) {
+			Some(tag) => {
+				let color = rgb(tag.color.unwrap_or(DEFAULT_TAG_COLOR));
+				if let Some(icon) = &tag.icon {
+					line.push(format!("{} ", icons::glyph(icon)).fg(color));
+				}
+				line.push(tag.name.clone().bold().fg(color));
+			}
+			None => line.push(
+				tag.clone()
+					.unwrap_or_else(|| "Untagged".into())
+					.bold()
+					.fg(theme().muted),
+			),
+		}
+		line.push(format!(" ({count})").italic().fg(theme().faint));
+		ListItem::from(Line::from(line)).bg(if selected {
+			theme().entry_bg[2]
+		} else {
+			theme().help_bg
+		})
+	}
+
+	/// Number of listed entries, with the filter, view and order they are listed with
+	fn header(&self) -> Line<'static> {
+		let separator = || format!(" {} ", icons::SEPARATOR).fg(theme().dim);
+		let mut spans = vec![
+			" ".into(),
+			self.filtered_entries
+				.len()
+				.to_string()
+				.bold()
+				.fg(theme().accent),
+			format!(" of {} entries", self.entries.len()).fg(theme().muted),
+		];
+		match self.view {
+			ExplorerView::All => {}
+			ExplorerView::Favorites => spans.extend([
+				separator(),
+				format!("{} favorites", icons::FAVORITE).fg(theme().highlight),
+			]),
+			ExplorerView::Recent => spans.extend([
+				separator(),
+				format!("{} recently used", icons::RECENT).fg(theme().accent),
+			]),
+		}
+		if self.grouped {
+			spans.extend([
+				separator(),
+				format!("{} grouped by tag", icons::TAG).fg(theme().accent),
+			]);
+		}
+		let query = self.filter_field.inner.get_input().trim();
+		if !query.is_empty() {
+			spans.extend([
+				separator(),
+				"filter: ".fg(theme().muted),
+				query.to_string().fg(theme().text),
+			]);
+		}
+		spans.push(separator());
+		let filter = ExplorerFilter::from(query);
+		if !filter.terms.is_empty() {
+			spans.push("best matches first".fg(theme().muted));
+		} else if self.view == ExplorerView::Recent {
+			spans.push("most recently used first".fg(theme().muted));
+		} else {
+			let icon = if self.sort.descending {
+				icons::SORT_DESCENDING
+			} else {
+				icons::SORT_ASCENDING
+			};
+			spans.push(format!("{icon} sorted by {}", self.sort.key).fg(theme().muted));
+		}
+		Line::from(spans)
+	}
+
+	/// Text shown instead of the entries when none are listed, with the commands to get some
+	fn empty_state(&self) -> Text<'static> {
+		let command = |keys: &'static str, action: &'static str| {
+			Line::from(vec![
+				Span::from(keys).bold().fg(theme().success),
+				format!("  {action}").fg(theme().muted),
+			])
+		};
+		let title = if self.entries.is_empty() {
+			"This database has no entries yet"
+		} else if !self.filter_field.inner.get_input().is_empty() {
+			"No entries match the filter"
+		} else {
+			match self.view {
+				ExplorerView::Favorites => "No favorite entries",
+				ExplorerView::Recent => "No recently used entries",
+				ExplorerView::All => "No entries",
+			}
+		};
+		let mut lines = vec![title.bold().fg(theme().accent).into(), Line::default()];
+		if self.view != ExplorerView::All {
+			lines.push(command("F", "list all the entries"));
+		}
+		if !self.entries.is_empty() {
+			lines.push(command("/", "change the filter"));
+		}
+		lines.push(command("a", "add an entry"));
+		lines.push(command("i", "import entries from another manager"));
+		if self.entries.is_empty() {
+			lines.push(command("/", "filter the entries once added"));
+		}
+		lines.push(command("?", "all the commands"));
+		Text::from(lines)
+	}
+
+	/// Tags with their number of entries, the tags required by the filter are checked
+	fn render_tag_sidebar(&self, frame: &mut Frame, area: Rect) {
+		let block = Block::new()
+			.borders(Borders::RIGHT)
+			.border_style(Style::default().fg(theme().dim))
+			.title(format!("{} Tags", icons::TAG))
+			.title_style(Style::default().fg(theme().accent).bold())
+			.bg(theme().help_bg);
+		let inner = block.inner(area);
+		frame.render_widget(block, area);
+		self.tag_area.set(inner);
+
+		let tags = self.tags.sorted();
+		if tags.is_empty() {
+			frame.render_widget(Line::from("No tags").fg(theme().muted).italic(), inner);
+			return;
+		}
+		let filter = ExplorerFilter::from(self.filter_field.inner.get_input().as_str());
+		let mut counts = BTreeMap::<String, usize>::new();
+		for tag in self.entries.iter().flat_map(|entry| entry.tags.iter()) {
+			*counts.entry(tag.name.to_lowercase()).or_default() += 1;
+		}
+		let selected = self.tag_selected.min(tags.len() - 1);
+		let items = tags
+			.iter()
+			.enumerate()
+			.map(|(id, tag)| {
+				let name = tag.name.to_lowercase();
+				let color = rgb(tag.color.unwrap_or(DEFAULT_TAG_COLOR));
+				let mut line = vec![if filter.tags.contains(&name) {
+					format!("{} ", icons::CHECKED).fg(theme().success)
+				} else {
+					format!("{} ", icons::UNCHECKED).fg(theme().dim)
+				}];
+				if let Some(icon) = &tag.icon {
+					line.push(format!("{} ", icons::glyph(icon)).fg(color));
+				}
+				line.push(tag.name.clone().italic().fg(color));
+				line.push(format!(" {}", counts.get(&name).unwrap_or(&0)).fg(theme().faint));
+				let item = ListItem::new(Line::from(line));
+				if self.active == ActiveWidget::Tags && id == selected {
+					item.bg(theme().entry_bg[2])
+				} else {
+					item
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut list_state = self.tag_list_state.borrow_mut();
+		list_state.select(Some(selected));
+		frame.render_stateful_widget(List::new(items), inner, &mut *list_state);
+	}
+
+	/// Index of the selected entry, `None` if a group heading or nothing is selected
+	fn current_id(&self) -> Option<usize> {
+		match self.rows.get(self.selected) {
+			Some(Row::Entry(id)) => Some(*id),
+			Some(Row::Group(..)) | None => None,
+		}
+	}
+
+	/// Index of the selected entry, which must be selected
+	fn current_index(&self) -> usize {
+		self.current_id().expect("No entry is selected")
+	}
+
+	fn current_entry(&self) -> &Entry {
+		&self.entries[self.current_index()]
+	}
+
+	fn current_entry_mut(&mut self) -> &mut Entry {
+		let index = self.current_index();
+		&mut self.entries[index]
+	}
+
+	/// Export the entries selected by `request` to an encrypted database
+	fn share_entries(&self, request: ShareRequest) -> Result<Popup<'static>, String> {
+		let entries = match &request.selection {
+			ShareSelection::Filtered => self
+				.filtered_entries
+				.iter()
+				.map(|id| self.entries[*id].clone())
+				.collect::<Vec<_>>(),
+			ShareSelection::Tag(tag) => self
+				.entries
+				.iter()
+				.filter(|entry| entry.tags.iter().any(|other| other.name == *tag))
+				.cloned()
+				.collect(),
+			ShareSelection::Marked => self
+				.entries
+				.iter()
+				.filter(|entry| self.marked.contains(&entry.name))
+				.cloned()
+				.collect(),
+		};
+		if entries.is_empty() {
+			return Err("No entries to share".into());
+		}
+		export_encrypted(&entries, &request.path, &request.passphrase)?;
+		Ok(Popup::new(
+			"Entries Shared".into(),
+			Paragraph::new(Text::from(format!(
+				"Exported {} entries to '{}'.\nPassphrase: {}",
+				entries.len(),
+				request.path.display(),
+				request.passphrase
+			))),
+		))
+	}
+
+	pub fn submit(&self) -> Vec<Entry> {
+		self.entries.clone()
+	}
+
+	/// Tag definitions
+	pub fn tags(&self) -> TagRegistry {
+		self.tags.clone()
+	}
+
+	/// Deleted entries
+	pub fn trash(&self) -> Vec<TrashedEntry> {
+		self.trash.clone()
+	}
+
+	/// Operations log
+	pub fn log(&self) -> AuditLog {
+		self.log.clone()
+	}
+
+	/// Record an operation in the log
+	pub fn record(&mut self, entry: Option<&str>, action: AuditAction) {
+		self.log.record(entry, action);
+	}
+
+	/// Current navigation state
+	pub fn session(&self) -> SessionState {
+		SessionState {
+			selected: self.current_id().map(|id| self.entries[id].name.clone()),
+			filter: self.filter_field.inner.get_input().clone(),
+			offset: self.list_state.borrow().offset(),
+			sort: Some(self.sort),
+			tag_selected: self.tag_selected,
+			tag_offset: self.tag_list_state.borrow().offset(),
+		}
+	}
+
+	/// Set the title shown in the help bar
+	pub fn set_title(&mut self, title: String) {
+		self.title = title;
+	}
+
+	/// Show whether changes are not saved to the database yet
+	pub fn set_dirty(&mut self, dirty: bool) {
+		self.dirty = dirty;
+	}
+
+	/// Restore a navigation state obtained from [`Explorer::session`]
+	pub fn restore_session(&mut self, session: &SessionState) {
+		if let Some(sort) = session.sort {
+			self.sort = sort;
+		}
+		self.filter_field.inner.set_input(session.filter.clone());
+		self.update_filter();
+		// Offsets are clamped to the selection when rendering
+		*self.list_state.borrow_mut().offset_mut() = session.offset;
+		self.tag_selected = session.tag_selected;
+		*self.tag_list_state.borrow_mut().offset_mut() = session.tag_offset;
+		let Some(name) = &session.selected else {
+			return;
+		};
+		if self.select_entry(name) {
+			self.active = ActiveWidget::Content;
+		}
+	}
+
+	/// Select the entry named `name`, returns false if it is not listed
+	fn select_entry(&mut self, name: &str) -> bool {
+		let Some(id) = self.entries.iter().position(|entry| entry.name == name) else {
+			return false;
+		};
+		self.select_id(id)
+	}
+
+	/// Select the first row of the entry at `id`, returns false if it is not listed
+	fn select_id(&mut self, id: usize) -> bool {
+		let Some(pos) = self.rows.iter().position(|row| *row == Row::Entry(id)) else {
+			return false;
+		};
+		self.selected = pos;
+		self.move_cursor(0);
+		true
+	}
+
+	/// Edit the name of the selected entry in its row
+	fn start_rename(&mut self) {
+		if self.current_id().is_none() {
+			return;
+		}
+		let name = self.current_entry().name.clone();
+		self.screens.push(ExplorerScreen::Rename {
+			input: TextInput::new().style(&RENAME_INPUT_STYLE).with_input(name),
+			next: false,
+			area: Cell::default(),
+		});
+	}
+
+	/// Rename the selected entry to `name`, which stays selected
+	fn rename_current(&mut self, name: &str) -> Result<(), String> {
+		if name.is_empty() {
+			return Err("The name is empty".into());
+		}
+		if name == self.current_entry().name {
+			return Ok(());
+		}
+		if self.entries.iter().any(|entry| entry.name == name) {
+			return Err(format!("Entry '{name}' already exists"));
+		}
+		let action = format!("rename of '{}'", self.current_entry().name);
+		self.history.checkpoint(&self.entries, &self.trash, action);
+		let entry = self.current_entry_mut();
+		let from = std::mem::replace(&mut entry.name, name.to_string());
+		entry.modified_at = Utc::now();
+		self.log
+			.record(Some(name), AuditAction::EntryRenamed { from });
+		self.modified = true;
+		self.update_filter();
+		self.select_entry(name);
+		Ok(())
+	}
+
+	/// Returns whether entries were modified since the last call
+	pub fn take_modified(&mut self) -> bool {
+		std::mem::take(&mut self.modified)
+	}
+
+	/// Entry to edit in the external editor, if requested since the last call
+	pub fn take_external_edit(&mut self) -> Option<Entry> {
+		std::mem::take(&mut self.external_edit).then(|| self.current_entry().clone())
+	}
+
+	/// Entry to move to another database, if requested since the last call
+	pub fn take_transfer(&mut self) -> Option<Entry> {
+		std::mem::take(&mut self.transfer).then(|| self.current_entry().clone())
+	}
+
+	/// Add an entry moved or copied from another database
+	pub fn add_entry(&mut self, entry: Entry) -> Result<(), String> {
+		if self.entries.iter().any(|other| other.name == entry.name) {
+			return Err(format!("Entry '{}' already exists", entry.name));
+		}
+		self.history.checkpoint(
+			&self.entries,
+			&self.trash,
+			format!("transfer of '{}'", entry.name),
+		);
+		self.log
+			.record(Some(&entry.name), AuditAction::EntryCreated);
+		self.entries.push(entry);
+		self.tags.sync(&self.entries);
+		self.tags.apply(&mut self.entries);
+		self.modified = true;
+		self.update_filter();
+		Ok(())
+	}
+
+	/// Remove the entry named `name` once moved to another database
+	pub fn remove_entry(&mut self, name: &str) -> Result<(), String> {
+		let Some(index) = self.entries.iter().position(|entry| entry.name == name) else {
+			return Err(format!("Entry '{name}' no longer exists"));
+		};
+		self.history
+			.checkpoint(&self.entries, &self.trash, format!("move of '{name}'"));
+		self.entries.remove(index);
+		self.log.record(Some(name), AuditAction::EntryDeleted);
+		self.modified = true;
+		self.update_filter();
+		self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+		Ok(())
+	}
+
+	/// Revert the last change of the entries
+	fn undo(&mut self) {
+		let Some(action) = self.history.undo(&mut self.entries, &mut self.trash) else {
+			status::post("Nothing to undo");
+			return;
+		};
+		status::post(format!("Undid {action}"));
+		self.restored();
+	}
+
+	/// Apply the last reverted change again
+	fn redo(&mut self) {
+		let Some(action) = self.history.redo(&mut self.entries, &mut self.trash) else {
+			status::post("Nothing to redo");
+			return;
+		};
+		status::post(format!("Redid {action}"));
+		self.restored();
+	}
+
+	/// Update the view after the entries were restored from the undo stack
+	fn restored(&mut self) {
+		self.tags.sync(&self.entries);
+		self.tags.apply(&mut self.entries);
+		self.modified = true;
+		self.update_filter();
+		self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+	}
+
+	/// Replace the entry named `name` with its version edited in the external editor
+	pub fn apply_external_edit(&mut self, name: &str, mut edited: Entry) -> Result<(), String> {
+		if edited.name != name && self.entries.iter().any(|entry| entry.name == edited.name) {
+			return Err(format!("Entry '{}' already exists", edited.name));
+		}
+		let Some(index) = self.entries.iter().position(|entry| entry.name == name) else {
+			return Err(format!("Entry '{name}' no longer exists"));
+		};
+		self.history
+			.checkpoint(&self.entries, &self.trash, format!("edit of '{name}'"));
+		let entry = &mut self.entries[index];
+		edited.commit(entry.revision());
+		*entry = edited;
+		for tag in &entry.tags {
+			if self.tags.get(&tag.name).is_none() {
+				self.tags.register(tag.clone());
+			}
+		}
+		if entry.name != name {
+			let from = name.to_string();
+			self.log
+				.record(Some(&entry.name), AuditAction::EntryRenamed { from });
+		}
+		self.log.record(Some(&entry.name), AuditAction::EntryEdited);
+		self.modified = true;
+		self.update_filter();
+		Ok(())
+	}
+
+	/// Apply what the top-most screen did with its last key while staying open: fields copied
+	/// and HOTP counters used in the entry editor, actions of the trash
+	fn take_screen_effects(&mut self) {
+		let current = self.current_id();
+		match self.screens.top_mut() {
+			Some(ExplorerScreen::Editor(editor)) => {
+				let Some(index) = current else {
+					return;
+				};
+				for field in editor.take_copied() {
+					let entry = &self.entries[index].name;
+					self.log
+						.record(Some(entry), AuditAction::FieldCopied { field });
+				}
+				for (secret, next) in editor.take_counters() {
+					let entry = &mut self.entries[index];
+					for field in &mut entry.fields {
+						if let FieldValue::HOTP {
+							secret: field_secret,
+							counter,
+						} = &mut field.value
+						{
+							if *field_secret == secret {
+								*counter = next.max(*counter);
+							}
+						}
+					}
+					self.modified = true;
+				}
+			}
+			Some(ExplorerScreen::Trash(view)) => {
+				if let Some(action) = view.take_action() {
+					self.apply_trash_action(action);
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// Apply `screen`, popped once submitted, screens that stay open are pushed back
+	fn submit_screen(&mut self, screen: ExplorerScreen) {
+		match screen {
+			ExplorerScreen::Editor(editor) => {
+				let Some(index) = self.current_id() else {
+					return;
+				};
+				if let Some(ent) = editor.submit() {
+					if ent.modified_at != self.entries[index].modified_at {
+						self.history.checkpoint(
+							&self.entries,
+							&self.trash,
+							format!("edit of '{}'", ent.name),
+						);
+						self.log.record(Some(&ent.name), AuditAction::EntryEdited);
+					}
+					self.entries[index] = ent;
+					self.modified = true;
+				}
+			}
+			ExplorerScreen::TagEditor(editor) => {
+				if let Some(tags) = editor.submit(&self.tags) {
+					for tag in &tags {
+						if self.tags.get(&tag.name).is_none() {
+							self.tags.register(tag.clone());
+						}
+					}
+					let index = self.current_index();
+					let name = &self.entries[index].name;
+					self.history.checkpoint(
+						&self.entries,
+						&self.trash,
+						format!("tags of '{name}'"),
+					);
+					let entry = &mut self.entries[index];
+					let previous = entry.revision();
+					entry.tags = tags;
+					entry.commit(previous);
+					self.log.record(Some(&entry.name), AuditAction::EntryEdited);
+					self.modified = true;
+				} else { /* TODO */
+				};
+			}
+			ExplorerScreen::TagRegistry(form) => {
+				if let Ok(tag) = form.submit() {
+					self.history.checkpoint(
+						&self.entries,
+						&self.trash,
+						format!("tag '{}'", tag.name),
+					);
+					self.tags.register(tag);
+					self.tags.apply(&mut self.entries);
+					self.modified = true;
+				}
+			}
+			ExplorerScreen::Import(mut import) => {
+				let imported = import.submit();
+				status::post(format!("Imported {} entries", imported.len()));
+				self.history.checkpoint(
+					&self.entries,
+					&self.trash,
+					format!("import of {} entries", imported.len()),
+				);
+				self.log.record(
+					None,
+					AuditAction::Imported {
+						count: imported.len(),
+					},
+				);
+				if import.attaches_otp() {
+					otp::attach(&mut self.entries, imported);
+				} else {
+					self.entries.extend(imported);
+				}
+				self.tags.sync(&self.entries);
+				self.tags.apply(&mut self.entries);
+				self.modified = true;
+				self.update_filter();
+			}
+			ExplorerScreen::Share(mut share) => {
+				let Some(request) = share.submit() else {
+					self.screens.push(ExplorerScreen::Share(share));
+					return;
+				};
+				match self.share_entries(request) {
+					Ok(popup) => self.screens.push(ExplorerScreen::Popup(popup)),
+					Err(err) => {
+						share.set_error(err);
+						self.screens.push(ExplorerScreen::Share(share));
+					}
+				}
+			}
+			ExplorerScreen::NewEntry(form) => {
+				if let Some(entry) = form.submit() {
+					self.history.checkpoint(
+						&self.entries,
+						&self.trash,
+						format!("creation of '{}'", entry.name),
+					);
+					self.log
+						.record(Some(&entry.name), AuditAction::EntryCreated);
+					self.entries.push(entry);
+					self.modified = true;
+					self.update_filter();
+				}
+			}
+			ExplorerScreen::Rename { input, next, area } => {
+				let name = input.submit();
+				if let Err(err) = self.rename_current(name.trim()) {
+					status::post(err);
+					self.screens
+						.push(ExplorerScreen::Rename { input, next, area });
+					return;
+				}
+				// Continue with the next entry, skipping the group headings
+				let step = self
+					.rows
+					.iter()
+					.skip(self.selected + 1)
+					.position(|row| matches!(row, Row::Entry(_)));
+				if let (true, Some(step)) = (next, step) {
+					self.move_cursor(step as i32 + 1);
+					self.start_rename();
+				}
+			}
+			ExplorerScreen::Bulk(action, input) => {
+				let value = input.inner.submit();
+				match action {
+					BulkAction::Tag => self.tag_marked(&value),
+					BulkAction::Move => {
+						if let Err(err) = self.move_marked(&value) {
+							status::post(err);
+							self.screens.push(ExplorerScreen::Bulk(action, input));
+						}
+					}
+				}
+			}
+			ExplorerScreen::Folder(from, input) => {
+				let value = input.inner.submit().trim().trim_matches('/').to_string();
+				match from {
+					None => {
+						let prefix = format!("{value}/");
+						if value.is_empty() {
+							status::post("The folder is empty");
+						} else if !self
+							.entries
+							.iter()
+							.any(|entry| entry.name.starts_with(&prefix))
+						{
+							status::post(format!("No entries in '{prefix}'"));
+						} else {
+							let label = format!("Move '{prefix}' to");
+							self.screens.push(ExplorerScreen::Folder(
+								Some(value.clone()),
+								folder_prompt(label, value),
+							));
+							return;
+						}
+						self.screens.push(ExplorerScreen::Folder(None, input));
+					}
+					Some(from) => match self.folder_renames(&from, &value) {
+						Ok(renames) => self.confirm_folder_rename(from, value, &renames),
+						Err(err) => {
+							status::post(err);
+							self.screens.push(ExplorerScreen::Folder(Some(from), input));
+						}
+					},
+				}
+			}
+			ExplorerScreen::Confirm(action, confirm) => {
+				if confirm.submit() != Some(true) {
+					return;
+				}
+				match action {
+					ConfirmAction::Delete => self.delete_current(),
+					ConfirmAction::DeleteMarked => self.delete_marked(),
+					ConfirmAction::RenameFolder { from, to } => {
+						if let Err(err) = self.rename_folder(&from, &to) {
+							status::post(err);
+						}
+					}
+				}
+			}
+			ExplorerScreen::AuditLog(_) | ExplorerScreen::Trash(_) | ExplorerScreen::Popup(_) => {}
+		}
+	}
+}
+
+impl Component for Explorer {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		let ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
+
+		// Screens over the entry list
+		if let Some(signal) = self.screens.input(key) {
+			self.take_screen_effects();
+			if signal == ScreenSignal::Submitted {
+				if let Some(screen) = self.screens.pop() {
+					self.submit_screen(screen);
+				}
+			}
+			return true;
+		}
+
+		if self.active == ActiveWidget::Search {
+			if self.filter_field.inner.input(key) {
+				// Select the best match
+				self.selected = 0;
+				self.update_filter();
+				return true;
+			}
+			match key.code {
+				KeyCode::Down | KeyCode::Tab | KeyCode::Esc => self.active = ActiveWidget::Content,
+				KeyCode::Char('n') if ctrl_pressed => self.active = ActiveWidget::Content,
+				_ => return false,
 			}
 			return true;
 		}
 
+		if self.active == ActiveWidget::Tags {
+			let tags = self.sidebar_tags();
+			match key.code {
+				KeyCode::Down | KeyCode::Char('j') => {
+					self.tag_selected = (self.tag_selected + 1).min(tags.len().saturating_sub(1))
+				}
+				KeyCode::Up | KeyCode::Char('k') => {
+					self.tag_selected = self.tag_selected.saturating_sub(1)
+				}
+				KeyCode::Enter | KeyCode::Char(' ') => {
+					if let Some(tag) = tags.get(self.tag_selected) {
+						self.toggle_filter_tag(tag);
+					}
+				}
+				KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab | KeyCode::Esc => {
+					self.active = ActiveWidget::Content
+				}
+				KeyCode::Char('h') => self.toggle_tag_sidebar(),
+				KeyCode::Char('/') => self.active = ActiveWidget::Search,
+				_ => return false,
+			}
+			return true;
+		}
+
+		// Jump to the entries starting with the typed characters, until another key is pressed
+		if self.jump {
+			match key.code {
+				KeyCode::Char(c) if !ctrl_pressed => {
+					self.jump_to(c);
+					return true;
+				}
+				KeyCode::Esc => {
+					self.jump = false;
+					return true;
+				}
+				_ => self.jump = false,
+			}
+		}
+
 		match key.code {
 			KeyCode::Char('/') => self.active = ActiveWidget::Search,
+			KeyCode::Char('\'') => self.jump = true,
+			KeyCode::Char('h') => self.toggle_tag_sidebar(),
+			KeyCode::Left if self.tag_sidebar => self.active = ActiveWidget::Tags,
 			KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => self.move_cursor(1),
 			KeyCode::Char('n') if ctrl_pressed => self.move_cursor(1),
 			KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => self.move_cursor(-1),
 			KeyCode::Char('p') if ctrl_pressed => self.move_cursor(-1),
+			KeyCode::Enter | KeyCode::Char(' ') if self.current_id().is_none() => {
+				self.toggle_group()
+			}
+			KeyCode::Char('z') => self.toggle_group(),
+			KeyCode::Char('g') => self.toggle_grouped(),
 			KeyCode::Char('e') | KeyCode::Enter => {
-				if !self.entries.is_empty() {
-					self.editor = Some(EntryEditor::new(self.current_entry().clone()))
+				if self.current_id().is_some() {
+					self.current_entry_mut().touch();
+					let editor = EntryEditor::new(self.current_entry().clone());
+					self.screens.push(ExplorerScreen::Editor(editor));
 				}
 			}
+			KeyCode::Char('v') => self.external_edit = self.current_id().is_some(),
+			KeyCode::Char('m') => self.transfer = self.current_id().is_some(),
+			KeyCode::Char(' ') => self.toggle_mark(),
+			KeyCode::Char('a') if ctrl_pressed => self.toggle_mark_all(),
+			KeyCode::Esc if !self.marked.is_empty() => self.marked.clear(),
+			KeyCode::Char('t') if !self.marked.is_empty() => self.open_bulk(BulkAction::Tag),
+			KeyCode::Char('r') if !ctrl_pressed && !self.marked.is_empty() => {
+				self.open_bulk(BulkAction::Move)
+			}
+			KeyCode::Char('d') | KeyCode::Delete if !self.marked.is_empty() => {
+				let mut confirm = Confirm::new(
+					"Confirm Deletion".into(),
+					Paragraph::new(Text::from(format!(
+						"Move the {} marked entries to the trash?",
+						self.marked.len()
+					))),
+				);
+				confirm.set_selected(1);
+				self.screens.push(ExplorerScreen::Confirm(
+					ConfirmAction::DeleteMarked,
+					confirm,
+				));
+			}
 			KeyCode::Char('t') => {
-				if !self.entries.is_empty() {
+				if self.current_id().is_some() {
 					let ent = self.current_entry();
-					self.tag_editor = Some(EntryTagEditor::new(
-						format!("Tags for {}", ent.name),
-						&ent.tags,
-					))
+					let editor = EntryTagEditor::new(format!("Tags for {}", ent.name), &ent.tags);
+					self.screens.push(ExplorerScreen::TagEditor(editor));
 				}
 			}
-			KeyCode::Char('d') => {
-				if !self.entries.is_empty() {
-					self.confirm_action = Some(ConfirmAction::Delete);
+			KeyCode::Char('d') | KeyCode::Delete => {
+				if self.current_id().is_some() {
 					let mut confirm = Confirm::new(
 						"Confirm Deletion".into(),
 						Paragraph::new(Text::from(format!(
-							"Delete entry: '{}'?",
+							"Move '{}' to the trash?",
 							self.current_entry().name
 						))),
 					);
 					confirm.set_selected(1);
-					self.confirm = Some(confirm);
+					self.screens
+						.push(ExplorerScreen::Confirm(ConfirmAction::Delete, confirm));
 				}
 			}
-			KeyCode::Char('a') => {
-				self.new_entry = Some(
-					Labeled::new(
-						"New Entry".into(),
-						TextInput::new().style(&NEWENTRY_INPUT_STYLE),
-					)
-					.style(&NEWENTRY_LABEL_STYLE),
-				);
+			KeyCode::Char('a') => self
+				.screens
+				.push(ExplorerScreen::NewEntry(NewEntryForm::new())),
+			KeyCode::Char('f') => self.toggle_favorite(),
+			KeyCode::Char('F') => self.set_view(self.view.next()),
+			KeyCode::Char('c') => self.copy_field("Password"),
+			KeyCode::Char('b') | KeyCode::Char('C') => self.copy_field("Username"),
+			KeyCode::Char('y') => self.copy_name(),
+			KeyCode::Char('o') => self.set_sort(self.sort.next()),
+			KeyCode::Char('O') => self.set_sort(EntrySort {
+				descending: !self.sort.descending,
+				..self.sort
+			}),
+			KeyCode::Char('L') => {
+				let view = AuditLogView::new(self.log.events());
+				self.screens.push(ExplorerScreen::AuditLog(view));
 			}
-			KeyCode::Char('r') => {
-				if !self.filtered_entries.is_empty() {
-					self.rename = Some(
-						Labeled::new(
-							"Rename".into(),
-							TextInput::new()
-								.style(&NEWENTRY_INPUT_STYLE)
-								.with_input(self.current_entry().name.clone()),
-						)
-						.style(&NEWENTRY_LABEL_STYLE),
-					);
-				}
+			KeyCode::Char('x') => {
+				let view = TrashView::new(&self.trash);
+				self.screens.push(ExplorerScreen::Trash(view));
+			}
+			KeyCode::Char('T') => {
+				let form = TagRegistryForm::new(&self.tags);
+				self.screens.push(ExplorerScreen::TagRegistry(form));
 			}
+			KeyCode::Char('i') => self.screens.push(ExplorerScreen::Import(ImportForm::new())),
+			KeyCode::Char('s') => {
+				let tags = self
+					.tags
+					.sorted()
+					.into_iter()
+					.map(|tag| tag.name.clone())
+					.collect();
+				let form = ShareForm::new(tags, self.marked.len());
+				self.screens.push(ExplorerScreen::Share(form));
+			}
+			KeyCode::Char('u') => self.undo(),
+			KeyCode::Char('r') if ctrl_pressed => self.redo(),
+			KeyCode::Char('R') => self.open_folder_rename(),
+			KeyCode::Char('r') | KeyCode::F(2) => self.start_rename(),
 			_ => return false,
 		}
 		true
 	}
 
+	fn tick(&mut self) {
+		self.screens.tick();
+	}
+
+	fn paste(&mut self, text: &str) -> bool {
+		if let Some(pasted) = self.screens.paste(text) {
+			return pasted;
+		}
+		if self.active == ActiveWidget::Search && self.filter_field.inner.paste(text) {
+			// Select the best match
+			self.selected = 0;
+			self.update_filter();
+			return true;
+		}
+		false
+	}
+
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		if let Some(keys) = self.screens.mouse_input(event) {
+			return keys;
+		}
+
+		let position = Position::new(event.column, event.row);
+		if self.filter_area.get().contains(position) {
+			if event.kind == MouseEventKind::Down(MouseButton::Left) {
+				self.active = ActiveWidget::Search;
+			}
+			return vec![];
+		}
+		let tag_area = self.tag_area.get();
+		if self.tag_sidebar && tag_area.contains(position) {
+			if event.kind == MouseEventKind::Down(MouseButton::Left) {
+				let index =
+					self.tag_list_state.borrow().offset() + (event.row - tag_area.y) as usize;
+				if let Some(tag) = self.sidebar_tags().get(index) {
+					self.active = ActiveWidget::Tags;
+					self.tag_selected = index;
+					self.toggle_filter_tag(tag);
+				}
+			}
+			return vec![];
+		}
+		let area = self.list_area.get();
+		if !area.contains(position) {
+			return vec![];
+		}
+		match event.kind {
+			MouseEventKind::ScrollDown => {
+				self.active = ActiveWidget::Content;
+				self.move_cursor(1);
+			}
+			MouseEventKind::ScrollUp => {
+				self.active = ActiveWidget::Content;
+				self.move_cursor(-1);
+			}
+			MouseEventKind::Down(MouseButton::Left) => {
+				let index = self.list_state.borrow().offset() + (event.row - area.y) as usize;
+				if index < self.rows.len() {
+					// Clicking the selected entry opens it
+					if self.active == ActiveWidget::Content && index == self.selected {
+						return vec![KeyCode::Enter.into()];
+					}
+					self.active = ActiveWidget::Content;
+					self.move_cursor(index as i32 - self.selected as i32);
+				}
+			}
+			_ => {}
+		}
+		vec![]
+	}
+
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
 		let area = ctx.area;
 		frame.render_widget(Clear, area);
 
+		// Expired credentials
+		let now = Utc::now();
+		let expired = self
+			.entries
+			.iter()
+			.map(|entry| entry.expiry_counts(now).0)
+			.sum::<usize>();
+
 		// Help bar
-		let help = Line::from(vec![
-			" PassK 0.1 ".bold().fg(Color::Red),
-			"⮁".bold().fg(Color::Green),
-			" (navigate) ".fg(Color::White),
-			"/".bold().fg(Color::Green),
-			" (filter) ".fg(Color::White),
-			"a".bold().fg(Color::Green),
-			" (add) ".fg(Color::White),
-			"d".bold().fg(Color::Green),
-			" (delete) ".fg(Color::White),
-			"r".bold().fg(Color::Green),
-			" (rename) ".fg(Color::White),
-			"q".bold().fg(Color::Green),
-			" (quit) ".fg(Color::White),
-			"enter".bold().fg(Color::Green),
-			" (open) ".fg(Color::White),
-		])
-		.bg(HELP_LINE_BG);
+		let mut help = vec![
+			format!(" {} ", self.title).bold().fg(theme().error),
+			if self.dirty {
+				format!("{} ", icons::UNSAVED).bold().fg(theme().special)
+			} else {
+				"".into()
+			},
+			if expired != 0 {
+				format!("{} {expired} expired ", icons::WARNING)
+					.bold()
+					.fg(theme().error)
+			} else {
+				"".into()
+			},
+			if self.jump {
+				"jump ".bold().fg(theme().highlight)
+			} else {
+				"".into()
+			},
+			if !self.marked.is_empty() {
+				format!("{} marked ", self.marked.len())
+					.bold()
+					.fg(theme().special)
+			} else {
+				"".into()
+			},
+		];
+		help.extend(keymap::help_line(&keymap::EXPLORER).spans);
+		let help = Line::from(help).bg(theme().help_bg);
 		let mut help_area = area;
 		help_area.height = 1;
 		ctx.selected = self.active == ActiveWidget::Search;
@@ -509,100 +2534,141 @@ impl Component for Explorer {
 		filter_area.height = self.filter_field.height();
 		ctx.area = filter_area;
 		self.filter_field.render(frame, ctx);
+		self.filter_area.set(filter_area);
+
+		// Header
+		let mut header_area = filter_area;
+		header_area.y += filter_area.height;
+		header_area.height = 1;
+		frame.render_widget(self.header(), header_area);
 
 		// Entries
 		let mut ent_area = area;
-		ent_area.y += filter_area.y + filter_area.height;
+		ent_area.y += filter_area.y + filter_area.height + header_area.height;
 		ent_area.height = area.height.saturating_sub(ent_area.y);
 		ent_area.width = ent_area.width.saturating_sub(1);
 
-		let mut items = self
-			.filtered_entries
-			.iter()
-			.map(|i| {
-				(
-					self.active == ActiveWidget::Content && *i == self.selected,
-					&self.entries[*i],
-				)
-			})
-			.enumerate()
-			.map(|(id, (selected, ent))| Self::format_entry(Some(ent), selected, id))
-			.collect::<Vec<_>>();
-		while items.len() < ent_area.height as usize {
-			items.push(Self::format_entry(None, false, items.len()));
+		// Tag sidebar
+		if self.tag_sidebar {
+			let mut sidebar_area = ent_area;
+			sidebar_area.width = (area.width / 4).clamp(16, 32).min(ent_area.width);
+			ent_area.x += sidebar_area.width;
+			ent_area.width -= sidebar_area.width;
+			self.render_tag_sidebar(frame, sidebar_area);
 		}
 
-		let scroll_offset = (self.selected + 1)
-			.saturating_sub(ent_area.height as usize)
-			.min(
-				self.filtered_entries
-					.len()
-					.saturating_sub(ent_area.height as usize),
-			);
-		let mut list_state = self.list_state.borrow_mut();
-		list_state.select(Some(self.selected));
+		if self.filtered_entries.is_empty() {
+			let text = self.empty_state();
+			let horizontal =
+				Layout::horizontal([Constraint::Length(text.width() as u16)]).flex(Flex::Center);
+			let vertical =
+				Layout::vertical([Constraint::Length(text.height() as u16)]).flex(Flex::Center);
+			let [text_area] = ent_area.layout(&horizontal);
+			let [text_area] = text_area.layout(&vertical);
+			frame.render_widget(Paragraph::new(text), text_area);
+			self.list_area.set(ent_area);
+		} else {
+			// Only the visible entries are formatted, the window follows the selection
+			let filter = ExplorerFilter::from(self.filter_field.inner.get_input().as_str());
+			let max_age = match config().password_max_age {
+				0 => TimeDelta::MAX,
+				days => TimeDelta::days(days.into()),
+			};
+			let health = health::scores(&self.entries, health::DEFAULT_MIN_ENTROPY, max_age, now);
+			// Columns of the configuration, after the marks
+			let widths = config()
+				.columns
+				.iter()
+				.map(|column| column_width(*column))
+				.collect::<Vec<_>>();
+			let columns = config()
+				.columns
+				.iter()
+				.copied()
+				.zip(columns::layout(&widths, ent_area.width.saturating_sub(1)))
+				.collect::<Vec<_>>();
+			let height = ent_area.height as usize;
+			let len = self.rows.len();
+			let mut list_state = self.list_state.borrow_mut();
+			let offset = list_state
+				.offset()
+				.min(self.selected)
+				.max((self.selected + 1).saturating_sub(height))
+				.min(len.saturating_sub(height));
+			list_state.select(Some(self.selected));
+			*list_state.offset_mut() = offset;
 
-		*self.scrollbar.borrow_mut() = ScrollbarState::new(
-			self.filtered_entries
-				.len()
-				.saturating_sub(ent_area.height as usize)
-				.max(1),
-		)
-		.position(scroll_offset);
-		frame.render_stateful_widget(List::new(items), ent_area, &mut *list_state);
-
-		// Scrollbar
-		let mut scrollbar_area = ent_area;
-		scrollbar_area.x = area.width.saturating_sub(1);
-		scrollbar_area.width = 1;
-
-		frame.render_stateful_widget(
-			Scrollbar::default()
-				.orientation(ScrollbarOrientation::VerticalRight)
-				.style(Style::default().fg(Color::from_u32(0x7f7faf))),
-			scrollbar_area,
-			&mut *self.scrollbar.borrow_mut(),
-		);
+			let end = (offset + height).min(len);
+			let mut items = self.rows[offset..end]
+				.iter()
+				.zip(offset..)
+				.map(|(row, id)| {
+					let selected = self.active == ActiveWidget::Content && id == self.selected;
+					match row {
+						Row::Entry(i) => {
+							let ent = &self.entries[*i];
+							let marked = self.marked.contains(&ent.name);
+							let bg = theme().entry_bg[if selected { 2 } else { id % 2 }];
+							Self::format_entry(
+								Some(ent),
+								&self.tags,
+								&filter,
+								&columns,
+								health[*i],
+								marked,
+								bg,
+							)
+						}
+						Row::Group(tag, count) => self.format_group(tag, *count, selected),
+					}
+				})
+				.collect::<Vec<_>>();
+			while items.len() < height {
+				let bg = theme().entry_bg[(offset + items.len()) % 2];
+				items.push(Self::format_entry(
+					None, &self.tags, &filter, &columns, None, false, bg,
+				));
+			}
 
-		// Editor
-		ctx.area = area;
-		if let Some(editor) = &self.editor {
-			editor.render(frame, ctx);
-		}
-		// Tag Editor
-		if let Some(editor) = &self.tag_editor {
-			let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
-			let vertical =
-				Layout::vertical([Constraint::Length(editor.height())]).flex(Flex::Center);
-			let [area] = ctx.area.layout(&horizontal);
-			let [area] = area.layout(&vertical);
-			ctx.area = area;
-			editor.render_form(frame, ctx);
-		}
-		// New entry
-		if let Some(new_editor) = &self.new_entry {
-			let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
-			let vertical = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center);
-			let [area] = ctx.area.layout(&horizontal);
-			let [area] = area.layout(&vertical);
-			ctx.area = area;
-			ctx.selected = true;
-			new_editor.render(frame, ctx);
-		}
-		// Rename
-		if let Some(rename) = &self.rename {
-			let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
-			let vertical = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center);
-			let [area] = ctx.area.layout(&horizontal);
-			let [area] = area.layout(&vertical);
-			ctx.area = area;
-			ctx.selected = true;
-			rename.render(frame, ctx);
-		}
-		// Confirm
-		if let Some(confirm) = &self.confirm {
-			confirm.render(frame, ctx);
+			*self.scrollbar.borrow_mut() =
+				ScrollbarState::new(len.saturating_sub(height).max(1)).position(offset);
+			let mut window = ListState::default().with_selected(Some(self.selected - offset));
+			frame.render_stateful_widget(List::new(items), ent_area, &mut window);
+			// Name being edited, over the name of the selected entry
+			if let Some(ExplorerScreen::Rename { area: rename, .. }) = self.screens.top() {
+				// After the mark, the columns before the name and the icon
+				let x = 1
+					+ columns
+						.iter()
+						.take_while(|(column, _)| *column != EntryColumn::Name)
+						.map(|(_, width)| width + 1)
+						.sum::<u16>() + 2;
+				rename.set(Rect {
+					x: ent_area.x + x,
+					y: ent_area.y + (self.selected - offset) as u16,
+					width: ent_area.width.saturating_sub(x),
+					height: 1,
+				});
+			}
+			self.list_area.set(ent_area);
+
+			// Scrollbar
+			let mut scrollbar_area = ent_area;
+			scrollbar_area.x = area.width.saturating_sub(1);
+			scrollbar_area.width = 1;
+
+			frame.render_stateful_widget(
+				Scrollbar::default()
+					.orientation(ScrollbarOrientation::VerticalRight)
+					.style(Style::default().fg(theme().scrollbar)),
+				scrollbar_area,
+				&mut *self.scrollbar.borrow_mut(),
+			);
 		}
+
+		// Screens
+		ctx.area = area;
+		self.screens.render(frame, ctx);
 	}
 
 	fn height(&self) -> u16 {
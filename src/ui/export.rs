@@ -0,0 +1,344 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::export::ExportFormat;
+use crate::icons;
+use crate::keymap;
+use crate::plugin::PLUGINS;
+use crate::plugin::PLUGIN_EXPORTERS;
+use crate::style::theme;
+use crate::ui::password::PasswordFormatter;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::confirm::Confirm;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::text_input_custom::CustomTextInput;
+use crate::widgets::text_input_custom::CustomTextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static EXPORT_FORMAT: LazyLock<Vec<ComboItem>> = LazyLock::new(|| {
+	let mut formats = vec![
+		ComboItem {
+			kind: "CSV".into(),
+			icon: format!("{} ", icons::CSV),
+			value: ExportFormat::Csv.name().into(),
+		},
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::JSON),
+			value: ExportFormat::Json.name().into(),
+		},
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::BITWARDEN),
+			value: ExportFormat::Bitwarden.name().into(),
+		},
+		ComboItem {
+			kind: "KDBX".into(),
+			icon: format!("{} ", icons::KEY),
+			value: ExportFormat::Kdbx.name().into(),
+		},
+	];
+	formats.extend(PLUGIN_EXPORTERS.iter().map(|(plugin, format)| ComboItem {
+		kind: PLUGINS[*plugin].description.name.clone(),
+		icon: format!("{} ", icons::PLUGIN),
+		value: format.to_string(),
+	}));
+	formats
+});
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static PASSWORD_INPUT_STYLE: LazyLock<CustomTextInputStyle> =
+	LazyLock::new(|| CustomTextInputStyle {
+		padding: [0, 0],
+		markers: ["".into(), "".into()],
+		style: Some(Style::default().fg(theme().text)),
+		style_selected: None,
+	});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+/// Validated export request
+pub struct ExportRequest {
+	pub format: ExportFormat,
+	pub path: PathBuf,
+	/// Master password, to be checked before exporting, also encrypts KeePass databases
+	pub password: String,
+}
+
+/// Export the decrypted entries to a plaintext file
+pub struct ExportForm {
+	style: FormStyle,
+
+	format: Option<ExportFormat>,
+	field_format: Labeled<'static, ComboBox<'static, 'static>>,
+	field_path: Labeled<'static, TextInput<'static>>,
+	field_password: Labeled<'static, CustomTextInput<'static, PasswordFormatter>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	/// Warning shown before exporting
+	confirm: Option<Confirm<'static>>,
+	popup: Option<Popup<'static>>,
+}
+
+impl Default for ExportForm {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl ExportForm {
+	pub fn new() -> Self {
+		let format = ExportFormat::default();
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			format: Some(format),
+			field_format: Labeled::new(
+				"Format".into(),
+				ComboBox::new(EXPORT_FORMAT.as_slice())
+					.style(&COMBOBOX_STYLE)
+					.with_input(format.name().into()),
+			)
+			.style(&LABEL_STYLE),
+			field_path: Labeled::new("File".into(), TextInput::new().style(&TEXTINPUT_STYLE))
+				.style(&LABEL_STYLE),
+			field_password: Labeled::new(
+				"Master Password".into(),
+				CustomTextInput::new(PasswordFormatter { hidden: true })
+					.style(&PASSWORD_INPUT_STYLE),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(1),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			confirm: None,
+			popup: None,
+		}
+	}
+
+	pub fn submit(&self) -> Option<ExportRequest> {
+		Some(ExportRequest {
+			format: self.format?,
+			path: PathBuf::from(self.field_path.inner.submit().trim()),
+			password: self.field_password.inner.submit(),
+		})
+	}
+
+	pub fn set_error(&mut self, title: String, message: String) {
+		self.popup = Some(Popup::new(title, Paragraph::new(Text::from(message))));
+	}
+}
+
+impl Form for ExportForm {
+	fn component_count(&self) -> usize {
+		3
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_format),
+			1 => Some(&self.field_path),
+			2 => Some(&self.field_password),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_format),
+			1 => Some(&mut self.field_path),
+			2 => Some(&mut self.field_password),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if let Some(confirm) = &mut self.confirm {
+			confirm.input(key);
+			return match confirm.submit() {
+				Some(true) => {
+					self.confirm = None;
+					Some(FormSignal::Return)
+				}
+				Some(false) => {
+					self.confirm = None;
+					None
+				}
+				None => None,
+			};
+		}
+
+		// Dispatch input to components
+		if FormExt::input(self, key) {
+			// Update format
+			if self.selected == Some(0) {
+				self.format = self
+					.field_format
+					.inner
+					.submit()
+					.and_then(|id| ExportFormat::try_from(id).ok());
+			}
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => {
+				if self.format.is_none() {
+					self.set_error("Export Failed".into(), "Invalid export format".into());
+				} else if self.field_path.inner.get_input().trim().is_empty() {
+					self.set_error("Export Failed".into(), "No file given".into());
+				} else if self.field_password.inner.get_input().is_empty() {
+					self.set_error("Invalid Password".into(), "Password is empty!".into());
+				} else if self.format.is_some_and(|format| format.needs_password()) {
+					return Some(FormSignal::Return);
+				} else {
+					let mut confirm = Confirm::new(
+						"Unencrypted Export".into(),
+						Paragraph::new(Text::from(format!(
+							"The export is NOT encrypted: anyone who can read '{}' will see all your passwords. Continue?",
+							self.field_path.inner.get_input().trim()
+						))),
+					);
+					confirm.set_selected(1);
+					self.confirm = Some(confirm);
+				}
+				None
+			}
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Export")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::EXPORT);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(confirm) = &self.confirm {
+			ctx.area = frame.area();
+			confirm.render(frame, ctx);
+		}
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
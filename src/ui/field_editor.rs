@@ -1,8 +1,18 @@
+use std::cell::Cell;
 use std::cell::RefCell;
+use std::path::Path;
 use std::sync::LazyLock;
 
+use crate::data::attachment;
 use crate::data::field::Field;
 use crate::data::field::FieldValue;
+use crate::data::field::TwoFACode;
+use crate::data::otp;
+use crate::data::otp::Totp;
+use crate::data::qr;
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
 use crate::ui::field_generator::FieldGenerator;
 use crate::widgets::checkbox::Checkbox;
 use crate::widgets::checkbox::CheckboxStyle;
@@ -16,67 +26,103 @@ use crate::widgets::form::FormStyle;
 use crate::widgets::label::LabelDisplay;
 use crate::widgets::label::LabelStyle;
 use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_area::TextArea;
+use crate::widgets::text_area::TextAreaStyle;
 use crate::widgets::text_input::TextInput;
 use crate::widgets::text_input::TextInputStyle;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::ComponentRenderCtx;
 use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
 use chrono::Utc;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::symbols::border::QUADRANT_OUTSIDE;
-use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::text::Text;
 use ratatui::widgets::Block;
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-static FIELD_TYPE: LazyLock<[ComboItem; 7]> = LazyLock::new(|| {
+static FIELD_TYPE: LazyLock<[ComboItem; 10]> = LazyLock::new(|| {
 	[
 		ComboItem {
 			kind: "Text".into(),
-			icon: "󰅍 ".into(),
+			icon: format!("{} ", icons::TEXT),
 			value: "Text".into(),
 		},
 		ComboItem {
 			kind: "Text".into(),
-			icon: " ".into(),
+			icon: format!("{} ", icons::URL),
 			value: "URL".into(),
 		},
 		ComboItem {
 			kind: "Text".into(),
-			icon: "󰥒 ".into(),
+			icon: format!("{} ", icons::PHONE),
 			value: "Phone Number".into(),
 		},
 		ComboItem {
 			kind: "Text".into(),
-			icon: "󰇰 ".into(),
+			icon: format!("{} ", icons::EMAIL),
 			value: "E-Mail".into(),
 		},
 		ComboItem {
 			kind: "2FA".into(),
-			icon: "󰐲 ".into(),
-			value: "TOTP/Steam".into(),
+			icon: format!("{} ", icons::OTP),
+			value: "TOTP/RFC 6238".into(),
 		},
 		ComboItem {
 			kind: "2FA".into(),
-			icon: "󰐲 ".into(),
-			value: "TOTP/RFC 6238".into(),
+			icon: format!("{} ", icons::OTP),
+			value: "TOTP/Steam".into(),
 		},
 		ComboItem {
 			kind: "2FA".into(),
-			icon: "󰦯 ".into(),
+			icon: format!("{} ", icons::RECOVERY_CODES),
 			value: "2FA Recovery".into(),
 		},
+		ComboItem {
+			kind: "File".into(),
+			icon: format!("{} ", icons::ATTACHMENT),
+			value: "Binary".into(),
+		},
+		ComboItem {
+			kind: "2FA".into(),
+			icon: format!("{} ", icons::OTP),
+			value: "HOTP".into(),
+		},
+		ComboItem {
+			kind: "Text".into(),
+			icon: format!("{} ", icons::NOTE),
+			value: "Note".into(),
+		},
 	]
 });
 
+/// Label of the secret input for TOTP fields
+const TOTP_SECRET_LABEL: &str = "TOTP Secret (base32 or otpauth:// URI)";
+
+/// Label of the secret input for Steam TOTP fields
+const STEAM_SECRET_LABEL: &str = "Steam Shared Secret (base64, base32 or otpauth:// URI)";
+
+/// Label of the secret input for HOTP fields
+const HOTP_SECRET_LABEL: &str = "HOTP Secret (base32)";
+
+/// Label of the codes input for 2FA recovery fields
+const RECOVERY_CODES_LABEL: &str = "Recovery Codes (separated by spaces or commas)";
+
+/// Number of lines shown by the note input
+const NOTE_ROWS: u16 = 6;
+
+/// Format of expiration dates
+const EXPIRY_FORMAT: &str = "%Y-%m-%d";
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum FieldValueKind {
@@ -89,6 +135,9 @@ pub enum FieldValueKind {
 	TOTPSteam,
 	TwoFactorRecovery,
 	Binary,
+	#[allow(clippy::upper_case_acronyms)]
+	HOTP,
+	Note,
 }
 
 impl TryFrom<usize> for FieldValueKind {
@@ -104,6 +153,8 @@ impl TryFrom<usize> for FieldValueKind {
 			5 => Ok(FieldValueKind::TOTPSteam),
 			6 => Ok(FieldValueKind::TwoFactorRecovery),
 			7 => Ok(FieldValueKind::Binary),
+			8 => Ok(FieldValueKind::HOTP),
+			9 => Ok(FieldValueKind::Note),
 			_ => Err("Invalid value"),
 		}
 	}
@@ -116,10 +167,12 @@ impl FieldValueKind {
 			FieldValueKind::Url => "URL",
 			FieldValueKind::Phone => "Phone Number",
 			FieldValueKind::EMail => "E-Mail",
-			FieldValueKind::TOTPRFC6238 => "TOTP (RFC-6238)",
-			FieldValueKind::TOTPSteam => "TOTP (Steam)",
+			FieldValueKind::TOTPRFC6238 => "TOTP/RFC 6238",
+			FieldValueKind::TOTPSteam => "TOTP/Steam",
 			FieldValueKind::TwoFactorRecovery => "2FA Recovery",
 			FieldValueKind::Binary => "Binary",
+			FieldValueKind::HOTP => "HOTP",
+			FieldValueKind::Note => "Note",
 		}
 	}
 }
@@ -138,11 +191,24 @@ pub struct FieldEditor {
 	value_kind: Option<FieldValueKind>,
 	prev_value_kind: Option<FieldValueKind>,
 	field_value: Option<Labeled<'static, TextInput<'static>>>,
+	/// Value of note fields, used instead of `field_value`
+	field_note: Labeled<'static, TextArea<'static>>,
+	/// Attached file, for binary fields
+	attachment: Option<FieldValue>,
+	/// Recovery codes of the edited field, to keep the used state of unchanged codes
+	recovery_codes: Vec<TwoFACode>,
+	field_expires: Labeled<'static, TextInput<'static>>,
+	/// Image of a QR code to read the TOTP secret from
+	field_qr: Labeled<'static, TextInput<'static>>,
+	/// Counter of the next HOTP code
+	field_counter: Labeled<'static, TextInput<'static>>,
 
 	selected: Option<usize>,
 	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
 
 	generator: Option<FieldGenerator>,
+	popup: Option<Popup<'static>>,
 }
 
 static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
@@ -150,35 +216,46 @@ static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
 	display: LabelDisplay::Block {
 		block: Box::new(Block::bordered()),
 	},
-	style: Some(Style::default().fg(Color::White)),
+	style: Some(Style::default().fg(theme().text)),
 	style_selected: None,
 });
 static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
 	padding: [0, 0],
 	markers: ["".into(), "".into()],
-	style: Some(Style::default().fg(Color::White)),
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTAREA_STYLE: LazyLock<TextAreaStyle> = LazyLock::new(|| TextAreaStyle {
+	padding: [0, 0],
+	style: Some(Style::default().fg(theme().text)),
 	style_selected: None,
 });
 static CHECKBOX_STYLE: LazyLock<CheckboxStyle> = LazyLock::new(|| CheckboxStyle {
 	padding: [1, 0],
 	spacing: 1,
-	markers: ["󰄱 ".into(), "󰄵 ".into()],
-	style: Some(Style::default().fg(Color::White)),
+	markers: [
+		format!("{} ", icons::UNCHECKED).into(),
+		format!("{} ", icons::CHECKED).into(),
+	],
+	style: Some(Style::default().fg(theme().text)),
 	selected_style: None,
 });
 static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
 	padding: Default::default(),
 	markers: ["".into(), "".into()],
-	indicator: [" ".into(), " ".into()],
+	indicator: [
+		format!("{} ", icons::COLLAPSED).into(),
+		format!("{} ", icons::EXPANDED).into(),
+	],
 	completion: [
-		Style::default().bg(Color::Cyan).fg(Color::Black),
-		Style::default().bg(Color::Black).fg(Color::White).bold(),
-		Style::default().bg(Color::Black).fg(Color::White).italic(),
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
 	],
 	completion_selected: [
-		Style::default().bg(Color::Cyan).fg(Color::Black),
-		Style::default().bg(Color::Black).fg(Color::Yellow).bold(),
-		Style::default().bg(Color::Black).fg(Color::Yellow).italic(),
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().highlight).bold(),
+		Style::default().bg(theme().base).fg(theme().highlight).italic(),
 	],
 	style: Default::default(),
 	selected_style: Default::default(),
@@ -189,7 +266,7 @@ impl FieldEditor {
 		Self {
 			title,
 			style: FormStyle {
-				bg: Color::from_u32(0x2f2f2f),
+				bg: theme().form_bg,
 				border: false
 			},
 			created_at: Utc::now(),
@@ -204,9 +281,35 @@ impl FieldEditor {
 			value_kind: None,
 			prev_value_kind: None,
 			field_value: None,
+			field_note: Labeled::new(
+				FieldValueKind::Note.name().into(),
+				TextArea::new().style(&TEXTAREA_STYLE).rows(NOTE_ROWS),
+			)
+			.style(&LABEL_STYLE),
+			attachment: None,
+			recovery_codes: vec![],
+			field_expires: Labeled::new(
+				"Expires (YYYY-MM-DD, empty for never)".into(),
+				TextInput::new().style(&TEXTINPUT_STYLE),
+			)
+			.style(&LABEL_STYLE),
+			field_qr: Labeled::new(
+				"QR Code Image (optional)".into(),
+				TextInput::new().style(&TEXTINPUT_STYLE),
+			)
+			.style(&LABEL_STYLE),
+			field_counter: Labeled::new(
+				"Counter".into(),
+				TextInput::new()
+					.style(&TEXTINPUT_STYLE)
+					.with_input("0".into()),
+			)
+			.style(&LABEL_STYLE),
 			selected: None,
 			scroll: RefCell::default(),
+			body_area: Cell::default(),
 			generator: None,
+			popup: None,
 		}
 	}
 
@@ -266,9 +369,91 @@ impl FieldEditor {
 				);
 				kind
 			}
-			_ => todo!(),
+			FieldValue::Note(text) => {
+				self.field_note.inner.set_input(text.clone());
+				FieldValueKind::Note
+			}
+			FieldValue::Binary { mimetype, base64 } => {
+				let kind = FieldValueKind::Binary;
+				self.field_value = Some(
+					Labeled::new(
+						format!(
+							"File (current: {mimetype}, {})",
+							attachment::format_size(attachment::size(base64))
+						)
+						.into(),
+						TextInput::new().style(&TEXTINPUT_STYLE),
+					)
+					.style(&LABEL_STYLE),
+				);
+				self.attachment = Some(field.value.clone());
+				kind
+			}
+			FieldValue::HOTP { secret, counter } => {
+				let kind = FieldValueKind::HOTP;
+				self.field_value = Some(
+					Labeled::new(
+						HOTP_SECRET_LABEL.into(),
+						TextInput::new()
+							.style(&TEXTINPUT_STYLE)
+							.with_input(secret.clone()),
+					)
+					.style(&LABEL_STYLE),
+				);
+				self.field_counter.inner.set_input(counter.to_string());
+				kind
+			}
+			FieldValue::TOTPRFC6238(secret) => {
+				let kind = FieldValueKind::TOTPRFC6238;
+				self.field_value = Some(
+					Labeled::new(
+						TOTP_SECRET_LABEL.into(),
+						TextInput::new()
+							.style(&TEXTINPUT_STYLE)
+							.with_input(secret.clone()),
+					)
+					.style(&LABEL_STYLE),
+				);
+				kind
+			}
+			FieldValue::TOTPSteam(secret) => {
+				let kind = FieldValueKind::TOTPSteam;
+				self.field_value = Some(
+					Labeled::new(
+						STEAM_SECRET_LABEL.into(),
+						TextInput::new()
+							.style(&TEXTINPUT_STYLE)
+							.with_input(secret.clone()),
+					)
+					.style(&LABEL_STYLE),
+				);
+				kind
+			}
+			FieldValue::TwoFactorRecovery(codes) => {
+				let kind = FieldValueKind::TwoFactorRecovery;
+				self.field_value = Some(
+					Labeled::new(
+						RECOVERY_CODES_LABEL.into(),
+						TextInput::new().style(&TEXTINPUT_STYLE).with_input(
+							codes
+								.iter()
+								.map(|code| code.value.as_str())
+								.collect::<Vec<_>>()
+								.join(" "),
+						),
+					)
+					.style(&LABEL_STYLE),
+				);
+				self.recovery_codes = codes.clone();
+				kind
+			}
 		};
 		self.created_at = field.date_added;
+		if let Some(expires_at) = field.expires_at {
+			self.field_expires
+				.inner
+				.set_input(expires_at.format(EXPIRY_FORMAT).to_string());
+		}
 		self.field_type.inner.set_input(kind.name().to_owned());
 		self.value_kind = Some(kind);
 		self.prev_value_kind = Some(kind);
@@ -295,20 +480,147 @@ impl FieldEditor {
 				FieldValueKind::EMail => {
 					FieldValue::Email(self.field_value.as_ref().unwrap().inner.submit())
 				}
-				_ => todo!(),
+				FieldValueKind::Note => FieldValue::Note(self.field_note.inner.submit()),
+				FieldValueKind::Binary => self.attachment.clone()?,
+				FieldValueKind::TOTPRFC6238 => FieldValue::TOTPRFC6238(
+					self.field_value.as_ref().unwrap().inner.submit().trim().to_string(),
+				),
+				FieldValueKind::TOTPSteam => FieldValue::TOTPSteam(
+					self.field_value.as_ref().unwrap().inner.submit().trim().to_string(),
+				),
+				FieldValueKind::HOTP => FieldValue::HOTP {
+					secret: self.field_value.as_ref().unwrap().inner.submit().trim().to_string(),
+					counter: self.counter().ok()?,
+				},
+				FieldValueKind::TwoFactorRecovery => {
+					FieldValue::TwoFactorRecovery(TwoFACode::parse_list(
+						self.field_value.as_ref().unwrap().inner.get_input(),
+						&self.recovery_codes,
+					))
+				}
 			},
 			hidden: self.field_hidden.value(),
 			date_added: self.created_at,
 			date_modified: now,
 			date_accessed: now,
+			expires_at: self.expires_at().ok()?,
+			history: vec![],
 		})
 	}
+
+	/// Parse the expiration date
+	fn expires_at(&self) -> Result<Option<DateTime<Utc>>, String> {
+		let input = self.field_expires.inner.get_input().trim();
+		if input.is_empty() {
+			return Ok(None);
+		}
+		NaiveDate::parse_from_str(input, EXPIRY_FORMAT)
+			.map(|date| Some(date.and_time(NaiveTime::MIN).and_utc()))
+			.map_err(|err| format!("Invalid expiration date '{input}': {err}"))
+	}
+
+	/// Load the file given for a binary field, returns false on error
+	fn load_attachment(&mut self) -> bool {
+		let Some(field) = &self.field_value else {
+			return false;
+		};
+		let path = field.inner.submit();
+		let path = Path::new(path.trim());
+		if path.as_os_str().is_empty() {
+			if self.attachment.is_some() {
+				return true;
+			}
+			self.set_error("Attachment Error", "No file given".into());
+			return false;
+		}
+
+		match attachment::load(path) {
+			Ok(value) => {
+				if self.field_name.inner.get_input().trim().is_empty() {
+					if let Some(name) = path.file_name() {
+						self.field_name
+							.inner
+							.set_input(name.to_string_lossy().into_owned());
+					}
+				}
+				self.attachment = Some(value);
+				true
+			}
+			Err(err) => {
+				self.set_error("Attachment Error", err);
+				false
+			}
+		}
+	}
+
+	/// Validate the TOTP secret or URI, returns false on error
+	///
+	/// The field is named after the issuer or account of URIs when no name was given.
+	fn parse_totp(&mut self) -> bool {
+		if !self.load_qr_code() {
+			return false;
+		}
+		let Some(field) = &self.field_value else {
+			return false;
+		};
+		let totp = match Totp::parse(&field.inner.submit()) {
+			Ok(totp) => totp,
+			Err(err) => {
+				self.set_error("Invalid TOTP Secret", err);
+				return false;
+			}
+		};
+		if self.field_name.inner.get_input().trim().is_empty() {
+			if let Some(name) = totp.issuer.or(totp.account) {
+				self.field_name.inner.set_input(name);
+			}
+		}
+		true
+	}
+
+	/// Parse the HOTP counter
+	fn counter(&self) -> Result<u64, String> {
+		let input = self.field_counter.inner.get_input().trim();
+		input
+			.parse()
+			.map_err(|err| format!("Invalid counter '{input}': {err}"))
+	}
+
+	/// Read the TOTP secret from the QR code image, if one was given, returns false on error
+	fn load_qr_code(&mut self) -> bool {
+		let path = self.field_qr.inner.submit();
+		let path = Path::new(path.trim());
+		if path.as_os_str().is_empty() {
+			return true;
+		}
+		match qr::decode(path) {
+			Ok(content) => {
+				if let Some(field) = &mut self.field_value {
+					field.inner.set_input(content);
+				}
+				self.field_qr.inner.set_input(String::default());
+				true
+			}
+			Err(err) => {
+				self.set_error("QR Code Error", err);
+				false
+			}
+		}
+	}
+
+	fn set_error(&mut self, title: &str, message: String) {
+		self.popup = Some(Popup::new(
+			title.into(),
+			Paragraph::new(Text::from(message)),
+		));
+	}
 }
 
 impl Form for FieldEditor {
 	fn component_count(&self) -> usize {
 		match self.value_kind {
-			Some(_) => 4,
+			Some(FieldValueKind::TOTPRFC6238 | FieldValueKind::HOTP) => 6,
+			Some(_) => 5,
 			None => 3,
 		}
 	}
@@ -318,6 +630,7 @@ impl Form for FieldEditor {
 			0 => Some(&self.field_name),
 			1 => Some(&self.field_hidden),
 			2 => Some(&self.field_type),
+			3 if self.value_kind == Some(FieldValueKind::Note) => Some(&self.field_note),
 			3 => {
 				if let Some(field) = &self.field_value {
 					Some(field)
@@ -325,6 +638,9 @@ impl Form for FieldEditor {
 					None
 				}
 			}
+			4 => Some(&self.field_expires),
+			5 if self.value_kind == Some(FieldValueKind::HOTP) => Some(&self.field_counter),
+			5 => Some(&self.field_qr),
 			_ => None,
 		}
 	}
@@ -334,6 +650,7 @@ impl Form for FieldEditor {
 			0 => Some(&mut self.field_name),
 			1 => Some(&mut self.field_hidden),
 			2 => Some(&mut self.field_type),
+			3 if self.value_kind == Some(FieldValueKind::Note) => Some(&mut self.field_note),
 			3 => {
 				if let Some(field) = &mut self.field_value {
 					Some(field)
@@ -341,6 +658,9 @@ impl Form for FieldEditor {
 					None
 				}
 			}
+			4 => Some(&mut self.field_expires),
+			5 if self.value_kind == Some(FieldValueKind::HOTP) => Some(&mut self.field_counter),
+			5 => Some(&mut self.field_qr),
 			_ => None,
 		}
 	}
@@ -365,7 +685,18 @@ impl Form for FieldEditor {
 		*self.scroll.borrow_mut() = scroll;
 	}
 
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
 	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+
 		// Subform
 		if let Some(generator) = &mut self.generator {
 			let signal = generator.input_form(key);
@@ -397,6 +728,7 @@ impl Form for FieldEditor {
 					if Some(kind) != self.prev_value_kind {
 						self.prev_value_kind = self.value_kind;
 						self.value_kind = Some(kind);
+						self.attachment = None;
 						match kind {
 							FieldValueKind::Text
 							| FieldValueKind::Url
@@ -410,7 +742,55 @@ impl Form for FieldEditor {
 									.style(&LABEL_STYLE),
 								)
 							}
-							_ => todo!(),
+							FieldValueKind::Note => {
+								self.field_value = None;
+								self.field_note.inner.set_input(String::default());
+							}
+							FieldValueKind::Binary => {
+								self.field_value = Some(
+									Labeled::new(
+										"File".into(),
+										TextInput::new().style(&TEXTINPUT_STYLE),
+									)
+									.style(&LABEL_STYLE),
+								)
+							}
+							FieldValueKind::TOTPRFC6238 => {
+								self.field_value = Some(
+									Labeled::new(
+										TOTP_SECRET_LABEL.into(),
+										TextInput::new().style(&TEXTINPUT_STYLE),
+									)
+									.style(&LABEL_STYLE),
+								)
+							}
+							FieldValueKind::HOTP => {
+								self.field_value = Some(
+									Labeled::new(
+										HOTP_SECRET_LABEL.into(),
+										TextInput::new().style(&TEXTINPUT_STYLE),
+									)
+									.style(&LABEL_STYLE),
+								)
+							}
+							FieldValueKind::TOTPSteam => {
+								self.field_value = Some(
+									Labeled::new(
+										STEAM_SECRET_LABEL.into(),
+										TextInput::new().style(&TEXTINPUT_STYLE),
+									)
+									.style(&LABEL_STYLE),
+								)
+							}
+							FieldValueKind::TwoFactorRecovery => {
+								self.field_value = Some(
+									Labeled::new(
+										RECOVERY_CODES_LABEL.into(),
+										TextInput::new().style(&TEXTINPUT_STYLE),
+									)
+									.style(&LABEL_STYLE),
+								)
+							}
 						}
 					}
 				} else {
@@ -422,18 +802,63 @@ impl Form for FieldEditor {
 			return None;
 		}
 
-		// Quit
+		// Quit, enter is processed by the note input
+		let ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
 		if key.code == KeyCode::Esc {
 			return Some(FormSignal::Exit);
-		} else if key.code == KeyCode::Enter {
+		} else if key.code == KeyCode::Enter || (ctrl_pressed && key.code == KeyCode::Char('s')) {
+			if let Err(err) = self.expires_at() {
+				self.set_error("Invalid Date", err);
+				return None;
+			}
+			if self.value_kind == Some(FieldValueKind::Binary) && !self.load_attachment() {
+				return None;
+			}
+			if self.value_kind == Some(FieldValueKind::TOTPRFC6238) && !self.parse_totp() {
+				return None;
+			}
+			if self.value_kind == Some(FieldValueKind::TOTPSteam) {
+				let secret = self.field_value.as_ref().unwrap().inner.submit();
+				if let Err(err) = Totp::parse_steam(&secret) {
+					self.set_error("Invalid Steam Secret", err);
+					return None;
+				}
+			}
+			if self.value_kind == Some(FieldValueKind::HOTP) {
+				let secret = self.field_value.as_ref().unwrap().inner.submit();
+				if let Err(err) = self.counter().and_then(|counter| {
+					otp::generate_hotp(&secret, counter)
+				}) {
+					self.set_error("Invalid HOTP", err);
+					return None;
+				}
+			}
+			if self.value_kind == Some(FieldValueKind::TwoFactorRecovery)
+				&& TwoFACode::parse_list(self.field_value.as_ref().unwrap().inner.get_input(), &[])
+					.is_empty()
+			{
+				self.set_error("Invalid Recovery Codes", "No recovery code given".into());
+				return None;
+			}
 			return Some(FormSignal::Return);
 		}
 
 		// Generator
-		let ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
 		if ctrl_pressed
 			&& key.code == KeyCode::Char('g')
-			&& (self.selected == Some(0) || self.selected == Some(3))
+			&& (self.selected == Some(0)
+				|| (self.selected == Some(3)
+					&& !matches!(
+						self.value_kind,
+						Some(
+							FieldValueKind::Binary
+								| FieldValueKind::TOTPRFC6238
+								| FieldValueKind::TOTPSteam
+								| FieldValueKind::HOTP
+								| FieldValueKind::TwoFactorRecovery
+								| FieldValueKind::Note
+						)
+					)))
 		{
 			let name = if self.selected == Some(0) {
 				"Name"
@@ -451,23 +876,12 @@ impl Form for FieldEditor {
 		let border = Block::bordered()
 			.border_set(QUADRANT_OUTSIDE)
 			.title(self.title.as_str())
-			.title_style(Style::default().fg(Color::White))
+			.title_style(Style::default().fg(theme().text))
 			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
 			.bg(self.style.bg)
-			.fg(Color::from_u32(0x1a1a1f));
+			.fg(theme().form_border);
 		frame.render_widget(border, area);
-		let text = Text::from(Line::from(vec![
-			"⮁".bold().fg(Color::Green),
-			" (navigate) ".fg(Color::White),
-			"esc".bold().fg(Color::Green),
-			" (cancel) ".fg(Color::White),
-			"enter".bold().fg(Color::Green),
-			" (submit) ".fg(Color::White),
-			"space".bold().fg(Color::Green),
-			" (toggle) ".fg(Color::White),
-			"C-g".bold().fg(Color::Green),
-			" (generate) ".fg(Color::White),
-		]));
+		let text = Text::from(keymap::help_line(&keymap::FIELD_EDITOR));
 		let help_message = Paragraph::new(text);
 		frame.render_widget(
 			help_message,
@@ -494,5 +908,9 @@ impl Form for FieldEditor {
 				generator.render_form(frame, ctx);
 			});
 		}
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
 	}
 }
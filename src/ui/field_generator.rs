@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::sync::LazyLock;
 
@@ -15,6 +16,9 @@ use ratatui::widgets::Block;
 use ratatui::widgets::Clear;
 use ratatui::Frame;
 
+use crate::config::config;
+use crate::icons;
+use crate::style::theme;
 use crate::widgets::combo_box::ComboBox;
 use crate::widgets::combo_box::ComboBoxStyle;
 use crate::widgets::combo_box::ComboItem;
@@ -30,31 +34,41 @@ use crate::widgets::text_input::TextInputStyle;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::ComponentRenderCtx;
 
-static CHARSET_TYPE: LazyLock<[ComboItem; 4]> = LazyLock::new(|| {
-	[
+/// Predefined charsets, followed by the generator presets of the configuration
+static CHARSET_TYPE: LazyLock<Vec<ComboItem>> = LazyLock::new(|| {
+	let mut items = vec![
 		ComboItem {
 			kind: "ASCII".into(),
-			icon: "󱅈 ".into(),
+			icon: format!("{} ", icons::CHARSET),
 			value: "Alphanumeric".into(),
 		},
 		ComboItem {
 			kind: "ASCII".into(),
-			icon: "󱅈 ".into(),
+			icon: format!("{} ", icons::CHARSET),
 			value: "Alphabet".into(),
 		},
 		ComboItem {
 			kind: "ASCII".into(),
-			icon: "󰟵 ".into(),
+			icon: format!("{} ", icons::SYMBOLS),
 			value: "Base86".into(),
 		},
 		ComboItem {
 			kind: "Unicode".into(),
-			icon: "󰟵 ".into(),
+			icon: format!("{} ", icons::SYMBOLS),
 			value: "Custom".into(),
 		},
-	]
+	];
+	items.extend(config().generators.iter().map(|preset| ComboItem {
+		kind: "Preset".into(),
+		icon: format!("{} ", icons::PRESET),
+		value: preset.name.clone(),
+	}));
+	items
 });
 
+/// Number of predefined charsets, presets come after them in [`CHARSET_TYPE`]
+const CHARSET_KINDS: usize = 4;
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum CharsetKind {
@@ -80,7 +94,25 @@ impl TryFrom<usize> for CharsetKind {
 }
 
 impl CharsetKind {
-	fn name(&self) -> &'static str {
+	/// Characters of a predefined charset, `None` for [`CharsetKind::Custom`]
+	pub fn chars(&self) -> Option<Vec<char>> {
+		match self {
+			CharsetKind::Alphanum => Some(
+				"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+					.chars()
+					.collect(),
+			),
+			CharsetKind::Alpha => Some(
+				"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
+					.chars()
+					.collect(),
+			),
+			CharsetKind::Base86 => Some("!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuz".chars().collect()),
+			CharsetKind::Custom => None,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
 		match self {
 			CharsetKind::Alphanum => "Alphanumeric",
 			CharsetKind::Alpha => "Alphabet",
@@ -95,28 +127,37 @@ static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
 	display: LabelDisplay::Block {
 		block: Box::new(Block::bordered()),
 	},
-	style: Some(Style::default().fg(Color::White)),
+	style: Some(Style::default().fg(theme().text)),
 	style_selected: None,
 });
 static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
 	padding: [0, 0],
 	markers: ["".into(), "".into()],
-	style: Some(Style::default().fg(Color::White)),
+	style: Some(Style::default().fg(theme().text)),
 	style_selected: None,
 });
 static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
 	padding: Default::default(),
 	markers: ["".into(), "".into()],
-	indicator: [" ".into(), " ".into()],
+	indicator: [
+		format!("{} ", icons::COLLAPSED).into(),
+		format!("{} ", icons::EXPANDED).into(),
+	],
 	completion: [
-		Style::default().bg(Color::Cyan).fg(Color::Black),
-		Style::default().bg(Color::Black).fg(Color::White).bold(),
-		Style::default().bg(Color::Black).fg(Color::White).italic(),
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
 	],
 	completion_selected: [
-		Style::default().bg(Color::Cyan).fg(Color::Black),
-		Style::default().bg(Color::Black).fg(Color::Yellow).bold(),
-		Style::default().bg(Color::Black).fg(Color::Yellow).italic(),
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
 	],
 	style: Default::default(),
 	selected_style: Default::default(),
@@ -128,12 +169,15 @@ pub struct FieldGenerator {
 
 	prev_charset_type: Option<CharsetKind>,
 	charset_type: Option<CharsetKind>,
+	/// Index of the selected preset in [`CHARSET_TYPE`]
+	preset: Option<usize>,
 	field_len: Labeled<'static, TextInput<'static>>,
 	field_charset: Labeled<'static, ComboBox<'static, 'static>>,
 	field_charset_custom: Option<Labeled<'static, TextInput<'static>>>,
 
 	selected: Option<usize>,
 	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
 }
 
 impl FieldGenerator {
@@ -141,11 +185,12 @@ impl FieldGenerator {
 		Self {
 			title,
 			style: FormStyle {
-				bg: Color::from_u32(0x2f2f2f),
+				bg: theme().form_bg,
 				border: true,
 			},
 			prev_charset_type: None,
 			charset_type: Some(CharsetKind::Alphanum),
+			preset: None,
 			field_len: Labeled::new("Length".into(), TextInput::new().style(&TEXTINPUT_STYLE))
 				.style(&LABEL_STYLE),
 			field_charset: Labeled::new(
@@ -158,30 +203,34 @@ impl FieldGenerator {
 			field_charset_custom: None,
 			selected: None,
 			scroll: RefCell::default(),
+			body_area: Cell::default(),
 		}
 	}
 
 	pub fn submit(&self) -> Option<String> {
 		let charset_kind = self.charset_type?;
 		let length = self.field_len.inner.submit().parse::<usize>().ok()?;
-		let charset: Vec<char> = match charset_kind {
-    CharsetKind::Alphanum => "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789".chars().collect(),
-    CharsetKind::Alpha=> "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect(),
-    CharsetKind::Base86 => "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuz".chars().collect(),
-    CharsetKind::Custom => {
-		let custom = self.field_charset_custom.as_ref()?;
-		custom.inner.submit().chars().collect()
-	},
+		let charset: Vec<char> = match charset_kind.chars() {
+			Some(chars) => chars,
+			None => {
+				let custom = self.field_charset_custom.as_ref()?;
+				custom.inner.submit().chars().collect()
+			}
 		};
-		if charset.is_empty() || length == 0 {
-			return None;
-		}
-		let mut rng = rand::rng();
-		let random = (0..length)
-			.map(|_| charset[rng.random_range(0..charset.len())])
-			.collect::<String>();
-		Some(random)
+		generate(&charset, length)
+	}
+}
+
+/// Generate a random string of `length` characters from `charset`
+pub fn generate(charset: &[char], length: usize) -> Option<String> {
+	if charset.is_empty() || length == 0 {
+		return None;
 	}
+	let mut rng = rand::rng();
+	let random = (0..length)
+		.map(|_| charset[rng.random_range(0..charset.len())])
+		.collect::<String>();
+	Some(random)
 }
 
 impl Form for FieldGenerator {
@@ -242,13 +291,39 @@ impl Form for FieldGenerator {
 		*self.scroll.borrow_mut() = scroll;
 	}
 
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
 	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
 		// Dispatch input to components
 		if FormExt::input(self, key) {
 			// Update state
 			if self.selected == Some(1) {
-				if let Some(Ok(kind)) = self.field_charset.inner.submit().map(CharsetKind::try_from)
-				{
+				let selected = self.field_charset.inner.submit();
+				let preset = selected
+					.and_then(|index| index.checked_sub(CHARSET_KINDS))
+					.and_then(|index| config().generators.get(index));
+				if let Some(preset) = preset {
+					// Presets fill in the custom charset and the length
+					if self.preset != selected {
+						self.preset = selected;
+						// Selecting a predefined charset afterwards always applies it
+						self.prev_charset_type = None;
+						self.charset_type = Some(CharsetKind::Custom);
+						self.field_charset_custom = Some(
+							Labeled::new(
+								CharsetKind::Custom.name().into(),
+								TextInput::new()
+									.style(&TEXTINPUT_STYLE)
+									.with_input(preset.charset.clone()),
+							)
+							.style(&LABEL_STYLE),
+						);
+						self.field_len.inner.set_input(preset.length.to_string());
+					}
+				} else if let Some(Ok(kind)) = selected.map(CharsetKind::try_from) {
+					self.preset = None;
 					if Some(kind) != self.prev_charset_type {
 						self.prev_charset_type = self.charset_type;
 						self.charset_type = Some(kind);
@@ -264,6 +339,7 @@ impl Form for FieldGenerator {
 						}
 					}
 				} else {
+					self.preset = None;
 					self.prev_charset_type = self.charset_type;
 					self.charset_type = None;
 					self.field_charset_custom = None;
@@ -287,10 +363,10 @@ impl Form for FieldGenerator {
 		let border = Block::bordered()
 			.border_set(QUADRANT_OUTSIDE)
 			.title(self.title.as_str())
-			.title_style(Style::default().fg(Color::White))
+			.title_style(Style::default().fg(theme().text))
 			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
 			.bg(self.style.bg)
-			.fg(Color::from_u32(0x1a1a1f));
+			.fg(theme().form_border);
 		frame.render_widget(Clear, area);
 		frame.render_widget(border, area);
 		ctx.area.x += 1;
@@ -324,14 +400,14 @@ impl Form for FieldGenerator {
 			None => 0.0,
 		};
 		let ent_style = Style::default().bold().fg(match ent_value as usize {
-			0..64 => Color::Red,
-			64..80 => Color::Yellow,
+			0..64 => theme().error,
+			64..80 => theme().highlight,
 			80..90 => Color::LightGreen,
-			_ => Color::Green,
+			_ => theme().success,
 		});
 		let entropy = Line::from(vec![
-			"Entropy".fg(Color::White).underlined(),
-			": ".fg(Color::White),
+			"Entropy".fg(theme().text).underlined(),
+			": ".fg(theme().text),
 			Span::from(format!("{ent_value}")).style(ent_style),
 			Span::from("bits").style(ent_style),
 		]);
@@ -0,0 +1,160 @@
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::List;
+use ratatui::Frame;
+
+use crate::data::field::Field;
+use crate::data::field::FieldRevision;
+use crate::keymap;
+use crate::style::theme;
+use crate::ui::entry::EntryEditor;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Browse the previous values of a field, to copy or restore one
+pub struct FieldHistory {
+	name: String,
+	hidden: bool,
+	/// Previous values, oldest first
+	revisions: Vec<FieldRevision>,
+	/// Selected position in the displayed list, newest first
+	selected: usize,
+	restore: Option<usize>,
+	/// Set when a value was copied to the clipboard
+	copied: bool,
+}
+
+impl FieldHistory {
+	pub fn new(field: &Field) -> Self {
+		Self {
+			name: field.name.clone(),
+			hidden: field.hidden,
+			revisions: field.history.clone(),
+			selected: 0,
+			restore: None,
+			copied: false,
+		}
+	}
+
+	/// Position in the history of the selected value
+	fn index(&self) -> Option<usize> {
+		(self.selected < self.revisions.len()).then(|| self.revisions.len() - 1 - self.selected)
+	}
+
+	/// Position in the history of the value to restore, if one was picked
+	pub fn submit(&self) -> Option<usize> {
+		self.restore
+	}
+
+	/// Whether a value was copied to the clipboard
+	pub fn copied(&self) -> bool {
+		self.copied
+	}
+}
+
+impl Component for FieldHistory {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+			KeyCode::Down | KeyCode::Char('j') => {
+				self.selected = (self.selected + 1).min(self.revisions.len().saturating_sub(1))
+			}
+			KeyCode::Char('y') => {
+				if let Some(index) = self.index() {
+					self.revisions[index].value.copy_to_clipboard();
+					self.copied = true;
+				}
+			}
+			KeyCode::Enter => {
+				self.restore = self.index();
+				return false;
+			}
+			KeyCode::Esc | KeyCode::Char('q') => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(format!("Field History: {}", self.name))
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::FIELD_HISTORY);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		if self.revisions.is_empty() {
+			frame.render_widget(
+				Line::from("No previous values".italic().fg(theme().dim)),
+				body,
+			);
+			return;
+		}
+
+		// Previous values are shown as fields named after the date they were replaced
+		let fields = self
+			.revisions
+			.iter()
+			.rev()
+			.map(|revision| Field {
+				name: revision.replaced_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+				value: revision.value.clone(),
+				hidden: self.hidden,
+				..Default::default()
+			})
+			.collect::<Vec<_>>();
+		let items = fields
+			.iter()
+			.enumerate()
+			.map(|(id, field)| {
+				EntryEditor::field_preview(
+					body.width,
+					Some(field),
+					id == self.selected,
+					None,
+					false,
+					id,
+				)
+			})
+			.collect::<Vec<_>>();
+		frame.render_widget(List::new(items), body);
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
@@ -0,0 +1,110 @@
+use std::cell::Cell;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Width of the keys column
+const KEYS_WIDTH: usize = 18;
+
+/// Full-screen list of the key bindings of every screen
+#[derive(Default)]
+pub struct HelpView {
+	scroll: u16,
+	/// Number of lines that can be scrolled, updated on render
+	max_scroll: Cell<u16>,
+}
+
+impl HelpView {
+	fn text() -> Text<'static> {
+		let mut lines = vec![];
+		for keymap in keymap::KEYMAPS {
+			if !lines.is_empty() {
+				lines.push(Line::default());
+			}
+			lines.push(Line::from(keymap.screen.bold().fg(theme().accent)));
+			lines.extend(keymap.bindings.iter().map(|binding| {
+				Line::from(vec![
+					format!("  {:<KEYS_WIDTH$}", binding.keys)
+						.bold()
+						.fg(theme().success),
+					binding.action.fg(theme().text),
+				])
+			}));
+		}
+		Text::from(lines)
+	}
+}
+
+impl Component for HelpView {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+			KeyCode::Down | KeyCode::Char('j') => {
+				self.scroll = (self.scroll + 1).min(self.max_scroll.get())
+			}
+			KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(16),
+			KeyCode::PageDown => self.scroll = (self.scroll + 16).min(self.max_scroll.get()),
+			KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+			KeyCode::End | KeyCode::Char('G') => self.scroll = self.max_scroll.get(),
+			KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') | KeyCode::F(1) => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Help")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::HELP);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		let text = Self::text();
+		self.max_scroll
+			.set((text.height() as u16).saturating_sub(body.height));
+		frame.render_widget(
+			Paragraph::new(text).scroll((self.scroll.min(self.max_scroll.get()), 0)),
+			body,
+		);
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
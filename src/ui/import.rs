@@ -0,0 +1,430 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::entry::Entry;
+use crate::data::import::csv;
+use crate::data::import::import;
+use crate::data::import::ImportFormat;
+use crate::icons;
+use crate::keymap;
+use crate::plugin::PLUGINS;
+use crate::plugin::PLUGIN_IMPORTERS;
+use crate::style::theme;
+use crate::ui::csv_mapping::CsvMappingForm;
+use crate::ui::password::PasswordFormatter;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::text_input_custom::CustomTextInput;
+use crate::widgets::text_input_custom::CustomTextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static IMPORT_FORMAT: LazyLock<Vec<ComboItem>> = LazyLock::new(|| {
+	let mut formats = vec![
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::LOCKED),
+			value: ImportFormat::ProtonPass.name().into(),
+		},
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::JSON),
+			value: ImportFormat::JsonMapping.name().into(),
+		},
+		ComboItem {
+			kind: "CSV".into(),
+			icon: format!("{} ", icons::CSV),
+			value: ImportFormat::Csv.name().into(),
+		},
+		ComboItem {
+			kind: "CSV".into(),
+			icon: format!("{} ", icons::LOCKED),
+			value: ImportFormat::LastPass.name().into(),
+		},
+		ComboItem {
+			kind: "CSV".into(),
+			icon: " ".into(),
+			value: ImportFormat::Chrome.name().into(),
+		},
+		ComboItem {
+			kind: "CSV".into(),
+			icon: format!("{} ", icons::BROWSER),
+			value: ImportFormat::Firefox.name().into(),
+		},
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::AUTHENTICATOR),
+			value: ImportFormat::Aegis.name().into(),
+		},
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::AUTHENTICATOR),
+			value: ImportFormat::AndOtp.name().into(),
+		},
+		ComboItem {
+			kind: "JSON".into(),
+			icon: format!("{} ", icons::BITWARDEN),
+			value: ImportFormat::Bitwarden.name().into(),
+		},
+		ComboItem {
+			kind: "KDBX".into(),
+			icon: format!("{} ", icons::KEY),
+			value: ImportFormat::Kdbx.name().into(),
+		},
+	];
+	formats.extend(PLUGIN_IMPORTERS.iter().map(|(plugin, format)| ComboItem {
+		kind: PLUGINS[*plugin].description.name.clone(),
+		icon: format!("{} ", icons::PLUGIN),
+		value: format.to_string(),
+	}));
+	formats
+});
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static PASSWORD_INPUT_STYLE: LazyLock<CustomTextInputStyle> =
+	LazyLock::new(|| CustomTextInputStyle {
+		padding: [0, 0],
+		markers: ["".into(), "".into()],
+		style: Some(Style::default().fg(theme().text)),
+		style_selected: None,
+	});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+pub struct ImportForm {
+	style: FormStyle,
+
+	format: Option<ImportFormat>,
+	field_format: Labeled<'static, ComboBox<'static, 'static>>,
+	field_path: Labeled<'static, TextInput<'static>>,
+	field_mapping: Option<Labeled<'static, TextInput<'static>>>,
+	field_password: Option<Labeled<'static, CustomTextInput<'static, PasswordFormatter>>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	popup: Option<Popup<'static>>,
+	/// Column mapping for CSV files
+	csv: Option<CsvMappingForm>,
+	imported: Vec<Entry>,
+}
+
+impl Default for ImportForm {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl ImportForm {
+	pub fn new() -> Self {
+		let format = ImportFormat::default();
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			format: Some(format),
+			field_format: Labeled::new(
+				"Format".into(),
+				ComboBox::new(IMPORT_FORMAT.as_slice())
+					.style(&COMBOBOX_STYLE)
+					.with_input(format.name().into()),
+			)
+			.style(&LABEL_STYLE),
+			field_path: Labeled::new("File".into(), TextInput::new().style(&TEXTINPUT_STYLE))
+				.style(&LABEL_STYLE),
+			field_mapping: None,
+			field_password: None,
+			selected: Some(1),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+			csv: None,
+			imported: vec![],
+		}
+	}
+
+	/// Take the imported entries
+	pub fn submit(&mut self) -> Vec<Entry> {
+		std::mem::take(&mut self.imported)
+	}
+
+	fn set_error(&mut self, message: String) {
+		self.popup = Some(Popup::new(
+			"Import Failed".into(),
+			Paragraph::new(Text::from(message)),
+		));
+	}
+
+	/// Whether the imported entries only hold OTP fields for existing entries
+	pub fn attaches_otp(&self) -> bool {
+		self.format.is_some_and(|format| format.attaches_otp())
+	}
+
+	fn run_import(&mut self) -> Result<Vec<Entry>, String> {
+		let format = self.format.ok_or("Invalid import format".to_string())?;
+		let path = PathBuf::from(self.field_path.inner.submit().trim());
+		let mapping = self
+			.field_mapping
+			.as_ref()
+			.map(|field| PathBuf::from(field.inner.submit().trim()));
+		let password = self
+			.field_password
+			.as_ref()
+			.map(|field| field.inner.submit())
+			.filter(|password| !password.is_empty());
+		import(format, &path, mapping.as_deref(), password.as_deref())
+	}
+}
+
+impl Form for ImportForm {
+	fn component_count(&self) -> usize {
+		2 + self.field_mapping.is_some() as usize + self.field_password.is_some() as usize
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_format),
+			1 => Some(&self.field_path),
+			2 => {
+				if let Some(field) = &self.field_mapping {
+					Some(field)
+				} else if let Some(field) = &self.field_password {
+					Some(field)
+				} else {
+					None
+				}
+			}
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_format),
+			1 => Some(&mut self.field_path),
+			2 => {
+				if let Some(field) = &mut self.field_mapping {
+					Some(field)
+				} else if let Some(field) = &mut self.field_password {
+					Some(field)
+				} else {
+					None
+				}
+			}
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if let Some(form) = &mut self.csv {
+			match form.input_form(key) {
+				Some(FormSignal::Return) => {
+					self.imported = form.submit();
+					self.csv = None;
+					return Some(FormSignal::Return);
+				}
+				Some(FormSignal::Exit) => self.csv = None,
+				_ => {}
+			}
+			return None;
+		}
+
+		// Dispatch input to components
+		if FormExt::input(self, key) {
+			// Update format
+			if self.selected == Some(0) {
+				self.format = self
+					.field_format
+					.inner
+					.submit()
+					.and_then(|id| ImportFormat::try_from(id).ok());
+				let needs_mapping = self.format.is_some_and(|format| format.needs_mapping());
+				if needs_mapping && self.field_mapping.is_none() {
+					self.field_mapping = Some(
+						Labeled::new(
+							"Mapping File".into(),
+							TextInput::new().style(&TEXTINPUT_STYLE),
+						)
+						.style(&LABEL_STYLE),
+					);
+				} else if !needs_mapping {
+					self.field_mapping = None;
+				}
+				let needs_password = self.format.is_some_and(|format| format.needs_password());
+				if needs_password && self.field_password.is_none() {
+					self.field_password = Some(
+						Labeled::new(
+							"Backup Password (if encrypted)".into(),
+							CustomTextInput::new(PasswordFormatter { hidden: true })
+								.style(&PASSWORD_INPUT_STYLE),
+						)
+						.style(&LABEL_STYLE),
+					);
+				} else if !needs_password {
+					self.field_password = None;
+				}
+			}
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter if self.format == Some(ImportFormat::Csv) => {
+				let path = PathBuf::from(self.field_path.inner.submit().trim());
+				match csv::load(&path) {
+					Ok(rows) => self.csv = Some(CsvMappingForm::new(rows)),
+					Err(err) => self.set_error(err),
+				}
+				None
+			}
+			KeyCode::Enter => match self.run_import() {
+				Ok(entries) => {
+					self.imported = entries;
+					Some(FormSignal::Return)
+				}
+				Err(err) => {
+					self.set_error(err);
+					None
+				}
+			},
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Import")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::IMPORT);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(form) = &self.csv {
+			let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+			let vertical =
+				Layout::vertical([Constraint::Length(form.full_height())]).flex(Flex::Center);
+			let [area] = frame.area().layout(&horizontal);
+			let [area] = area.layout(&vertical);
+			ctx.area = area;
+			form.render_form(frame, ctx);
+		}
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
@@ -1,6 +1,29 @@
+pub mod attachment;
+pub mod audit_log;
+pub mod change_password;
+pub mod csv_mapping;
 pub mod field_editor;
+pub mod field_history;
 pub mod field_generator;
 pub mod explorer;
+pub mod help;
 pub mod entry;
+pub mod entry_history;
+pub mod entry_icon;
 pub mod entry_tag_editor;
+pub mod export;
+pub mod import;
+pub mod new_entry;
 pub mod password;
+pub mod properties;
+pub mod quick_unlock;
+pub mod recent;
+pub mod recovery_codes;
+pub mod screen;
+pub mod share;
+pub mod stats;
+pub mod status_bar;
+pub mod tag_registry;
+pub mod transfer;
+pub mod trash;
+pub mod undo;
@@ -0,0 +1,223 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::Frame;
+
+use crate::data::entry::Entry;
+use crate::data::template::EntryTemplate;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static TEMPLATES: LazyLock<Vec<ComboItem>> = LazyLock::new(|| {
+	EntryTemplate::ALL
+		.iter()
+		.map(|template| ComboItem {
+			kind: format!("{} fields", template.fields().len()),
+			icon: format!("{} ", template.icon().unwrap_or(" ")),
+			value: template.name().into(),
+		})
+		.collect()
+});
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+/// Create an entry, optionally from a template
+pub struct NewEntryForm {
+	style: FormStyle,
+
+	field_name: Labeled<'static, TextInput<'static>>,
+	field_template: Labeled<'static, ComboBox<'static, 'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+}
+
+impl Default for NewEntryForm {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl NewEntryForm {
+	pub fn new() -> Self {
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			field_name: Labeled::new("Name".into(), TextInput::new().style(&TEXTINPUT_STYLE))
+				.style(&LABEL_STYLE),
+			field_template: Labeled::new(
+				"Template".into(),
+				ComboBox::new(TEMPLATES.as_slice())
+					.style(&COMBOBOX_STYLE)
+					.with_input(EntryTemplate::default().name().into()),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+		}
+	}
+
+	/// Create the entry, `None` if the name or template is invalid
+	pub fn submit(&self) -> Option<Entry> {
+		let name = self.field_name.inner.get_input().trim();
+		if name.is_empty() {
+			return None;
+		}
+		let template = self
+			.field_template
+			.inner
+			.submit()
+			.and_then(|id| EntryTemplate::try_from(id).ok())?;
+		Some(template.entry(name.to_string()))
+	}
+}
+
+impl Form for NewEntryForm {
+	fn component_count(&self) -> usize {
+		2
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_name),
+			1 => Some(&self.field_template),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_name),
+			1 => Some(&mut self.field_template),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter if self.submit().is_some() => Some(FormSignal::Return),
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("New Entry")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::NEW_ENTRY);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+	}
+}
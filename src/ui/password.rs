@@ -1,3 +1,5 @@
+use std::cell::Cell;
+use std::path::PathBuf;
 use std::sync::LazyLock;
 
 use crossterm::event::KeyCode;
@@ -7,7 +9,6 @@ use ratatui::layout::Flex;
 use ratatui::layout::HorizontalAlignment;
 use ratatui::layout::Layout;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::text::Span;
 use ratatui::text::Text;
@@ -18,6 +19,7 @@ use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::style::theme;
 use crate::widgets::checkbox::Checkbox;
 use crate::widgets::form::Form;
 use crate::widgets::form::FormExt;
@@ -27,6 +29,8 @@ use crate::widgets::label::LabelDisplay;
 use crate::widgets::label::LabelStyle;
 use crate::widgets::label::Labeled;
 use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
 use crate::widgets::text_input_custom::CustomTextInput;
 use crate::widgets::text_input_custom::CustomTextInputStyle;
 use crate::widgets::text_input_custom::TextFormatter;
@@ -38,43 +42,34 @@ static PASSWORD_LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle
 	display: LabelDisplay::Block {
 		block: Box::new(Block::bordered().border_type(ratatui::widgets::BorderType::Thick)),
 	},
-	style: Some(
-		Style::default()
-			.fg(Color::Black)
-			.bg(Color::from_u32(0x241f31)),
-	),
-	style_selected: Some(
-		Style::default()
-			.fg(Color::Cyan)
-			.bg(Color::from_u32(0x241f31)),
-	),
+	style: Some(Style::default().fg(theme().base).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
 });
 static PASSWORD_INPUT_STYLE: LazyLock<CustomTextInputStyle> =
 	LazyLock::new(|| CustomTextInputStyle {
 		padding: [0, 0],
 		markers: ["".into(), "".into()],
-		style: Some(
-			Style::default()
-				.fg(Color::White)
-				.bg(Color::from_u32(0x241f31)),
-		),
-		style_selected: Some(
-			Style::default()
-				.fg(Color::Cyan)
-				.bg(Color::from_u32(0x241f31)),
-		),
+		style: Some(Style::default().fg(theme().text).bg(theme().selection_bg)),
+		style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
 	});
 
+static KEYFILE_INPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text).bg(theme().selection_bg)),
+	style_selected: Some(Style::default().fg(theme().accent).bg(theme().selection_bg)),
+});
+
 fn block(title: String) -> Block<'static> {
 	Block::bordered()
 		.title(title)
 		.title_alignment(HorizontalAlignment::Center)
 		.border_type(BorderType::QuadrantOutside)
-		.border_style(Style::default().fg(Color::from_u32(0x7f7f7f)))
+		.border_style(Style::default().fg(theme().muted))
 }
 
-struct PasswordFormatter {
-	hidden: bool,
+pub struct PasswordFormatter {
+	pub hidden: bool,
 }
 
 impl<'s> TextFormatter<'s> for PasswordFormatter {
@@ -100,20 +95,26 @@ pub struct PasswordPrompt {
 	selected: usize,
 	input: Labeled<'static, CustomTextInput<'static, PasswordFormatter>>,
 	hidden: Checkbox<'static>,
+	key_file: Labeled<'static, TextInput<'static>>,
 
 	popup: Option<Popup<'static>>,
 	block: Block<'static>,
+	body_area: Cell<Rect>,
 	password: Option<String>,
 	has_confirmation: bool,
 }
 
 impl PasswordPrompt {
 	pub fn new(db_name: String, new_password: bool) -> Self {
-		let title = format!("Password for '{}'", db_name);
+		// Missing databases are created, which must stand out in case of a typo in the path
+		let title = match new_password {
+			true => format!("Password for new database '{}'", db_name),
+			false => format!("Password for '{}'", db_name),
+		};
 		Self {
 			style: FormStyle {
 				border: true,
-				bg: Color::from_u32(0x1f1f1f),
+				bg: theme().prompt_bg,
 			},
 			db_name,
 			new_password,
@@ -125,8 +126,14 @@ impl PasswordPrompt {
 			)
 			.style(&PASSWORD_LABEL_STYLE),
 			hidden: Checkbox::new(true, "Hidden".into()),
+			key_file: Labeled::new(
+				"Key File (optional)".into(),
+				TextInput::new().style(&KEYFILE_INPUT_STYLE),
+			)
+			.style(&PASSWORD_LABEL_STYLE),
 			popup: None,
 			block: block(title),
+			body_area: Cell::default(),
 			password: None,
 			has_confirmation: !new_password,
 		}
@@ -139,6 +146,12 @@ impl PasswordPrompt {
 		self.password.clone()
 	}
 
+	/// Path to the key file, if any
+	pub fn key_file(&self) -> Option<PathBuf> {
+		let path = self.key_file.inner.get_input().trim();
+		(!path.is_empty()).then(|| PathBuf::from(path))
+	}
+
 	pub fn is_new(&self) -> bool {
 		self.new_password
 	}
@@ -150,13 +163,14 @@ impl PasswordPrompt {
 
 impl Form for PasswordPrompt {
 	fn component_count(&self) -> usize {
-		2
+		3
 	}
 
 	fn component(&self, index: usize) -> Option<&dyn Component> {
 		match index {
 			0 => Some(&self.input),
 			1 => Some(&self.hidden),
+			2 => Some(&self.key_file),
 			_ => None,
 		}
 	}
@@ -165,6 +179,7 @@ impl Form for PasswordPrompt {
 		match index {
 			0 => Some(&mut self.input),
 			1 => Some(&mut self.hidden),
+			2 => Some(&mut self.key_file),
 			_ => None,
 		}
 	}
@@ -187,6 +202,10 @@ impl Form for PasswordPrompt {
 
 	fn set_scroll(&self, _scroll: u16) {}
 
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
 	fn input_form(&mut self, key: &KeyEvent) -> Option<crate::widgets::form::FormSignal> {
 		if let Some(popup) = &mut self.popup {
 			if popup.input(key) {
@@ -225,7 +244,7 @@ impl Form for PasswordPrompt {
 							Paragraph::new(Text::from("Passwords do not match!")),
 						));
 						self.password = None;
-						self.block = block(format!("Password for '{}'", self.db_name));
+						self.block = block(format!("Password for new database '{}'", self.db_name));
 						self.input.inner.set_input(String::default());
 					} else {
 						self.has_confirmation = true;
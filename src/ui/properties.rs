@@ -0,0 +1,210 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::Frame;
+
+use crate::data::database::DatabaseMetadata;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+
+/// Edit the metadata stored in the database header
+pub struct DatabaseProperties {
+	style: FormStyle,
+	metadata: DatabaseMetadata,
+
+	name: Labeled<'static, TextInput<'static>>,
+	description: Labeled<'static, TextInput<'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+}
+
+impl DatabaseProperties {
+	pub fn new(metadata: DatabaseMetadata) -> Self {
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			name: Labeled::new(
+				"Name".into(),
+				TextInput::new()
+					.style(&TEXTINPUT_STYLE)
+					.with_input(metadata.name.clone()),
+			)
+			.style(&LABEL_STYLE),
+			description: Labeled::new(
+				"Description".into(),
+				TextInput::new()
+					.style(&TEXTINPUT_STYLE)
+					.with_input(metadata.description.clone()),
+			)
+			.style(&LABEL_STYLE),
+			metadata,
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+		}
+	}
+
+	/// Edited metadata
+	pub fn submit(&self) -> DatabaseMetadata {
+		DatabaseMetadata {
+			name: self.name.inner.get_input().trim().to_string(),
+			description: self.description.inner.get_input().trim().to_string(),
+			..self.metadata.clone()
+		}
+	}
+
+	/// Height of the form and timestamps
+	pub fn full_height(&self) -> u16 {
+		self.height() + 2
+	}
+}
+
+impl Form for DatabaseProperties {
+	fn component_count(&self) -> usize {
+		2
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.name),
+			1 => Some(&self.description),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.name),
+			1 => Some(&mut self.description),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => Some(FormSignal::Return),
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Database Properties")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::PROPERTIES);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+		let timestamps = Line::from(vec![
+			"Created ".fg(theme().text),
+			self.metadata
+				.created_at
+				.format("%Y-%m-%d %H:%M")
+				.to_string()
+				.fg(theme().muted),
+			"  Modified ".fg(theme().text),
+			self.metadata
+				.modified_at
+				.format("%Y-%m-%d %H:%M")
+				.to_string()
+				.fg(theme().muted),
+		]);
+		frame.render_widget(
+			timestamps,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 2,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 3;
+		ctx.area.height = ctx.area.height.saturating_sub(4);
+		self.render_body(frame, ctx);
+	}
+}
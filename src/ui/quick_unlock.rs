@@ -0,0 +1,330 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::quick_unlock::MIN_PIN_LENGTH;
+use crate::keymap;
+use crate::style::theme;
+use crate::ui::password::PasswordFormatter;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input_custom::CustomTextInput;
+use crate::widgets::text_input_custom::CustomTextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static PASSWORD_INPUT_STYLE: LazyLock<CustomTextInputStyle> =
+	LazyLock::new(|| CustomTextInputStyle {
+		padding: [0, 0],
+		markers: ["".into(), "".into()],
+		style: Some(Style::default().fg(theme().text)),
+		style_selected: None,
+	});
+
+type PinInput = Labeled<'static, CustomTextInput<'static, PasswordFormatter>>;
+
+fn pin_input(label: &'static str) -> PinInput {
+	Labeled::new(
+		label.into(),
+		CustomTextInput::new(PasswordFormatter { hidden: true }).style(&PASSWORD_INPUT_STYLE),
+	)
+	.style(&LABEL_STYLE)
+}
+
+fn render_frame(frame: &mut Frame, area: Rect, title: &str, help: Line<'static>, bg: Color) {
+	let border = Block::bordered()
+		.border_set(QUADRANT_OUTSIDE)
+		.title(title)
+		.title_style(Style::default().fg(theme().text))
+		.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+		.bg(bg)
+		.fg(theme().form_border);
+	frame.render_widget(Clear, area);
+	frame.render_widget(border, area);
+	frame.render_widget(
+		help,
+		Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width.saturating_sub(2),
+			height: 1,
+		},
+	);
+}
+
+/// Form to enable quick unlock by choosing a PIN
+pub struct QuickUnlockSetup {
+	style: FormStyle,
+
+	pin: PinInput,
+	confirm: PinInput,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+	popup: Option<Popup<'static>>,
+}
+
+impl Default for QuickUnlockSetup {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl QuickUnlockSetup {
+	pub fn new() -> Self {
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			pin: pin_input("PIN"),
+			confirm: pin_input("Confirm PIN"),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	pub fn submit(&self) -> String {
+		self.pin.inner.submit()
+	}
+
+	pub fn set_error(&mut self, title: String, message: String) {
+		self.popup = Some(Popup::new(title, Paragraph::new(Text::from(message))));
+	}
+}
+
+impl Form for QuickUnlockSetup {
+	fn component_count(&self) -> usize {
+		2
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.pin),
+			1 => Some(&self.confirm),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.pin),
+			1 => Some(&mut self.confirm),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => {
+				if self.pin.inner.get_input().chars().count() < MIN_PIN_LENGTH {
+					self.set_error(
+						"Invalid PIN".into(),
+						format!("PIN must be at least {MIN_PIN_LENGTH} characters long"),
+					);
+					None
+				} else if self.pin.inner.get_input() != self.confirm.inner.get_input() {
+					self.set_error("Invalid PIN".into(), "PINs do not match!".into());
+					None
+				} else {
+					Some(FormSignal::Return)
+				}
+			}
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let help = keymap::help_line(&keymap::QUICK_UNLOCK_SETUP);
+		render_frame(frame, ctx.area, "Enable Quick Unlock", help, self.style.bg);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
+
+/// Prompt for the PIN of a locked session
+pub struct PinPrompt {
+	style: FormStyle,
+	db_name: String,
+
+	pin: PinInput,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+	popup: Option<Popup<'static>>,
+}
+
+impl PinPrompt {
+	pub fn new(db_name: String) -> Self {
+		Self {
+			style: FormStyle {
+				bg: theme().prompt_bg,
+				border: true,
+			},
+			db_name,
+			pin: pin_input("PIN"),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	pub fn submit(&self) -> String {
+		self.pin.inner.submit()
+	}
+
+	pub fn set_error(&mut self, title: String, message: String) {
+		self.pin.inner.set_input(String::default());
+		self.popup = Some(Popup::new(title, Paragraph::new(Text::from(message))));
+	}
+}
+
+impl Form for PinPrompt {
+	fn component_count(&self) -> usize {
+		1
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		(index == 0).then_some(&self.pin as &dyn Component)
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		(index == 0).then_some(&mut self.pin as &mut dyn Component)
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter if !self.pin.inner.get_input().is_empty() => Some(FormSignal::Return),
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let help = keymap::help_line(&keymap::PIN_PROMPT);
+		let title = format!("'{}' is locked", self.db_name);
+		render_frame(frame, ctx.area, &title, help, self.style.bg);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::widgets::ListState;
+use ratatui::Frame;
+
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Selection of a recently opened database, shown on startup when no database is given
+pub struct RecentView {
+	/// Databases, most recent first
+	paths: Vec<PathBuf>,
+	selected: usize,
+	/// Whether the selected database was chosen
+	chosen: bool,
+	list_state: RefCell<ListState>,
+}
+
+impl RecentView {
+	pub fn new(paths: Vec<PathBuf>) -> Self {
+		Self {
+			paths,
+			selected: 0,
+			chosen: false,
+			list_state: RefCell::default(),
+		}
+	}
+
+	fn move_selected(&mut self, offset: isize) {
+		self.selected = self
+			.selected
+			.saturating_add_signed(offset)
+			.min(self.paths.len().saturating_sub(1));
+	}
+
+	/// Chosen database, `None` if the selection was cancelled
+	pub fn submit(&self) -> Option<&Path> {
+		self.chosen
+			.then(|| self.paths.get(self.selected))
+			.flatten()
+			.map(PathBuf::as_path)
+	}
+}
+
+impl Component for RecentView {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.move_selected(-1),
+			KeyCode::Down | KeyCode::Char('j') => self.move_selected(1),
+			KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+			KeyCode::End | KeyCode::Char('G') => self.move_selected(isize::MAX),
+			KeyCode::Char(c @ '1'..='9') => {
+				let index = c as usize - '1' as usize;
+				if index < self.paths.len() {
+					self.selected = index;
+					self.chosen = true;
+					return false;
+				}
+			}
+			KeyCode::Enter => {
+				self.chosen = true;
+				return false;
+			}
+			KeyCode::Esc | KeyCode::Char('q') => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical =
+			Layout::vertical([Constraint::Length(self.paths.len() as u16 + 3)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Recent Databases")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::RECENT);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		let paths = self
+			.paths
+			.iter()
+			.enumerate()
+			.map(|(id, path)| {
+				let name = path
+					.file_name()
+					.map(|name| name.to_string_lossy().into_owned())
+					.unwrap_or_default();
+				let dir = path
+					.parent()
+					.map(|dir| dir.display().to_string())
+					.unwrap_or_default();
+				let item = ListItem::new(Line::from(vec![
+					format!(" {} ", id + 1).fg(theme().muted),
+					format!("{} ", icons::DATABASE).fg(theme().accent),
+					name.bold().fg(theme().success),
+					"  ".into(),
+					dir.italic().fg(theme().faint),
+				]));
+				if id == self.selected {
+					item.bg(theme().entry_bg[2])
+				} else {
+					item.bg(theme().entry_bg[id % 2])
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut list_state = self.list_state.borrow_mut();
+		list_state.select(Some(self.selected));
+		frame.render_stateful_widget(List::new(paths), body, &mut *list_state);
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
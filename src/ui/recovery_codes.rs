@@ -0,0 +1,181 @@
+use chrono::Utc;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::widgets::ListState;
+use ratatui::Frame;
+
+use crate::clipboard;
+use crate::data::field::TwoFACode;
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// List the 2FA recovery codes of a field and track which were used
+pub struct RecoveryCodesView {
+	name: String,
+	codes: Vec<TwoFACode>,
+	selected: usize,
+	/// Set when a code was marked as used or unused
+	modified: bool,
+	/// Index of the last code copied to the clipboard
+	copied: Option<usize>,
+}
+
+impl RecoveryCodesView {
+	pub fn new(name: String, codes: Vec<TwoFACode>) -> Self {
+		// Start on the first unused code
+		let selected = codes.iter().position(|code| !code.is_used()).unwrap_or(0);
+		Self {
+			name,
+			codes,
+			selected,
+			modified: false,
+			copied: None,
+		}
+	}
+
+	/// Updated codes, if any was marked as used or unused
+	pub fn submit(&self) -> Option<&[TwoFACode]> {
+		self.modified.then_some(self.codes.as_slice())
+	}
+
+	/// Whether a code was copied to the clipboard
+	pub fn copied(&self) -> bool {
+		self.copied.is_some()
+	}
+
+	fn toggle_used(&mut self) {
+		let Some(code) = self.codes.get_mut(self.selected) else {
+			return;
+		};
+		code.expired = match code.expired {
+			Some(_) => None,
+			None => Some(Utc::now()),
+		};
+		self.modified = true;
+	}
+
+	fn copy_selected(&mut self) {
+		let Some(code) = self.codes.get(self.selected) else {
+			return;
+		};
+		if code.is_used() {
+			return;
+		}
+		clipboard::copy(code.value.clone()).unwrap();
+		self.copied = Some(self.selected);
+	}
+}
+
+impl Component for RecoveryCodesView {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+			KeyCode::Down | KeyCode::Char('j') => {
+				self.selected = (self.selected + 1).min(self.codes.len().saturating_sub(1))
+			}
+			KeyCode::Char(' ') | KeyCode::Char('u') => self.toggle_used(),
+			KeyCode::Char('y') => self.copy_selected(),
+			KeyCode::Esc | KeyCode::Char('q') => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let unused = self.codes.iter().filter(|code| !code.is_used()).count();
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(format!(
+				"Recovery Codes: {} ({unused}/{} unused)",
+				self.name,
+				self.codes.len()
+			))
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::RECOVERY_CODES);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		if self.codes.is_empty() {
+			frame.render_widget(
+				Line::from("No recovery codes".italic().fg(theme().dim)),
+				body,
+			);
+			return;
+		}
+		let items = self
+			.codes
+			.iter()
+			.enumerate()
+			.map(|(id, code)| {
+				let line = match code.expired {
+					Some(expired) => Line::from(vec![
+						format!(" {} ", icons::CHECKED).fg(theme().dim),
+						code.value.as_str().crossed_out().fg(theme().dim),
+						format!("  used {}", expired.format("%Y-%m-%d %H:%M"))
+							.italic()
+							.fg(theme().muted),
+					]),
+					None => Line::from(vec![
+						format!(" {} ", icons::UNCHECKED).fg(theme().text),
+						code.value.as_str().bold().fg(theme().special),
+						if self.copied == Some(id) {
+							format!(" {}", icons::COPIED).fg(theme().error)
+						} else {
+							"".into()
+						},
+					]),
+				};
+				let item = ListItem::new(line);
+				if id == self.selected {
+					item.bg(theme().entry_bg[2])
+				} else {
+					item.bg(theme().entry_bg[id % 2])
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut state = ListState::default().with_selected(Some(self.selected));
+		frame.render_stateful_widget(List::new(items), body, &mut state);
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
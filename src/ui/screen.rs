@@ -0,0 +1,207 @@
+use crossterm::event::KeyEvent;
+use crossterm::event::MouseEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::Frame;
+
+use crate::ui::change_password::ChangePassword;
+use crate::ui::export::ExportForm;
+use crate::ui::properties::DatabaseProperties;
+use crate::ui::quick_unlock::QuickUnlockSetup;
+use crate::ui::stats::StatsView;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Overlay that takes the input while it is on top of a [`ScreenStack`]
+pub trait Screen {
+	/// Handle a key sent to the screen
+	fn input(&mut self, key: &KeyEvent) -> ScreenSignal;
+
+	/// Handle a mouse event, returns the keys it translates to
+	fn mouse_input(&mut self, _event: &MouseEvent) -> Vec<KeyEvent> {
+		vec![]
+	}
+
+	/// Handle pasted text, returns whether it was used
+	fn paste(&mut self, _text: &str) -> bool {
+		false
+	}
+
+	/// Update the screen at each tick of the event loop
+	fn tick(&mut self) {}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx);
+}
+
+/// Screen opened over the explorer
+// Screens are stored on the heap by their stack, so the size of the variants matters little
+#[allow(clippy::large_enum_variant)]
+pub enum AppScreen {
+	ChangePassword(ChangePassword),
+	QuickUnlockSetup(QuickUnlockSetup),
+	Export(ExportForm),
+	Properties(DatabaseProperties),
+	Stats(StatsView),
+}
+
+/// Result of a key sent to a screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenSignal {
+	/// The screen stays open
+	Handled,
+	/// The screen was closed
+	Closed,
+	/// The form of the screen was submitted, the screen stays open until its owner pops it
+	Submitted,
+}
+
+/// Screen signal of a form signal
+pub fn form_signal(signal: Option<FormSignal>) -> ScreenSignal {
+	match signal {
+		Some(FormSignal::Return) => ScreenSignal::Submitted,
+		Some(FormSignal::Exit) => ScreenSignal::Closed,
+		_ => ScreenSignal::Handled,
+	}
+}
+
+impl Screen for AppScreen {
+	fn input(&mut self, key: &KeyEvent) -> ScreenSignal {
+		let signal = match self {
+			AppScreen::ChangePassword(form) => form.input_form(key),
+			AppScreen::QuickUnlockSetup(form) => form.input_form(key),
+			AppScreen::Export(form) => form.input_form(key),
+			AppScreen::Properties(form) => form.input_form(key),
+			AppScreen::Stats(view) => {
+				return if view.input(key) {
+					ScreenSignal::Handled
+				} else {
+					ScreenSignal::Closed
+				};
+			}
+		};
+		form_signal(signal)
+	}
+
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		match self {
+			AppScreen::ChangePassword(form) => FormExt::mouse_input(form, event),
+			AppScreen::QuickUnlockSetup(form) => FormExt::mouse_input(form, event),
+			AppScreen::Export(form) => FormExt::mouse_input(form, event),
+			AppScreen::Properties(form) => FormExt::mouse_input(form, event),
+			AppScreen::Stats(_) => vec![],
+		}
+	}
+
+	fn paste(&mut self, text: &str) -> bool {
+		match self {
+			AppScreen::ChangePassword(form) => FormExt::paste(form, text),
+			AppScreen::QuickUnlockSetup(form) => FormExt::paste(form, text),
+			AppScreen::Export(form) => FormExt::paste(form, text),
+			AppScreen::Properties(form) => FormExt::paste(form, text),
+			AppScreen::Stats(_) => false,
+		}
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		match self {
+			AppScreen::ChangePassword(form) => render_centered(form, form.height() + 1, frame, ctx),
+			AppScreen::QuickUnlockSetup(form) => {
+				render_centered(form, form.height() + 1, frame, ctx)
+			}
+			AppScreen::Export(form) => render_centered(form, form.height() + 1, frame, ctx),
+			AppScreen::Properties(form) => render_centered(form, form.full_height(), frame, ctx),
+			AppScreen::Stats(view) => view.render(frame, ctx),
+		}
+	}
+}
+
+/// Render `form` in the middle of the area of `ctx`
+pub fn render_centered(
+	form: &impl Form,
+	height: u16,
+	frame: &mut Frame,
+	ctx: &mut ComponentRenderCtx,
+) {
+	let full = ctx.area;
+	let horizontal = Layout::horizontal([Constraint::Percentage(40)]).flex(Flex::Center);
+	let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+	let [area] = full.layout(&horizontal);
+	let [area] = area.layout(&vertical);
+	ctx.area = area;
+	ctx.selected = true;
+	form.render_form(frame, ctx);
+	ctx.area = full;
+}
+
+/// Stack of opened screens, the top-most one receives the input
+pub struct ScreenStack<S> {
+	screens: Vec<S>,
+}
+
+impl<S> Default for ScreenStack<S> {
+	fn default() -> Self {
+		Self { screens: vec![] }
+	}
+}
+
+impl<S: Screen> ScreenStack<S> {
+	pub fn push(&mut self, screen: S) {
+		self.screens.push(screen);
+	}
+
+	pub fn pop(&mut self) -> Option<S> {
+		self.screens.pop()
+	}
+
+	/// Close every screen
+	pub fn clear(&mut self) {
+		self.screens.clear();
+	}
+
+	pub fn top(&self) -> Option<&S> {
+		self.screens.last()
+	}
+
+	pub fn top_mut(&mut self) -> Option<&mut S> {
+		self.screens.last_mut()
+	}
+
+	/// Send a key to the top-most screen, `None` when no screen is open
+	///
+	/// The screen is popped when it is closed.
+	pub fn input(&mut self, key: &KeyEvent) -> Option<ScreenSignal> {
+		let signal = self.screens.last_mut()?.input(key);
+		if signal == ScreenSignal::Closed {
+			self.screens.pop();
+		}
+		Some(signal)
+	}
+
+	/// Send a mouse event to the top-most screen, `None` when no screen is open
+	pub fn mouse_input(&mut self, event: &MouseEvent) -> Option<Vec<KeyEvent>> {
+		Some(self.screens.last_mut()?.mouse_input(event))
+	}
+
+	/// Send pasted text to the top-most screen, `None` when no screen is open
+	pub fn paste(&mut self, text: &str) -> Option<bool> {
+		Some(self.screens.last_mut()?.paste(text))
+	}
+
+	/// Tick every screen, including the ones under the top-most one
+	pub fn tick(&mut self) {
+		for screen in &mut self.screens {
+			screen.tick();
+		}
+	}
+
+	/// Render the screens from the bottom one up, each over the previous ones
+	pub fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		for screen in &self.screens {
+			screen.render(frame, ctx);
+		}
+	}
+}
@@ -0,0 +1,304 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
+use crate::ui::field_generator::generate;
+use crate::ui::field_generator::CharsetKind;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+/// Value of the selection item for the entries matching the current filter
+const FILTERED: &str = "Filtered entries";
+/// Value of the selection item for the entries marked in the explorer
+const MARKED: &str = "Marked entries";
+
+/// Random one-time passphrase
+fn passphrase() -> String {
+	let charset = CharsetKind::Alphanum.chars().unwrap_or_default();
+	(0..4)
+		.filter_map(|_| generate(&charset, 5))
+		.collect::<Vec<_>>()
+		.join("-")
+}
+
+/// Entries to share
+pub enum ShareSelection {
+	/// Entries matching the explorer filter
+	Filtered,
+	/// Entries with a tag
+	Tag(String),
+	/// Entries marked in the explorer
+	Marked,
+}
+
+pub struct ShareRequest {
+	pub selection: ShareSelection,
+	pub path: PathBuf,
+	pub passphrase: String,
+}
+
+/// Export a subset of the entries to a database encrypted with a one-time passphrase
+pub struct ShareForm {
+	style: FormStyle,
+	/// Whether entries are marked, offered after the filtered entries
+	marked: bool,
+
+	field_selection: Labeled<'static, ComboBox<'static, 'static>>,
+	field_path: Labeled<'static, TextInput<'static>>,
+	field_passphrase: Labeled<'static, TextInput<'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	popup: Option<Popup<'static>>,
+}
+
+impl ShareForm {
+	/// Create the form, `tags` are the tags that can be selected and `marked` the number of
+	/// entries marked in the explorer
+	pub fn new(tags: Vec<String>, marked: usize) -> Self {
+		let mut items = vec![ComboItem {
+			kind: String::default(),
+			icon: format!("{} ", icons::FILTER),
+			value: FILTERED.into(),
+		}];
+		if marked != 0 {
+			items.push(ComboItem {
+				kind: format!("{marked} entries"),
+				icon: format!("{} ", icons::MARKED),
+				value: MARKED.into(),
+			});
+		}
+		items.extend(tags.into_iter().map(|tag| ComboItem {
+			kind: "Tag".into(),
+			icon: format!("{} ", icons::TAG),
+			value: tag,
+		}));
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			marked: marked != 0,
+			field_selection: Labeled::new(
+				"Entries".into(),
+				ComboBox::from_items(items)
+					.style(&COMBOBOX_STYLE)
+					.with_input(FILTERED.into()),
+			)
+			.style(&LABEL_STYLE),
+			field_path: Labeled::new("File".into(), TextInput::new().style(&TEXTINPUT_STYLE))
+				.style(&LABEL_STYLE),
+			field_passphrase: Labeled::new(
+				"Passphrase".into(),
+				TextInput::new()
+					.style(&TEXTINPUT_STYLE)
+					.with_input(passphrase()),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(1),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	pub fn submit(&self) -> Option<ShareRequest> {
+		let selection = match self.field_selection.inner.submit()? {
+			0 => ShareSelection::Filtered,
+			1 if self.marked => ShareSelection::Marked,
+			_ => ShareSelection::Tag(self.field_selection.inner.get_input().clone()),
+		};
+		Some(ShareRequest {
+			selection,
+			path: PathBuf::from(self.field_path.inner.submit().trim()),
+			passphrase: self.field_passphrase.inner.submit(),
+		})
+	}
+
+	pub fn set_error(&mut self, message: String) {
+		self.popup = Some(Popup::new(
+			"Share Failed".into(),
+			Paragraph::new(Text::from(message)),
+		));
+	}
+}
+
+impl Form for ShareForm {
+	fn component_count(&self) -> usize {
+		3
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_selection),
+			1 => Some(&self.field_path),
+			2 => Some(&self.field_passphrase),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_selection),
+			1 => Some(&mut self.field_path),
+			2 => Some(&mut self.field_passphrase),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => {
+				if self.field_selection.inner.submit().is_none() {
+					self.set_error("Unknown tag".into());
+				} else if self.field_path.inner.get_input().trim().is_empty() {
+					self.set_error("No file given".into());
+				} else if self.field_passphrase.inner.get_input().is_empty() {
+					self.set_error("Passphrase is empty".into());
+				} else {
+					return Some(FormSignal::Return);
+				}
+				None
+			}
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Share Entries")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::SHARE);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
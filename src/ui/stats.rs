@@ -0,0 +1,185 @@
+use std::cell::Cell;
+
+use chrono::Utc;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::attachment::format_size;
+use crate::data::stats::Stats;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Width of the histogram bars
+const BAR_WIDTH: usize = 20;
+
+/// Read-only view of the database statistics
+pub struct StatsView {
+	stats: Stats,
+	scroll: u16,
+	/// Number of lines that can be scrolled, updated on render
+	max_scroll: Cell<u16>,
+}
+
+fn section(title: &str) -> Line<'static> {
+	Line::from(title.to_string().bold().fg(theme().accent))
+}
+
+fn row(label: String, value: String) -> Line<'static> {
+	Line::from(vec![
+		format!("  {label:<24}").fg(theme().text),
+		value.fg(theme().highlight),
+	])
+}
+
+/// Row with a bar proportional to `count / max`
+fn bar(label: &str, count: usize, max: usize) -> Line<'static> {
+	let width = (count * BAR_WIDTH).div_ceil(max.max(1));
+	Line::from(vec![
+		format!("  {label:<24}").fg(theme().text),
+		Span::from("█".repeat(width)).fg(theme().success),
+		format!(" {count}").fg(theme().highlight),
+	])
+}
+
+impl StatsView {
+	pub fn new(stats: Stats) -> Self {
+		Self {
+			stats,
+			scroll: 0,
+			max_scroll: Cell::default(),
+		}
+	}
+
+	fn text(&self) -> Text<'static> {
+		let stats = &self.stats;
+		let mut lines = vec![
+			section("Database"),
+			row("Entries".into(), stats.entries.to_string()),
+			row("Fields".into(), stats.fields.to_string()),
+			row("Attachments".into(), format_size(stats.attachments_size)),
+			row(
+				"File size".into(),
+				stats
+					.database_size
+					.map(format_size)
+					.unwrap_or_else(|| "not saved".into()),
+			),
+			Line::default(),
+			section("Fields by kind"),
+		];
+		let max = stats.kinds.iter().map(|(_, count)| *count).max();
+		lines.extend(
+			stats
+				.kinds
+				.iter()
+				.map(|(kind, count)| bar(kind, *count, max.unwrap_or(0))),
+		);
+
+		lines.push(Line::default());
+		lines.push(section("Tags"));
+		if stats.tags.is_empty() {
+			lines.push(Line::from("  No tags".italic().fg(theme().dim)));
+		}
+		let max = stats.tags.first().map_or(0, |(_, count)| *count);
+		lines.extend(stats.tags.iter().map(|(tag, count)| bar(tag, *count, max)));
+
+		lines.push(Line::default());
+		lines.push(section("Oldest passwords"));
+		if stats.oldest_passwords.is_empty() {
+			lines.push(Line::from("  No hidden fields".italic().fg(theme().dim)));
+		}
+		let now = Utc::now();
+		lines.extend(stats.oldest_passwords.iter().map(|password| {
+			Line::from(vec![
+				"  ".into(),
+				password.entry.clone().bold().fg(theme().accent),
+				" / ".fg(theme().dim),
+				password.field.clone().fg(theme().text),
+				format!(
+					"  {} ({} days)",
+					password.modified_at.format("%Y-%m-%d"),
+					(now - password.modified_at).num_days()
+				)
+				.fg(theme().muted),
+			])
+		}));
+		Text::from(lines)
+	}
+}
+
+impl Component for StatsView {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+			KeyCode::Down | KeyCode::Char('j') => {
+				self.scroll = (self.scroll + 1).min(self.max_scroll.get())
+			}
+			KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+			KeyCode::End | KeyCode::Char('G') => self.scroll = self.max_scroll.get(),
+			KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Statistics")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::STATS);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		let text = self.text();
+		self.max_scroll
+			.set((text.height() as u16).saturating_sub(body.height));
+		frame.render_widget(
+			Paragraph::new(text).scroll((self.scroll.min(self.max_scroll.get()), 0)),
+			body,
+		);
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
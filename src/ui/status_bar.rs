@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use crossterm::event::KeyEvent;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::Frame;
+
+use crate::clipboard;
+use crate::icons;
+use crate::status;
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Remaining time as seconds, or minutes and seconds
+fn format_remaining(remaining: Duration) -> String {
+	let secs = remaining.as_secs();
+	match secs {
+		0..60 => format!("{secs}s"),
+		_ => format!("{}:{:02}", secs / 60, secs % 60),
+	}
+}
+
+/// Line at the bottom of the interface, with the state of the database and the messages posted
+/// through [`status::post`]
+pub struct StatusBar<'s> {
+	pub name: &'s str,
+	/// Changes were not saved to the database yet
+	pub dirty: bool,
+	pub read_only: bool,
+	/// Time left before the session is locked for inactivity
+	pub lock_in: Option<Duration>,
+}
+
+impl Component for StatusBar<'_> {
+	fn input(&mut self, _key: &KeyEvent) -> bool {
+		false
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		frame.render_widget(Block::new().bg(theme().help_bg), area);
+
+		let mut left = vec![format!(" {} ", self.name).bold().fg(theme().accent)];
+		if self.read_only {
+			left.push("read-only ".fg(theme().highlight));
+		} else if self.dirty {
+			left.push(format!("{} unsaved ", icons::UNSAVED).fg(theme().special));
+		}
+		if let Some(message) = status::message() {
+			left.push(Span::from(message).fg(theme().text));
+		}
+		frame.render_widget(Line::from(left), area);
+
+		let mut right = vec![];
+		if let Some(remaining) = clipboard::remaining() {
+			right.push(
+				format!("{} {} ", icons::COPIED, format_remaining(remaining)).fg(theme().highlight),
+			);
+		}
+		if let Some(remaining) = self.lock_in {
+			right.push(
+				format!("{} {} ", icons::LOCKED, format_remaining(remaining)).fg(theme().dim),
+			);
+		}
+		frame.render_widget(Line::from(right).right_aligned(), area);
+	}
+
+	fn height(&self) -> u16 {
+		1
+	}
+}
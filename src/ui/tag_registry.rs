@@ -0,0 +1,279 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::entry::EntryTag;
+use crate::data::tag::TagRegistry;
+use crate::data::tag::DEFAULT_TAG_COLOR;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::popup::Popup;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::text_input::TextInputStyle;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static TEXTINPUT_STYLE: LazyLock<TextInputStyle> = LazyLock::new(|| TextInputStyle {
+	padding: [0, 0],
+	markers: ["".into(), "".into()],
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+/// Edit the icon and color of a tag in the [`TagRegistry`]
+pub struct TagRegistryForm {
+	style: FormStyle,
+	tags: Vec<EntryTag>,
+
+	field_name: Labeled<'static, ComboBox<'static, 'static>>,
+	field_icon: Labeled<'static, TextInput<'static>>,
+	field_color: Labeled<'static, TextInput<'static>>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+
+	popup: Option<Popup<'static>>,
+}
+
+impl TagRegistryForm {
+	pub fn new(registry: &TagRegistry) -> Self {
+		let tags = registry.sorted().into_iter().cloned().collect::<Vec<_>>();
+		let items = tags
+			.iter()
+			.map(|tag| ComboItem {
+				kind: String::default(),
+				icon: format!("{} ", tag.icon.as_deref().unwrap_or(" ")),
+				value: tag.name.clone(),
+			})
+			.collect();
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			tags,
+			field_name: Labeled::new(
+				"Tag".into(),
+				ComboBox::from_items(items).style(&COMBOBOX_STYLE),
+			)
+			.style(&LABEL_STYLE),
+			field_icon: Labeled::new("Icon".into(), TextInput::new().style(&TEXTINPUT_STYLE))
+				.style(&LABEL_STYLE),
+			field_color: Labeled::new(
+				"Color (hex RGB)".into(),
+				TextInput::new().style(&TEXTINPUT_STYLE),
+			)
+			.style(&LABEL_STYLE),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+			popup: None,
+		}
+	}
+
+	/// Get the edited tag definition
+	pub fn submit(&self) -> Result<EntryTag, String> {
+		let name = self.field_name.inner.get_input().trim();
+		if name.is_empty() {
+			return Err("Tag name is empty".into());
+		}
+		if name.contains(',') {
+			return Err("Tag names cannot contain ','".into());
+		}
+		let icon = self.field_icon.inner.get_input().trim();
+		let color = self.field_color.inner.get_input().trim();
+		let color = if color.is_empty() {
+			None
+		} else {
+			let hex = color.trim_start_matches('#');
+			match u32::from_str_radix(hex, 16) {
+				Ok(color) if hex.len() == 6 => Some(color),
+				_ => return Err(format!("Invalid color '{color}', expected RRGGBB")),
+			}
+		};
+		Ok(EntryTag {
+			name: name.to_string(),
+			icon: (!icon.is_empty()).then(|| icon.to_string()),
+			color,
+		})
+	}
+
+	/// Fill the icon and color of the selected tag
+	fn load_tag(&mut self) {
+		let Some(tag) = self.field_name.inner.submit().map(|id| &self.tags[id]) else {
+			return;
+		};
+		self.field_icon
+			.inner
+			.set_input(tag.icon.clone().unwrap_or_default());
+		self.field_color
+			.inner
+			.set_input(format!("{:06X}", tag.color.unwrap_or(DEFAULT_TAG_COLOR)));
+	}
+}
+
+impl Form for TagRegistryForm {
+	fn component_count(&self) -> usize {
+		3
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_name),
+			1 => Some(&self.field_icon),
+			2 => Some(&self.field_color),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_name),
+			1 => Some(&mut self.field_icon),
+			2 => Some(&mut self.field_color),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if let Some(popup) = &mut self.popup {
+			if popup.input(key) {
+				self.popup = None;
+			}
+			return None;
+		}
+		if FormExt::input(self, key) {
+			if self.selected == Some(0) {
+				self.load_tag();
+			}
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter => match self.submit() {
+				Ok(_) => Some(FormSignal::Return),
+				Err(err) => {
+					self.popup = Some(Popup::new(
+						"Invalid Tag".into(),
+						Paragraph::new(Text::from(err)),
+					));
+					None
+				}
+			},
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title("Tags")
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::TAG_REGISTRY);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+
+		if let Some(popup) = &self.popup {
+			ctx.area = frame.area();
+			popup.render(frame, ctx);
+		}
+	}
+}
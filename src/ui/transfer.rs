@@ -0,0 +1,230 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::Frame;
+
+use crate::data::entry::Entry;
+use crate::icons;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::checkbox::Checkbox;
+use crate::widgets::combo_box::ComboBox;
+use crate::widgets::combo_box::ComboBoxStyle;
+use crate::widgets::combo_box::ComboItem;
+use crate::widgets::form::Form;
+use crate::widgets::form::FormExt;
+use crate::widgets::form::FormSignal;
+use crate::widgets::form::FormStyle;
+use crate::widgets::label::LabelDisplay;
+use crate::widgets::label::LabelStyle;
+use crate::widgets::label::Labeled;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+static LABEL_STYLE: LazyLock<LabelStyle> = LazyLock::new(|| LabelStyle {
+	padding: [0, 0],
+	display: LabelDisplay::Block {
+		block: Box::new(Block::bordered()),
+	},
+	style: Some(Style::default().fg(theme().text)),
+	style_selected: None,
+});
+static COMBOBOX_STYLE: LazyLock<ComboBoxStyle> = LazyLock::new(|| ComboBoxStyle {
+	padding: Default::default(),
+	markers: ["".into(), "".into()],
+	indicator: [" ".into(), " ".into()],
+	completion: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default().bg(theme().base).fg(theme().text).bold(),
+		Style::default().bg(theme().base).fg(theme().text).italic(),
+	],
+	completion_selected: [
+		Style::default().bg(theme().accent).fg(theme().base),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.bold(),
+		Style::default()
+			.bg(theme().base)
+			.fg(theme().highlight)
+			.italic(),
+	],
+	style: Default::default(),
+	selected_style: Default::default(),
+});
+
+/// Destination of an entry moved or copied to another database
+pub struct Transfer {
+	/// Tab of the destination database
+	pub target: usize,
+	/// Keep the entry in its current database
+	pub keep: bool,
+}
+
+/// Move or copy an entry to another open database
+pub struct TransferForm {
+	style: FormStyle,
+	entry: Entry,
+	/// Tab of each item of the destination combo box
+	tabs: Vec<usize>,
+
+	field_target: Labeled<'static, ComboBox<'static, 'static>>,
+	field_keep: Checkbox<'static>,
+
+	selected: Option<usize>,
+	scroll: RefCell<u16>,
+	body_area: Cell<Rect>,
+}
+
+impl TransferForm {
+	/// Transfer `entry` to one of `targets`, given as their tab and title
+	pub fn new(entry: Entry, targets: Vec<(usize, String)>) -> Self {
+		let mut items: Vec<ComboItem> = vec![];
+		for (tab, title) in &targets {
+			// Values must be unique to be matched back to a tab
+			let value = if items.iter().any(|item| &item.value == title) {
+				format!("{title} #{}", tab + 1)
+			} else {
+				title.clone()
+			};
+			items.push(ComboItem {
+				kind: format!("tab {}", tab + 1),
+				icon: format!("{} ", icons::DATABASE),
+				value,
+			});
+		}
+		let input = items
+			.first()
+			.map(|item| item.value.clone())
+			.unwrap_or_default();
+		Self {
+			style: FormStyle {
+				bg: theme().form_bg,
+				border: true,
+			},
+			entry,
+			tabs: targets.into_iter().map(|(tab, _)| tab).collect(),
+			field_target: Labeled::new(
+				"Database".into(),
+				ComboBox::from_items(items)
+					.style(&COMBOBOX_STYLE)
+					.with_input(input),
+			)
+			.style(&LABEL_STYLE),
+			field_keep: Checkbox::new(false, "Keep a copy in this database".into()),
+			selected: Some(0),
+			scroll: RefCell::default(),
+			body_area: Cell::default(),
+		}
+	}
+
+	/// Entry to transfer
+	pub fn entry(&self) -> &Entry {
+		&self.entry
+	}
+
+	/// Destination of the entry, `None` if no database is selected
+	pub fn submit(&self) -> Option<Transfer> {
+		let target = self.field_target.inner.submit()?;
+		Some(Transfer {
+			target: self.tabs[target],
+			keep: self.field_keep.value(),
+		})
+	}
+}
+
+impl Form for TransferForm {
+	fn component_count(&self) -> usize {
+		2
+	}
+
+	fn component(&self, index: usize) -> Option<&dyn Component> {
+		match index {
+			0 => Some(&self.field_target),
+			1 => Some(&self.field_keep),
+			_ => None,
+		}
+	}
+
+	fn component_mut(&mut self, index: usize) -> Option<&mut dyn Component> {
+		match index {
+			0 => Some(&mut self.field_target),
+			1 => Some(&mut self.field_keep),
+			_ => None,
+		}
+	}
+
+	fn selected(&self) -> Option<usize> {
+		self.selected
+	}
+
+	fn set_selected(&mut self, selected: Option<usize>) {
+		self.selected = selected
+	}
+
+	fn get_style(&self) -> &FormStyle {
+		&self.style
+	}
+
+	fn scroll(&self) -> u16 {
+		*self.scroll.borrow()
+	}
+
+	fn set_scroll(&self, scroll: u16) {
+		*self.scroll.borrow_mut() = scroll;
+	}
+
+	fn body_area(&self) -> &Cell<Rect> {
+		&self.body_area
+	}
+
+	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal> {
+		if FormExt::input(self, key) {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Esc => Some(FormSignal::Exit),
+			KeyCode::Enter if self.submit().is_some() => Some(FormSignal::Return),
+			_ => None,
+		}
+	}
+
+	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = ctx.area;
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(format!("Move '{}'", self.entry.name))
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(self.style.bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::TRANSFER);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		ctx.area.x += 1;
+		ctx.area.width = ctx.area.width.saturating_sub(2);
+		ctx.area.y += 2;
+		ctx.area.height = ctx.area.height.saturating_sub(3);
+		self.render_body(frame, ctx);
+	}
+}
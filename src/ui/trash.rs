@@ -0,0 +1,228 @@
+use std::cell::RefCell;
+
+use chrono::DateTime;
+use chrono::Utc;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Flex;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::symbols::border::QUADRANT_OUTSIDE;
+use ratatui::text::Line;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::widgets::ListState;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::data::entry::TrashedEntry;
+use crate::keymap;
+use crate::style::theme;
+use crate::widgets::confirm::Confirm;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Change of the trash requested from the [`TrashView`], by position in the trash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrashAction {
+	Restore(usize),
+	/// Permanently delete the entry
+	Delete(usize),
+	/// Permanently delete every entry of the trash
+	Empty,
+}
+
+/// Deleted entries, which can be restored or permanently deleted
+pub struct TrashView {
+	/// Names, number of fields and deletion dates of the trashed entries, in the order of the trash
+	entries: Vec<(String, usize, DateTime<Utc>)>,
+	/// Selected entry, the most recently deleted entries are listed first
+	selected: usize,
+	/// Permanent deletion waiting for confirmation
+	confirm: Option<(TrashAction, Confirm<'static>)>,
+	/// Set when a change was requested, see [`TrashView::take_action`]
+	action: Option<TrashAction>,
+	list_state: RefCell<ListState>,
+}
+
+impl TrashView {
+	pub fn new(trash: &[TrashedEntry]) -> Self {
+		let mut view = Self {
+			entries: vec![],
+			selected: 0,
+			confirm: None,
+			action: None,
+			list_state: RefCell::default(),
+		};
+		view.set_trash(trash);
+		view
+	}
+
+	/// Update the listed entries after the trash changed
+	pub fn set_trash(&mut self, trash: &[TrashedEntry]) {
+		self.entries = trash
+			.iter()
+			.map(|trashed| {
+				(
+					trashed.entry.name.clone(),
+					trashed.entry.fields.len(),
+					trashed.deleted_at,
+				)
+			})
+			.collect();
+		self.move_selected(0);
+	}
+
+	/// Change requested since the last call
+	pub fn take_action(&mut self) -> Option<TrashAction> {
+		self.action.take()
+	}
+
+	fn move_selected(&mut self, offset: isize) {
+		self.selected = self
+			.selected
+			.saturating_add_signed(offset)
+			.min(self.entries.len().saturating_sub(1));
+	}
+
+	/// Position in the trash of the selected entry
+	fn index(&self) -> Option<usize> {
+		(self.selected < self.entries.len()).then(|| self.entries.len() - 1 - self.selected)
+	}
+
+	fn ask(&mut self, action: TrashAction, message: String) {
+		let mut confirm = Confirm::new(
+			"Confirm Deletion".into(),
+			Paragraph::new(Text::from(message)),
+		);
+		confirm.set_selected(1);
+		self.confirm = Some((action, confirm));
+	}
+}
+
+impl Component for TrashView {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		if let Some((action, confirm)) = &mut self.confirm {
+			confirm.input(key);
+			match confirm.submit() {
+				Some(true) => {
+					self.action = Some(*action);
+					self.confirm = None;
+				}
+				Some(false) => self.confirm = None,
+				None => {}
+			}
+			return true;
+		}
+
+		match key.code {
+			KeyCode::Up | KeyCode::Char('k') => self.move_selected(-1),
+			KeyCode::Down | KeyCode::Char('j') => self.move_selected(1),
+			KeyCode::PageUp => self.move_selected(-16),
+			KeyCode::PageDown => self.move_selected(16),
+			KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+			KeyCode::End | KeyCode::Char('G') => self.move_selected(isize::MAX),
+			KeyCode::Enter | KeyCode::Char('r') => {
+				self.action = self.index().map(TrashAction::Restore);
+			}
+			KeyCode::Char('d') | KeyCode::Delete => {
+				if let Some(index) = self.index() {
+					let message = format!("Permanently delete '{}'?", self.entries[index].0);
+					self.ask(TrashAction::Delete(index), message);
+				}
+			}
+			KeyCode::Char('E') if !self.entries.is_empty() => {
+				let message = format!(
+					"Permanently delete the {} entries of the trash?",
+					self.entries.len()
+				);
+				self.ask(TrashAction::Empty, message);
+			}
+			KeyCode::Esc | KeyCode::Char('q') => return false,
+			_ => {}
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+		let vertical = Layout::vertical([Constraint::Percentage(60)]).flex(Flex::Center);
+		let [area] = ctx.area.layout(&horizontal);
+		let [area] = area.layout(&vertical);
+
+		let border = Block::bordered()
+			.border_set(QUADRANT_OUTSIDE)
+			.title(format!("Trash ({} entries)", self.entries.len()))
+			.title_style(Style::default().fg(theme().text))
+			.title_alignment(ratatui::layout::HorizontalAlignment::Center)
+			.bg(theme().form_bg)
+			.fg(theme().form_border);
+		frame.render_widget(Clear, area);
+		frame.render_widget(border, area);
+		let help = keymap::help_line(&keymap::TRASH);
+		frame.render_widget(
+			help,
+			Rect {
+				x: area.x + 1,
+				y: area.y + 1,
+				width: area.width.saturating_sub(2),
+				height: 1,
+			},
+		);
+
+		let body = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+		if self.entries.is_empty() {
+			frame.render_widget(
+				Line::from("The trash is empty".italic().fg(theme().dim)),
+				body,
+			);
+			return;
+		}
+
+		let entries = self
+			.entries
+			.iter()
+			.rev()
+			.enumerate()
+			.map(|(id, (name, fields, deleted_at))| {
+				let item = ListItem::new(Line::from(vec![
+					" ".into(),
+					deleted_at
+						.format("%Y-%m-%d %H:%M:%S")
+						.to_string()
+						.fg(theme().muted),
+					"  ".into(),
+					name.clone().bold().fg(theme().accent),
+					format!(" ({fields})").italic().fg(theme().faint),
+				]));
+				if id == self.selected {
+					item.bg(theme().entry_bg[2])
+				} else {
+					item.bg(theme().entry_bg[id % 2])
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut list_state = self.list_state.borrow_mut();
+		list_state.select(Some(self.selected));
+		frame.render_stateful_widget(List::new(entries), body, &mut *list_state);
+
+		if let Some((_, confirm)) = &self.confirm {
+			confirm.render(frame, ctx);
+		}
+	}
+
+	fn height(&self) -> u16 {
+		panic!()
+	}
+}
@@ -0,0 +1,66 @@
+use crate::data::entry::Entry;
+use crate::data::entry::TrashedEntry;
+
+/// Number of changes that can be undone
+const UNDO_DEPTH: usize = 64;
+
+/// Entries before or after a change
+struct Snapshot {
+	entries: Vec<Entry>,
+	trash: Vec<TrashedEntry>,
+	/// Description of the change, e.g. "delete 'mail'"
+	action: String,
+}
+
+/// Undo and redo stacks of the changes made to the entries and the trash
+#[derive(Default)]
+pub struct UndoStack {
+	undo: Vec<Snapshot>,
+	redo: Vec<Snapshot>,
+}
+
+impl UndoStack {
+	/// Save `entries` and `trash` before `action` modifies them, changes undone so far can no
+	/// longer be redone
+	pub fn checkpoint(&mut self, entries: &[Entry], trash: &[TrashedEntry], action: String) {
+		if self.undo.len() == UNDO_DEPTH {
+			self.undo.remove(0);
+		}
+		self.undo.push(Snapshot {
+			entries: entries.to_vec(),
+			trash: trash.to_vec(),
+			action,
+		});
+		self.redo.clear();
+	}
+
+	/// Restore the entries and the trash before the last change, returns its description
+	pub fn undo(
+		&mut self,
+		entries: &mut Vec<Entry>,
+		trash: &mut Vec<TrashedEntry>,
+	) -> Option<String> {
+		let snapshot = self.undo.pop()?;
+		self.redo.push(Snapshot {
+			entries: std::mem::replace(entries, snapshot.entries),
+			trash: std::mem::replace(trash, snapshot.trash),
+			action: snapshot.action.clone(),
+		});
+		Some(snapshot.action)
+	}
+
+	/// Apply the last undone change again, returns its description
+	pub fn redo(
+		&mut self,
+		entries: &mut Vec<Entry>,
+		trash: &mut Vec<TrashedEntry>,
+	) -> Option<String> {
+		let snapshot = self.redo.pop()?;
+		self.undo.push(Snapshot {
+			entries: std::mem::replace(entries, snapshot.entries),
+			trash: std::mem::replace(trash, snapshot.trash),
+			action: snapshot.action.clone(),
+		});
+		Some(snapshot.action)
+	}
+}
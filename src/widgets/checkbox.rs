@@ -2,13 +2,13 @@ use std::sync::LazyLock;
 
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Styled;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::Frame;
 
+use crate::style::theme;
 use crate::widgets::widget::Component;
 
 use super::widget::ComponentRenderCtx;
@@ -47,7 +47,7 @@ impl CheckboxStyle<'_> {
 	pub fn style_selected(&self) -> Style {
 		match self.selected_style {
 			Some(style) => style,
-			None => Style::default().fg(Color::Yellow),
+			None => Style::default().fg(theme().highlight),
 		}
 	}
 }
@@ -0,0 +1,81 @@
+use ratatui::text::Span;
+use unicode_width::UnicodeWidthChar;
+
+/// Width of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+	/// Always the same width
+	Fixed(u16),
+	/// Share of the width left by the fixed columns, by weight
+	Fill(u16),
+}
+
+/// Widths of `columns` laid out over `width`, with a space between them
+///
+/// Fixed columns keep their width even when they overflow, the fill columns then get nothing.
+pub fn layout(columns: &[ColumnWidth], width: u16) -> Vec<u16> {
+	let gaps = columns.len().saturating_sub(1) as u16;
+	let fixed = columns
+		.iter()
+		.map(|column| match column {
+			ColumnWidth::Fixed(width) => *width,
+			ColumnWidth::Fill(_) => 0,
+		})
+		.sum::<u16>();
+	let mut left = width.saturating_sub(fixed + gaps) as u32;
+	let mut weights = columns
+		.iter()
+		.map(|column| match column {
+			ColumnWidth::Fixed(_) => 0,
+			ColumnWidth::Fill(weight) => *weight as u32,
+		})
+		.sum::<u32>();
+	columns
+		.iter()
+		.map(|column| match column {
+			ColumnWidth::Fixed(width) => *width,
+			ColumnWidth::Fill(weight) => {
+				// The last fill column gets what the divisions left
+				let share = left * *weight as u32 / weights.max(1);
+				left -= share;
+				weights -= *weight as u32;
+				share as u16
+			}
+		})
+		.collect()
+}
+
+/// Cell made of `spans`, cut with an ellipsis or padded with spaces to `width`
+pub fn fit(spans: Vec<Span<'_>>, width: u16) -> Vec<Span<'_>> {
+	let mut left = width as usize;
+	let mut cell = vec![];
+	for span in spans {
+		let span_width = span.width();
+		if span_width <= left {
+			left -= span_width;
+			cell.push(span);
+			continue;
+		}
+		// Keep room for the ellipsis
+		let mut content = String::new();
+		let mut content_width = 0;
+		for c in span.content.chars() {
+			let c_width = c.width().unwrap_or(0);
+			if content_width + c_width + 1 > left {
+				break;
+			}
+			content.push(c);
+			content_width += c_width;
+		}
+		if left != 0 {
+			content.push('…');
+			left -= content_width + 1;
+		}
+		cell.push(Span::styled(content, span.style));
+		break;
+	}
+	if left != 0 {
+		cell.push(Span::raw(" ".repeat(left)));
+	}
+	cell
+}
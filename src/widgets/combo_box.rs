@@ -1,13 +1,17 @@
+use std::borrow::Cow;
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::sync::LazyLock;
 
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Position;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Styled;
 use ratatui::text::Line;
@@ -23,11 +27,14 @@ use ratatui::Frame;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+use crate::icons;
+use crate::style::theme;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::Overlay;
 
 use super::widget::ComponentRenderCtx;
 
+#[derive(Clone)]
 pub struct ComboItem {
 	pub kind: String,
 	pub icon: String,
@@ -55,16 +62,25 @@ impl Default for ComboBoxStyle<'_> {
 		Self {
 			padding: Default::default(),
 			markers: ["[".into(), "]".into()],
-			indicator: [" ".into(), " ".into()],
+			indicator: [
+				format!("{} ", icons::COLLAPSED).into(),
+				format!("{} ", icons::EXPANDED).into(),
+			],
 			completion: [
-				Style::default().bg(Color::Cyan).fg(Color::Black),
-				Style::default().bg(Color::Black).fg(Color::White).bold(),
-				Style::default().bg(Color::Black).fg(Color::White).italic(),
+				Style::default().bg(theme().accent).fg(theme().base),
+				Style::default().bg(theme().base).fg(theme().text).bold(),
+				Style::default().bg(theme().base).fg(theme().text).italic(),
 			],
 			completion_selected: [
-				Style::default().bg(Color::Cyan).fg(Color::Black),
-				Style::default().bg(Color::Black).fg(Color::Yellow).bold(),
-				Style::default().bg(Color::Black).fg(Color::Yellow).italic(),
+				Style::default().bg(theme().accent).fg(theme().base),
+				Style::default()
+					.bg(theme().base)
+					.fg(theme().highlight)
+					.bold(),
+				Style::default()
+					.bg(theme().base)
+					.fg(theme().highlight)
+					.italic(),
 			],
 			style: Default::default(),
 			selected_style: Default::default(),
@@ -80,7 +96,7 @@ impl ComboBoxStyle<'_> {
 	pub fn style_selected(&self) -> Style {
 		match self.selected_style {
 			Some(style) => style,
-			None => Style::default().fg(Color::Yellow),
+			None => Style::default().fg(theme().highlight),
 		}
 	}
 }
@@ -96,7 +112,7 @@ pub struct ComboBox<'s, 'e> {
 	cursor_x: u16,
 
 	/// Entries
-	entries: &'e [ComboItem],
+	entries: Cow<'e, [ComboItem]>,
 	/// Filtered entries
 	entries_filter: Vec<usize>,
 	/// Position in the completion menu
@@ -106,10 +122,21 @@ pub struct ComboBox<'s, 'e> {
 
 	list_state: RefCell<ListState>,
 	scrollbar: RefCell<ScrollbarState>,
+	/// Area of the completion menu when it was last rendered, empty when hidden
+	menu_area: Cell<Rect>,
 }
 
 impl<'s, 'e> ComboBox<'s, 'e> {
 	pub fn new(entries: &'e [ComboItem]) -> Self {
+		Self::with_entries(Cow::Borrowed(entries))
+	}
+
+	/// Create a combo box owning its entries
+	pub fn from_items(entries: Vec<ComboItem>) -> Self {
+		Self::with_entries(Cow::Owned(entries))
+	}
+
+	fn with_entries(entries: Cow<'e, [ComboItem]>) -> Self {
 		let num_entries = entries.len();
 		Self {
 			style: &DEFAULT_STYLE,
@@ -125,6 +152,7 @@ impl<'s, 'e> ComboBox<'s, 'e> {
 
 			list_state: RefCell::default(),
 			scrollbar: RefCell::new(ScrollbarState::new(num_entries).position(0)),
+			menu_area: Cell::default(),
 		}
 	}
 
@@ -150,6 +178,10 @@ impl<'s, 'e> ComboBox<'s, 'e> {
 		self.update_filter();
 	}
 
+	pub fn get_input(&self) -> &String {
+		&self.input
+	}
+
 	pub fn submit(&self) -> Option<usize> {
 		for ent_id in &self.entries_filter {
 			if self.entries[*ent_id].value == self.input {
@@ -324,7 +356,35 @@ impl Component for ComboBox<'_, '_> {
 		true
 	}
 
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		let area = self.menu_area.get();
+		if !self.completion_menu || !area.contains(Position::new(event.column, event.row)) {
+			return vec![];
+		}
+		match event.kind {
+			MouseEventKind::ScrollDown => vec![KeyCode::Down.into()],
+			MouseEventKind::ScrollUp => vec![KeyCode::Up.into()],
+			// Move to the clicked item and complete it
+			MouseEventKind::Down(MouseButton::Left) => {
+				let offset = self.list_state.borrow().offset();
+				let index = offset + (event.row - area.y) as usize;
+				if index >= self.entries_filter.len() {
+					return vec![];
+				}
+				let mut keys = match self.entries_index {
+					Some(current) if current > index => vec![KeyCode::Up.into(); current - index],
+					Some(current) => vec![KeyCode::Down.into(); index - current],
+					None => vec![KeyCode::Down.into(); index + 1],
+				};
+				keys.push(KeyCode::Enter.into());
+				keys
+			}
+			_ => vec![],
+		}
+	}
+
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		self.menu_area.set(Rect::default());
 		let padding_left = Span::raw(" ".repeat(self.style.padding[0] as usize));
 		let padding_right = Span::raw(" ".repeat(self.style.padding[1] as usize));
 		let input_span = Span::from(self.input.as_str());
@@ -408,6 +468,7 @@ impl Component for ComboBox<'_, '_> {
 		if show_scrollbar {
 			comp_content.width -= 1;
 		}
+		self.menu_area.set(comp_content);
 		let mut buffer = Buffer::empty(comp_area);
 
 		let list = self
@@ -454,7 +515,7 @@ impl Component for ComboBox<'_, '_> {
 				};
 				let spacer = Span::styled(
 					" ".repeat(padding_width as usize),
-					ratatui::style::Style::default().bg(Color::Black),
+					ratatui::style::Style::default().bg(theme().base),
 				);
 
 				let line = Line::from(
@@ -486,9 +547,9 @@ impl Component for ComboBox<'_, '_> {
 				.begin_symbol(None)
 				.end_symbol(None)
 				.track_symbol(Some(" "))
-				.track_style(Style::default().bg(Color::Black))
+				.track_style(Style::default().bg(theme().base))
 				.thumb_symbol("█")
-				.thumb_style(Style::default().fg(Color::White));
+				.thumb_style(Style::default().fg(theme().text));
 			let mut comp_scrollbar = comp_area;
 			comp_scrollbar.x += comp_scrollbar.width.saturating_sub(2);
 			comp_scrollbar.width = 2;
@@ -10,7 +10,6 @@ use ratatui::layout::Flex;
 use ratatui::layout::HorizontalAlignment;
 use ratatui::layout::Layout;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -21,6 +20,7 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::Frame;
 
+use crate::style::theme;
 use crate::widgets::widget::Component;
 
 use super::widget::ComponentRenderCtx;
@@ -38,11 +38,11 @@ impl Default for ConfirmStyle<'_> {
 		Self {
 			padding: [0, 1, 0, 1],
 			block: Block::bordered()
-				.bg(Color::from_u32(0x1f1f1f))
+				.bg(theme().prompt_bg)
 				.title_alignment(HorizontalAlignment::Center),
 			buttons: [
-				Style::default().fg(Color::White),
-				Style::default().bg(Color::White).fg(Color::Black).bold(),
+				Style::default().fg(theme().text),
+				Style::default().bg(theme().text).fg(theme().base).bold(),
 			],
 			spacing: 2,
 		}
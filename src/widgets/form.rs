@@ -1,6 +1,12 @@
+use std::cell::Cell;
+
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
+use ratatui::layout::Position;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
 use ratatui::style::Style;
@@ -36,6 +42,8 @@ pub trait Form {
 
 	fn scroll(&self) -> u16;
 	fn set_scroll(&self, scroll: u16);
+	/// Area the body was last rendered in, to find the components under the mouse
+	fn body_area(&self) -> &Cell<Rect>;
 
 	fn input_form(&mut self, key: &KeyEvent) -> Option<FormSignal>;
 	fn render_form(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx);
@@ -117,6 +125,52 @@ pub trait FormExt: Form {
 		true
 	}
 
+	/// Send pasted text to the focused component, see [`Component::paste`]
+	fn paste(&mut self, text: &str) -> bool {
+		let Some(selected) = self.selected() else {
+			return false;
+		};
+		self.component_mut(selected)
+			.is_some_and(|component| component.paste(text))
+	}
+
+	/// Send mouse events to the form: clicks focus the component under the cursor and the wheel
+	/// moves the focus
+	///
+	/// Returns the keys of the equivalent keyboard action, see [`Component::mouse_input`].
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		// Menus of the focused component may be drawn outside of the form
+		if let Some(selected) = self.selected() {
+			let keys = self.component_mut(selected).unwrap().mouse_input(event);
+			if !keys.is_empty() {
+				return keys;
+			}
+		}
+
+		let area = self.body_area().get();
+		if !area.contains(Position::new(event.column, event.row)) {
+			return vec![];
+		}
+		match event.kind {
+			MouseEventKind::ScrollDown => self.focus_next(),
+			MouseEventKind::ScrollUp => self.focus_prev(),
+			MouseEventKind::Down(MouseButton::Left) => {
+				// Same positions as in render_body
+				let mut y = area.y.saturating_sub(self.scroll());
+				for index in 0..self.component_count() {
+					let height = self.component(index).unwrap().height();
+					if (y..y + height).contains(&event.row) {
+						self.set_selected(Some(index));
+						return self.component_mut(index).unwrap().mouse_input(event);
+					}
+					y += height;
+				}
+			}
+			_ => {}
+		}
+		vec![]
+	}
+
 	/// Render the form body
 	fn render_body(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
 		frame.render_widget(Clear, ctx.area);
@@ -138,6 +192,7 @@ pub trait FormExt: Form {
 		}
 
 		self.ensure_visible(inner_area.height);
+		self.body_area().set(inner_area);
 		let mut queue = vec![];
 
 		let mut y = inner_area.y.saturating_sub(self.scroll());
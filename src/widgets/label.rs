@@ -1,12 +1,13 @@
 use std::sync::LazyLock;
 
 use crossterm::event::KeyEvent;
-use ratatui::style::Color;
+use crossterm::event::MouseEvent;
 use ratatui::style::Style;
 use ratatui::text::Span;
 use ratatui::widgets::Block;
 use ratatui::Frame;
 
+use crate::style::theme;
 use crate::widgets::widget::Component;
 
 use super::widget::ComponentRenderCtx;
@@ -43,7 +44,7 @@ impl LabelStyle<'_> {
 	pub fn style_selected(&self) -> Style {
 		match self.style_selected {
 			Some(style) => style,
-			None => Style::default().fg(Color::Yellow),
+			None => Style::default().fg(theme().highlight),
 		}
 	}
 }
@@ -85,6 +86,14 @@ where
 		self.inner.input(key)
 	}
 
+	fn mouse_input(&mut self, event: &MouseEvent) -> Vec<KeyEvent> {
+		self.inner.mouse_input(event)
+	}
+
+	fn paste(&mut self, text: &str) -> bool {
+		self.inner.paste(text)
+	}
+
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
 		match &self.style.display {
 			LabelDisplay::Inline { spacing } => {
@@ -1,9 +1,12 @@
 pub mod checkbox;
+pub mod columns;
 pub mod combo_box;
 pub mod confirm;
 pub mod form;
 pub mod label;
+pub mod text_area;
 pub mod text_input;
 pub mod widget;
 pub mod popup;
 pub mod text_input_custom;
+pub mod toast;
@@ -9,7 +9,6 @@ use ratatui::layout::Flex;
 use ratatui::layout::HorizontalAlignment;
 use ratatui::layout::Layout;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use ratatui::widgets::Block;
@@ -18,6 +17,7 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::Frame;
 
+use crate::style::theme;
 use crate::widgets::widget::Component;
 
 use super::widget::ComponentRenderCtx;
@@ -34,9 +34,9 @@ impl Default for PopupStyle<'_> {
 		Self {
 			padding: [0, 1, 0, 1],
 			block: Block::bordered()
-				.bg(Color::from_u32(0x1f1f1f))
+				.bg(theme().prompt_bg)
 				.title_alignment(HorizontalAlignment::Center),
-			button: "Ok".fg(Color::Black).bg(Color::White).bold(),
+			button: "Ok".fg(theme().base).bg(theme().text).bold(),
 		}
 	}
 }
@@ -130,10 +130,7 @@ impl<'s> Popup<'s> {
 
 impl Component for Popup<'_> {
 	fn input(&mut self, key: &KeyEvent) -> bool {
-		match key.code {
-			KeyCode::Enter | KeyCode::Esc => true,
-			_ => false,
-		}
+		matches!(key.code, KeyCode::Enter | KeyCode::Esc)
 	}
 
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
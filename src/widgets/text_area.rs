@@ -0,0 +1,315 @@
+use std::cell::RefCell;
+use std::sync::LazyLock;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::layout::Position;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Text;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+#[derive(Debug, Clone, Default)]
+pub struct TextAreaStyle {
+	/// |<padding0>Input<padding1>|
+	pub padding: [u16; 2],
+	/// Style override
+	pub style: Option<Style>,
+	/// Selected style override
+	pub style_selected: Option<Style>,
+}
+
+impl TextAreaStyle {
+	pub fn style(&self) -> Style {
+		self.style.unwrap_or_default()
+	}
+
+	pub fn style_selected(&self) -> Style {
+		match self.style_selected {
+			Some(style) => style,
+			None => Style::default().fg(theme().highlight),
+		}
+	}
+}
+
+static DEFAULT_STYLE: LazyLock<TextAreaStyle> = LazyLock::new(TextAreaStyle::default);
+
+/// Multiline text input, long lines are wrapped to the width of the area
+///
+/// Enter inserts a new line, up and down are only processed while the cursor is not on the first
+/// or last line, so that the focus can leave the area.
+pub struct TextArea<'s> {
+	style: &'s TextAreaStyle,
+
+	lines: Vec<String>,
+	/// Line of the cursor
+	row: usize,
+	/// Grapheme of the cursor in its line
+	column: usize,
+
+	/// Number of visible rows
+	rows: u16,
+	scroll_y: RefCell<u16>,
+}
+
+impl Default for TextArea<'_> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Index of the graphemes starting each row of a line made of graphemes of widths `widths`
+///
+/// A line that fills its last row is followed by an empty row, for the cursor at its end.
+fn wrap(widths: &[u16], width: u16) -> Vec<usize> {
+	let mut starts = vec![0];
+	let mut col = 0;
+	for (index, w) in widths.iter().enumerate() {
+		if col != 0 && col + w > width {
+			starts.push(index);
+			col = 0;
+		}
+		col += w;
+	}
+	if col >= width {
+		starts.push(widths.len());
+	}
+	starts
+}
+
+impl<'s> TextArea<'s> {
+	pub fn new() -> Self {
+		Self {
+			style: &DEFAULT_STYLE,
+			lines: vec![String::default()],
+			row: 0,
+			column: 0,
+			rows: 5,
+			scroll_y: RefCell::default(),
+		}
+	}
+
+	pub fn style(mut self, style: &'s TextAreaStyle) -> Self {
+		self.style = style;
+		self
+	}
+
+	/// Set the number of visible rows
+	pub fn rows(mut self, rows: u16) -> Self {
+		self.rows = rows;
+		self
+	}
+
+	pub fn with_input(mut self, input: String) -> Self {
+		self.set_input(input);
+		self
+	}
+
+	/// Replace the content, the cursor is moved to its end
+	pub fn set_input(&mut self, input: String) {
+		self.lines = input.split('\n').map(str::to_string).collect();
+		self.row = self.lines.len() - 1;
+		self.column = self.line_len();
+		*self.scroll_y.borrow_mut() = 0;
+	}
+
+	pub fn submit(&self) -> String {
+		self.lines.join("\n")
+	}
+
+	/// Number of graphemes in the line of the cursor
+	fn line_len(&self) -> usize {
+		self.lines[self.row].graphemes(true).count()
+	}
+
+	/// Byte offset of grapheme `column` in the line of the cursor
+	fn byte_index(&self, column: usize) -> usize {
+		let line = &self.lines[self.row];
+		line.grapheme_indices(true)
+			.nth(column)
+			.map_or(line.len(), |(index, _)| index)
+	}
+
+	fn move_cursor_left(&mut self) {
+		if self.column != 0 {
+			self.column -= 1;
+		} else if self.row != 0 {
+			self.row -= 1;
+			self.column = self.line_len();
+		}
+	}
+
+	fn move_cursor_right(&mut self) {
+		if self.column < self.line_len() {
+			self.column += 1;
+		} else if self.row + 1 < self.lines.len() {
+			self.row += 1;
+			self.column = 0;
+		}
+	}
+
+	/// Move to the previous line, returns false on the first line
+	fn move_cursor_up(&mut self) -> bool {
+		if self.row == 0 {
+			return false;
+		}
+		self.row -= 1;
+		self.column = self.column.min(self.line_len());
+		true
+	}
+
+	/// Move to the next line, returns false on the last line
+	fn move_cursor_down(&mut self) -> bool {
+		if self.row + 1 == self.lines.len() {
+			return false;
+		}
+		self.row += 1;
+		self.column = self.column.min(self.line_len());
+		true
+	}
+
+	fn enter_char(&mut self, new_char: char) {
+		let index = self.byte_index(self.column);
+		self.lines[self.row].insert(index, new_char);
+		// The character may have joined the previous grapheme
+		self.column = self.lines[self.row][..index + new_char.len_utf8()]
+			.graphemes(true)
+			.count();
+	}
+
+	fn new_line(&mut self) {
+		let index = self.byte_index(self.column);
+		let rest = self.lines[self.row].split_off(index);
+		self.lines.insert(self.row + 1, rest);
+		self.row += 1;
+		self.column = 0;
+	}
+
+	/// Delete the grapheme before the cursor, joining the line with the previous one at its start
+	fn delete_char(&mut self) {
+		if self.column != 0 {
+			let start = self.byte_index(self.column - 1);
+			let end = self.byte_index(self.column);
+			self.lines[self.row].replace_range(start..end, "");
+			self.column -= 1;
+		} else if self.row != 0 {
+			let line = self.lines.remove(self.row);
+			self.row -= 1;
+			self.column = self.line_len();
+			self.lines[self.row].push_str(&line);
+		}
+	}
+
+	/// Delete the grapheme after the cursor, joining the next line at the end of the line
+	fn delete_next_char(&mut self) {
+		if self.column < self.line_len() {
+			let start = self.byte_index(self.column);
+			let end = self.byte_index(self.column + 1);
+			self.lines[self.row].replace_range(start..end, "");
+		} else if self.row + 1 < self.lines.len() {
+			let line = self.lines.remove(self.row + 1);
+			self.lines[self.row].push_str(&line);
+		}
+	}
+
+	/// Update scroll so that row `cursor_y` is visible
+	fn ensure_cursor_visible(&self, cursor_y: u16, viewport_height: u16) {
+		let mut scroll_y = self.scroll_y.borrow_mut();
+		if cursor_y < *scroll_y {
+			*scroll_y = cursor_y;
+		} else if cursor_y >= *scroll_y + viewport_height {
+			*scroll_y = cursor_y + 1 - viewport_height;
+		}
+	}
+}
+
+impl Component for TextArea<'_> {
+	fn input(&mut self, key: &KeyEvent) -> bool {
+		let ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
+		match key.code {
+			KeyCode::Enter => self.new_line(),
+			KeyCode::Backspace => self.delete_char(),
+			KeyCode::Delete => self.delete_next_char(),
+			// Movement
+			KeyCode::Left => self.move_cursor_left(),
+			KeyCode::Char('b') if ctrl_pressed => self.move_cursor_left(),
+			KeyCode::Right => self.move_cursor_right(),
+			KeyCode::Char('f') if ctrl_pressed => self.move_cursor_right(),
+			KeyCode::Up => return self.move_cursor_up(),
+			KeyCode::Down => return self.move_cursor_down(),
+			KeyCode::Home => self.column = 0,
+			KeyCode::Char('a') if ctrl_pressed => self.column = 0,
+			KeyCode::End => self.column = self.line_len(),
+			KeyCode::Char('e') if ctrl_pressed => self.column = self.line_len(),
+			KeyCode::Char(to_insert) if !ctrl_pressed => self.enter_char(to_insert),
+			_ => return false,
+		}
+		true
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		let area = Rect {
+			x: ctx.area.x + self.style.padding[0],
+			y: ctx.area.y,
+			width: ctx
+				.area
+				.width
+				.saturating_sub(self.style.padding[0] + self.style.padding[1]),
+			height: ctx.area.height.min(self.rows),
+		};
+		let width = area.width.max(1);
+
+		// Wrap the lines and find the position of the cursor in the rows
+		let mut rows: Vec<Line> = vec![];
+		let mut cursor = (0, 0);
+		for (index, line) in self.lines.iter().enumerate() {
+			let graphemes = line.graphemes(true).collect::<Vec<_>>();
+			let widths = graphemes
+				.iter()
+				.map(|g| UnicodeWidthStr::width(*g).max(1) as u16)
+				.collect::<Vec<_>>();
+			let starts = wrap(&widths, width);
+			if index == self.row {
+				let row = starts.partition_point(|start| *start <= self.column) - 1;
+				let x = widths[starts[row]..self.column].iter().sum::<u16>();
+				cursor = (x, (rows.len() + row) as u16);
+			}
+			for (row, start) in starts.iter().enumerate() {
+				let end = starts.get(row + 1).copied().unwrap_or(graphemes.len());
+				rows.push(Line::from(graphemes[*start..end].concat()));
+			}
+		}
+
+		self.ensure_cursor_visible(cursor.1, area.height.max(1));
+		let scroll_y = *self.scroll_y.borrow();
+		let text = Text::from(rows.into_iter().skip(scroll_y as usize).collect::<Vec<_>>());
+		frame.render_widget(
+			Paragraph::new(text).style(if ctx.selected {
+				self.style.style_selected()
+			} else {
+				self.style.style()
+			}),
+			area,
+		);
+
+		if ctx.selected {
+			ctx.set_cursor(Position::new(
+				area.x + cursor.0,
+				area.y + cursor.1 - scroll_y,
+			));
+		}
+	}
+
+	fn height(&self) -> u16 {
+		self.rows
+	}
+}
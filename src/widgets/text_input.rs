@@ -5,7 +5,6 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
 use ratatui::layout::Position;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Styled;
 use ratatui::text::Line;
@@ -14,6 +13,7 @@ use ratatui::Frame;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+use crate::style::theme;
 use crate::widgets::widget::Component;
 
 use super::widget::ComponentRenderCtx;
@@ -48,7 +48,7 @@ impl TextInputStyle<'_> {
 	pub fn style_selected(&self) -> Style {
 		match self.style_selected {
 			Some(style) => style,
-			None => Style::default().fg(Color::Yellow),
+			None => Style::default().fg(theme().highlight),
 		}
 	}
 }
@@ -138,6 +138,20 @@ impl<'s> TextInput<'s> {
 		}
 	}
 
+	/// Insert `text` as is at the cursor
+	fn enter_str(&mut self, text: &str) {
+		let index: usize = self
+			.input
+			.graphemes(true)
+			.take(self.grapheme_index)
+			.map(|g| g.len())
+			.sum();
+		self.input.insert_str(index, text);
+		self.rebuild_geometry();
+		self.grapheme_index = self.input[..index + text.len()].graphemes(true).count();
+		self.update_cursor_x();
+	}
+
 	fn delete_char(&mut self) {
 		if self.grapheme_index == 0 {
 			return;
@@ -261,6 +275,11 @@ impl Component for TextInput<'_> {
 		true
 	}
 
+	fn paste(&mut self, text: &str) -> bool {
+		self.enter_str(text);
+		true
+	}
+
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
 		let viewport_width = self.text_width(ctx.area.width);
 		self.ensure_cursor_visible(viewport_width);
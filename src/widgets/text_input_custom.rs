@@ -5,7 +5,6 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
 use ratatui::layout::Position;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Styled;
 use ratatui::text::Line;
@@ -13,6 +12,7 @@ use ratatui::text::Span;
 use ratatui::Frame;
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::style::theme;
 use crate::widgets::widget::Component;
 use crate::widgets::widget::ComponentRenderCtx;
 
@@ -58,7 +58,7 @@ impl CustomTextInputStyle<'_> {
 	pub fn style_selected(&self) -> Style {
 		match self.style_selected {
 			Some(style) => style,
-			None => Style::default().fg(Color::Yellow),
+			None => Style::default().fg(theme().highlight),
 		}
 	}
 }
@@ -153,6 +153,23 @@ where
 		}
 	}
 
+	/// Insert `text` as is at the cursor
+	fn enter_str(&mut self, text: &str) {
+		let index: usize = self
+			.input
+			.graphemes(true)
+			.take(self.index)
+			.map(|g| g.len())
+			.sum();
+		self.input.insert_str(index, text);
+		self.rebuild_geometry();
+		self.index = self.input[..index + text.len()]
+			.graphemes(true)
+			.count()
+			.min(self.formatted_geometry.len());
+		self.update_cursor_x();
+	}
+
 	fn delete_char(&mut self) {
 		if self.index == 0 {
 			return;
@@ -296,6 +313,11 @@ where
 		true
 	}
 
+	fn paste(&mut self, text: &str) -> bool {
+		self.enter_str(text);
+		true
+	}
+
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
 		let viewport_width = self.text_width(ctx.area.width);
 		self.ensure_cursor_visible(viewport_width);
@@ -0,0 +1,69 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::Frame;
+
+use crate::style::theme;
+use crate::widgets::widget::Component;
+use crate::widgets::widget::ComponentRenderCtx;
+
+/// Duration a toast stays visible
+pub const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// Short message displayed in the bottom right corner, which disappears on its own
+pub struct Toast<'s> {
+	message: Line<'s>,
+	shown_at: Instant,
+}
+
+impl<'s> Toast<'s> {
+	pub fn new(message: Line<'s>) -> Self {
+		Self {
+			message,
+			shown_at: Instant::now(),
+		}
+	}
+
+	pub fn is_expired(&self) -> bool {
+		self.shown_at.elapsed() >= TOAST_DURATION
+	}
+}
+
+impl Component for Toast<'_> {
+	fn input(&mut self, _key: &KeyEvent) -> bool {
+		false
+	}
+
+	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx) {
+		if self.is_expired() {
+			return;
+		}
+		let width = (self.message.width() as u16 + 2).min(ctx.area.width);
+		let area = Rect {
+			x: ctx.area.x + ctx.area.width - width,
+			y: ctx.area.y + ctx.area.height.saturating_sub(2),
+			width,
+			height: 1,
+		};
+		frame.render_widget(Clear, area);
+		frame.render_widget(Block::new().bg(theme().form_bg), area);
+		frame.render_widget(
+			&self.message,
+			Rect {
+				x: area.x + 1,
+				width: area.width.saturating_sub(2),
+				..area
+			},
+		);
+	}
+
+	fn height(&self) -> u16 {
+		1
+	}
+}
@@ -1,4 +1,5 @@
 use crossterm::event::KeyEvent;
+use crossterm::event::MouseEvent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Position, Rect};
 use ratatui::Frame;
@@ -60,6 +61,18 @@ pub trait Component {
 	/// Send inputs to the component
 	/// Return `true` if the input was processed, `false` otherwise
 	fn input(&mut self, key: &KeyEvent) -> bool;
+	/// Send mouse events to the component, positions are in terminal cells
+	/// Return the keys of the equivalent keyboard action, to be sent to [`Component::input`]
+	fn mouse_input(&mut self, _event: &MouseEvent) -> Vec<KeyEvent> {
+		vec![]
+	}
+	/// Send pasted text to the component, only text inputs accept it
+	/// Return `true` if the text was inserted
+	fn paste(&mut self, _text: &str) -> bool {
+		false
+	}
+	/// Update the state that changes with time, called periodically even without input
+	fn tick(&mut self) {}
 	/// Render the component
 	fn render(&self, frame: &mut Frame, ctx: &mut ComponentRenderCtx);
 	/// Widget height, for vertical layouts